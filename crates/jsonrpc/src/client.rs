@@ -15,13 +15,13 @@ pub trait ResponseHandler {
 }
 
 pub struct Client {
-    output: mpsc::Sender<String>,
+    output: mpsc::Sender<Message>,
     request_id: AtomicU64,
     senders_by_id: CHashMap<Id, oneshot::Sender<Result<serde_json::Value>>>,
 }
 
 impl Client {
-    pub fn new(output: mpsc::Sender<String>) -> Self {
+    pub fn new(output: mpsc::Sender<Message>) -> Self {
         Self {
             output,
             request_id: AtomicU64::new(0),
@@ -51,8 +51,7 @@ impl Client {
 
     async fn send(&self, message: Message) {
         let mut output = self.output.clone();
-        let json = serde_json::to_string(&message).unwrap();
-        output.send(json).await.unwrap();
+        output.send(message).await.unwrap();
     }
 }
 
@@ -69,6 +68,7 @@ impl ResponseHandler for Client {
             .senders_by_id
             .remove(&id)
             .expect("Unexpected response received");
-        result_tx.send(result).unwrap();
+        // The request might have timed out in the meantime.
+        let _ = result_tx.send(result);
     }
 }
@@ -8,15 +8,26 @@ pub use self::{
     types::*,
 };
 
+use chashmap::CHashMap;
 use futures::channel::*;
+use futures::future::{AbortHandle, Abortable};
 use futures::prelude::*;
 use log::error;
+use serde::Deserialize;
 use std::sync::Arc;
 
+const CANCEL_REQUEST_METHOD: &str = "$/cancelRequest";
+
+#[derive(Debug, Deserialize)]
+struct CancelParams {
+    id: Id,
+}
+
 pub struct MessageHandler<S, C> {
     pub server: Arc<S>,
     pub client: Arc<C>,
-    pub output: mpsc::Sender<String>,
+    pub output: mpsc::Sender<Message>,
+    pending_requests: Arc<CHashMap<Id, AbortHandle>>,
 }
 
 impl<S, C> MessageHandler<S, C>
@@ -24,24 +35,46 @@ where
     S: RequestHandler + Middleware + Send + Sync + 'static,
     C: ResponseHandler + Send + Sync + 'static,
 {
+    pub fn new(server: Arc<S>, client: Arc<C>, output: mpsc::Sender<Message>) -> Self {
+        Self {
+            server,
+            client,
+            output,
+            pending_requests: Arc::new(CHashMap::new()),
+        }
+    }
+
     pub async fn handle(&mut self, json: &str) {
         self.server.before_message().await;
 
         match serde_json::from_str(json).map_err(|_| Error::parse_error()) {
             Ok(Message::Request(request)) => {
                 let server = Arc::clone(&self.server);
+                let pending_requests = Arc::clone(&self.pending_requests);
                 let mut output = self.output.clone();
+                let (abort_handle, abort_registration) = AbortHandle::new_pair();
+                pending_requests.insert(request.id.clone(), abort_handle);
                 tokio::spawn(async move {
-                    let response = server.handle_request(request).await;
+                    let id = request.id.clone();
+                    let response =
+                        match Abortable::new(server.handle_request(request), abort_registration)
+                            .await
+                        {
+                            Ok(response) => response,
+                            Err(_) => Response::error(Error::request_cancelled(), Some(id.clone())),
+                        };
+                    pending_requests.remove(&id);
                     if let Some(error) = response.error.as_ref() {
                         error!("{:?}", error);
                     }
-                    let json = serde_json::to_string(&response).unwrap();
-                    output.send(json).await.unwrap();
+                    output.send(Message::Response(response)).await.unwrap();
                     server.after_message().await;
                 });
             }
             Ok(Message::Notification(notification)) => {
+                if notification.method == CANCEL_REQUEST_METHOD {
+                    self.cancel(&notification.params);
+                }
                 self.server.handle_notification(notification).await;
                 self.after_message();
             }
@@ -51,13 +84,21 @@ where
             }
             Err(why) => {
                 let response = Response::error(why, None);
-                let json = serde_json::to_string(&response).unwrap();
-                self.output.send(json).await.unwrap();
+                self.output.send(Message::Response(response)).await.unwrap();
                 self.after_message();
             }
         };
     }
 
+    /// Aborts a pending request so that it stops at its next suspension point.
+    fn cancel(&self, params: &serde_json::Value) {
+        if let Ok(params) = serde_json::from_value::<CancelParams>(params.clone()) {
+            if let Some(abort_handle) = self.pending_requests.remove(&params.id) {
+                abort_handle.abort();
+            }
+        }
+    }
+
     fn after_message(&self) {
         let server = Arc::clone(&self.server);
         tokio::spawn(async move {
@@ -41,11 +41,11 @@ impl Error {
         }
     }
 
-    pub fn method_not_found_error() -> Self {
+    pub fn method_not_found_error(method: &str) -> Self {
         Self {
             code: ErrorCode::MethodNotFound,
-            message: "Method not found".to_owned(),
-            data: serde_json::Value::Null,
+            message: format!("Method not found: {}", method),
+            data: serde_json::Value::String(method.to_owned()),
         }
     }
 
@@ -57,6 +57,14 @@ impl Error {
         }
     }
 
+    pub fn request_cancelled() -> Self {
+        Self {
+            code: ErrorCode::RequestCancelled,
+            message: "Request cancelled".to_owned(),
+            data: serde_json::Value::Null,
+        }
+    }
+
     pub fn internal_error(message: String) -> Self {
         Self {
             code: ErrorCode::InternalError,
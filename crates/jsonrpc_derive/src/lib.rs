@@ -75,7 +75,8 @@ pub fn jsonrpc_server(_attr: TokenStream, item: TokenStream) -> TokenStream {
                 match request.method.as_str() {
                     #(#requests),*,
                     _ => {
-                        Response::error(Error::method_not_found_error(), Some(request.id))
+                        let error = Error::method_not_found_error(&request.method);
+                        Response::error(error, Some(request.id))
                     }
                 }
             }
@@ -84,6 +85,8 @@ pub fn jsonrpc_server(_attr: TokenStream, item: TokenStream) -> TokenStream {
             async fn handle_notification(&self, notification: jsonrpc::Notification) {
                 match notification.method.as_str() {
                     #(#notifications),*,
+                    // Optional notifications like `$/setTraceNotification` may be ignored.
+                    _ if notification.method.starts_with("$/") => (),
                     _ => log::warn!("{}: {}", "Method not found", notification.method),
                 }
             }
@@ -111,7 +114,7 @@ pub fn jsonrpc_client(attr: TokenStream, item: TokenStream) -> TokenStream {
 
         impl #struct_ident
         {
-            pub fn new(output: futures::channel::mpsc::Sender<String>) -> Self {
+            pub fn new(output: futures::channel::mpsc::Sender<jsonrpc::Message>) -> Self {
                 Self {
                     client: jsonrpc::Client::new(output),
                 }
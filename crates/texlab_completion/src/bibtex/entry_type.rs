@@ -46,8 +46,17 @@ fn contains(ty: &BibtexToken, position: Position) -> bool {
 
 fn make_items(request: &FeatureRequest<CompletionParams>, mut range: Range) -> Vec<CompletionItem> {
     range.start.character += 1;
+    let dialect = request
+        .options
+        .bibtex
+        .as_ref()
+        .and_then(|bibtex| bibtex.dialect);
     let mut items = Vec::new();
-    for ty in &LANGUAGE_DATA.entry_types {
+    for ty in LANGUAGE_DATA
+        .entry_types
+        .iter()
+        .filter(|ty| ty.supports(dialect))
+    {
         let text_edit = TextEdit::new(range, (&ty.name).into());
         let item = factory::entry_type(request, ty, text_edit);
         items.push(item);
@@ -150,4 +159,43 @@ mod tests {
         );
         assert!(items.is_empty());
     }
+
+    fn entry_types(dialect: Option<BibtexDialect>) -> Vec<String> {
+        test_feature(
+            BibtexEntryTypeCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@")],
+                main_file: "foo.bib",
+                position: Position::new(0, 1),
+                options: Options {
+                    latex: None,
+                    bibtex: Some(BibtexOptions {
+                        dialect,
+                        ..BibtexOptions::default()
+                    }),
+                },
+                ..FeatureSpec::default()
+            },
+        )
+        .into_iter()
+        .map(|item| item.label)
+        .collect()
+    }
+
+    #[test]
+    fn dialect() {
+        let labels = entry_types(Some(BibtexDialect::Biblatex));
+        assert!(labels.contains(&"online".to_owned()));
+        assert!(labels.contains(&"article".to_owned()));
+        assert!(!labels.contains(&"phdthesis".to_owned()));
+
+        let labels = entry_types(Some(BibtexDialect::Bibtex));
+        assert!(!labels.contains(&"online".to_owned()));
+        assert!(labels.contains(&"article".to_owned()));
+        assert!(labels.contains(&"phdthesis".to_owned()));
+
+        let labels = entry_types(None);
+        assert!(labels.contains(&"online".to_owned()));
+        assert!(labels.contains(&"phdthesis".to_owned()));
+    }
 }
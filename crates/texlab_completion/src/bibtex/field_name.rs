@@ -44,8 +44,17 @@ fn make_items(
     request: &FeatureRequest<CompletionParams>,
     edit_range: Range,
 ) -> Vec<CompletionItem> {
+    let dialect = request
+        .options
+        .bibtex
+        .as_ref()
+        .and_then(|bibtex| bibtex.dialect);
     let mut items = Vec::new();
-    for field in &LANGUAGE_DATA.fields {
+    for field in LANGUAGE_DATA
+        .fields
+        .iter()
+        .filter(|field| field.supports(dialect))
+    {
         let text_edit = TextEdit::new(edit_range, (&field.name).into());
         let item = factory::field_name(request, field, text_edit);
         items.push(item);
@@ -171,4 +180,31 @@ mod tests {
         );
         assert!(items.is_empty());
     }
+
+    #[test]
+    fn dialect() {
+        let labels: Vec<String> = test_feature(
+            BibtexFieldNameCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@article{foo,\nbar}")],
+                main_file: "foo.bib",
+                position: Position::new(1, 1),
+                options: Options {
+                    latex: None,
+                    bibtex: Some(BibtexOptions {
+                        dialect: Some(BibtexDialect::Biblatex),
+                        ..BibtexOptions::default()
+                    }),
+                },
+                ..FeatureSpec::default()
+            },
+        )
+        .into_iter()
+        .map(|item| item.label)
+        .collect();
+        assert!(labels.contains(&"journaltitle".to_owned()));
+        assert!(labels.contains(&"urldate".to_owned()));
+        assert!(labels.contains(&"title".to_owned()));
+        assert!(!labels.contains(&"journal".to_owned()));
+    }
 }
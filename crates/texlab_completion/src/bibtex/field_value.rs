@@ -0,0 +1,169 @@
+use crate::factory;
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexFieldValueCompletionProvider;
+
+impl FeatureProvider for BibtexFieldValueCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let mut items = Vec::new();
+        if let SyntaxTree::Bibtex(tree) = &request.document().tree {
+            let position = request.params.text_document_position.position;
+            let current_field = tree.find(position).into_iter().find_map(|node| match node {
+                BibtexNode::Field(field) => Some(field),
+                _ => None,
+            });
+
+            let field = match current_field {
+                Some(field) => field,
+                None => return items,
+            };
+
+            let edit_range = match field.content.as_ref().and_then(inner_range) {
+                Some(range) if range.contains(position) => range,
+                _ => return items,
+            };
+
+            let mut count_by_value: HashMap<String, usize> = HashMap::new();
+            for document in &request.workspace().documents {
+                if let SyntaxTree::Bibtex(other_tree) = &document.tree {
+                    for other_field in other_tree
+                        .entries()
+                        .into_iter()
+                        .flat_map(|entry| &entry.fields)
+                        .filter(|other| other.name.text().eq_ignore_ascii_case(field.name.text()))
+                    {
+                        if document.uri == request.document().uri
+                            && other_field.range == field.range
+                        {
+                            continue;
+                        }
+
+                        if let Some(value) = other_field
+                            .content
+                            .as_ref()
+                            .and_then(inner_range)
+                            .map(|range| CharStream::extract(&document.text, range))
+                            .map(|text| text.split_whitespace().collect::<Vec<_>>().join(" "))
+                            .filter(|value| !value.is_empty())
+                        {
+                            *count_by_value.entry(value).or_default() += 1;
+                        }
+                    }
+                }
+            }
+
+            let mut values: Vec<_> = count_by_value.into_iter().collect();
+            values.sort_by(|(value1, count1), (value2, count2)| {
+                count2.cmp(count1).then_with(|| value1.cmp(value2))
+            });
+
+            for (rank, (value, count)) in values.into_iter().enumerate() {
+                let text_edit = TextEdit::new(edit_range, value.clone());
+                let item = factory::field_value(request, value, count, rank, text_edit);
+                items.push(item);
+            }
+        }
+        items
+    }
+}
+
+fn inner_range(content: &BibtexContent) -> Option<Range> {
+    let (left, right) = match content {
+        BibtexContent::BracedContent(content) => (&content.left, content.right.as_ref()?),
+        BibtexContent::QuotedContent(content) => (&content.left, content.right.as_ref()?),
+        _ => return None,
+    };
+    Some(Range::new(left.end(), right.start()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ranked_by_frequency() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.bib",
+                        "@book{foo, publisher = {Spr}}\n\
+                         @book{bar, publisher = {Springer}}",
+                    ),
+                    FeatureSpec::file(
+                        "bar.bib",
+                        "@book{baz, publisher = \"Springer\"}\n\
+                         @book{qux, publisher = {MIT   Press}}",
+                    ),
+                ],
+                main_file: "foo.bib",
+                position: Position::new(0, 26),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["Springer", "MIT Press"]);
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(0, 24, 0, 27))
+        );
+    }
+
+    #[test]
+    fn other_field() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@book{foo, publisher = {}}\n@book{bar, author = {Baz}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 24),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn inside_field_name() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@book{foo, publisher = {}}\n@book{bar, publisher = {Baz}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 13),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn inside_latex() {
+        let items = test_feature(
+            BibtexFieldValueCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "@book{foo, publisher = {}}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 24),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+}
@@ -1,3 +1,4 @@
 pub mod command;
 pub mod entry_type;
 pub mod field_name;
+pub mod field_value;
@@ -1,3 +1,4 @@
+use crate::symbol::{Symbol, SYMBOL_INDEX};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -22,12 +23,18 @@ pub enum CompletionItemData {
     Color,
     ColorModel,
     Package,
+    PackageOption,
     Class,
+    ClassOption,
     EntryType,
     FieldName,
+    FieldValue,
     Citation { uri: Uri, key: String },
+    BibItem,
     Argument,
     GlossaryEntry,
+    Parameter,
+    Abbreviation,
 }
 
 impl Into<serde_json::Value> for CompletionItemData {
@@ -64,12 +71,17 @@ impl<'a> LatexComponentId<'a> {
 fn supports_images(request: &FeatureRequest<CompletionParams>) -> bool {
     request
         .client_capabilities
-        .text_document
+        .has_completion_markdown_support()
+}
+
+fn image_mode(request: &FeatureRequest<CompletionParams>) -> LatexCompletionImageMode {
+    request
+        .options
+        .latex
         .as_ref()
-        .and_then(|cap| cap.completion.as_ref())
-        .and_then(|cap| cap.completion_item.as_ref())
-        .and_then(|cap| cap.documentation_format.as_ref())
-        .map_or(true, |formats| formats.contains(&MarkupKind::Markdown))
+        .and_then(|opts| opts.completion.as_ref())
+        .map(LatexCompletionOptions::images)
+        .unwrap_or_default()
 }
 
 pub fn command(
@@ -80,14 +92,18 @@ pub fn command(
     text_edit: TextEdit,
     component: &LatexComponentId,
 ) -> CompletionItem {
-    let detail = glyph.map_or_else(
-        || component.detail(),
-        |glyph| format!("{}, {}", glyph, component.detail()),
-    );
+    let symbol = SYMBOL_INDEX.find(&name);
+    let detail = glyph
+        .or_else(|| symbol.map(|symbol| symbol.glyph.as_str()))
+        .map_or_else(
+            || component.detail(),
+            |glyph| format!("{}, {}", glyph, component.detail()),
+        );
     CompletionItem {
         kind: Some(adjust_kind(request, Structure::Command.completion_kind())),
         data: Some(CompletionItemData::Command.into()),
         documentation: image.and_then(|image| image_documentation(&request, &name, image)),
+        filter_text: symbol.map(Symbol::filter_text),
         text_edit: Some(text_edit),
         ..CompletionItem::new_simple(name, detail)
     }
@@ -110,6 +126,22 @@ pub fn command_snippet(
     }
 }
 
+pub fn abbreviation(
+    request: &FeatureRequest<CompletionParams>,
+    abbreviation: String,
+    snippet: String,
+    range: Range,
+) -> CompletionItem {
+    CompletionItem {
+        kind: Some(adjust_kind(request, Structure::Snippet.completion_kind())),
+        data: Some(CompletionItemData::Abbreviation.into()),
+        preselect: Some(true),
+        insert_text_format: Some(InsertTextFormat::Snippet),
+        text_edit: Some(TextEdit::new(range, snippet.clone())),
+        ..CompletionItem::new_simple(abbreviation, snippet)
+    }
+}
+
 pub fn environment(
     request: &FeatureRequest<CompletionParams>,
     name: String,
@@ -195,6 +227,22 @@ pub fn file(
     }
 }
 
+pub fn bibliography_file(
+    request: &FeatureRequest<CompletionParams>,
+    name: String,
+    path: &Path,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    CompletionItem {
+        label: name,
+        kind: Some(adjust_kind(request, Structure::File.completion_kind())),
+        data: Some(CompletionItemData::File.into()),
+        detail: Some(path.to_string_lossy().into_owned()),
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
 pub fn pgf_library(
     request: &FeatureRequest<CompletionParams>,
     name: &'static str,
@@ -263,12 +311,46 @@ pub fn color_model(
 pub fn package(
     request: &FeatureRequest<CompletionParams>,
     name: String,
+    detail: Option<String>,
     text_edit: TextEdit,
 ) -> CompletionItem {
     CompletionItem {
         label: name,
         kind: Some(adjust_kind(request, Structure::Package.completion_kind())),
         data: Some(CompletionItemData::Package.into()),
+        detail,
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
+pub fn package_option(
+    request: &FeatureRequest<CompletionParams>,
+    name: &str,
+    package: &str,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    CompletionItem {
+        label: name.into(),
+        kind: Some(adjust_kind(request, Structure::Argument.completion_kind())),
+        data: Some(CompletionItemData::PackageOption.into()),
+        detail: Some(package.into()),
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
+pub fn class_option(
+    request: &FeatureRequest<CompletionParams>,
+    name: &str,
+    class: &str,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    CompletionItem {
+        label: name.into(),
+        kind: Some(adjust_kind(request, Structure::Argument.completion_kind())),
+        data: Some(CompletionItemData::ClassOption.into()),
+        detail: Some(class.into()),
         text_edit: Some(text_edit),
         ..CompletionItem::default()
     }
@@ -277,12 +359,14 @@ pub fn package(
 pub fn class(
     request: &FeatureRequest<CompletionParams>,
     name: String,
+    detail: Option<String>,
     text_edit: TextEdit,
 ) -> CompletionItem {
     CompletionItem {
         label: name,
         kind: Some(adjust_kind(request, Structure::Class.completion_kind())),
         data: Some(CompletionItemData::Class.into()),
+        detail,
         text_edit: Some(text_edit),
         ..CompletionItem::default()
     }
@@ -327,6 +411,31 @@ pub fn citation(
     }
 }
 
+pub fn bib_item(
+    request: &FeatureRequest<CompletionParams>,
+    key: String,
+    text: String,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    let kind = Structure::Entry(BibtexEntryTypeCategory::Misc).completion_kind();
+    let filter_text = format!("{} {}", key, text);
+    let documentation = if text.is_empty() {
+        None
+    } else {
+        Some(Documentation::String(text))
+    };
+
+    CompletionItem {
+        label: key,
+        kind: Some(adjust_kind(request, kind)),
+        filter_text: Some(filter_text),
+        documentation,
+        data: Some(CompletionItemData::BibItem.into()),
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
 pub fn entry_type(
     request: &FeatureRequest<CompletionParams>,
     ty: &BibtexEntryTypeDoc,
@@ -366,6 +475,24 @@ pub fn field_name(
     }
 }
 
+pub fn field_value(
+    request: &FeatureRequest<CompletionParams>,
+    value: String,
+    count: usize,
+    rank: usize,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    CompletionItem {
+        label: value,
+        kind: Some(adjust_kind(request, Structure::Argument.completion_kind())),
+        detail: Some(format!("Used {} times", count)),
+        sort_text: Some(format!("{:04}", rank)),
+        data: Some(CompletionItemData::FieldValue.into()),
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
 pub fn argument(
     request: &FeatureRequest<CompletionParams>,
     name: &'static str,
@@ -399,24 +526,42 @@ pub fn glossary_entry(
     }
 }
 
+pub fn parameter(
+    request: &FeatureRequest<CompletionParams>,
+    name: String,
+    text_edit: TextEdit,
+) -> CompletionItem {
+    CompletionItem {
+        label: name,
+        kind: Some(adjust_kind(request, Structure::Parameter.completion_kind())),
+        data: Some(CompletionItemData::Parameter.into()),
+        text_edit: Some(text_edit),
+        ..CompletionItem::default()
+    }
+}
+
 fn image_documentation(
     request: &FeatureRequest<CompletionParams>,
     name: &str,
     image: &str,
 ) -> Option<Documentation> {
-    if supports_images(request) {
-        Some(Documentation::MarkupContent(MarkupContent {
-            kind: MarkupKind::Markdown,
-            value: format!(
-                "![{}](data:image/png;base64,{}|width=48,height=48)",
-                name, image
-            ),
-        }))
+    if supports_images(request) && image_mode(request) == LatexCompletionImageMode::Inline {
+        Some(Documentation::MarkupContent(image_markup(name, image)))
     } else {
         None
     }
 }
 
+pub fn image_markup(name: &str, image: &str) -> MarkupContent {
+    MarkupContent {
+        kind: MarkupKind::Markdown,
+        value: format!(
+            "![{}](data:image/png;base64,{}|width=48,height=48)",
+            name, image
+        ),
+    }
+}
+
 fn adjust_kind(
     request: &FeatureRequest<CompletionParams>,
     kind: CompletionItemKind,
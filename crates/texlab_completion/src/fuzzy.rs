@@ -0,0 +1,102 @@
+const MATCH_BONUS: i32 = 10;
+const CONSECUTIVE_BONUS: i32 = 15;
+const WORD_START_BONUS: i32 = 30;
+const CASE_BONUS: i32 = 1;
+const LEADING_PENALTY: i32 = 5;
+const MAX_LEADING_PENALTY: i32 = 15;
+const UNMATCHED_PENALTY: i32 = 1;
+
+/// Matches the characters of the pattern in order and scores the match
+/// similar to the fuzzy finder of Sublime Text.
+///
+/// Consecutive characters and characters at the start of a word are preferred.
+/// Returns `None` if the text does not contain every character of the pattern.
+pub fn score(pattern: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.chars().collect();
+    let mut score = 0;
+    let mut next_index = 0;
+    let mut previous_index: Option<usize> = None;
+    let mut first_index = None;
+    let mut matched = 0;
+    for p in pattern.chars() {
+        let index = (next_index..text.len()).find(|i| eq_ignore_case(text[*i], p))?;
+        score += MATCH_BONUS;
+        if index > 0 && previous_index == Some(index - 1) {
+            score += CONSECUTIVE_BONUS;
+        }
+
+        if is_word_start(&text, index) {
+            score += WORD_START_BONUS;
+        }
+
+        if text[index] == p {
+            score += CASE_BONUS;
+        }
+
+        first_index.get_or_insert(index);
+        previous_index = Some(index);
+        next_index = index + 1;
+        matched += 1;
+    }
+
+    let leading = first_index.unwrap_or(0) as i32;
+    score -= (leading * LEADING_PENALTY).min(MAX_LEADING_PENALTY);
+    score -= (text.len() - matched) as i32 * UNMATCHED_PENALTY;
+    Some(score)
+}
+
+fn eq_ignore_case(c: char, p: char) -> bool {
+    c == p || c.to_lowercase().eq(p.to_lowercase())
+}
+
+fn is_word_start(text: &[char], index: usize) -> bool {
+    if index == 0 {
+        return true;
+    }
+
+    let previous = text[index - 1];
+    let current = text[index];
+    !previous.is_alphanumeric() || (previous.is_lowercase() && current.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_match() {
+        assert_eq!(score("xyz", "alpha"), None);
+        assert_eq!(score("ba", "ab"), None);
+    }
+
+    #[test]
+    fn empty_pattern() {
+        assert!(score("", "alpha").is_some());
+    }
+
+    #[test]
+    fn prefix_before_substring() {
+        assert!(score("sec", "section").unwrap() > score("sec", "subsection").unwrap());
+    }
+
+    #[test]
+    fn exact_before_prefix() {
+        assert!(score("alpha", "alpha").unwrap() > score("alpha", "alphabet").unwrap());
+    }
+
+    #[test]
+    fn word_start() {
+        assert!(score("fb", "foo:bar").unwrap() > score("fb", "fooxbar").unwrap());
+        assert!(score("vD", "varDelta").unwrap() > score("vD", "vardelta").unwrap());
+    }
+
+    #[test]
+    fn consecutive() {
+        assert!(score("doc", "document").unwrap() > score("doc", "dxoxc").unwrap());
+    }
+
+    #[test]
+    fn case_insensitive() {
+        assert!(score("RGB", "rgb").is_some());
+    }
+}
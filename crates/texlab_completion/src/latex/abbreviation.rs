@@ -0,0 +1,189 @@
+use crate::factory;
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+/// The built-in abbreviations that expand inside of math mode.
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("//", "\\frac{$1}{$2}$0"),
+    ("sq", "\\sqrt{$1}$0"),
+    ("sr", "^2"),
+    ("cb", "^3"),
+    ("td", "^{$1}$0"),
+    ("__", "_{$1}$0"),
+    ("ooo", "\\infty"),
+    ("->", "\\to "),
+    ("<=", "\\le "),
+    (">=", "\\ge "),
+    ("!=", "\\neq "),
+    ("**", "\\cdot "),
+    ("xx", "\\times "),
+    ("...", "\\ldots"),
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexAbbreviationCompletionProvider;
+
+impl FeatureProvider for LatexAbbreviationCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let options = request
+            .options
+            .latex
+            .as_ref()
+            .and_then(|latex| latex.completion.as_ref())
+            .and_then(|completion| completion.abbreviations.clone())
+            .unwrap_or_default();
+
+        if !options.enabled() {
+            return Vec::new();
+        }
+
+        let position = request.params.text_document_position.position;
+        match &request.document().tree {
+            SyntaxTree::Latex(tree) if tree.is_math_mode(position) => (),
+            _ => return Vec::new(),
+        }
+
+        let line = CharStream::extract(
+            &request.document().text,
+            Range::new(Position::new(position.line, 0), position),
+        );
+
+        match find_abbreviation(&line, &snippets(&options)) {
+            Some((abbreviation, snippet)) => {
+                let length = abbreviation.chars().count() as u64;
+                let range = Range::new_simple(
+                    position.line,
+                    position.character - length,
+                    position.line,
+                    position.character,
+                );
+                vec![factory::abbreviation(request, abbreviation, snippet, range)]
+            }
+            None => Vec::new(),
+        }
+    }
+}
+
+fn snippets(options: &LatexAbbreviationOptions) -> HashMap<String, String> {
+    let mut snippets: HashMap<String, String> = ABBREVIATIONS
+        .iter()
+        .map(|(abbreviation, snippet)| ((*abbreviation).to_owned(), (*snippet).to_owned()))
+        .collect();
+    snippets.extend(options.snippets());
+    snippets.retain(|abbreviation, snippet| !abbreviation.is_empty() && !snippet.is_empty());
+    snippets
+}
+
+/// Finds the longest abbreviation at the end of the line.
+///
+/// Abbreviations that start with a letter only expand at the beginning of a word
+/// so that words like `esq` are left alone.
+fn find_abbreviation(line: &str, snippets: &HashMap<String, String>) -> Option<(String, String)> {
+    snippets
+        .iter()
+        .filter(|(abbreviation, _)| line.ends_with(abbreviation.as_str()))
+        .filter(|(abbreviation, _)| {
+            let previous = line[..line.len() - abbreviation.len()].chars().last();
+            match previous {
+                Some('\\') => false,
+                Some(c) if c.is_alphabetic() => !abbreviation.starts_with(char::is_alphabetic),
+                _ => true,
+            }
+        })
+        .max_by_key(|(abbreviation, _)| abbreviation.len())
+        .map(|(abbreviation, snippet)| (abbreviation.clone(), snippet.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abbreviations(
+        text: &'static str,
+        position: Position,
+        snippets: Option<HashMap<String, String>>,
+    ) -> Vec<CompletionItem> {
+        test_feature(
+            LatexAbbreviationCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", text)],
+                main_file: "foo.tex",
+                position,
+                options: Options {
+                    latex: Some(LatexOptions {
+                        completion: Some(LatexCompletionOptions {
+                            abbreviations: Some(LatexAbbreviationOptions {
+                                enabled: Some(true),
+                                snippets,
+                            }),
+                            ..LatexCompletionOptions::default()
+                        }),
+                        ..LatexOptions::default()
+                    }),
+                    bibtex: None,
+                },
+                ..FeatureSpec::default()
+            },
+        )
+    }
+
+    #[test]
+    fn fraction() {
+        let items = abbreviations("$x//$", Position::new(0, 4), None);
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].text_edit,
+            Some(TextEdit::new(
+                Range::new_simple(0, 2, 0, 4),
+                "\\frac{$1}{$2}$0".into()
+            ))
+        );
+        assert_eq!(items[0].insert_text_format, Some(InsertTextFormat::Snippet));
+    }
+
+    #[test]
+    fn inside_word() {
+        assert!(abbreviations("$esq$", Position::new(0, 4), None).is_empty());
+        assert!(abbreviations("$\\sq$", Position::new(0, 4), None).is_empty());
+    }
+
+    #[test]
+    fn outside_math() {
+        assert!(abbreviations("x//", Position::new(0, 3), None).is_empty());
+    }
+
+    #[test]
+    fn user_snippets() {
+        let mut snippets = HashMap::new();
+        snippets.insert("sq".to_owned(), String::new());
+        snippets.insert("RR".to_owned(), "\\mathbb{R}".to_owned());
+
+        let text = "$sq RR$";
+        let items = abbreviations(text, Position::new(0, 3), Some(snippets.clone()));
+        assert!(items.is_empty());
+
+        let items = abbreviations(text, Position::new(0, 6), Some(snippets));
+        assert_eq!(items[0].label, "RR");
+    }
+
+    #[test]
+    fn disabled() {
+        let items = test_feature(
+            LatexAbbreviationCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "$x//$")],
+                main_file: "foo.tex",
+                position: Position::new(0, 4),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+}
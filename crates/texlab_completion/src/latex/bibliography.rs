@@ -0,0 +1,300 @@
+use super::combinators::{self, Parameter};
+use crate::factory;
+use futures_boxed::boxed;
+use std::collections::BTreeSet;
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Component, Path, PathBuf};
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::sandbox::{self, SANDBOX_DIRECTORY};
+use texlab_workspace::*;
+use walkdir::WalkDir;
+
+/// The environment variables that BibTeX and Biber use to look up bibliographies.
+const SEARCH_PATH_VARIABLES: &[&str] = &["BIBINPUTS", "TEXINPUTS"];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexBibliographyCompletionProvider;
+
+impl FeatureProvider for LatexBibliographyCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let parameters = LANGUAGE_DATA
+            .include_commands
+            .iter()
+            .filter(|cmd| cmd.kind == LatexIncludeKind::Bibliography)
+            .map(|cmd| Parameter::new(&cmd.name, cmd.index));
+
+        combinators::argument(request, parameters, |context| async move {
+            if !request.document().is_file() {
+                return Vec::new();
+            }
+
+            let base_directory = match base_directory(request) {
+                Some(base_directory) => base_directory,
+                None => return Vec::new(),
+            };
+
+            let include_extension = LANGUAGE_DATA
+                .include_commands
+                .iter()
+                .find(|cmd| cmd.name == context.parameter.name)
+                .map_or(false, |cmd| cmd.include_extension);
+
+            let mut items = Vec::new();
+            for path in find_bibliographies(request, &base_directory) {
+                let mut name =
+                    relative_path(&path, &base_directory).unwrap_or_else(|| path.clone());
+                if !include_extension {
+                    name.set_extension("");
+                }
+
+                let name = name.to_string_lossy().replace('\\', "/");
+                let text_edit = TextEdit::new(context.range, name.clone());
+                items.push(factory::bibliography_file(request, name, &path, text_edit));
+            }
+            items
+        })
+        .await
+    }
+}
+
+/// The directory of the root document, which the inserted paths are relative to.
+fn base_directory(request: &FeatureRequest<CompletionParams>) -> Option<PathBuf> {
+    let root_directory = request
+        .options
+        .latex
+        .as_ref()
+        .and_then(|latex| latex.root_directory.clone());
+
+    if root_directory.is_some() {
+        return root_directory;
+    }
+
+    let parent = request
+        .workspace()
+        .find_parent(&request.document().uri, &request.options);
+    let uri = parent
+        .as_ref()
+        .map_or(&request.document().uri, |parent| &parent.uri);
+
+    let mut path = uri.to_file_path().ok()?;
+    path.pop();
+    Some(path)
+}
+
+fn find_bibliographies(
+    request: &FeatureRequest<CompletionParams>,
+    base_directory: &Path,
+) -> BTreeSet<PathBuf> {
+    let mut paths: BTreeSet<PathBuf> = request
+        .workspace()
+        .documents
+        .iter()
+        .filter(|document| document.is_file())
+        .filter(|document| match document.tree {
+            SyntaxTree::Latex(_) => false,
+            SyntaxTree::Bibtex(_) => true,
+        })
+        .filter_map(|document| document.uri.to_file_path().ok())
+        .collect();
+
+    for (directory, recursive) in search_directories(&request.options, base_directory) {
+        let max_depth = if recursive { std::usize::MAX } else { 1 };
+        for entry in WalkDir::new(directory)
+            .min_depth(1)
+            .max_depth(max_depth)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+        {
+            if entry.file_type().is_file() && is_bibliography(entry.path()) {
+                paths.insert(entry.into_path());
+            }
+        }
+    }
+    paths
+}
+
+/// Returns the directories of the search paths and the configured bibliography directory.
+///
+/// Like in kpathsea, a trailing `//` includes the subdirectories of a search path.
+/// Directories outside of the sandbox are skipped.
+fn search_directories(options: &Options, base_directory: &Path) -> Vec<(PathBuf, bool)> {
+    let mut directories: Vec<(PathBuf, bool)> = SEARCH_PATH_VARIABLES
+        .iter()
+        .filter_map(env::var_os)
+        .flat_map(|value| env::split_paths(&value).collect::<Vec<_>>())
+        .filter(|path| !path.as_os_str().is_empty())
+        .map(|path| {
+            let path = path.to_string_lossy().into_owned();
+            let recursive = path.ends_with("//");
+            (base_directory.join(path.trim_end_matches('/')), recursive)
+        })
+        .collect();
+
+    if let Some(directory) = options
+        .latex
+        .as_ref()
+        .and_then(|latex| latex.completion.as_ref())
+        .and_then(|completion| completion.bibliography_directory.as_ref())
+    {
+        directories.push((base_directory.join(directory), false));
+    }
+
+    /* Security Patch */
+    directories
+        .into_iter()
+        .filter_map(|(directory, recursive)| {
+            let directory = sandbox::confine(&directory, Path::new(SANDBOX_DIRECTORY))?;
+            Some((directory, recursive))
+        })
+        .collect()
+}
+
+fn is_bibliography(path: &Path) -> bool {
+    path.extension()
+        .and_then(OsStr::to_str)
+        .map_or(false, |extension| extension.eq_ignore_ascii_case("bib"))
+}
+
+fn relative_path(path: &Path, base: &Path) -> Option<PathBuf> {
+    let mut path_components = path.components().peekable();
+    let mut base_components = base.components().peekable();
+    if path_components.peek() != base_components.peek() {
+        return None;
+    }
+
+    while path_components.peek().is_some() && path_components.peek() == base_components.peek() {
+        path_components.next();
+        base_components.next();
+    }
+
+    let mut result = PathBuf::new();
+    for component in base_components {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => return None,
+            Component::Prefix(_) | Component::RootDir | Component::Normal(_) => result.push(".."),
+        }
+    }
+    result.extend(path_components);
+    Some(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_bib_resource() {
+        let items = test_feature(
+            LatexBibliographyCompletionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\documentclass{article}\n\\addbibresource{}"),
+                    FeatureSpec::file("bar/baz.bib", ""),
+                    FeatureSpec::file("qux.bib", ""),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(1, 16),
+                ..FeatureSpec::default()
+            },
+        );
+
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["bar/baz.bib", "qux.bib"]);
+        assert_eq!(
+            items[0].text_edit,
+            Some(TextEdit::new(
+                Range::new_simple(1, 16, 1, 16),
+                "bar/baz.bib".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn bibliography_without_extension() {
+        let items = test_feature(
+            LatexBibliographyCompletionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\bibliography{qux,b}"),
+                    FeatureSpec::file("bar.bib", ""),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(0, 19),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(
+            items[0].text_edit,
+            Some(TextEdit::new(Range::new_simple(0, 18, 0, 19), "bar".into()))
+        );
+    }
+
+    #[test]
+    fn outside_of_command() {
+        let items = test_feature(
+            LatexBibliographyCompletionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\include{}"),
+                    FeatureSpec::file("bar.bib", ""),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(0, 9),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert!(items.is_empty());
+    }
+
+    fn bibliography_directory(directory: &str) -> Options {
+        Options {
+            latex: Some(LatexOptions {
+                completion: Some(LatexCompletionOptions {
+                    bibliography_directory: Some(directory.into()),
+                    ..LatexCompletionOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        }
+    }
+
+    #[test]
+    fn bibliography_directory_inside_of_sandbox() {
+        let options = bibliography_directory("../bib");
+        let directories = search_directories(&options, Path::new("/minio/foo/bar"));
+        assert!(directories.contains(&(PathBuf::from("/minio/foo/bib"), false)));
+    }
+
+    #[test]
+    fn bibliography_directory_outside_of_sandbox() {
+        let options = bibliography_directory("../../../etc");
+        let directories = search_directories(&options, Path::new("/minio/foo/bar"));
+        assert!(directories
+            .iter()
+            .all(|(directory, _)| !directory.ends_with("etc")));
+    }
+
+    #[test]
+    fn relative_to_parent_directory() {
+        let path = relative_path(Path::new("/foo/bar/baz.bib"), Path::new("/foo/qux"));
+        assert_eq!(path, Some(PathBuf::from("../bar/baz.bib")));
+    }
+
+    #[test]
+    fn relative_to_same_directory() {
+        let path = relative_path(Path::new("/foo/bar.bib"), Path::new("/foo"));
+        assert_eq!(path, Some(PathBuf::from("bar.bib")));
+    }
+}
@@ -23,20 +23,34 @@ impl FeatureProvider for LatexCitationCompletionProvider {
             async move {
                 let mut items = Vec::new();
                 for document in request.related_documents() {
-                    if let SyntaxTree::Bibtex(tree) = &document.tree {
-                        for entry in &tree.entries() {
-                            if !entry.is_comment() {
-                                if let Some(key) = &entry.key {
-                                    let key = key.text().to_owned();
-                                    let text_edit = TextEdit::new(context.range, key.clone());
-                                    let item = factory::citation(
-                                        request,
-                                        document.uri.clone(),
-                                        entry,
-                                        key,
-                                        text_edit,
-                                    );
-                                    items.push(item);
+                    match &document.tree {
+                        SyntaxTree::Latex(tree) => {
+                            for item in &tree.bib_items {
+                                let key = item.key().text().to_owned();
+                                let text = CharStream::extract(&document.text, item.content_range)
+                                    .split_whitespace()
+                                    .collect::<Vec<_>>()
+                                    .join(" ");
+                                let text_edit = TextEdit::new(context.range, key.clone());
+                                let item = factory::bib_item(request, key, text, text_edit);
+                                items.push(item);
+                            }
+                        }
+                        SyntaxTree::Bibtex(tree) => {
+                            for entry in &tree.entries() {
+                                if !entry.is_comment() {
+                                    if let Some(key) = &entry.key {
+                                        let key = key.text().to_owned();
+                                        let text_edit = TextEdit::new(context.range, key.clone());
+                                        let item = factory::citation(
+                                            request,
+                                            document.uri.clone(),
+                                            entry,
+                                            key,
+                                            text_edit,
+                                        );
+                                        items.push(item);
+                                    }
                                 }
                             }
                         }
@@ -139,4 +153,30 @@ mod tests {
         );
         assert!(items.is_empty());
     }
+
+    #[test]
+    fn bib_item() {
+        let items = test_feature(
+            LatexCitationCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\cite{}\n\\begin{thebibliography}{9}\n\\bibitem{foo} Foo\n  Bar\n\\end{thebibliography}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(0, 6),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].label, "foo");
+        assert_eq!(
+            items[0].documentation,
+            Some(Documentation::String("Foo Bar".into()))
+        );
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(0, 6, 0, 6))
+        );
+    }
 }
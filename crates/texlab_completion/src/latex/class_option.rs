@@ -0,0 +1,100 @@
+use super::combinators::{self, Parameter};
+use super::package_option::used_options;
+use crate::factory;
+use futures_boxed::boxed;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexClassOptionCompletionProvider;
+
+impl FeatureProvider for LatexClassOptionCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let parameters = LANGUAGE_DATA
+            .include_commands
+            .iter()
+            .filter(|cmd| cmd.kind == LatexIncludeKind::Class)
+            .map(|cmd| Parameter::new(&cmd.name, 0));
+
+        combinators::option(request, parameters, |context| {
+            async move {
+                let used_options = used_options(&context);
+                let mut items = Vec::new();
+                for class in context.command.extract_comma_separated_words(0) {
+                    for LatexClassOptions {
+                        class: name,
+                        options,
+                    } in &LANGUAGE_DATA.class_options
+                    {
+                        if name != class.text() {
+                            continue;
+                        }
+
+                        for option in options
+                            .iter()
+                            .filter(|option| !used_options.contains(&option.as_str()))
+                        {
+                            let text_edit = TextEdit::new(context.range, option.clone());
+                            let item = factory::class_option(request, option, name, text_edit);
+                            items.push(item);
+                        }
+                    }
+                }
+                items
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(text: &'static str, position: Position) -> Vec<CompletionItem> {
+        test_feature(
+            LatexClassOptionCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", text)],
+                main_file: "foo.tex",
+                position,
+                ..FeatureSpec::default()
+            },
+        )
+    }
+
+    #[test]
+    fn article() {
+        let items = items("\\documentclass[a4paper, 1]{article}", Position::new(0, 25));
+        assert!(items.iter().all(|item| item.label != "a4paper"));
+        assert!(items.iter().any(|item| item.label == "11pt"));
+        assert!(items.iter().all(|item| item.label != "openany"));
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(0, 24, 0, 25))
+        );
+        assert_eq!(items[0].detail.as_deref(), Some("article"));
+    }
+
+    #[test]
+    fn beamer() {
+        let items = items("\\documentclass[]{beamer}", Position::new(0, 15));
+        assert!(items.iter().any(|item| item.label == "aspectratio="));
+        assert!(items.iter().all(|item| item.label != "twocolumn"));
+    }
+
+    #[test]
+    fn unknown_class() {
+        assert!(items("\\documentclass[]{foo}", Position::new(0, 15)).is_empty());
+    }
+
+    #[test]
+    fn package() {
+        assert!(items("\\usepackage[]{article}", Position::new(0, 12)).is_empty());
+    }
+}
@@ -64,22 +64,51 @@ where
                         continue;
                     }
 
-                    let mut range = None;
-                    for child in &args.children {
-                        if let LatexContent::Text(text) = &child {
-                            for word in &text.words {
-                                if word.range().contains(position) {
-                                    range = Some(word.range());
-                                    break;
-                                }
-                            }
-                        }
+                    let context = ArgumentContext {
+                        parameter,
+                        command: Arc::clone(&command),
+                        range: word_range(args, position),
+                    };
+                    return execute(context).await;
+                }
+            }
+        }
+    }
+    Vec::new()
+}
+
+/// Like `argument` but for the optional arguments in square brackets.
+pub async fn option<'a, I, E, F>(
+    request: &'a FeatureRequest<CompletionParams>,
+    mut parameters: I,
+    execute: E,
+) -> Vec<CompletionItem>
+where
+    I: Iterator<Item = Parameter<'a>>,
+    E: FnOnce(ArgumentContext<'a>) -> F,
+    F: Future<Output = Vec<CompletionItem>>,
+{
+    if let SyntaxTree::Latex(tree) = &request.document().tree {
+        let position = request.params.text_document_position.position;
+        if let Some(command) = find_command(tree, position) {
+            for parameter in parameters.by_ref() {
+                if command.name.text() != parameter.name {
+                    continue;
+                }
+
+                if let Some(options) = command.options.get(parameter.index) {
+                    if options.right.is_some() && !options.range().contains_exclusive(position) {
+                        continue;
                     }
-                    let text_range = range.unwrap_or_else(|| Range::new(position, position));
+
+                    if options.right.is_none() && !options.range().contains(position) {
+                        continue;
+                    }
+
                     let context = ArgumentContext {
                         parameter,
                         command: Arc::clone(&command),
-                        range: text_range,
+                        range: word_range(options, position),
                     };
                     return execute(context).await;
                 }
@@ -89,6 +118,20 @@ where
     Vec::new()
 }
 
+/// Returns the range of the word at the given position or an empty range if there is none.
+fn word_range(group: &LatexGroup, position: Position) -> Range {
+    for child in &group.children {
+        if let LatexContent::Text(text) = &child {
+            for word in &text.words {
+                if word.range().contains(position) {
+                    return word.range();
+                }
+            }
+        }
+    }
+    Range::new(position, position)
+}
+
 pub async fn argument_word<'a, I, E, F>(
     request: &'a FeatureRequest<CompletionParams>,
     mut parameters: I,
@@ -121,6 +121,26 @@ mod tests {
         assert!(items.is_empty());
     }
 
+    #[test]
+    fn command_symbol() {
+        let items = test_feature(
+            LatexComponentCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "$\\appro$")],
+                main_file: "foo.tex",
+                position: Position::new(0, 7),
+                ..FeatureSpec::default()
+            },
+        );
+        let item = items.iter().find(|item| item.label == "approx").unwrap();
+        assert!(item.detail.as_ref().unwrap().starts_with("≈, "));
+        assert!(item
+            .filter_text
+            .as_ref()
+            .unwrap()
+            .contains("approximately equal"));
+    }
+
     #[test]
     fn command_package() {
         let items = test_feature(
@@ -274,4 +294,46 @@ mod tests {
         );
         assert!(!items.is_empty());
     }
+
+    #[test]
+    fn command_image_inline() {
+        let items = test_feature(
+            LatexComponentCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\Delt")],
+                main_file: "foo.tex",
+                position: Position::new(0, 5),
+                ..FeatureSpec::default()
+            },
+        );
+        let item = items.iter().find(|item| item.label == "Delta").unwrap();
+        assert!(item.documentation.is_some());
+    }
+
+    #[test]
+    fn command_image_resolve() {
+        let items = test_feature(
+            LatexComponentCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\Delt")],
+                main_file: "foo.tex",
+                position: Position::new(0, 5),
+                options: Options {
+                    latex: Some(LatexOptions {
+                        completion: Some(LatexCompletionOptions {
+                            images: Some(LatexCompletionImageMode::Resolve),
+                            bibliography_directory: None,
+                            abbreviations: None,
+                            max_items: None,
+                        }),
+                        ..LatexOptions::default()
+                    }),
+                    bibtex: None,
+                },
+                ..FeatureSpec::default()
+            },
+        );
+        let item = items.iter().find(|item| item.label == "Delta").unwrap();
+        assert!(item.documentation.is_none());
+    }
 }
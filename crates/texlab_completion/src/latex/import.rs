@@ -37,7 +37,7 @@ async fn import<F>(
     factory: F,
 ) -> Vec<CompletionItem>
 where
-    F: Fn(&FeatureRequest<CompletionParams>, String, TextEdit) -> CompletionItem,
+    F: Fn(&FeatureRequest<CompletionParams>, String, Option<String>, TextEdit) -> CompletionItem,
 {
     let extension = if kind == LatexIncludeKind::Package {
         "sty"
@@ -54,16 +54,23 @@ where
     combinators::argument(request, parameters, |context| {
         async move {
             let resolver = request.distribution.resolver().await;
-            COMPONENT_DATABASE
-                .components
-                .iter()
-                .flat_map(|comp| comp.file_names.iter())
-                .chain(resolver.files_by_name.keys())
+            resolver
+                .files_by_name
+                .keys()
+                .chain(
+                    COMPONENT_DATABASE
+                        .components
+                        .iter()
+                        .flat_map(|comp| comp.file_names.iter()),
+                )
                 .filter(|file_name| file_name.ends_with(extension))
                 .map(|file_name| {
                     let stem = &file_name[0..file_name.len() - 4];
+                    let detail = resolver
+                        .tex_tree(file_name)
+                        .map(|tree| tree.to_string_lossy().into_owned());
                     let text_edit = TextEdit::new(context.range, stem.to_owned());
-                    factory(request, stem.into(), text_edit)
+                    factory(request, stem.into(), detail, text_edit)
                 })
                 .collect()
         }
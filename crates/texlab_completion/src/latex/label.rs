@@ -6,6 +6,20 @@ use texlab_protocol::*;
 use texlab_syntax::*;
 use texlab_workspace::*;
 
+/// The reference commands that accept a comma-separated list of labels.
+const MULTI_REFERENCE_COMMANDS: &[&str] = &[
+    "\\cref",
+    "\\Cref",
+    "\\cref*",
+    "\\Cref*",
+    "\\cpageref",
+    "\\Cpageref",
+    "\\labelcref",
+    "\\labelcpageref",
+    "\\zcref",
+    "\\zcpageref",
+];
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct LatexLabelCompletionProvider;
 
@@ -25,6 +39,19 @@ impl FeatureProvider for LatexLabelCompletionProvider {
             async move {
                 let options = &request.options;
                 let source = Self::find_source(&context);
+                let is_list = MULTI_REFERENCE_COMMANDS.contains(&context.parameter.name);
+                let selected_names: Vec<&str> = if is_list {
+                    context
+                        .command
+                        .extract_comma_separated_words(context.parameter.index)
+                        .into_iter()
+                        .filter(|word| word.range() != context.range)
+                        .map(LatexToken::text)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+
                 let mut items = Vec::new();
                 for document in request.related_documents() {
                     let workspace = Arc::clone(&request.view.workspace);
@@ -41,14 +68,21 @@ impl FeatureProvider for LatexLabelCompletionProvider {
                         {
                             let outline_context = OutlineContext::parse(&view, &label, &outline);
                             for name in label.names() {
+                                if selected_names.contains(&name.text()) {
+                                    continue;
+                                }
+
                                 let text = name.text().to_owned();
                                 let text_edit = TextEdit::new(context.range, text.clone());
-                                let item = factory::label(
+                                let mut item = factory::label(
                                     request,
                                     text,
                                     text_edit,
                                     outline_context.as_ref(),
                                 );
+                                if is_list {
+                                    item.commit_characters = Some(vec![",".into()]);
+                                }
                                 items.push(item);
                             }
                         }
@@ -152,4 +186,82 @@ mod tests {
         let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
         assert_eq!(labels, vec!["foo"]);
     }
+
+    #[test]
+    fn inside_of_zref() {
+        let items = test_feature(
+            LatexLabelCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\zlabel{foo}\\customlabel{bar}{1.2}\n\\zref{}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 6),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn second_argument_of_crefrange() {
+        let items = test_feature(
+            LatexLabelCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\label{foo}\\label{bar}\n\\cpagerefrange{foo}{}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 20),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn cref_list() {
+        let items = test_feature(
+            LatexLabelCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\label{foo}\\label{bar}\\label{baz}\n\\cref{foo,}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 10),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["bar", "baz"]);
+        assert_eq!(items[0].commit_characters, Some(vec![",".to_owned()]));
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(1, 10, 1, 10))
+        );
+    }
+
+    #[test]
+    fn ref_single() {
+        let items = test_feature(
+            LatexLabelCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\label{foo}\\label{bar}\n\\ref{foo}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 6),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["foo", "bar"]);
+        assert_eq!(items[0].commit_characters, None);
+    }
 }
@@ -1,6 +1,9 @@
+pub mod abbreviation;
 pub mod argument;
 pub mod begin_command;
+pub mod bibliography;
 pub mod citation;
+pub mod class_option;
 pub mod color;
 pub mod color_model;
 pub mod combinators;
@@ -9,6 +12,9 @@ pub mod glossary;
 pub mod import;
 pub mod include;
 pub mod label;
+pub mod package_option;
+pub mod parameter;
+pub mod range;
 pub mod theorem;
 pub mod tikz;
 pub mod user;
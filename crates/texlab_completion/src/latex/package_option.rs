@@ -0,0 +1,115 @@
+use super::combinators::{self, ArgumentContext, Parameter};
+use crate::factory;
+use futures_boxed::boxed;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexPackageOptionCompletionProvider;
+
+impl FeatureProvider for LatexPackageOptionCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let parameters = LANGUAGE_DATA
+            .include_commands
+            .iter()
+            .filter(|cmd| cmd.kind == LatexIncludeKind::Package)
+            .map(|cmd| Parameter::new(&cmd.name, 0));
+
+        combinators::option(request, parameters, |context| {
+            async move {
+                let used_options = used_options(&context);
+                let mut items = Vec::new();
+                for package in context.command.extract_comma_separated_words(0) {
+                    for LatexPackageOptions {
+                        package: name,
+                        options,
+                    } in &LANGUAGE_DATA.package_options
+                    {
+                        if name != package.text() {
+                            continue;
+                        }
+
+                        for option in options
+                            .iter()
+                            .filter(|option| !used_options.contains(&option.as_str()))
+                        {
+                            let text_edit = TextEdit::new(context.range, option.clone());
+                            let item = factory::package_option(request, option, name, text_edit);
+                            items.push(item);
+                        }
+                    }
+                }
+                items
+            }
+        })
+        .await
+    }
+}
+
+/// Returns the options of the command except for the one that is being completed.
+pub(super) fn used_options<'a>(context: &'a ArgumentContext) -> Vec<&'a str> {
+    context
+        .command
+        .options
+        .iter()
+        .flat_map(|group| &group.children)
+        .filter_map(|child| match child {
+            LatexContent::Text(text) => Some(text),
+            _ => None,
+        })
+        .flat_map(|text| &text.words)
+        .filter(|word| word.range() != context.range)
+        .map(LatexToken::text)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(text: &'static str, position: Position) -> Vec<CompletionItem> {
+        test_feature(
+            LatexPackageOptionCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", text)],
+                main_file: "foo.tex",
+                position,
+                ..FeatureSpec::default()
+            },
+        )
+    }
+
+    #[test]
+    fn inside_options() {
+        let items = items("\\usepackage[ngerman, eng]{babel}", Position::new(0, 24));
+        assert!(!items.is_empty());
+        assert!(items.iter().all(|item| item.label != "ngerman"));
+        assert!(items.iter().any(|item| item.label == "english"));
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(0, 21, 0, 24))
+        );
+        assert_eq!(items[0].detail.as_deref(), Some("babel"));
+    }
+
+    #[test]
+    fn require_package() {
+        let items = items("\\RequirePackage[]{geometry}", Position::new(0, 16));
+        assert!(items.iter().any(|item| item.label == "margin="));
+    }
+
+    #[test]
+    fn unknown_package() {
+        assert!(items("\\usepackage[]{foo}", Position::new(0, 12)).is_empty());
+    }
+
+    #[test]
+    fn inside_argument() {
+        assert!(items("\\usepackage[]{babel}", Position::new(0, 16)).is_empty());
+    }
+}
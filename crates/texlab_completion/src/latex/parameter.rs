@@ -0,0 +1,119 @@
+use crate::factory;
+use futures_boxed::boxed;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexParameterCompletionProvider;
+
+impl FeatureProvider for LatexParameterCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let position = request.params.text_document_position.position;
+        let tree = match &request.document().tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return Vec::new(),
+        };
+
+        let definition = match tree
+            .command_definitions
+            .iter()
+            .filter(|def| def.implementation.range().contains_exclusive(position))
+            .max_by_key(|def| (def.start().line, def.start().character))
+        {
+            Some(definition) => definition,
+            None => return Vec::new(),
+        };
+
+        let range = match Self::parameter_range(&request.document().text, position) {
+            Some(range) => range,
+            None => return Vec::new(),
+        };
+
+        (1..=definition.argument_count())
+            .map(|number| {
+                let name = format!("#{}", number);
+                let text_edit = TextEdit::new(range, name.clone());
+                factory::parameter(request, name, text_edit)
+            })
+            .collect()
+    }
+}
+
+impl LatexParameterCompletionProvider {
+    fn parameter_range(text: &str, position: Position) -> Option<Range> {
+        let line = text.lines().nth(position.line as usize)?;
+        let chars: Vec<char> = line.chars().take(position.character as usize).collect();
+        let mut start = chars.len();
+        while start > 0 && chars[start - 1].is_ascii_digit() {
+            start -= 1;
+        }
+
+        if start == 0 || chars[start - 1] != '#' {
+            return None;
+        }
+
+        Some(Range::new_simple(
+            position.line,
+            (start - 1) as u64,
+            position.line,
+            position.character,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inside_implementation() {
+        let items = test_feature(
+            LatexParameterCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\newcommand{\\foo}[2]{#}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 22),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+        assert_eq!(labels, vec!["#1", "#2"]);
+        assert_eq!(
+            items[0].text_edit.as_ref().map(|edit| edit.range),
+            Some(Range::new_simple(0, 21, 0, 22))
+        );
+    }
+
+    #[test]
+    fn without_arguments() {
+        let items = test_feature(
+            LatexParameterCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\newcommand{\\foo}{#}")],
+                main_file: "foo.tex",
+                position: Position::new(0, 19),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn outside_implementation() {
+        let items = test_feature(
+            LatexParameterCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\newcommand{\\foo}[2]{}\n#")],
+                main_file: "foo.tex",
+                position: Position::new(1, 1),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+}
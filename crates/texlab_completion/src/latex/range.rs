@@ -0,0 +1,107 @@
+use super::combinators;
+use crate::factory::{self, LatexComponentId};
+use futures_boxed::boxed;
+use texlab_protocol::{CompletionItem, CompletionParams};
+use texlab_workspace::*;
+
+const RANGE_SNIPPETS: &[(&str, &str)] = &[
+    ("crefrange", "crefrange{$1}{$2}"),
+    ("Crefrange", "Crefrange{$1}{$2}"),
+    ("cpagerefrange", "cpagerefrange{$1}{$2}"),
+    ("Cpagerefrange", "Cpagerefrange{$1}{$2}"),
+    ("numrange", "numrange{${1:from}}{${2:to}}"),
+    ("SIrange", "SIrange{${1:from}}{${2:to}}{${3:unit}}"),
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexRangeCommandCompletionProvider;
+
+impl FeatureProvider for LatexRangeCommandCompletionProvider {
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        combinators::command(request, |_| {
+            async move {
+                let mut items = Vec::new();
+                for component in COMPONENT_DATABASE.related_components(request.related_documents())
+                {
+                    let file_names = component.file_names.iter().map(AsRef::as_ref).collect();
+                    let id = LatexComponentId::Component(file_names);
+                    for &(name, template) in RANGE_SNIPPETS {
+                        if component
+                            .commands
+                            .iter()
+                            .any(|command| command.name == name)
+                        {
+                            let image = COMPONENT_DATABASE.command_image(name);
+                            let item =
+                                factory::command_snippet(request, name, image, template, &id);
+                            items.push(item);
+                        }
+                    }
+                }
+                items
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{InsertTextFormat, Position};
+
+    #[test]
+    fn siunitx() {
+        let items = test_feature(
+            LatexRangeCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\usepackage{siunitx}\n\\num")],
+                main_file: "foo.tex",
+                position: Position::new(1, 4),
+                ..FeatureSpec::default()
+            },
+        );
+        let item = items.iter().find(|item| item.label == "numrange").unwrap();
+        assert_eq!(
+            item.insert_text.as_ref().map(AsRef::as_ref),
+            Some("numrange{${1:from}}{${2:to}}")
+        );
+        assert_eq!(item.insert_text_format, Some(InsertTextFormat::Snippet));
+    }
+
+    #[test]
+    fn cleveref() {
+        let items = test_feature(
+            LatexRangeCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\usepackage{cleveref}\n\\cref",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 5),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["crefrange", "cpagerefrange", "Cpagerefrange"]);
+    }
+
+    #[test]
+    fn without_package() {
+        let items = test_feature(
+            LatexRangeCommandCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\num")],
+                main_file: "foo.tex",
+                position: Position::new(0, 4),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.is_empty());
+    }
+}
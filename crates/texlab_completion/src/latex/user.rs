@@ -63,10 +63,29 @@ impl FeatureProvider for LatexUserEnvironmentCompletionProvider {
                 let mut items = Vec::new();
                 for document in request.related_documents() {
                     if let SyntaxTree::Latex(tree) = &document.tree {
+                        for definition in &tree.env.definitions {
+                            let name = definition.name().text().to_owned();
+                            let text_edit = TextEdit::new(context.range, name.clone());
+                            let item = factory::environment(
+                                request,
+                                name,
+                                text_edit,
+                                &LatexComponentId::User,
+                            );
+                            items.push(item);
+                        }
+
                         for environment in &tree.env.environments {
-                            if environment.left.command == context.command
-                                || environment.right.command == context.command
-                            {
+                            if environment.left.command == context.command {
+                                continue;
+                            }
+
+                            if environment.right.command == context.command {
+                                if let Some(item) =
+                                    Self::make_item(request, &environment.left, context.range)
+                                {
+                                    items.push(item);
+                                }
                                 continue;
                             }
 
@@ -152,4 +171,40 @@ mod tests {
             .collect();
         assert_eq!(labels, vec!["bar"]);
     }
+
+    #[test]
+    fn environment_definition() {
+        let items = test_feature(
+            LatexUserEnvironmentCompletionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.tex",
+                        "\\usepackage{bar}\n\\newenvironment{foo}{}{}\n\\begin{fo}",
+                    ),
+                    FeatureSpec::file("bar.sty", "\\NewDocumentEnvironment{bar}{}{}{}"),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(2, 8),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn environment_end() {
+        let items = test_feature(
+            LatexUserEnvironmentCompletionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\begin{foo}\n\\end{f}")],
+                main_file: "foo.tex",
+                position: Position::new(1, 6),
+                ..FeatureSpec::default()
+            },
+        );
+        let labels: Vec<&str> = items.iter().map(|item| item.label.as_ref()).collect();
+        assert_eq!(labels, vec!["foo"]);
+    }
 }
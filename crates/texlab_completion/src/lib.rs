@@ -1,17 +1,24 @@
 mod bibtex;
 mod factory;
+mod fuzzy;
 mod latex;
+mod math_mode;
 mod preselect;
 mod quality;
+mod symbol;
 
-pub use self::factory::CompletionItemData;
+pub use self::factory::{image_markup, CompletionItemData};
 
 use self::bibtex::command::BibtexCommandCompletionProvider;
 use self::bibtex::entry_type::BibtexEntryTypeCompletionProvider;
 use self::bibtex::field_name::BibtexFieldNameCompletionProvider;
+use self::bibtex::field_value::BibtexFieldValueCompletionProvider;
+use self::latex::abbreviation::LatexAbbreviationCompletionProvider;
 use self::latex::argument::LatexArgumentCompletionProvider;
 use self::latex::begin_command::LatexBeginCommandCompletionProvider;
+use self::latex::bibliography::LatexBibliographyCompletionProvider;
 use self::latex::citation::LatexCitationCompletionProvider;
+use self::latex::class_option::LatexClassOptionCompletionProvider;
 use self::latex::color::LatexColorCompletionProvider;
 use self::latex::color_model::LatexColorModelCompletionProvider;
 use self::latex::component::*;
@@ -19,9 +26,13 @@ use self::latex::glossary::LatexGlossaryCompletionProvider;
 use self::latex::import::{LatexClassImportProvider, LatexPackageImportProvider};
 use self::latex::include::LatexIncludeCompletionProvider;
 use self::latex::label::LatexLabelCompletionProvider;
+use self::latex::package_option::LatexPackageOptionCompletionProvider;
+use self::latex::parameter::LatexParameterCompletionProvider;
+use self::latex::range::LatexRangeCommandCompletionProvider;
 use self::latex::theorem::LatexTheoremEnvironmentCompletionProvider;
 use self::latex::tikz::*;
 use self::latex::user::*;
+use self::math_mode::MathModeCompletionProvider;
 use self::preselect::PreselectCompletionProvider;
 use self::quality::OrderByQualityCompletionProvider;
 use futures_boxed::boxed;
@@ -30,21 +41,22 @@ use std::hash::{Hash, Hasher};
 use texlab_protocol::{CompletionItem, CompletionParams};
 use texlab_workspace::*;
 
-pub const COMPLETION_LIMIT: usize = 50;
-
 type MergeProvider = ConcatProvider<CompletionParams, CompletionItem>;
 
 pub struct CompletionProvider {
-    provider: OrderByQualityCompletionProvider<PreselectCompletionProvider<MergeProvider>>,
+    provider: OrderByQualityCompletionProvider<
+        MathModeCompletionProvider<PreselectCompletionProvider<MergeProvider>>,
+    >,
 }
 
 impl CompletionProvider {
     pub fn new() -> Self {
         Self {
-            provider: OrderByQualityCompletionProvider::new(PreselectCompletionProvider::new(
-                ConcatProvider::new(vec![
+            provider: OrderByQualityCompletionProvider::new(MathModeCompletionProvider::new(
+                PreselectCompletionProvider::new(ConcatProvider::new(vec![
                     Box::new(BibtexEntryTypeCompletionProvider),
                     Box::new(BibtexFieldNameCompletionProvider),
+                    Box::new(BibtexFieldValueCompletionProvider),
                     Box::new(BibtexCommandCompletionProvider),
                     Box::new(LatexPgfLibraryCompletionProvider),
                     Box::new(LatexTikzLibraryCompletionProvider),
@@ -56,17 +68,28 @@ impl CompletionProvider {
                     Box::new(LatexLabelCompletionProvider),
                     Box::new(LatexCitationCompletionProvider),
                     Box::new(LatexGlossaryCompletionProvider),
+                    Box::new(LatexParameterCompletionProvider),
                     Box::new(LatexIncludeCompletionProvider),
+                    Box::new(LatexBibliographyCompletionProvider),
                     Box::new(LatexClassImportProvider),
+                    Box::new(LatexClassOptionCompletionProvider),
                     Box::new(LatexPackageImportProvider),
+                    Box::new(LatexPackageOptionCompletionProvider),
                     Box::new(LatexBeginCommandCompletionProvider),
+                    Box::new(LatexRangeCommandCompletionProvider),
+                    Box::new(LatexAbbreviationCompletionProvider),
                     Box::new(LatexComponentCommandCompletionProvider),
                     Box::new(LatexUserCommandCompletionProvider),
                     Box::new(LatexUserEnvironmentCompletionProvider),
-                ]),
+                ])),
             )),
         }
     }
+
+    /// Ranks the recently used citations first among the items of the same quality.
+    pub fn set_recent_citations(&self, keys: Vec<String>) {
+        self.provider.set_recent_citations(keys);
+    }
 }
 
 impl Default for CompletionProvider {
@@ -81,6 +104,14 @@ impl FeatureProvider for CompletionProvider {
 
     #[boxed]
     async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let limit = request
+            .options
+            .latex
+            .as_ref()
+            .and_then(|latex| latex.completion.clone())
+            .unwrap_or_default()
+            .max_items();
+
         self.provider
             .execute(request)
             .await
@@ -88,7 +119,7 @@ impl FeatureProvider for CompletionProvider {
             .map(LabeledCompletionItem)
             .unique()
             .map(|item| item.0)
-            .take(COMPLETION_LIMIT)
+            .take(limit)
             .collect()
     }
 }
@@ -0,0 +1,119 @@
+use crate::factory::CompletionItemData;
+use futures_boxed::boxed;
+use texlab_protocol::{CompletionItem, CompletionParams};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug)]
+pub struct MathModeCompletionProvider<F> {
+    provider: F,
+}
+
+impl<F> MathModeCompletionProvider<F> {
+    pub fn new(provider: F) -> Self {
+        Self { provider }
+    }
+}
+
+impl<F> FeatureProvider for MathModeCompletionProvider<F>
+where
+    F: FeatureProvider<Params = CompletionParams, Output = Vec<CompletionItem>> + Send + Sync,
+{
+    type Params = CompletionParams;
+    type Output = Vec<CompletionItem>;
+
+    #[boxed]
+    async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
+        let items = self.provider.execute(request).await;
+        let tree = match &request.document().tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return items,
+        };
+
+        let position = request.params.text_document_position.position;
+        let (preferred, other): (Vec<_>, Vec<_>) = if tree.is_math_mode(position) {
+            items
+                .into_iter()
+                .filter(|item| !is_command(item) || !LANGUAGE_DATA.is_text_command(&name(item)))
+                .partition(|item| is_math_command(item))
+        } else {
+            items.into_iter().partition(|item| !is_math_command(item))
+        };
+
+        preferred.into_iter().chain(other).collect()
+    }
+}
+
+fn is_command(item: &CompletionItem) -> bool {
+    match item
+        .data
+        .clone()
+        .and_then(|data| serde_json::from_value(data).ok())
+    {
+        Some(CompletionItemData::Command) | Some(CompletionItemData::CommandSnippet) => true,
+        _ => false,
+    }
+}
+
+fn is_math_command(item: &CompletionItem) -> bool {
+    is_command(item) && LANGUAGE_DATA.is_math_command(&name(item))
+}
+
+fn name(item: &CompletionItem) -> String {
+    format!("\\{}", item.label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::latex::component::LatexComponentCommandCompletionProvider;
+    use texlab_protocol::Position;
+
+    #[test]
+    fn inside_inline_math() {
+        let items = test_feature(
+            MathModeCompletionProvider::new(LatexComponentCommandCompletionProvider),
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "$\\s$")],
+                main_file: "foo.tex",
+                position: Position::new(0, 3),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.iter().all(|item| item.label != "section"));
+        assert!(is_math_command(&items[0]));
+    }
+
+    #[test]
+    fn inside_text_in_math() {
+        let items = test_feature(
+            MathModeCompletionProvider::new(LatexComponentCommandCompletionProvider),
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\begin{equation}\\text{\\s}\\end{equation}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(0, 24),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.iter().any(|item| item.label == "section"));
+    }
+
+    #[test]
+    fn outside_math() {
+        let items = test_feature(
+            MathModeCompletionProvider::new(LatexComponentCommandCompletionProvider),
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\s")],
+                main_file: "foo.tex",
+                position: Position::new(0, 2),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(items.iter().any(|item| item.label == "section"));
+        let index = items.iter().position(is_math_command).unwrap();
+        assert!(items[index..].iter().all(is_math_command));
+    }
+}
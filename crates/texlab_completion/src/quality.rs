@@ -1,17 +1,30 @@
+use crate::factory::CompletionItemData;
+use crate::fuzzy;
 use futures_boxed::boxed;
 use std::borrow::Cow;
+use std::cmp::Reverse;
+use std::sync::Mutex;
 use texlab_protocol::RangeExt;
-use texlab_protocol::{CompletionItem, CompletionParams, Position};
+use texlab_protocol::{CompletionItem, CompletionParams, Position, Range};
 use texlab_syntax::*;
 use texlab_workspace::*;
 
 pub struct OrderByQualityCompletionProvider<F> {
     pub provider: F,
+    recent_citations: Mutex<Vec<String>>,
 }
 
 impl<F> OrderByQualityCompletionProvider<F> {
     pub fn new(provider: F) -> Self {
-        Self { provider }
+        Self {
+            provider,
+            recent_citations: Mutex::default(),
+        }
+    }
+
+    /// Ranks the recently used citations first among the items of the same quality.
+    pub fn set_recent_citations(&self, keys: Vec<String>) {
+        *self.recent_citations.lock().unwrap() = keys;
     }
 }
 
@@ -24,13 +37,22 @@ where
 
     #[boxed]
     async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
-        let query = Self::get_query(
-            request.document(),
-            request.params.text_document_position.position,
-        );
-        let mut items = self.provider.execute(&request).await;
-        items.sort_by_key(|item| -Self::get_quality(&query, &item));
-        items
+        let document = request.document();
+        let position = request.params.text_document_position.position;
+        let query = Self::get_query(document, position);
+        let mut items: Vec<(i32, CompletionItem)> = self
+            .provider
+            .execute(&request)
+            .await
+            .into_iter()
+            .filter_map(|item| {
+                let quality = Self::get_quality(document, position, &query, &item)?;
+                Some((quality, item))
+            })
+            .collect();
+        let recent_citations = self.recent_citations.lock().unwrap().clone();
+        sort_by_quality(&mut items, &recent_citations);
+        items.into_iter().map(|(_, item)| item).collect()
     }
 }
 
@@ -86,40 +108,109 @@ impl<F> OrderByQualityCompletionProvider<F> {
         }
     }
 
-    fn get_quality(query: &Option<Cow<str>>, item: &CompletionItem) -> i32 {
+    /// Scores the label or the filter text of the item by the text that has been typed so far.
+    ///
+    /// Items that do not match the typed text are removed.
+    fn get_quality(
+        document: &Document,
+        position: Position,
+        query: &Option<Cow<str>>,
+        item: &CompletionItem,
+    ) -> Option<i32> {
         if item.preselect == Some(true) {
-            return 8;
+            return Some(i32::MAX);
         }
 
-        let label = &item.label;
-        if let Some(query) = query {
-            if label == query {
-                return 7;
-            }
-
-            if label.to_lowercase() == query.to_lowercase() {
-                return 6;
+        let query = match &item.text_edit {
+            Some(edit) if edit.range.contains(position) => {
+                let range = Range::new(edit.range.start, position);
+                Some(CharStream::extract(&document.text, range).into())
             }
+            _ => query.clone(),
+        };
 
-            if label.starts_with(query.as_ref()) {
-                return 5;
+        match query {
+            Some(query) if query.chars().any(char::is_alphanumeric) => {
+                let filter_score = item
+                    .filter_text
+                    .as_ref()
+                    .and_then(|text| fuzzy::score(&query, text));
+                fuzzy::score(&query, &item.label).max(filter_score)
             }
+            _ => Some(0),
+        }
+    }
+}
 
-            if label.to_lowercase().starts_with(&query.to_lowercase()) {
-                return 4;
-            }
+/// Orders the items by their quality and breaks ties
+/// with the order of the recently used citations.
+fn sort_by_quality(items: &mut [(i32, CompletionItem)], recent_citations: &[String]) {
+    items.sort_by_cached_key(|(quality, item)| {
+        let is_citation = item
+            .data
+            .clone()
+            .and_then(|data| serde_json::from_value(data).ok())
+            .map_or(false, |data| match data {
+                CompletionItemData::Citation { .. } | CompletionItemData::BibItem => true,
+                _ => false,
+            });
 
-            if label.contains(query.as_ref()) {
-                return 3;
-            }
+        let recency = recent_citations
+            .iter()
+            .position(|key| is_citation && *key == item.label)
+            .unwrap_or_else(|| recent_citations.len());
+        (Reverse(*quality), recency)
+    });
+}
 
-            if label.to_lowercase().contains(&query.to_lowercase()) {
-                return 2;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use texlab_protocol::Uri;
 
-            1
-        } else {
-            0
+    fn item(label: &str, data: CompletionItemData) -> CompletionItem {
+        CompletionItem {
+            label: label.into(),
+            data: Some(data.into()),
+            ..CompletionItem::default()
         }
     }
+
+    fn citation(key: &str) -> CompletionItem {
+        let uri = Uri::from_file_path(env::temp_dir().join("foo.bib")).unwrap();
+        item(
+            key,
+            CompletionItemData::Citation {
+                uri,
+                key: key.into(),
+            },
+        )
+    }
+
+    fn labels(items: &[(i32, CompletionItem)]) -> Vec<&str> {
+        items.iter().map(|(_, item)| item.label.as_ref()).collect()
+    }
+
+    #[test]
+    fn recent_citations_break_ties() {
+        let recent_citations = vec!["bar".to_owned(), "baz".to_owned()];
+        let mut items = vec![
+            (0, citation("foo")),
+            (0, item("baz", CompletionItemData::Label)),
+            (0, citation("baz")),
+            (0, citation("bar")),
+        ];
+        sort_by_quality(&mut items, &recent_citations);
+        assert_eq!(labels(&items), vec!["bar", "baz", "foo", "baz"]);
+        assert_eq!(items[3].1.data, Some(CompletionItemData::Label.into()));
+    }
+
+    #[test]
+    fn quality_before_recent_citations() {
+        let recent_citations = vec!["bar".to_owned()];
+        let mut items = vec![(1, citation("bar")), (2, citation("foo"))];
+        sort_by_quality(&mut items, &recent_citations);
+        assert_eq!(labels(&items), vec!["foo", "bar"]);
+    }
 }
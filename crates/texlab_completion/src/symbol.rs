@@ -0,0 +1,74 @@
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// A command that produces a single symbol together with the Unicode name
+/// of the symbol and common ways to describe it.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Symbol {
+    pub command: String,
+    pub glyph: String,
+    pub name: String,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+impl Symbol {
+    /// The text that is matched against the input of the user,
+    /// e.g. `approx ≈ almost equal to approximately equal`.
+    pub fn filter_text(&self) -> String {
+        let mut text = format!("{} {} {}", self.command, self.glyph, self.name);
+        for alias in &self.aliases {
+            text.push(' ');
+            text.push_str(alias);
+        }
+        text
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SymbolIndex {
+    symbols: HashMap<String, Symbol>,
+}
+
+impl SymbolIndex {
+    /// Looks up the symbol of a command without the leading backslash.
+    pub fn find(&self, command: &str) -> Option<&Symbol> {
+        self.symbols.get(command)
+    }
+}
+
+pub static SYMBOL_INDEX: Lazy<SymbolIndex> = Lazy::new(|| {
+    const JSON: &str = include_str!("symbols.json");
+    let symbols: Vec<Symbol> =
+        serde_json::from_str(JSON).expect("Failed to deserialize symbols.json");
+    SymbolIndex {
+        symbols: symbols
+            .into_iter()
+            .map(|symbol| (symbol.command.clone(), symbol))
+            .collect(),
+    }
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find() {
+        let symbol = SYMBOL_INDEX.find("approx").unwrap();
+        assert_eq!(symbol.glyph, "≈");
+        assert_eq!(symbol.name, "almost equal to");
+        assert!(SYMBOL_INDEX.find("foo").is_none());
+    }
+
+    #[test]
+    fn filter_text() {
+        let symbol = SYMBOL_INDEX.find("alpha").unwrap();
+        assert_eq!(symbol.filter_text(), "alpha α greek small letter alpha");
+
+        let symbol = SYMBOL_INDEX.find("neq").unwrap();
+        assert_eq!(symbol.filter_text(), "neq ≠ not equal to not equal unequal");
+    }
+}
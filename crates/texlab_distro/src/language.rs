@@ -15,8 +15,8 @@ impl Language {
 
     pub fn by_language_id(language_id: &str) -> Option<Self> {
         match language_id {
-            "latex" | "tex" => Some(Language::Latex),
-            "bibtex" | "bib" => Some(Language::Bibtex),
+            "latex" | "tex" | "latex-expl3" | "doctex" => Some(Language::Latex),
+            "bibtex" | "bib" | "biblatex" => Some(Language::Bibtex),
             _ => None,
         }
     }
@@ -2,18 +2,20 @@ mod compile;
 mod kpsewhich;
 mod language;
 mod miktex;
+mod shared;
 mod tectonic;
 mod texlive;
 
 pub use self::compile::*;
 pub use self::language::Language;
+pub use self::shared::SharedDistribution;
 
 use self::miktex::Miktex;
 use self::tectonic::Tectonic;
 use self::texlive::Texlive;
 use futures_boxed::boxed;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use tokio::process::Command;
@@ -64,6 +66,17 @@ impl Resolver {
     pub fn new(files_by_name: HashMap<String, PathBuf>) -> Self {
         Self { files_by_name }
     }
+
+    /// Returns the root of the TeX directory structure that contains the given file.
+    pub fn tex_tree(&self, file_name: &str) -> Option<&Path> {
+        let path = self.files_by_name.get(file_name)?;
+        path.ancestors().skip(1).find(|directory| {
+            path.strip_prefix(directory)
+                .ok()
+                .and_then(|suffix| suffix.components().next())
+                .map_or(false, |component| component.as_os_str() == "tex")
+        })
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -72,6 +85,14 @@ pub enum LoadError {
     CorruptFileDatabase,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InstallError {
+    NotSupported,
+    InstallerNotFound,
+    PackageNotFound,
+    Failed,
+}
+
 pub trait Distribution: Send + Sync {
     fn kind(&self) -> DistributionKind;
 
@@ -99,6 +120,14 @@ pub trait Distribution: Send + Sync {
 
     #[boxed]
     async fn resolver(&self) -> Arc<Resolver>;
+
+    #[boxed]
+    async fn install_package<'a>(&'a self, _name: &'a str) -> Result<(), InstallError> {
+        Err(InstallError::NotSupported)
+    }
+
+    #[boxed]
+    async fn refresh(&self) {}
 }
 
 impl dyn Distribution {
@@ -150,3 +179,23 @@ impl Distribution for UnknownDistribution {
         Arc::new(Resolver::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tex_tree() {
+        let tree = PathBuf::from("/usr/share/texmf-dist");
+        let mut files_by_name = HashMap::new();
+        files_by_name.insert(
+            "amsmath.sty".to_owned(),
+            tree.join("tex/latex/amsmath/amsmath.sty"),
+        );
+        files_by_name.insert("foo.sty".to_owned(), PathBuf::from("/foo/foo.sty"));
+        let resolver = Resolver::new(files_by_name);
+        assert_eq!(resolver.tex_tree("amsmath.sty"), Some(tree.as_path()));
+        assert_eq!(resolver.tex_tree("foo.sty"), None);
+        assert_eq!(resolver.tex_tree("bar.sty"), None);
+    }
+}
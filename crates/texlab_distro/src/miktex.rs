@@ -1,6 +1,6 @@
 use super::compile::*;
 use super::kpsewhich;
-use super::{Distribution, DistributionKind, LoadError, Resolver};
+use super::{Distribution, DistributionKind, InstallError, LoadError, Resolver};
 use byteorder::{LittleEndian, ReadBytesExt};
 use futures::lock::Mutex;
 use futures_boxed::boxed;
@@ -9,7 +9,9 @@ use std::fs;
 use std::io::{self, Cursor};
 use std::mem;
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
 use std::sync::Arc;
+use tokio::process::Command;
 
 #[derive(Debug, Default)]
 pub struct Miktex {
@@ -47,6 +49,50 @@ impl Distribution for Miktex {
         let resolver = self.resolver.lock().await;
         Arc::clone(&resolver)
     }
+
+    #[boxed]
+    async fn install_package<'a>(&'a self, name: &'a str) -> Result<(), InstallError> {
+        let output = Command::new("mpm")
+            .arg(format!("--find-packages={}", name))
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .await
+            .map_err(|_| InstallError::InstallerNotFound)?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let package = match package_name(&stdout) {
+            Some(package) if output.status.success() => package,
+            _ => return Err(InstallError::PackageNotFound),
+        };
+
+        let status = Command::new("mpm")
+            .arg(format!("--install={}", package))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .await
+            .map_err(|_| InstallError::InstallerNotFound)?;
+
+        if !status.success() {
+            return Err(InstallError::Failed);
+        }
+
+        self.load().await.map_err(|_| InstallError::Failed)
+    }
+}
+
+/// Extracts the package that contains a file from the output of `mpm --find-packages`,
+/// which lists one package per line, e.g. `pgf` for `tikz.sty`.
+fn package_name(output: &str) -> Option<&str> {
+    output
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        .find(|name| {
+            name.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        })
 }
 
 const DATABASE_PATH: &str = "miktex/data/le";
@@ -115,3 +161,20 @@ fn read_string(bytes: &[u8], offset: usize) -> io::Result<&str> {
     std::str::from_utf8(&bytes[offset..offset + length])
         .map_err(|_| io::ErrorKind::InvalidData.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_name_first_line() {
+        assert_eq!(package_name("pgf\n"), Some("pgf"));
+        assert_eq!(package_name("\nms  \nxcolor\n"), Some("ms"));
+    }
+
+    #[test]
+    fn package_name_empty() {
+        assert_eq!(package_name(""), None);
+        assert_eq!(package_name("--install=foo; rm -rf /\n"), None);
+    }
+}
@@ -0,0 +1,137 @@
+use super::compile::*;
+use super::{Distribution, DistributionKind, InstallError, LoadError, Resolver};
+use futures_boxed::boxed;
+use std::sync::{Arc, RwLock};
+
+/// A distribution that can be shared between servers and detected again on demand.
+///
+/// The file database is loaded once when the distribution is detected,
+/// so that connecting clients do not parse it again.
+pub struct SharedDistribution {
+    current: RwLock<LoadedDistribution>,
+}
+
+struct LoadedDistribution {
+    distro: Arc<Box<dyn Distribution>>,
+    result: Result<(), LoadError>,
+}
+
+impl SharedDistribution {
+    pub async fn new(distro: Box<dyn Distribution>) -> Self {
+        Self {
+            current: RwLock::new(LoadedDistribution::load(distro).await),
+        }
+    }
+
+    pub async fn detect() -> Self {
+        Self::new(Distribution::detect().await).await
+    }
+
+    fn current(&self) -> Arc<Box<dyn Distribution>> {
+        Arc::clone(&self.current.read().unwrap().distro)
+    }
+}
+
+impl LoadedDistribution {
+    async fn load(distro: Box<dyn Distribution>) -> Self {
+        let result = distro.load().await;
+        Self {
+            distro: Arc::new(distro),
+            result,
+        }
+    }
+}
+
+impl Distribution for SharedDistribution {
+    fn kind(&self) -> DistributionKind {
+        self.current().kind()
+    }
+
+    fn supports_format(&self, format: Format) -> bool {
+        self.current().supports_format(format)
+    }
+
+    fn output_kind(&self, format: Format) -> OutputKind {
+        self.current().output_kind(format)
+    }
+
+    #[boxed]
+    async fn compile<'a>(
+        &'a self,
+        params: CompileParams<'a>,
+    ) -> Result<CompileResult, CompileError> {
+        let distro = self.current();
+        distro.compile(params).await
+    }
+
+    #[boxed]
+    async fn load(&self) -> Result<(), LoadError> {
+        self.current.read().unwrap().result
+    }
+
+    #[boxed]
+    async fn resolver(&self) -> Arc<Resolver> {
+        let distro = self.current();
+        distro.resolver().await
+    }
+
+    #[boxed]
+    async fn install_package<'a>(&'a self, name: &'a str) -> Result<(), InstallError> {
+        let distro = self.current();
+        distro.install_package(name).await
+    }
+
+    #[boxed]
+    async fn refresh(&self) {
+        let loaded = LoadedDistribution::load(Distribution::detect().await).await;
+        *self.current.write().unwrap() = loaded;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Debug, Default)]
+    struct CountingDistribution {
+        loads: Arc<AtomicUsize>,
+    }
+
+    impl Distribution for CountingDistribution {
+        fn kind(&self) -> DistributionKind {
+            DistributionKind::Unknown
+        }
+
+        fn supports_format(&self, _format: Format) -> bool {
+            false
+        }
+
+        #[boxed]
+        async fn load(&self) -> Result<(), LoadError> {
+            self.loads.fetch_add(1, Ordering::SeqCst);
+            Err(LoadError::CorruptFileDatabase)
+        }
+
+        #[boxed]
+        async fn resolver(&self) -> Arc<Resolver> {
+            Arc::new(Resolver::default())
+        }
+    }
+
+    #[test]
+    fn load_once() {
+        let loads = Arc::new(AtomicUsize::new(0));
+        let distro = CountingDistribution {
+            loads: Arc::clone(&loads),
+        };
+        let shared = block_on(SharedDistribution::new(Box::new(distro)));
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+        for _ in 0..2 {
+            assert_eq!(block_on(shared.load()), Err(LoadError::CorruptFileDatabase));
+        }
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+}
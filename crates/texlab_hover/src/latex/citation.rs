@@ -17,16 +17,35 @@ impl FeatureProvider for LatexCitationHoverProvider {
         &'a self,
         request: &'a FeatureRequest<TextDocumentPositionParams>,
     ) -> Option<Hover> {
-        let (tree, entry) = Self::get_entry(request)?;
+        let (document, tree, entry) = match Self::get_entry(request) {
+            Some(entry) => entry,
+            None => return Self::get_bib_item_hover(request),
+        };
+
         if entry.is_comment() {
             None
         } else {
             let key = entry.key.as_ref().unwrap().text();
             match render_citation(&tree, key) {
-                Some(markdown) => Some(Hover {
-                    contents: HoverContents::Markup(markdown),
-                    range: None,
-                }),
+                Some(mut markdown) => {
+                    let options = request
+                        .options
+                        .bibtex
+                        .as_ref()
+                        .and_then(|opts| opts.hover.clone())
+                        .unwrap_or_default();
+
+                    if !options.compact() {
+                        if let Some(details) = render_details(entry, &document.uri, &options) {
+                            markdown.value = format!("{}\n\n{}", markdown.value, details);
+                        }
+                    }
+
+                    Some(Hover {
+                        contents: HoverContents::Markup(markdown),
+                        range: None,
+                    })
+                }
                 None => {
                     warn!("Failed to render entry: {}", key);
                     None
@@ -39,15 +58,43 @@ impl FeatureProvider for LatexCitationHoverProvider {
 impl LatexCitationHoverProvider {
     fn get_entry(
         request: &FeatureRequest<TextDocumentPositionParams>,
-    ) -> Option<(&BibtexSyntaxTree, &BibtexEntry)> {
+    ) -> Option<(&Document, &BibtexSyntaxTree, &BibtexEntry)> {
         let key = Self::get_key(request)?;
         for document in request.related_documents() {
             if let SyntaxTree::Bibtex(tree) = &document.tree {
                 for entry in tree.entries() {
                     if let Some(current_key) = &entry.key {
                         if current_key.text() == key {
-                            return Some((tree, entry));
+                            return Some((document.as_ref(), tree, entry));
+                        }
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn get_bib_item_hover(request: &FeatureRequest<TextDocumentPositionParams>) -> Option<Hover> {
+        let key = Self::get_key(request)?;
+        for document in request.related_documents() {
+            if let SyntaxTree::Latex(tree) = &document.tree {
+                for item in &tree.bib_items {
+                    if item.key().text() == key {
+                        let text = CharStream::extract(&document.text, item.content_range)
+                            .split_whitespace()
+                            .collect::<Vec<_>>()
+                            .join(" ");
+                        if text.is_empty() {
+                            return None;
                         }
+
+                        return Some(Hover {
+                            contents: HoverContents::Markup(MarkupContent {
+                                kind: MarkupKind::PlainText,
+                                value: text,
+                            }),
+                            range: None,
+                        });
                     }
                 }
             }
@@ -58,10 +105,8 @@ impl LatexCitationHoverProvider {
     fn get_key(request: &FeatureRequest<TextDocumentPositionParams>) -> Option<&str> {
         match &request.document().tree {
             SyntaxTree::Latex(tree) => tree
-                .citations
-                .iter()
-                .flat_map(LatexCitation::keys)
-                .find(|citation| citation.range().contains(request.params.position))
+                .citation_key_at(request.params.position)
+                .or_else(|| tree.bib_item_key_at(request.params.position))
                 .map(LatexToken::text),
             SyntaxTree::Bibtex(tree) => {
                 for entry in tree.entries() {
@@ -76,3 +121,170 @@ impl LatexCitationHoverProvider {
         }
     }
 }
+
+/// Renders the abstract and the links to the full text of an entry.
+fn render_details(entry: &BibtexEntry, uri: &Uri, options: &BibtexHoverOptions) -> Option<String> {
+    let mut sections = Vec::new();
+    if let Some(text) = field_text(entry, "abstract") {
+        sections.push(truncate(&text, options.abstract_length()));
+    }
+
+    let mut links = Vec::new();
+    if let Some(files) = field_text(entry, "file") {
+        let base_directory = uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| path.parent().map(ToOwned::to_owned));
+
+        for file in files.split(';').filter_map(file_path) {
+            let path = match &base_directory {
+                Some(base_directory) => base_directory.join(file),
+                None => file.into(),
+            };
+
+            if let Ok(target) = Url::from_file_path(&path) {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| file.to_owned());
+                links.push(format!("[{}]({})", name, target));
+            }
+        }
+    }
+
+    if let Some(url) = field_text(entry, "url") {
+        links.push(format!("[{}]({})", url, url));
+    }
+
+    if !links.is_empty() {
+        sections.push(links.join(" · "));
+    }
+
+    if sections.is_empty() {
+        None
+    } else {
+        Some(sections.join("\n\n"))
+    }
+}
+
+fn field_text(entry: &BibtexEntry, name: &str) -> Option<String> {
+    let content = entry.field(name)?.content.as_ref()?;
+    let text = format_content(content, &BibtexFormattingParams::default())
+        .replace(|c: char| c == '{' || c == '}' || c == '"', "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if text.is_empty() {
+        None
+    } else {
+        Some(text)
+    }
+}
+
+/// Extracts the path of a `file` field, which may use the `Description:Path:Type` format.
+fn file_path(file: &str) -> Option<&str> {
+    let file = file.trim();
+    let parts: Vec<&str> = file.split(':').collect();
+    let path = if parts.len() == 3 { parts[1] } else { file };
+    if path.is_empty() {
+        None
+    } else {
+        Some(path)
+    }
+}
+
+fn truncate(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        text.to_owned()
+    } else {
+        let truncated: String = text.chars().take(max_length).collect();
+        format!("{}…", truncated.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn details(text: &str, options: &BibtexHoverOptions) -> Option<String> {
+        let tree = BibtexSyntaxTree::from(text);
+        let uri = Uri::from_file_path(env::temp_dir().join("foo.bib")).unwrap();
+        render_details(tree.entries()[0], &uri, options)
+    }
+
+    #[test]
+    fn abstract_and_links() {
+        let details = details(
+            "@article{foo, abstract = {Lorem ipsum\n  dolor sit amet}, \
+             file = {:bar.pdf:PDF}, url = {https://example.com}}",
+            &BibtexHoverOptions::default(),
+        )
+        .unwrap();
+
+        let target = Url::from_file_path(env::temp_dir().join("bar.pdf")).unwrap();
+        assert_eq!(
+            details,
+            format!(
+                "Lorem ipsum dolor sit amet\n\n[bar.pdf]({}) · [https://example.com](https://example.com)",
+                target
+            )
+        );
+    }
+
+    #[test]
+    fn truncated_abstract() {
+        let options = BibtexHoverOptions {
+            abstract_length: Some(11),
+            ..BibtexHoverOptions::default()
+        };
+        let details = details("@article{foo, abstract = {Lorem ipsum dolor}}", &options);
+        assert_eq!(details.unwrap(), "Lorem ipsum…");
+    }
+
+    #[test]
+    fn no_details() {
+        let details = details(
+            "@article{foo, title = {Bar}}",
+            &BibtexHoverOptions::default(),
+        );
+        assert_eq!(details, None);
+    }
+
+    #[test]
+    fn compact() {
+        let hover = test_feature(
+            LatexCitationHoverProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\addbibresource{bar.bib}\n\\cite{foo}"),
+                    FeatureSpec::file(
+                        "bar.bib",
+                        "@article{foo, author = {Foo Bar}, title = {Baz}, year = {2019}, \
+                         abstract = {Qux}}",
+                    ),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(1, 7),
+                options: Options {
+                    bibtex: Some(BibtexOptions {
+                        formatting: None,
+                        hover: Some(BibtexHoverOptions {
+                            compact: Some(true),
+                            abstract_length: None,
+                        }),
+                        dialect: None,
+                    }),
+                    latex: None,
+                },
+                ..FeatureSpec::default()
+            },
+        );
+
+        match hover.unwrap().contents {
+            HoverContents::Markup(markdown) => assert!(!markdown.value.contains("Qux")),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -22,7 +22,8 @@ impl FeatureProvider for LatexComponentHoverProvider {
                 {
                     for path in include.paths() {
                         if path.range().contains(request.params.position) {
-                            let documentation = COMPONENT_DATABASE.documentation(path.text())?;
+                            let documentation =
+                                Self::documentation(request, include.kind, path.text()).await?;
                             return Some(Hover {
                                 contents: HoverContents::Markup(documentation),
                                 range: Some(path.range()),
@@ -35,3 +36,36 @@ impl FeatureProvider for LatexComponentHoverProvider {
         None
     }
 }
+
+impl LatexComponentHoverProvider {
+    async fn documentation(
+        request: &FeatureRequest<TextDocumentPositionParams>,
+        kind: LatexIncludeKind,
+        name: &str,
+    ) -> Option<MarkupContent> {
+        let extension = if kind == LatexIncludeKind::Package {
+            "sty"
+        } else {
+            "cls"
+        };
+
+        let resolver = request.distribution.resolver().await;
+        let tex_tree = resolver
+            .tex_tree(&format!("{}.{}", name, extension))
+            .map(|tree| format!("Installed in {}", tree.to_string_lossy()));
+
+        let value = match (COMPONENT_DATABASE.documentation(name), tex_tree) {
+            (Some(documentation), Some(tex_tree)) => {
+                format!("{}\n\n{}", documentation.value, tex_tree)
+            }
+            (Some(documentation), None) => documentation.value,
+            (None, Some(tex_tree)) => tex_tree,
+            (None, None) => return None,
+        };
+
+        Some(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value,
+        })
+    }
+}
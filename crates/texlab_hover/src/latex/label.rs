@@ -1,10 +1,33 @@
 use futures_boxed::boxed;
+use std::cmp::Reverse;
 use std::sync::Arc;
 use texlab_protocol::RangeExt;
 use texlab_protocol::*;
 use texlab_syntax::*;
 use texlab_workspace::*;
 
+const TABLE_ENVIRONMENTS: &[&str] = &[
+    "tabular",
+    "tabular*",
+    "tabularx",
+    "tabulary",
+    "longtable",
+    "array",
+    "matrix",
+    "pmatrix",
+    "bmatrix",
+    "Bmatrix",
+    "vmatrix",
+    "Vmatrix",
+    "smallmatrix",
+];
+
+const RULE_COMMANDS: &[&str] = &["\\hline", "\\toprule", "\\midrule", "\\bottomrule"];
+
+const MAX_TABLE_ROWS: usize = 3;
+
+const MAX_CELL_WIDTH: usize = 12;
+
 pub struct LatexLabelHoverProvider;
 
 impl FeatureProvider for LatexLabelHoverProvider {
@@ -15,15 +38,31 @@ impl FeatureProvider for LatexLabelHoverProvider {
     async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
         if let SyntaxTree::Latex(tree) = &request.document().tree {
             let reference = Self::find_reference(tree, request.params.position)?;
-            let (document, definition) = Self::find_definition(&request.view, reference)?;
+            let references = Self::find_range(tree, reference).unwrap_or_else(|| vec![reference]);
+            let preview = if references.len() == 1 {
+                Self::find_table_preview(&request.view, references[0])
+            } else {
+                None
+            };
+
+            let contexts: Vec<_> = references
+                .into_iter()
+                .filter_map(|reference| Self::find_context(request, reference))
+                .map(|context| context.reference())
+                .collect();
+
+            let value = match (contexts.is_empty(), preview) {
+                (true, None) => return None,
+                (true, Some(preview)) => preview,
+                (false, None) => contexts.join(" – "),
+                (false, Some(preview)) => format!("{}\n\n{}", contexts.join(" – "), preview),
+            };
 
-            let workspace = Arc::clone(&request.view.workspace);
-            let view = DocumentView::new(workspace, document, &request.options);
-            let outline = Outline::analyze(&view, &request.options);
-            let outline_context = OutlineContext::parse(&view, &definition, &outline)?;
-            let markup = outline_context.documentation();
             Some(Hover {
-                contents: HoverContents::Markup(markup),
+                contents: HoverContents::Markup(MarkupContent {
+                    kind: MarkupKind::PlainText,
+                    value,
+                }),
                 range: Some(reference.range()),
             })
         } else {
@@ -34,16 +73,90 @@ impl FeatureProvider for LatexLabelHoverProvider {
 
 impl LatexLabelHoverProvider {
     fn find_reference(tree: &LatexSyntaxTree, position: Position) -> Option<&LatexToken> {
-        for label in &tree.structure.labels {
-            let names = label.names();
-            if names.len() == 1 && label.range().contains(position) {
-                return Some(&label.names()[0]);
+        tree.label_name_at(position).or_else(|| {
+            tree.structure
+                .labels
+                .iter()
+                .find(|label| label.range().contains(position))
+                .map(LatexLabel::names)
+                .filter(|names| names.len() == 1)
+                .map(|names| names[0])
+        })
+    }
+
+    fn find_range<'a>(
+        tree: &'a LatexSyntaxTree,
+        reference: &LatexToken,
+    ) -> Option<Vec<&'a LatexToken>> {
+        let labels: Vec<_> = tree
+            .structure
+            .labels
+            .iter()
+            .filter(|label| label.kind != LatexLabelKind::Definition)
+            .filter(|label| label.range().contains(reference.start()))
+            .collect();
+
+        if labels.len() == 2 {
+            Some(labels.into_iter().flat_map(LatexLabel::names).collect())
+        } else {
+            None
+        }
+    }
+
+    fn find_context(
+        request: &FeatureRequest<TextDocumentPositionParams>,
+        reference: &LatexToken,
+    ) -> Option<OutlineContext> {
+        let (document, definition) = Self::find_definition(&request.view, reference)?;
+        let workspace = Arc::clone(&request.view.workspace);
+        let view = DocumentView::new(workspace, document, &request.options);
+        let outline = Outline::analyze(&view, &request.options);
+        OutlineContext::parse(&view, &definition, &outline)
+    }
+
+    fn find_table_preview(view: &DocumentView, reference: &LatexToken) -> Option<String> {
+        let (document, definition) = Self::find_definition(view, reference)?;
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            let table = Self::find_table(tree, definition)?;
+            let body = CharStream::extract(
+                &document.text,
+                Range::new(table.left.end(), table.right.start()),
+            );
+            render_table(&body)
+        } else {
+            None
+        }
+    }
+
+    /// Finds the table or matrix that belongs to the innermost environment of the label.
+    fn find_table<'a>(
+        tree: &'a LatexSyntaxTree,
+        label: &LatexLabel,
+    ) -> Option<&'a LatexEnvironment> {
+        let mut ancestors: Vec<_> = tree
+            .env
+            .environments
+            .iter()
+            .filter(|env| !env.is_root())
+            .filter(|env| env.range().contains(label.start()))
+            .collect();
+        ancestors.sort_by_key(|env| Reverse(env.start()));
+
+        for ancestor in ancestors {
+            if is_table(ancestor) {
+                return Some(ancestor);
             }
 
-            for name in &names {
-                if name.range().contains(position) {
-                    return Some(name);
-                }
+            let table = tree
+                .env
+                .environments
+                .iter()
+                .filter(|env| is_table(env))
+                .filter(|env| ancestor.range().contains(env.start()))
+                .min_by_key(|env| env.start());
+
+            if table.is_some() {
+                return table;
             }
         }
         None
@@ -69,3 +182,193 @@ impl LatexLabelHoverProvider {
         None
     }
 }
+
+fn is_table(environment: &LatexEnvironment) -> bool {
+    environment
+        .left
+        .name()
+        .map_or(false, |name| TABLE_ENVIRONMENTS.contains(&name.text()))
+}
+
+fn render_table(text: &str) -> Option<String> {
+    let rows: Vec<_> = text
+        .split("\\\\")
+        .map(|row| row.split('&').map(render_cell).collect::<Vec<_>>())
+        .filter(|cells| cells.iter().any(|cell| !cell.is_empty()))
+        .take(MAX_TABLE_ROWS)
+        .map(|cells| cells.join(" | "))
+        .collect();
+
+    if rows.is_empty() {
+        None
+    } else {
+        Some(rows.join("\n"))
+    }
+}
+
+fn render_cell(cell: &str) -> String {
+    let text = cell
+        .split_whitespace()
+        .filter(|word| !RULE_COMMANDS.contains(word) && !word.starts_with("\\cline"))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if text.chars().count() > MAX_CELL_WIDTH {
+        let truncated: String = text.chars().take(MAX_CELL_WIDTH - 1).collect();
+        format!("{}…", truncated)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn range() {
+        let hover = test_feature(
+            LatexLabelHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\section{Foo}\\label{sec:foo}\n\\section{Bar}\\label{sec:bar}\n\\crefrange{sec:foo}{sec:bar}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 24),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert_eq!(
+            hover.unwrap().contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "Section (Foo) – Section (Bar)".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn single() {
+        let hover = test_feature(
+            LatexLabelHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\section{Foo}\\label{sec:foo}\n\\ref{sec:foo}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 7),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert_eq!(
+            hover.unwrap().contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "Section (Foo)".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn appendix() {
+        let hover = test_feature(
+            LatexLabelHoverProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.tex",
+                        "\\appendix\n\\section{Foo}\\label{sec:foo}\n\\ref{sec:foo}",
+                    ),
+                    FeatureSpec::file("foo.aux", "\\newlabel{sec:foo}{{B}{4}}"),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(2, 7),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert_eq!(
+            hover.unwrap().contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "Appendix B (Foo)".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn front_matter() {
+        let hover = test_feature(
+            LatexLabelHoverProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.tex",
+                        "\\frontmatter\n\\chapter{Foo}\\label{chap:foo}\n\\ref{chap:foo}",
+                    ),
+                    FeatureSpec::file("foo.aux", "\\newlabel{chap:foo}{{0}{4}}"),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(2, 7),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert_eq!(
+            hover.unwrap().contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "Chapter (Foo)".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn table() {
+        let hover = test_feature(
+            LatexLabelHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\begin{table}\n\
+                     \\begin{tabular}{ll}\n\
+                     \\hline\n\
+                     Name & Description \\\\\n\
+                     foo & A very long description \\\\\n\
+                     bar & baz \\\\\n\
+                     qux & quux\n\
+                     \\end{tabular}\n\
+                     \\caption{Foo}\\label{tab:foo}\n\
+                     \\end{table}\n\
+                     \\ref{tab:foo}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(10, 7),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert_eq!(
+            hover.unwrap().contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "Table: Foo\n\nName | Description\nfoo | A very long…\nbar | baz".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn render_matrix() {
+        assert_eq!(
+            render_table("\n  a & b \\\\ c & d\n")
+                .as_ref()
+                .map(AsRef::as_ref),
+            Some("a | b\nc | d")
+        );
+        assert_eq!(render_table("  "), None);
+    }
+}
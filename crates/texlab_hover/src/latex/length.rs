@@ -0,0 +1,355 @@
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+const LENGTHS: &[(&str, &str)] = &[
+    ("\\textwidth", "Width of the text area"),
+    ("\\textheight", "Height of the text area"),
+    ("\\linewidth", "Width of a line in the current environment"),
+    ("\\columnwidth", "Width of a column"),
+    ("\\columnsep", "Distance between columns"),
+    ("\\paperwidth", "Width of the page"),
+    ("\\paperheight", "Height of the page"),
+    (
+        "\\parindent",
+        "Indentation of the first line of a paragraph",
+    ),
+    ("\\parskip", "Vertical space between paragraphs"),
+    (
+        "\\baselineskip",
+        "Distance between the baselines of consecutive lines",
+    ),
+    ("\\topmargin", "Vertical offset of the header"),
+    ("\\headheight", "Height of the header"),
+    ("\\headsep", "Distance between the header and the text"),
+    (
+        "\\footskip",
+        "Distance between the last line of text and the footer",
+    ),
+    ("\\oddsidemargin", "Left margin of odd pages"),
+    ("\\evensidemargin", "Left margin of even pages"),
+    ("\\marginparwidth", "Width of marginal notes"),
+    (
+        "\\marginparsep",
+        "Distance between the text and marginal notes",
+    ),
+    (
+        "\\tabcolsep",
+        "Half of the space between the columns of a table",
+    ),
+    ("\\arrayrulewidth", "Thickness of the rules in a table"),
+    ("\\fboxsep", "Distance between a framed box and its frame"),
+    ("\\fboxrule", "Thickness of the frame of a framed box"),
+    ("\\unitlength", "Unit of the picture environment"),
+    ("\\abovecaptionskip", "Vertical space above a caption"),
+    ("\\belowcaptionskip", "Vertical space below a caption"),
+    ("\\itemsep", "Vertical space between list items"),
+    ("\\topsep", "Vertical space above and below a list"),
+];
+
+const ASSIGNMENT_COMMANDS: &[&str] = &["\\setlength", "\\addtolength"];
+
+const DEFINITION_COMMANDS: &[&str] = &["\\newlength"];
+
+/// The size of the units in TeX points.
+const UNITS: &[(&str, f64)] = &[
+    ("pt", 1.0),
+    ("bp", 72.27 / 72.0),
+    ("mm", 72.27 / 25.4),
+    ("cm", 72.27 / 2.54),
+    ("in", 72.27),
+    ("pc", 12.0),
+    ("dd", 1238.0 / 1157.0),
+    ("cc", 14856.0 / 1157.0),
+    ("sp", 1.0 / 65536.0),
+];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexLengthHoverProvider;
+
+impl FeatureProvider for LatexLengthHoverProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Option<Hover>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<TextDocumentPositionParams>,
+    ) -> Option<Hover> {
+        let tree = match &request.document().tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return None,
+        };
+
+        let position = request.params.position;
+        let (value, name, range) = match Self::find_assignment(tree, position) {
+            Some((name, group)) => {
+                let end = group.right.as_ref()?.start();
+                let text = CharStream::extract(
+                    &request.document().text,
+                    Range::new(group.left.end(), end),
+                );
+                (Some(text.trim().to_owned()), name, group.range())
+            }
+            None => {
+                let command = tree.find_command_by_name(position)?;
+                (None, command.name.text().to_owned(), command.name.range())
+            }
+        };
+
+        let description = Self::describe(&request.view, &name)?;
+        let lengths = Self::shown_lengths(request).await;
+        let mut lines = vec![format!("{}: {}", name, description)];
+        match value {
+            Some(value) => {
+                if let Some(points) = parse_dimension(&value, &lengths) {
+                    lines.push(format!("{} = {}", value, format_points(points)));
+                }
+            }
+            None => {
+                if let Some(points) = lengths.get(&name) {
+                    lines.push(format!("Resolved value: {}", format_points(*points)));
+                }
+            }
+        }
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: lines.join("\n\n"),
+            }),
+            range: Some(range),
+        })
+    }
+}
+
+impl LatexLengthHoverProvider {
+    /// Finds the value argument of `\setlength{\name}{value}` at the given position.
+    fn find_assignment(
+        tree: &LatexSyntaxTree,
+        position: Position,
+    ) -> Option<(String, &LatexGroup)> {
+        tree.commands
+            .iter()
+            .filter(|command| ASSIGNMENT_COMMANDS.contains(&command.name.text()))
+            .filter(|command| command.args.len() >= 2)
+            .find(|command| command.args[1].range().contains(position))
+            .and_then(|command| {
+                let name = length_name(&command.args[0])?;
+                Some((name.to_owned(), command.args[1].as_ref()))
+            })
+    }
+
+    fn describe(view: &DocumentView, name: &str) -> Option<String> {
+        if let Some((_, description)) = LENGTHS.iter().find(|(length, _)| *length == name) {
+            return Some((*description).to_owned());
+        }
+
+        let is_user_defined = view.related_documents.iter().any(|document| {
+            if let SyntaxTree::Latex(tree) = &document.tree {
+                tree.commands
+                    .iter()
+                    .filter(|command| DEFINITION_COMMANDS.contains(&command.name.text()))
+                    .filter_map(|command| command.args.get(0))
+                    .any(|arg| length_name(arg) == Some(name))
+            } else {
+                false
+            }
+        });
+
+        if is_user_defined {
+            Some("User-defined length".into())
+        } else {
+            None
+        }
+    }
+
+    /// Reads the lengths that were printed with `\showthe` from the build log.
+    async fn shown_lengths(
+        request: &FeatureRequest<TextDocumentPositionParams>,
+    ) -> HashMap<String, f64> {
+        let parent = request
+            .workspace()
+            .find_parent(&request.document().uri, &request.options);
+        let uri = parent
+            .as_ref()
+            .map_or(&request.document().uri, |parent| &parent.uri);
+
+        let log_path = uri
+            .to_file_path()
+            .ok()
+            .and_then(|tex_path| request.options.resolve_output_file(&tex_path, "log"));
+
+        match log_path {
+            Some(log_path) => match tokio::fs::read(&log_path).await {
+                Ok(bytes) => parse_shown_lengths(&String::from_utf8_lossy(&bytes)),
+                Err(_) => HashMap::new(),
+            },
+            None => HashMap::new(),
+        }
+    }
+}
+
+fn length_name(group: &LatexGroup) -> Option<&str> {
+    group.children.iter().find_map(|child| match child {
+        LatexContent::Command(command) => Some(command.name.text()),
+        _ => None,
+    })
+}
+
+/// Extracts the output of `\showthe\name` from the log:
+///
+/// ```text
+/// > 345.0pt.
+/// l.12 \showthe\textwidth
+/// ```
+fn parse_shown_lengths(log: &str) -> HashMap<String, f64> {
+    let mut lengths = HashMap::new();
+    let lines: Vec<&str> = log.lines().collect();
+    for window in lines.windows(2) {
+        let value = window[0].trim_end();
+        if !value.starts_with("> ") || !value.ends_with("pt.") {
+            continue;
+        }
+
+        let points = match value[2..value.len() - 3].parse::<f64>() {
+            Ok(points) => points,
+            Err(_) => continue,
+        };
+
+        if !window[1].starts_with("l.") {
+            continue;
+        }
+
+        if let Some(index) = window[1].find("\\showthe") {
+            let rest = window[1][index + "\\showthe".len()..].trim_start();
+            if rest.starts_with('\\') {
+                let name_length = rest[1..]
+                    .find(|c: char| !c.is_alphabetic() && c != '@')
+                    .unwrap_or(rest.len() - 1);
+                lengths.insert(rest[..=name_length].to_owned(), points);
+            }
+        }
+    }
+    lengths
+}
+
+/// Converts a dimension like `1.5cm` or `0.5\textwidth` into TeX points.
+fn parse_dimension(text: &str, lengths: &HashMap<String, f64>) -> Option<f64> {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.' && c != '-' && c != '+')
+        .unwrap_or_else(|| text.len());
+
+    let factor = match &text[..end] {
+        "" | "+" => 1.0,
+        "-" => -1.0,
+        number => number.parse().ok()?,
+    };
+
+    let unit = text[end..].trim();
+    let scale = if unit.starts_with('\\') {
+        *lengths.get(unit)?
+    } else {
+        UNITS.iter().find(|(name, _)| *name == unit)?.1
+    };
+    Some(factor * scale)
+}
+
+fn format_points(points: f64) -> String {
+    format!(
+        "{:.2}pt = {:.2}mm = {:.2}in",
+        points,
+        points * 25.4 / 72.27,
+        points / 72.27
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn known_length() {
+        let hover = test_feature(
+            LatexLengthHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\includegraphics[width=\\textwidth]{foo}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(0, 27),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert_eq!(
+            hover.unwrap().contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "\\textwidth: Width of the text area".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn set_length_value() {
+        let hover = test_feature(
+            LatexLengthHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\newlength{\\foo}\n\\setlength{\\foo}{1in}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(1, 18),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert_eq!(
+            hover.unwrap().contents,
+            HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::PlainText,
+                value: "\\foo: User-defined length\n\n1in = 72.27pt = 25.40mm = 1.00in".into(),
+            })
+        );
+    }
+
+    #[test]
+    fn unknown_command() {
+        let hover = test_feature(
+            LatexLengthHoverProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\foo")],
+                main_file: "foo.tex",
+                position: Position::new(0, 2),
+                ..FeatureSpec::default()
+            },
+        );
+
+        assert_eq!(hover, None);
+    }
+
+    #[test]
+    fn shown_lengths() {
+        let log =
+            "foo\n> 345.0pt.\nl.5 \\showthe\\textwidth\n\n> 12.0pt.\nl.6 \\showthe \\parskip\n";
+        let lengths = parse_shown_lengths(log);
+        assert_eq!(lengths.len(), 2);
+        assert_eq!(lengths.get("\\textwidth"), Some(&345.0));
+        assert_eq!(lengths.get("\\parskip"), Some(&12.0));
+    }
+
+    #[test]
+    fn relative_dimension() {
+        let mut lengths = HashMap::new();
+        lengths.insert("\\textwidth".to_owned(), 300.0);
+        assert_eq!(parse_dimension("0.5\\textwidth", &lengths), Some(150.0));
+        assert_eq!(parse_dimension("-\\textwidth", &lengths), Some(-300.0));
+        assert_eq!(parse_dimension("2pc", &lengths), Some(24.0));
+        assert_eq!(parse_dimension("1em", &lengths), None);
+    }
+}
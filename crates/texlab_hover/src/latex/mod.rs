@@ -2,4 +2,5 @@ pub mod citation;
 pub mod component;
 pub mod include;
 pub mod label;
+pub mod length;
 pub mod preview;
@@ -8,6 +8,7 @@ use self::latex::citation::LatexCitationHoverProvider;
 use self::latex::component::LatexComponentHoverProvider;
 use self::latex::include::LatexIncludeHoverProvider;
 use self::latex::label::LatexLabelHoverProvider;
+use self::latex::length::LatexLengthHoverProvider;
 use self::latex::preview::LatexPreviewHoverProvider;
 use futures_boxed::boxed;
 use texlab_protocol::{Hover, TextDocumentPositionParams};
@@ -15,6 +16,7 @@ use texlab_workspace::*;
 
 pub struct HoverProvider {
     provider: ChoiceProvider<TextDocumentPositionParams, Hover>,
+    preview_provider: LatexPreviewHoverProvider,
 }
 
 impl HoverProvider {
@@ -28,10 +30,19 @@ impl HoverProvider {
                 Box::new(LatexComponentHoverProvider),
                 Box::new(LatexIncludeHoverProvider),
                 Box::new(LatexLabelHoverProvider),
-                Box::new(LatexPreviewHoverProvider),
+                Box::new(LatexLengthHoverProvider),
             ]),
+            preview_provider: LatexPreviewHoverProvider,
         }
     }
+
+    /// Computes the hover without rendering previews of math expressions.
+    pub async fn execute_without_images(
+        &self,
+        request: &FeatureRequest<TextDocumentPositionParams>,
+    ) -> Option<Hover> {
+        self.provider.execute(request).await
+    }
 }
 
 impl Default for HoverProvider {
@@ -49,6 +60,9 @@ impl FeatureProvider for HoverProvider {
         &'a self,
         request: &'a FeatureRequest<TextDocumentPositionParams>,
     ) -> Option<Hover> {
-        self.provider.execute(request).await
+        match self.provider.execute(request).await {
+            Some(hover) => Some(hover),
+            None => self.preview_provider.execute(request).await,
+        }
     }
 }
@@ -9,9 +9,17 @@ pub trait ClientCapabilitiesExt {
 
     fn has_hover_markdown_support(&self) -> bool;
 
+    fn has_completion_markdown_support(&self) -> bool;
+
     fn has_pull_configuration_support(&self) -> bool;
 
+    fn has_document_changes_support(&self) -> bool;
+
     fn has_push_configuration_support(&self) -> bool;
+
+    fn has_watched_files_registration_support(&self) -> bool;
+
+    fn has_prepare_rename_support(&self) -> bool;
 }
 
 impl ClientCapabilitiesExt for ClientCapabilities {
@@ -46,11 +54,28 @@ impl ClientCapabilitiesExt for ClientCapabilities {
         false
     }
 
+    fn has_completion_markdown_support(&self) -> bool {
+        self.text_document
+            .as_ref()
+            .and_then(|cap| cap.completion.as_ref())
+            .and_then(|cap| cap.completion_item.as_ref())
+            .and_then(|cap| cap.documentation_format.as_ref())
+            .map_or(true, |formats| formats.contains(&MarkupKind::Markdown))
+    }
+
     fn has_pull_configuration_support(&self) -> bool {
         // self.workspace.as_ref().and_then(|cap| cap.configuration) == Some(true)
         false
     }
 
+    fn has_document_changes_support(&self) -> bool {
+        self.workspace
+            .as_ref()
+            .and_then(|cap| cap.workspace_edit.as_ref())
+            .and_then(|cap| cap.document_changes)
+            == Some(true)
+    }
+
     fn has_push_configuration_support(&self) -> bool {
         // self.workspace
         //     .as_ref()
@@ -59,6 +84,22 @@ impl ClientCapabilitiesExt for ClientCapabilities {
         //     == Some(true)
         false
     }
+
+    fn has_watched_files_registration_support(&self) -> bool {
+        self.workspace
+            .as_ref()
+            .and_then(|cap| cap.did_change_watched_files.as_ref())
+            .and_then(|cap| cap.dynamic_registration)
+            == Some(true)
+    }
+
+    fn has_prepare_rename_support(&self) -> bool {
+        self.text_document
+            .as_ref()
+            .and_then(|cap| cap.rename.as_ref())
+            .and_then(|cap| cap.prepare_support)
+            == Some(true)
+    }
 }
 
 #[cfg(test)]
@@ -145,4 +186,90 @@ mod tests {
         let capabilities = ClientCapabilities::default();
         assert!(!capabilities.has_hover_markdown_support());
     }
+
+    #[test]
+    fn has_completion_markdown_support_true() {
+        let capabilities = ClientCapabilities::default();
+        assert!(capabilities.has_completion_markdown_support());
+    }
+
+    #[test]
+    fn has_completion_markdown_support_false() {
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                completion: Some(CompletionCapability {
+                    completion_item: Some(CompletionItemCapability {
+                        documentation_format: Some(vec![MarkupKind::PlainText]),
+                        ..CompletionItemCapability::default()
+                    }),
+                    ..CompletionCapability::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(!capabilities.has_completion_markdown_support());
+    }
+
+    #[test]
+    fn has_document_changes_support_true() {
+        let capabilities = ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                workspace_edit: Some(WorkspaceEditCapability {
+                    document_changes: Some(true),
+                    ..WorkspaceEditCapability::default()
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(capabilities.has_document_changes_support());
+    }
+
+    #[test]
+    fn has_document_changes_support_false() {
+        let capabilities = ClientCapabilities::default();
+        assert!(!capabilities.has_document_changes_support());
+    }
+
+    #[test]
+    fn has_watched_files_registration_support_true() {
+        let capabilities = ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                did_change_watched_files: Some(GenericCapability {
+                    dynamic_registration: Some(true),
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(capabilities.has_watched_files_registration_support());
+    }
+
+    #[test]
+    fn has_watched_files_registration_support_false() {
+        let capabilities = ClientCapabilities::default();
+        assert!(!capabilities.has_watched_files_registration_support());
+    }
+
+    #[test]
+    fn has_prepare_rename_support_true() {
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                rename: Some(RenameCapability {
+                    prepare_support: Some(true),
+                    ..RenameCapability::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(capabilities.has_prepare_rename_support());
+    }
+
+    #[test]
+    fn has_prepare_rename_support_false() {
+        let capabilities = ClientCapabilities::default();
+        assert!(!capabilities.has_prepare_rename_support());
+    }
 }
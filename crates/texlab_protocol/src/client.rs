@@ -1,3 +1,6 @@
+use crate::{
+    ArtifactsChangedParams, LogAppendedParams, PartialResultParams, PresenceParams, PreviewParams,
+};
 use futures_boxed::boxed;
 use jsonrpc::client::Result;
 use jsonrpc_derive::{jsonrpc_client, jsonrpc_method};
@@ -13,6 +16,13 @@ pub trait LspClient {
     #[boxed]
     async fn show_message(&self, params: ShowMessageParams);
 
+    #[jsonrpc_method("window/showMessageRequest", kind = "request")]
+    #[boxed]
+    async fn show_message_request(
+        &self,
+        params: ShowMessageRequestParams,
+    ) -> Result<Option<MessageActionItem>>;
+
     #[jsonrpc_method("client/registerCapability", kind = "request")]
     #[boxed]
     async fn register_capability(&self, params: RegistrationParams) -> Result<()>;
@@ -25,6 +35,10 @@ pub trait LspClient {
     #[boxed]
     async fn progress(&self, params: ProgressParams);
 
+    #[jsonrpc_method("$/progress", kind = "notification")]
+    #[boxed]
+    async fn partial_result(&self, params: PartialResultParams);
+
     #[jsonrpc_method("window/workDoneProgress/create", kind = "request")]
     #[boxed]
     async fn work_done_progress_create(&self, params: WorkDoneProgressCreateParams) -> Result<()>;
@@ -32,4 +46,24 @@ pub trait LspClient {
     #[jsonrpc_method("window/logMessage", kind = "notification")]
     #[boxed]
     async fn log_message(&self, params: LogMessageParams);
+
+    #[jsonrpc_method("texlab/artifactsChanged", kind = "notification")]
+    #[boxed]
+    async fn artifacts_changed(&self, params: ArtifactsChangedParams);
+
+    #[jsonrpc_method("texlab/presence", kind = "notification")]
+    #[boxed]
+    async fn presence(&self, params: PresenceParams);
+
+    #[jsonrpc_method("texlab/preview", kind = "notification")]
+    #[boxed]
+    async fn preview(&self, params: PreviewParams);
+
+    #[jsonrpc_method("texlab/logAppended", kind = "notification")]
+    #[boxed]
+    async fn log_appended(&self, params: LogAppendedParams);
+
+    #[jsonrpc_method("$/texlab/ping", kind = "request")]
+    #[boxed]
+    async fn ping(&self, params: ()) -> Result<()>;
 }
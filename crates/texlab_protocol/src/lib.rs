@@ -15,6 +15,8 @@ pub use lsp_types::*;
 
 use serde::{Deserialize, Serialize};
 use serde_repr::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
 
 #[serde(untagged)]
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -23,6 +25,25 @@ pub enum DefinitionResponse {
     LocationLinks(Vec<LocationLink>),
 }
 
+/// A reference together with the lines around it.
+///
+/// The lines start at `first_line` and include the line of the reference.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferencePreview {
+    #[serde(flatten)]
+    pub location: Location,
+    pub first_line: u64,
+    pub lines: Vec<String>,
+}
+
+#[serde(untagged)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum ReferenceResponse {
+    Locations(Vec<Location>),
+    Previews(Vec<ReferencePreview>),
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize_repr, Deserialize_repr)]
 #[repr(i32)]
 pub enum ForwardSearchStatus {
@@ -41,6 +62,10 @@ pub struct ForwardSearchResult {
 #[serde(rename_all = "camelCase")]
 pub struct BuildParams {
     pub text_document: TextDocumentIdentifier,
+
+    /// The build variant that the user picked, which is remembered for the project.
+    #[serde(default)]
+    pub variant: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize_repr, Deserialize_repr)]
@@ -57,3 +82,456 @@ pub enum BuildStatus {
 pub struct BuildResult {
     pub status: BuildStatus,
 }
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArtifactKind {
+    Aux,
+    Pdf,
+    Log,
+}
+
+impl ArtifactKind {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ArtifactKind::Aux => "aux",
+            ArtifactKind::Pdf => "pdf",
+            ArtifactKind::Log => "log",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactsChangedParams {
+    pub text_document: TextDocumentIdentifier,
+    pub artifacts: Vec<ArtifactKind>,
+    pub log_warnings: Option<u64>,
+}
+
+/// Asks the client to compile a preview of the project with its WebAssembly engine.
+///
+/// A preview supersedes the previews of lower generations, whose builds can be cancelled.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreviewParams {
+    pub text_document: TextDocumentIdentifier,
+    pub generation: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribeLogParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscribeLogResult {
+    pub id: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsubscribeLogParams {
+    pub id: String,
+}
+
+/// The lines that were appended to the build log of a subscription.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogAppendedParams {
+    pub id: String,
+    pub uri: Url,
+    pub lines: Vec<String>,
+}
+
+/// A document link that can be sent to the client before its target is known.
+///
+/// The target is filled in by `documentLink/resolve` using the attached data.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvableDocumentLink {
+    pub range: Range,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<Url>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl From<DocumentLink> for ResolvableDocumentLink {
+    fn from(link: DocumentLink) -> Self {
+        Self {
+            range: link.range,
+            target: Some(link.target),
+            data: None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDocumentContentParams {
+    pub uri: Url,
+}
+
+/// The contents of a virtual document like the build log of a TeX file.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextDocumentContentResult {
+    pub text: String,
+    pub links: Vec<LocationLink>,
+}
+
+/// The parameters of `workspace/symbol` including the optional partial result token.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceSymbolRequestParams {
+    pub query: String,
+    pub partial_result_token: Option<ProgressToken>,
+}
+
+/// A batch of results that is reported before the response of a request.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartialResultParams {
+    pub token: ProgressToken,
+    pub value: serde_json::Value,
+}
+
+/// The options that the client passes to the `initialize` request.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializationOptions {
+    pub low_bandwidth: Option<bool>,
+
+    /// The directory of the project on the server for clients that do not have file URIs.
+    pub project_directory: Option<PathBuf>,
+
+    /// The number of lines before and after each reference that are sent
+    /// along with the references.
+    pub reference_preview_lines: Option<u64>,
+}
+
+impl InitializationOptions {
+    /// Parses the options and describes the settings that are ignored
+    /// because they are unknown or have the wrong type.
+    pub fn parse(value: Option<serde_json::Value>) -> (Self, Vec<String>) {
+        let entries = match value {
+            None | Some(serde_json::Value::Null) => return (Self::default(), Vec::new()),
+            Some(serde_json::Value::Object(entries)) => entries,
+            Some(_) => {
+                let problem = "The initialization options must be an object".to_owned();
+                return (Self::default(), vec![problem]);
+            }
+        };
+
+        let known_keys = match serde_json::to_value(Self::default()) {
+            Ok(serde_json::Value::Object(keys)) => keys,
+            _ => serde_json::Map::new(),
+        };
+
+        let mut problems = Vec::new();
+        let mut valid_entries = serde_json::Map::new();
+        for (key, value) in entries {
+            if !known_keys.contains_key(&key) {
+                problems.push(format!("Unknown setting \"{}\"", key));
+                continue;
+            }
+
+            let mut entry = serde_json::Map::new();
+            entry.insert(key.clone(), value.clone());
+            match serde_json::from_value::<Self>(serde_json::Value::Object(entry)) {
+                Ok(_) => {
+                    valid_entries.insert(key, value);
+                }
+                Err(why) => problems.push(format!("Invalid setting \"{}\": {}", key, why)),
+            }
+        }
+
+        let options =
+            serde_json::from_value(serde_json::Value::Object(valid_entries)).unwrap_or_default();
+        (options, problems)
+    }
+}
+
+/// The cursor positions and selections that a participant reports in a shared workspace.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceUpdateParams {
+    pub uri: Url,
+    pub selections: Vec<Range>,
+}
+
+/// The cursor positions and selections of a remote participant.
+///
+/// A missing `uri` indicates that the participant has left the workspace.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceParams {
+    pub session: String,
+    pub uri: Option<Url>,
+    pub selections: Vec<Range>,
+}
+
+/// The part of a document that is linted by `texlab/lintRange`, e.g. the paragraph being edited.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintRangeParams {
+    pub text_document: TextDocumentIdentifier,
+    pub range: Range,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandUsageParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+/// The number of times each command and environment is used.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandUsage {
+    pub commands: HashMap<String, u64>,
+    pub environments: HashMap<String, u64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentCommandUsage {
+    pub uri: Url,
+    #[serde(flatten)]
+    pub usage: CommandUsage,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandUsageResult {
+    pub documents: Vec<DocumentCommandUsage>,
+    pub project: CommandUsage,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatsParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+/// The size and the state of a document or of the whole project.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Statistics {
+    pub words: u64,
+    pub figures: u64,
+    pub tables: u64,
+    pub equations: u64,
+    pub citations: u64,
+    pub errors: u64,
+    pub warnings: u64,
+    pub information: u64,
+    pub hints: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentStatistics {
+    pub uri: Url,
+    #[serde(flatten)]
+    pub statistics: Statistics,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatsResult {
+    pub documents: Vec<DocumentStatistics>,
+    pub project: Statistics,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CitationSectionsParams {
+    pub text_document: TextDocumentIdentifier,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CitationSection {
+    pub uri: Url,
+    pub range: Range,
+    pub title: String,
+}
+
+/// The sections in which a bibliography entry is cited.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EntryCitations {
+    pub key: String,
+    pub uri: Url,
+    pub count: u64,
+    pub sections: Vec<CitationSection>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CitationSectionsResult {
+    pub entries: Vec<EntryCitations>,
+    pub uncited_sections: Vec<CitationSection>,
+}
+
+/// The documents that are indexed ahead of time.
+///
+/// If no URIs are given, every TeX and BibTeX file below the root directory is indexed.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PreloadParams {
+    #[serde(default)]
+    pub uris: Option<Vec<Url>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum UniquenessLevel {
+    Document,
+    Project,
+    Group,
+    Scheme,
+    Global,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MonikerKind {
+    Import,
+    Export,
+    Local,
+}
+
+/// A stable identifier of a symbol, which is returned by `textDocument/moniker`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Moniker {
+    pub scheme: String,
+    pub identifier: String,
+    pub unique: UniquenessLevel,
+    pub kind: Option<MonikerKind>,
+}
+
+/// The reason why an optional feature is not active.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FeatureDisabledReason {
+    MissingBinary,
+    CapabilityOff,
+    ConfigOff,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeatureReport {
+    pub name: String,
+    pub enabled: bool,
+    pub reason: Option<FeatureDisabledReason>,
+    pub detail: Option<String>,
+}
+
+/// The optional features of the current session, which are returned by `texlab/capabilitiesReport`.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilitiesReport {
+    pub features: Vec<FeatureReport>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextAtParams {
+    pub text_document: TextDocumentIdentifier,
+    pub position: Position,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContextItemKind {
+    Section,
+    Environment,
+}
+
+/// A section or an environment that encloses the requested position.
+///
+/// The range of a section covers its heading only.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextItem {
+    pub kind: ContextItemKind,
+    pub name: String,
+    pub title: Option<String>,
+    pub range: Range,
+}
+
+/// The enclosing sections and environments from the outermost to the innermost one.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContextAtResult {
+    pub items: Vec<ContextItem>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PasteFormat {
+    PlainText,
+    Html,
+}
+
+/// The text from the clipboard that is converted to LaTeX before it is inserted.
+///
+/// The format is detected from the text if it is not given.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatPasteParams {
+    pub text_document: TextDocumentIdentifier,
+    pub position: Position,
+    pub text: String,
+    #[serde(default)]
+    pub format: Option<PasteFormat>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn valid_initialization_options() {
+        let (options, problems) = InitializationOptions::parse(Some(json!({
+            "lowBandwidth": true,
+            "projectDirectory": "/minio/foo",
+            "referencePreviewLines": 2,
+        })));
+        assert_eq!(options.low_bandwidth, Some(true));
+        assert_eq!(options.project_directory, Some(PathBuf::from("/minio/foo")));
+        assert_eq!(options.reference_preview_lines, Some(2));
+        assert!(problems.is_empty());
+    }
+
+    #[test]
+    fn invalid_initialization_options() {
+        let (options, problems) = InitializationOptions::parse(Some(json!({
+            "lowBandwidth": "yes",
+            "projectDirectory": "/minio/foo",
+            "lowbandwidth": true,
+        })));
+        assert_eq!(options.low_bandwidth, None);
+        assert_eq!(options.project_directory, Some(PathBuf::from("/minio/foo")));
+        assert_eq!(problems.len(), 2);
+        assert!(problems[0].starts_with("Invalid setting \"lowBandwidth\""));
+        assert_eq!(problems[1], "Unknown setting \"lowbandwidth\"");
+    }
+
+    #[test]
+    fn missing_initialization_options() {
+        assert_eq!(
+            InitializationOptions::parse(None),
+            (InitializationOptions::default(), Vec::new())
+        );
+        assert_eq!(InitializationOptions::parse(Some(json!(42))).1.len(), 1);
+    }
+}
@@ -1,26 +1,76 @@
+use schemars::{schema_for, JsonSchema};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BibtexFormattingOptions {
     pub line_length: Option<i32>,
+    pub preserve_field_case: Option<bool>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+impl BibtexFormattingOptions {
+    pub fn preserve_field_case(&self) -> bool {
+        self.preserve_field_case.unwrap_or(false)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct LatexForwardSearchOptions {
     pub executable: Option<String>,
     pub args: Option<Vec<String>>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexChktexOptions {
+    pub args: Option<Vec<String>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LatexLintTool {
+    Chktex,
+    Lacheck,
+    None,
+}
+
+impl Default for LatexLintTool {
+    fn default() -> Self {
+        LatexLintTool::Chktex
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LatexLintSeverity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+    Off,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexLintOptions {
+    pub tool: Option<LatexLintTool>,
     pub on_change: Option<bool>,
     pub on_save: Option<bool>,
+    pub max_diagnostics: Option<usize>,
+    pub delay: Option<u64>,
+    pub chktex: Option<LatexChktexOptions>,
+    pub max_input_size: Option<usize>,
+    pub severities: Option<HashMap<String, LatexLintSeverity>>,
 }
 
 impl LatexLintOptions {
+    /// The linter that checks the LaTeX documents.
+    pub fn tool(&self) -> LatexLintTool {
+        self.tool.unwrap_or_default()
+    }
+
     pub fn on_change(&self) -> bool {
         self.on_change.unwrap_or(true)
     }
@@ -28,9 +78,42 @@ impl LatexLintOptions {
     pub fn on_save(&self) -> bool {
         self.on_save.unwrap_or(true)
     }
+
+    /// The maximum number of diagnostics per source and document.
+    pub fn max_diagnostics(&self) -> usize {
+        self.max_diagnostics.unwrap_or(100)
+    }
+
+    /// The minimum number of seconds between two runs of the linter while editing.
+    pub fn delay(&self) -> u64 {
+        self.delay.unwrap_or(60)
+    }
+
+    /// The maximum number of bytes that are passed to the linter.
+    pub fn max_input_size(&self) -> usize {
+        self.max_input_size.unwrap_or(1024 * 1024)
+    }
+
+    /// The additional arguments of `chktex`, e.g. `-n1` to disable warning 1.
+    ///
+    /// Only the switches `-n`, `-w`, `-e` and `-m` with a warning number are used.
+    pub fn chktex_args(&self) -> Vec<String> {
+        self.chktex
+            .as_ref()
+            .and_then(|chktex| chktex.args.clone())
+            .unwrap_or_default()
+    }
+
+    /// The configured severity of a warning code of the linter, e.g. `24` for `chktex`.
+    pub fn severity(&self, code: &str) -> Option<LatexLintSeverity> {
+        self.severities
+            .as_ref()
+            .and_then(|severities| severities.get(code))
+            .copied()
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexBuildOptions {
     pub executable: Option<String>,
@@ -62,22 +145,327 @@ impl LatexBuildOptions {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum LatexCompletionImageMode {
+    Inline,
+    Resolve,
+}
+
+impl Default for LatexCompletionImageMode {
+    fn default() -> Self {
+        LatexCompletionImageMode::Inline
+    }
+}
+
+/// The largest number of completion items that a client can request.
+pub const MAX_COMPLETION_ITEMS: usize = 1000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexCompletionOptions {
+    pub images: Option<LatexCompletionImageMode>,
+    pub bibliography_directory: Option<PathBuf>,
+    pub abbreviations: Option<LatexAbbreviationOptions>,
+    pub max_items: Option<usize>,
+}
+
+impl LatexCompletionOptions {
+    pub fn images(&self) -> LatexCompletionImageMode {
+        self.images.unwrap_or_default()
+    }
+
+    /// The number of items that are sent to the client after ranking them.
+    pub fn max_items(&self) -> usize {
+        self.max_items.unwrap_or(50).min(MAX_COMPLETION_ITEMS)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexAbbreviationOptions {
+    pub enabled: Option<bool>,
+    pub snippets: Option<HashMap<String, String>>,
+}
+
+impl LatexAbbreviationOptions {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    /// The user-defined snippets by their abbreviation.
+    ///
+    /// They replace the built-in snippets and an empty snippet removes an abbreviation.
+    pub fn snippets(&self) -> HashMap<String, String> {
+        self.snippets.clone().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexSpellCheckOptions {
+    pub excluded_environments: Option<Vec<String>>,
+    pub excluded_commands: Option<Vec<String>>,
+    pub delay: Option<u64>,
+    pub languages: Option<Vec<String>>,
+    pub dictionary_paths: Option<Vec<PathBuf>>,
+    pub max_input_size: Option<usize>,
+}
+
+impl LatexSpellCheckOptions {
+    /// The minimum number of seconds between two runs of the spell checker while editing.
+    pub fn delay(&self) -> u64 {
+        self.delay.unwrap_or(10)
+    }
+
+    /// The maximum number of bytes that are passed to the spell checker.
+    pub fn max_input_size(&self) -> usize {
+        self.max_input_size.unwrap_or(1024 * 1024)
+    }
+
+    /// The dictionaries that are used unless a document selects its own.
+    ///
+    /// Only plain names like `en_US` are accepted.
+    pub fn languages(&self) -> Vec<String> {
+        self.languages
+            .clone()
+            .filter(|languages| !languages.is_empty())
+            .unwrap_or_else(|| vec!["en_US".to_owned()])
+    }
+
+    /// The additional directories that contain dictionaries.
+    ///
+    /// Only the directories inside of the sandbox are used.
+    pub fn dictionary_paths(&self) -> Vec<PathBuf> {
+        self.dictionary_paths.clone().unwrap_or_default()
+    }
+
+    pub fn excluded_environments(&self) -> Vec<String> {
+        self.excluded_environments.clone().unwrap_or_default()
+    }
+
+    pub fn excluded_commands(&self) -> Vec<String> {
+        self.excluded_commands
+            .as_ref()
+            .map(|names| {
+                names
+                    .iter()
+                    .map(|name| format!("\\{}", name.trim_start_matches('\\')))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexGrammarCheckOptions {
+    pub language: Option<String>,
+    pub disabled_rules: Option<Vec<String>>,
+    pub delay: Option<u64>,
+    pub max_input_size: Option<usize>,
+}
+
+impl LatexGrammarCheckOptions {
+    /// The language of the documents or `auto` to let LanguageTool detect it.
+    pub fn language(&self) -> String {
+        self.language.clone().unwrap_or_else(|| "auto".to_owned())
+    }
+
+    pub fn disabled_rules(&self) -> Vec<String> {
+        self.disabled_rules.clone().unwrap_or_default()
+    }
+
+    /// The minimum number of seconds between two runs of the grammar checker while editing.
+    pub fn delay(&self) -> u64 {
+        self.delay.unwrap_or(10)
+    }
+
+    /// The maximum number of bytes that are sent to the server.
+    pub fn max_input_size(&self) -> usize {
+        self.max_input_size.unwrap_or(1024 * 1024)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexExternalCheckerOptions {
+    pub preset: Option<String>,
+    pub source: Option<String>,
+}
+
+impl LatexExternalCheckerOptions {
+    /// The name of a built-in checker like `textidote`
+    /// or of a checker that is defined by the operator of the server.
+    ///
+    /// The command line is part of the preset, so that a client cannot run other programs.
+    pub fn preset(&self) -> Option<&str> {
+        self.preset.as_ref().map(AsRef::as_ref)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexMiktexOptions {
+    pub install_missing_packages: Option<bool>,
+}
+
+impl LatexMiktexOptions {
+    pub fn install_missing_packages(&self) -> bool {
+        self.install_missing_packages.unwrap_or(false)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexStyleOptions {
+    pub discouraged_commands: Option<Vec<String>>,
+}
+
+impl LatexStyleOptions {
+    /// Returns the discouraged command names with a leading backslash.
+    ///
+    /// Math delimiters like `$$` are returned unchanged.
+    pub fn discouraged_commands(&self) -> Vec<String> {
+        self.discouraged_commands
+            .as_ref()
+            .map(|names| {
+                names
+                    .iter()
+                    .map(|name| {
+                        if name.starts_with('$') {
+                            name.to_owned()
+                        } else {
+                            format!("\\{}", name.trim_start_matches('\\'))
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexTerminologyOptions {
+    pub enabled: Option<bool>,
+    pub variants: Option<Vec<Vec<String>>>,
+}
+
+impl LatexTerminologyOptions {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    /// Groups of spellings that denote the same term, e.g. `["toolkit", "tool set"]`.
+    pub fn variants(&self) -> Vec<Vec<String>> {
+        self.variants.clone().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexUnicodeOptions {
+    pub enabled: Option<bool>,
+    pub allowed: Option<Vec<String>>,
+}
+
+impl LatexUnicodeOptions {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(true)
+    }
+
+    /// The characters that the project uses on purpose, e.g. `["é", "–"]`.
+    pub fn allowed(&self) -> Vec<String> {
+        self.allowed.clone().unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexPreviewOptions {
+    pub enabled: Option<bool>,
+    pub idle_delay: Option<u64>,
+    pub burst: Option<u32>,
+    pub refill_interval: Option<u64>,
+}
+
+impl LatexPreviewOptions {
+    pub fn enabled(&self) -> bool {
+        self.enabled.unwrap_or(false)
+    }
+
+    /// The number of milliseconds without changes after which a preview is requested.
+    pub fn idle_delay(&self) -> u64 {
+        self.idle_delay.unwrap_or(750)
+    }
+
+    /// The number of previews that may be requested in quick succession.
+    pub fn burst(&self) -> u32 {
+        self.burst.unwrap_or(3).max(1)
+    }
+
+    /// The number of milliseconds until another preview may be requested after a burst.
+    pub fn refill_interval(&self) -> u64 {
+        self.refill_interval.unwrap_or(5000)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexOptions {
     pub forward_search: Option<LatexForwardSearchOptions>,
     pub lint: Option<LatexLintOptions>,
     pub build: Option<LatexBuildOptions>,
+    pub completion: Option<LatexCompletionOptions>,
+    pub spell_check: Option<LatexSpellCheckOptions>,
+    pub grammar_check: Option<LatexGrammarCheckOptions>,
+    pub external_checker: Option<LatexExternalCheckerOptions>,
+    pub miktex: Option<LatexMiktexOptions>,
+    pub style: Option<LatexStyleOptions>,
+    pub terminology: Option<LatexTerminologyOptions>,
+    pub unicode: Option<LatexUnicodeOptions>,
+    pub preview: Option<LatexPreviewOptions>,
     pub root_directory: Option<PathBuf>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BibtexHoverOptions {
+    pub compact: Option<bool>,
+    pub abstract_length: Option<usize>,
+}
+
+impl BibtexHoverOptions {
+    /// Compact hovers only show the formatted citation.
+    pub fn compact(&self) -> bool {
+        self.compact.unwrap_or(false)
+    }
+
+    pub fn abstract_length(&self) -> usize {
+        self.abstract_length.unwrap_or(300)
+    }
+}
+
+/// The bibliography processor for which the entry types and fields are completed.
+///
+/// Both are offered if no dialect is configured.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum BibtexDialect {
+    Bibtex,
+    Biblatex,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct BibtexOptions {
     pub formatting: Option<BibtexFormattingOptions>,
+    pub hover: Option<BibtexHoverOptions>,
+    pub dialect: Option<BibtexDialect>,
 }
 
-#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct Options {
     pub latex: Option<LatexOptions>,
@@ -85,6 +473,10 @@ pub struct Options {
 }
 
 impl Options {
+    pub fn schema() -> serde_json::Value {
+        serde_json::to_value(schema_for!(Options)).unwrap()
+    }
+
     pub fn resolve_output_file(&self, tex_path: &Path, extension: &str) -> Option<PathBuf> {
         let stem = tex_path.file_stem()?.to_str()?;
         let name = format!("{}.{}", stem, extension);
@@ -116,3 +508,46 @@ impl Options {
             .or_else(|| tex_path.parent().map(|path| path.join(&name)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema() {
+        let schema = Options::schema();
+        assert!(schema["properties"]["latex"].is_object());
+        assert!(schema["properties"]["bibtex"].is_object());
+        assert!(schema["definitions"]["LatexLintOptions"]["properties"]["onChange"].is_object());
+    }
+
+    #[test]
+    fn max_completion_items() {
+        let options = LatexCompletionOptions {
+            max_items: Some(usize::MAX),
+            ..LatexCompletionOptions::default()
+        };
+        assert_eq!(options.max_items(), MAX_COMPLETION_ITEMS);
+    }
+
+    #[test]
+    fn spell_check_excluded_commands() {
+        let options = LatexSpellCheckOptions {
+            excluded_environments: None,
+            excluded_commands: Some(vec!["texttt".into(), "\\cite".into()]),
+            delay: None,
+            languages: None,
+            dictionary_paths: None,
+            max_input_size: None,
+        };
+        assert_eq!(options.excluded_commands(), vec!["\\texttt", "\\cite"]);
+    }
+
+    #[test]
+    fn style_discouraged_commands() {
+        let options = LatexStyleOptions {
+            discouraged_commands: Some(vec!["bf".into(), "\\it".into(), "$$".into()]),
+        };
+        assert_eq!(options.discouraged_commands(), vec!["\\bf", "\\it", "$$"]);
+    }
+}
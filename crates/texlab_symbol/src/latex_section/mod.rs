@@ -137,17 +137,23 @@ impl<'a> LatexSectionNode<'a> {
         {
             if let Some(ctx) = OutlineContext::parse(view, label, outline) {
                 let mut is_section = false;
-                if let OutlineContextItem::Section { text, .. } = &ctx.item {
+                let mut is_appendix = false;
+                if let OutlineContextItem::Section { prefix, text } = &ctx.item {
                     if self.name() == *text {
                         for name in label.names() {
                             self.label = Some(name.text().to_owned());
                         }
 
                         is_section = true;
+                        is_appendix = *prefix == APPENDIX_PREFIX;
                     }
                 }
 
-                if is_section {
+                if is_appendix {
+                    self.number = ctx
+                        .number
+                        .map(|number| format!("{} {}", APPENDIX_PREFIX, number));
+                } else if is_section {
                     self.number = ctx.number;
                 }
             }
@@ -356,6 +362,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn appendix() {
+        let symbols = test_feature(
+            LatexSectionSymbolProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.tex",
+                        "\\section{Foo}\n\\appendix\n\\section{Bar}\\label{sec:bar}",
+                    ),
+                    FeatureSpec::file("foo.aux", "\\newlabel{sec:bar}{{B}{4}}"),
+                ],
+                main_file: "foo.tex",
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            symbols,
+            vec![
+                LatexSymbol {
+                    name: "Foo".into(),
+                    label: None,
+                    kind: LatexSymbolKind::Section,
+                    deprecated: false,
+                    full_range: Range::new_simple(0, 0, 2, 0),
+                    selection_range: Range::new_simple(0, 0, 0, 13),
+                    children: Vec::new(),
+                },
+                LatexSymbol {
+                    name: "Appendix B Bar".into(),
+                    label: Some("sec:bar".into()),
+                    kind: LatexSymbolKind::Section,
+                    deprecated: false,
+                    full_range: Range::new_simple(2, 0, 2, 28),
+                    selection_range: Range::new_simple(2, 0, 2, 13),
+                    children: Vec::new(),
+                }
+            ]
+        );
+    }
+
     #[test]
     fn section_inside_document_environment() {
         let symbols = test_feature(
@@ -173,11 +173,6 @@ pub fn document_symbols(
     }
 }
 
-struct WorkspaceSymbol {
-    info: SymbolInformation,
-    search_text: String,
-}
-
 pub async fn workspace_symbols<'a>(
     distribution: Arc<Box<dyn Distribution>>,
     client_capabilities: Arc<ClientCapabilities>,
@@ -185,57 +180,75 @@ pub async fn workspace_symbols<'a>(
     options: &'a Options,
     params: &'a WorkspaceSymbolParams,
 ) -> Vec<SymbolInformation> {
-    let provider = SymbolProvider::new();
     let mut symbols = Vec::new();
-
     for document in &workspace.documents {
-        let uri: Uri = document.uri.clone();
-        let request = FeatureRequest {
-            client_capabilities: Arc::clone(&client_capabilities),
-            view: DocumentView::new(Arc::clone(&workspace), Arc::clone(&document), options),
-            params: DocumentSymbolParams {
-                text_document: TextDocumentIdentifier::new(uri.clone().into()),
-            },
-            distribution: Arc::clone(&distribution),
-            options: Options::default(),
-        };
+        symbols.append(
+            &mut document_workspace_symbols(
+                Arc::clone(&distribution),
+                Arc::clone(&client_capabilities),
+                Arc::clone(&workspace),
+                options,
+                Arc::clone(document),
+                &params.query,
+            )
+            .await,
+        );
+    }
+    sort_symbols(&workspace, options, &mut symbols);
+    symbols
+}
 
-        let mut buffer = Vec::new();
-        for symbol in provider.execute(&request).await {
-            symbol.flatten(&mut buffer);
-        }
+/// Returns the symbols of a single document that match the given query.
+pub async fn document_workspace_symbols<'a>(
+    distribution: Arc<Box<dyn Distribution>>,
+    client_capabilities: Arc<ClientCapabilities>,
+    workspace: Arc<Workspace>,
+    options: &'a Options,
+    document: Arc<Document>,
+    query: &'a str,
+) -> Vec<SymbolInformation> {
+    let provider = SymbolProvider::new();
+    let uri: Uri = document.uri.clone();
+    let request = FeatureRequest {
+        client_capabilities,
+        view: DocumentView::new(Arc::clone(&workspace), document, options),
+        params: DocumentSymbolParams {
+            text_document: TextDocumentIdentifier::new(uri.clone().into()),
+        },
+        distribution,
+        options: Options::default(),
+    };
 
-        for symbol in buffer {
-            symbols.push(WorkspaceSymbol {
-                search_text: symbol.search_text(),
-                info: symbol.into_symbol_info(uri.clone()),
-            });
-        }
+    let mut buffer = Vec::new();
+    for symbol in provider.execute(&request).await {
+        symbol.flatten(&mut buffer);
     }
 
-    let query_words: Vec<_> = params
-        .query
-        .split_whitespace()
-        .map(str::to_lowercase)
-        .collect();
+    let query_words: Vec<_> = query.split_whitespace().map(str::to_lowercase).collect();
     let mut filtered = Vec::new();
-    for symbol in symbols {
-        let mut included = true;
-        for word in &query_words {
-            if !symbol.search_text.contains(word) {
-                included = false;
-                break;
-            }
-        }
-
-        if included {
-            filtered.push(symbol.info);
+    for symbol in buffer {
+        let search_text = symbol.search_text();
+        if query_words.iter().all(|word| search_text.contains(word)) {
+            filtered.push(symbol.into_symbol_info(uri.clone()));
         }
     }
-    sort_symbols(&workspace, options, &mut filtered);
+    filtered.sort_by_key(|symbol| {
+        (
+            symbol.location.range.start,
+            Reverse(symbol.location.range.end),
+        )
+    });
     filtered
 }
 
+/// Returns the documents of the workspace in the order of the project structure.
+pub fn ordered_documents(workspace: &Workspace, options: &Options) -> Vec<Arc<Document>> {
+    let ordering = ProjectOrdering::new(workspace, options);
+    let mut documents = workspace.documents.clone();
+    documents.sort_by_key(|document| ordering.get(&document.uri));
+    documents
+}
+
 fn sort_symbols(workspace: &Workspace, options: &Options, symbols: &mut Vec<SymbolInformation>) {
     let ordering = ProjectOrdering::new(workspace, options);
     symbols.sort_by(|left, right| {
@@ -1,7 +1,11 @@
 use super::ast::*;
+use crate::formatting::indentation;
 use crate::text::SyntaxNode;
 use texlab_protocol::BibtexFormattingOptions;
 
+/// The fields whose values are never wrapped because line breaks would change their meaning.
+const UNBREAKABLE_FIELDS: &[&str] = &["url", "doi"];
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BibtexFormattingParams {
     pub tab_size: usize,
@@ -38,19 +42,9 @@ struct BibtexFormatter<'a> {
 
 impl<'a> BibtexFormatter<'a> {
     fn new(params: &'a BibtexFormattingParams) -> Self {
-        let indent = if params.insert_spaces {
-            let mut buffer = String::new();
-            for _ in 0..params.tab_size {
-                buffer.push(' ');
-            }
-            buffer
-        } else {
-            "\t".into()
-        };
-
         Self {
             params,
-            indent,
+            indent: indentation(params.tab_size, params.insert_spaces),
             output: String::new(),
         }
     }
@@ -63,7 +57,7 @@ impl<'a> BibtexFormatter<'a> {
         self.format_token(&preamble.ty);
         self.output.push('{');
         if let Some(ref content) = preamble.content {
-            self.format_content(content, self.output.chars().count());
+            self.format_content(content, self.output.chars().count(), true);
             self.output.push('}');
         }
     }
@@ -75,7 +69,7 @@ impl<'a> BibtexFormatter<'a> {
             self.output.push_str(name.text());
             self.output.push_str(" = ");
             if let Some(ref value) = string.value {
-                self.format_content(value, self.output.chars().count());
+                self.format_content(value, self.output.chars().count(), true);
                 self.output.push('}');
             }
         }
@@ -97,18 +91,24 @@ impl<'a> BibtexFormatter<'a> {
 
     fn format_field(&mut self, field: &BibtexField) {
         self.output.push_str(self.indent.as_ref());
-        self.format_token(&field.name);
+        if self.params.options.preserve_field_case() {
+            self.output.push_str(field.name.text());
+        } else {
+            self.format_token(&field.name);
+        }
         self.output.push_str(" = ");
         let count = field.name.text().chars().count();
         let align = self.params.tab_size as usize + count + 3;
         if let Some(ref content) = field.content {
-            self.format_content(content, align);
+            let name = field.name.text().to_lowercase();
+            let wrap = !UNBREAKABLE_FIELDS.contains(&name.as_str());
+            self.format_content(content, align, wrap);
             self.output.push(',');
             self.output.push('\n');
         }
     }
 
-    fn format_content(&mut self, content: &BibtexContent, align: usize) {
+    fn format_content(&mut self, content: &BibtexContent, align: usize, wrap: bool) {
         let mut analyzer = BibtexContentAnalyzer::new();
         content.accept(&mut analyzer);
         let tokens = analyzer.tokens;
@@ -123,12 +123,14 @@ impl<'a> BibtexFormatter<'a> {
             let insert_space = Self::should_insert_space(previous, current);
             let space_length = if insert_space { 1 } else { 0 };
 
-            if length + current_length + space_length > self.params.line_length() as usize {
+            if Self::is_paragraph_break(previous, current) {
                 self.output.push('\n');
-                self.output.push_str(self.indent.as_ref());
-                for _ in 0..=align - self.params.tab_size {
-                    self.output.push(' ');
-                }
+                self.new_line(align);
+                length = align;
+            } else if wrap
+                && length + current_length + space_length > self.params.line_length() as usize
+            {
+                self.new_line(align);
                 length = align;
             } else if insert_space {
                 self.output.push(' ');
@@ -139,6 +141,14 @@ impl<'a> BibtexFormatter<'a> {
         }
     }
 
+    fn new_line(&mut self, align: usize) {
+        self.output.push('\n');
+        self.output.push_str(self.indent.as_ref());
+        for _ in 0..=align - self.params.tab_size {
+            self.output.push(' ');
+        }
+    }
+
     fn format_token(&mut self, token: &BibtexToken) {
         self.output.push_str(token.text().to_lowercase().as_ref());
     }
@@ -147,6 +157,11 @@ impl<'a> BibtexFormatter<'a> {
         previous.start().line != current.start().line
             || previous.end().character < current.start().character
     }
+
+    /// Empty lines separate the paragraphs of long values like abstracts.
+    fn is_paragraph_break(previous: &BibtexToken, current: &BibtexToken) -> bool {
+        current.start().line > previous.end().line + 1
+    }
 }
 
 struct BibtexContentAnalyzer<'a> {
@@ -243,7 +258,7 @@ pub fn format_entry(entry: &BibtexEntry, params: &BibtexFormattingParams) -> Str
 
 pub fn format_content(content: &BibtexContent, params: &BibtexFormattingParams) -> String {
     let mut formatter = BibtexFormatter::new(params);
-    formatter.format_content(content, 0);
+    formatter.format_content(content, 0, true);
     formatter.output
 }
 
@@ -254,13 +269,22 @@ mod tests {
     use indoc::indoc;
 
     fn verify(source: &str, expected: &str, line_length: i32) {
+        verify_with_options(
+            source,
+            expected,
+            BibtexFormattingOptions {
+                line_length: Some(line_length),
+                preserve_field_case: None,
+            },
+        );
+    }
+
+    fn verify_with_options(source: &str, expected: &str, options: BibtexFormattingOptions) {
         let tree = BibtexSyntaxTree::from(source);
         let params = BibtexFormattingParams {
             tab_size: 4,
             insert_spaces: true,
-            options: BibtexFormattingOptions {
-                line_length: Some(line_length),
-            },
+            options,
         };
         assert_eq!(
             expected,
@@ -368,4 +392,51 @@ mod tests {
         let expected = "@preamble{\"foo bar baz\"}";
         verify(source, expected, 30);
     }
+
+    #[test]
+    fn unbreakable_fields() {
+        let source =
+            "@misc{foo, URL = {https://example.com/a/very/long/path}, doi = {10.1000/foo bar}}";
+        let expected = indoc!(
+            "
+            @misc{foo,
+                url = {https://example.com/a/very/long/path},
+                doi = {10.1000/foo bar},
+            }"
+        );
+        verify(source, expected, 20);
+    }
+
+    #[test]
+    fn paragraphs() {
+        let source = "@article{foo, abstract = {foo bar\n\n  baz}}";
+        let expected = indoc!(
+            "
+            @article{foo,
+                abstract = {foo bar
+
+                            baz},
+            }"
+        );
+        verify(source, expected, 120);
+    }
+
+    #[test]
+    fn preserve_field_case() {
+        let source = "@Article{foo, Title = {Bar}}";
+        let expected = indoc!(
+            "
+            @article{foo,
+                Title = {Bar},
+            }"
+        );
+        verify_with_options(
+            source,
+            expected,
+            BibtexFormattingOptions {
+                line_length: None,
+                preserve_field_case: Some(true),
+            },
+        );
+    }
 }
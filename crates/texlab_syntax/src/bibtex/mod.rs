@@ -10,7 +10,8 @@ pub use self::formatting::*;
 
 use self::lexer::BibtexLexer;
 use self::parser::BibtexParser;
-use texlab_protocol::Position;
+use crate::text::SyntaxNode;
+use texlab_protocol::{Position, RangeExt};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct BibtexSyntaxTree {
@@ -60,6 +61,28 @@ impl BibtexSyntaxTree {
         finder.results
     }
 
+    /// Returns the name of the `@string` macro that is defined or used at the given position.
+    pub fn string_name_at(&self, position: Position) -> Option<&BibtexToken> {
+        let mut nodes = self.find(position);
+        nodes.reverse();
+        match (nodes.get(0)?, nodes.get(1)) {
+            (BibtexNode::Word(word), Some(BibtexNode::Field(_)))
+            | (BibtexNode::Word(word), Some(BibtexNode::Concat(_))) => Some(&word.token),
+            (BibtexNode::String(string), _) => string
+                .name
+                .as_ref()
+                .filter(|name| name.range().contains(position)),
+            _ => None,
+        }
+    }
+
+    /// Returns the words in field values that may refer to a `@string` macro.
+    pub fn string_references(&self) -> Vec<&BibtexToken> {
+        let mut visitor = BibtexStringReferenceVisitor::default();
+        visitor.visit_root(&self.root);
+        visitor.references
+    }
+
     pub fn crossref(&self, entry: &BibtexEntry) -> Option<&BibtexEntry> {
         let field = entry.field("crossref")?;
         if let Some(BibtexContent::BracedContent(content)) = &field.content {
@@ -84,3 +107,59 @@ impl From<&str> for BibtexSyntaxTree {
         parser.root().into()
     }
 }
+
+#[derive(Debug, Default)]
+struct BibtexStringReferenceVisitor<'a> {
+    references: Vec<&'a BibtexToken>,
+}
+
+impl<'a> BibtexVisitor<'a> for BibtexStringReferenceVisitor<'a> {
+    fn visit_root(&mut self, root: &'a BibtexRoot) {
+        BibtexWalker::walk_root(self, root);
+    }
+
+    fn visit_comment(&mut self, _comment: &'a BibtexComment) {}
+
+    fn visit_preamble(&mut self, preamble: &'a BibtexPreamble) {
+        BibtexWalker::walk_preamble(self, preamble);
+    }
+
+    fn visit_string(&mut self, string: &'a BibtexString) {
+        BibtexWalker::walk_string(self, string);
+    }
+
+    fn visit_entry(&mut self, entry: &'a BibtexEntry) {
+        BibtexWalker::walk_entry(self, entry);
+    }
+
+    fn visit_field(&mut self, field: &'a BibtexField) {
+        if let Some(BibtexContent::Word(word)) = &field.content {
+            self.references.push(&word.token);
+        }
+        BibtexWalker::walk_field(self, field);
+    }
+
+    fn visit_word(&mut self, _word: &'a BibtexWord) {}
+
+    fn visit_command(&mut self, _command: &'a BibtexCommand) {}
+
+    fn visit_quoted_content(&mut self, content: &'a BibtexQuotedContent) {
+        BibtexWalker::walk_quoted_content(self, content);
+    }
+
+    fn visit_braced_content(&mut self, content: &'a BibtexBracedContent) {
+        BibtexWalker::walk_braced_content(self, content);
+    }
+
+    fn visit_concat(&mut self, concat: &'a BibtexConcat) {
+        if let BibtexContent::Word(word) = &concat.left {
+            self.references.push(&word.token);
+        }
+
+        if let Some(BibtexContent::Word(word)) = &concat.right {
+            self.references.push(&word.token);
+        }
+
+        BibtexWalker::walk_concat(self, concat);
+    }
+}
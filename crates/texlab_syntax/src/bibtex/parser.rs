@@ -1,14 +1,17 @@
 use super::ast::*;
+use crate::text::SyntaxNode;
 use std::iter::Peekable;
 
 pub struct BibtexParser<I: Iterator<Item = BibtexToken>> {
     tokens: Peekable<I>,
+    line: Option<u64>,
 }
 
 impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
     pub fn new(tokens: I) -> Self {
         Self {
             tokens: tokens.peekable(),
+            line: None,
         }
     }
 
@@ -29,7 +32,7 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
                     children.push(BibtexDeclaration::Entry(entry));
                 }
                 _ => {
-                    let comment = BibtexComment::new(self.tokens.next().unwrap());
+                    let comment = BibtexComment::new(self.next().unwrap());
                     children.push(BibtexDeclaration::Comment(Box::new(comment)));
                 }
             }
@@ -38,7 +41,7 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
     }
 
     fn preamble(&mut self) -> BibtexPreamble {
-        let ty = self.tokens.next().unwrap();
+        let ty = self.next().unwrap();
 
         let left = self.expect2(BibtexTokenKind::BeginBrace, BibtexTokenKind::BeginParen);
         if left.is_none() {
@@ -55,7 +58,7 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
     }
 
     fn string(&mut self) -> BibtexString {
-        let ty = self.tokens.next().unwrap();
+        let ty = self.next().unwrap();
 
         let left = self.expect2(BibtexTokenKind::BeginBrace, BibtexTokenKind::BeginParen);
         if left.is_none() {
@@ -82,7 +85,7 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
     }
 
     fn entry(&mut self) -> BibtexEntry {
-        let ty = self.tokens.next().unwrap();
+        let ty = self.next().unwrap();
 
         let left = self.expect2(BibtexTokenKind::BeginBrace, BibtexTokenKind::BeginParen);
         if left.is_none() {
@@ -109,7 +112,7 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
     }
 
     fn field(&mut self) -> BibtexField {
-        let name = self.tokens.next().unwrap();
+        let name = self.next().unwrap();
 
         let assign = self.expect1(BibtexTokenKind::Assign);
         if assign.is_none() {
@@ -126,7 +129,7 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
     }
 
     fn content(&mut self) -> BibtexContent {
-        let token = self.tokens.next().unwrap();
+        let token = self.next().unwrap();
         let left = match token.kind {
             BibtexTokenKind::PreambleKind
             | BibtexTokenKind::StringKind
@@ -171,6 +174,10 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
     }
 
     fn can_match_content(&mut self) -> bool {
+        if self.is_recovery_point() {
+            return false;
+        }
+
         if let Some(ref token) = self.tokens.peek() {
             match token.kind {
                 BibtexTokenKind::PreambleKind
@@ -191,10 +198,33 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
         }
     }
 
+    /// Checks if the next token starts a new declaration at the beginning of a line.
+    ///
+    /// Malformed declarations end at such a token so that
+    /// the remaining declarations can still be parsed.
+    fn is_recovery_point(&mut self) -> bool {
+        let line = self.line;
+        match self.tokens.peek() {
+            Some(token) => match token.kind {
+                BibtexTokenKind::PreambleKind
+                | BibtexTokenKind::StringKind
+                | BibtexTokenKind::EntryKind => line.map_or(true, |line| token.start().line > line),
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    fn next(&mut self) -> Option<BibtexToken> {
+        let token = self.tokens.next()?;
+        self.line = Some(token.end().line);
+        Some(token)
+    }
+
     fn expect1(&mut self, kind: BibtexTokenKind) -> Option<BibtexToken> {
         if let Some(ref token) = self.tokens.peek() {
             if token.kind == kind {
-                return self.tokens.next();
+                return self.next();
             }
         }
         None
@@ -203,7 +233,7 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
     fn expect2(&mut self, kind1: BibtexTokenKind, kind2: BibtexTokenKind) -> Option<BibtexToken> {
         if let Some(ref token) = self.tokens.peek() {
             if token.kind == kind1 || token.kind == kind2 {
-                return self.tokens.next();
+                return self.next();
             }
         }
         None
@@ -217,3 +247,35 @@ impl<I: Iterator<Item = BibtexToken>> BibtexParser<I> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::bibtex::*;
+
+    #[test]
+    fn recover_from_unterminated_content() {
+        let tree = BibtexSyntaxTree::from(
+            "@article{foo, title = {Foo,\n\
+             @article{bar, title = {Bar}}\n\
+             @book{baz, title = \"Baz}",
+        );
+        let entries = tree.entries();
+        let keys: Vec<&str> = entries
+            .iter()
+            .filter_map(|entry| entry.key.as_ref())
+            .map(BibtexToken::text)
+            .collect();
+        assert_eq!(keys, vec!["foo", "bar", "baz"]);
+        assert!(entries[0].right.is_none());
+        assert!(entries[1].right.is_some());
+        assert_eq!(entries[1].fields.len(), 1);
+    }
+
+    #[test]
+    fn keep_inline_at_sign() {
+        let tree = BibtexSyntaxTree::from("@misc{foo, email = {foo@bar.com}}");
+        let entries = tree.entries();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].right.is_some());
+    }
+}
@@ -0,0 +1,43 @@
+/// Returns the text of a single indentation level.
+pub fn indentation(tab_size: usize, insert_spaces: bool) -> String {
+    if insert_spaces {
+        " ".repeat(tab_size)
+    } else {
+        "\t".into()
+    }
+}
+
+/// Prepends the given prefix to every line that is not blank.
+pub fn indent_lines<'a, I>(lines: I, prefix: &str) -> String
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut output = String::new();
+    for line in lines {
+        if !line.trim().is_empty() {
+            output.push_str(prefix);
+            output.push_str(line);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spaces() {
+        assert_eq!(indentation(2, true), "  ");
+        assert_eq!(indentation(2, false), "\t");
+    }
+
+    #[test]
+    fn skip_blank_lines() {
+        assert_eq!(
+            indent_lines(vec!["foo", "", "bar"], "  "),
+            "  foo\n\n  bar\n"
+        );
+    }
+}
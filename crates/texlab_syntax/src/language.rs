@@ -1,5 +1,6 @@
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use texlab_protocol::BibtexDialect;
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -116,6 +117,30 @@ pub struct LatexTheoremDefinitionCommand {
     pub index: usize,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexEnvironmentDefinitionCommand {
+    pub name: String,
+    pub index: usize,
+}
+
+/// The options of a package that are offered inside of `\usepackage[...]`.
+///
+/// Options that end with `=` expect a value.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexClassOptions {
+    pub class: String,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexPackageOptions {
+    pub package: String,
+    pub options: Vec<String>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct LatexColorCommand {
@@ -171,6 +196,14 @@ pub struct BibtexEntryTypeDoc {
     pub name: String,
     pub category: BibtexEntryTypeCategory,
     pub documentation: Option<String>,
+    #[serde(default)]
+    pub dialect: Option<BibtexDialect>,
+}
+
+impl BibtexEntryTypeDoc {
+    pub fn supports(&self, dialect: Option<BibtexDialect>) -> bool {
+        supports_dialect(self.dialect, dialect)
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -178,6 +211,53 @@ pub struct BibtexEntryTypeDoc {
 pub struct BibtexFieldDoc {
     pub name: String,
     pub documentation: String,
+    #[serde(default)]
+    pub dialect: Option<BibtexDialect>,
+}
+
+impl BibtexFieldDoc {
+    pub fn supports(&self, dialect: Option<BibtexDialect>) -> bool {
+        supports_dialect(self.dialect, dialect)
+    }
+}
+
+/// Entry types and fields without a dialect are understood by BibTeX and BibLaTeX.
+fn supports_dialect(required: Option<BibtexDialect>, dialect: Option<BibtexDialect>) -> bool {
+    match (required, dialect) {
+        (Some(required), Some(dialect)) => required == dialect,
+        _ => true,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LatexParameterKind {
+    Optional,
+    Required,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexParameterDoc {
+    pub label: String,
+    pub kind: LatexParameterKind,
+    pub documentation: String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LatexCommandSignature {
+    pub name: String,
+    pub parameters: Vec<LatexParameterDoc>,
+}
+
+impl LatexParameterDoc {
+    pub fn bracketed_label(&self) -> String {
+        match self.kind {
+            LatexParameterKind::Optional => format!("[{}]", self.label),
+            LatexParameterKind::Required => format!("{{{}}}", self.label),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
@@ -191,6 +271,9 @@ pub struct LanguageData {
     pub command_definition_commands: Vec<LatexCommandDefinitionCommand>,
     pub math_operator_commands: Vec<LatexMathOperatorCommand>,
     pub theorem_definition_commands: Vec<LatexTheoremDefinitionCommand>,
+    pub environment_definition_commands: Vec<LatexEnvironmentDefinitionCommand>,
+    pub class_options: Vec<LatexClassOptions>,
+    pub package_options: Vec<LatexPackageOptions>,
     pub colors: Vec<String>,
     pub color_commands: Vec<LatexColorCommand>,
     pub color_model_commands: Vec<LatexColorModelCommand>,
@@ -202,6 +285,10 @@ pub struct LanguageData {
     pub tikz_libraries: Vec<String>,
     pub math_environments: Vec<String>,
     pub enum_environments: Vec<String>,
+    pub math_commands: Vec<String>,
+    pub text_commands: Vec<String>,
+    pub math_text_commands: Vec<String>,
+    pub command_signatures: Vec<LatexCommandSignature>,
 }
 
 impl LanguageData {
@@ -217,6 +304,20 @@ impl LanguageData {
             .and_then(|ty| ty.documentation.as_ref().map(AsRef::as_ref))
     }
 
+    pub fn is_math_command(&self, name: &str) -> bool {
+        self.math_commands.iter().any(|command| command == name)
+    }
+
+    pub fn is_text_command(&self, name: &str) -> bool {
+        self.text_commands.iter().any(|command| command == name)
+    }
+
+    pub fn find_command_signature(&self, name: &str) -> Option<&LatexCommandSignature> {
+        self.command_signatures
+            .iter()
+            .find(|signature| signature.name == name)
+    }
+
     pub fn field_documentation(&self, name: &str) -> Option<&str> {
         self.fields
             .iter()
@@ -98,18 +98,57 @@ impl SyntaxNode for LatexEnvironment {
     }
 }
 
+/// An environment that is defined with `\newenvironment` or a similar command.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexEnvironmentDefinition {
+    pub command: Arc<LatexCommand>,
+    pub index: usize,
+}
+
+impl LatexEnvironmentDefinition {
+    pub fn name(&self) -> &LatexToken {
+        self.command.extract_word(self.index).unwrap()
+    }
+
+    fn parse(commands: &[Arc<LatexCommand>]) -> Vec<Self> {
+        let mut definitions = Vec::new();
+        for command in commands {
+            for LatexEnvironmentDefinitionCommand { name, index } in
+                &LANGUAGE_DATA.environment_definition_commands
+            {
+                if command.name.text() == name && command.has_word(*index) {
+                    definitions.push(Self {
+                        command: Arc::clone(&command),
+                        index: *index,
+                    });
+                }
+            }
+        }
+        definitions
+    }
+}
+
+impl SyntaxNode for LatexEnvironmentDefinition {
+    fn range(&self) -> Range {
+        self.command.range()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct LatexEnvironmentInfo {
     pub environments: Vec<LatexEnvironment>,
+    pub definitions: Vec<LatexEnvironmentDefinition>,
     pub is_standalone: bool,
 }
 
 impl LatexEnvironmentInfo {
     pub fn parse(commands: &[Arc<LatexCommand>]) -> Self {
         let environments = LatexEnvironment::parse(commands);
+        let definitions = LatexEnvironmentDefinition::parse(commands);
         let is_standalone = environments.iter().any(LatexEnvironment::is_root);
         Self {
             environments,
+            definitions,
             is_standalone,
         }
     }
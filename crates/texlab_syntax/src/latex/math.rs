@@ -1,4 +1,5 @@
 use super::ast::*;
+use super::env::LatexEnvironment;
 use crate::language::*;
 use crate::text::SyntaxNode;
 use std::sync::Arc;
@@ -58,6 +59,35 @@ impl SyntaxNode for LatexInline {
     }
 }
 
+/// A math construct that surrounds a position in the document.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LatexMathContext<'a> {
+    Inline(&'a LatexInline),
+    Equation(&'a LatexEquation),
+    Environment(&'a LatexEnvironment),
+}
+
+impl<'a> LatexMathContext<'a> {
+    /// The range between the delimiters of the math construct.
+    pub fn content_range(&self) -> Range {
+        match self {
+            LatexMathContext::Inline(inline) => Range::new(inline.left.end(), inline.right.start()),
+            LatexMathContext::Equation(eq) => Range::new(eq.left.end(), eq.right.start()),
+            LatexMathContext::Environment(env) => Range::new(env.left.end(), env.right.start()),
+        }
+    }
+}
+
+impl<'a> SyntaxNode for LatexMathContext<'a> {
+    fn range(&self) -> Range {
+        match self {
+            LatexMathContext::Inline(inline) => inline.range(),
+            LatexMathContext::Equation(eq) => eq.range(),
+            LatexMathContext::Environment(env) => env.range(),
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 struct LatexInlineAnalyzer {
     inlines: Vec<LatexInline>,
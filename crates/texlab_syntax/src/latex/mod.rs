@@ -101,6 +101,65 @@ impl SyntaxNode for LatexCitation {
     }
 }
 
+/// A `\bibitem` of a manual `thebibliography` environment.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexBibItem {
+    pub command: Arc<LatexCommand>,
+    /// The range of the reference text that follows the key.
+    pub content_range: Range,
+}
+
+impl LatexBibItem {
+    pub fn key(&self) -> &LatexToken {
+        self.command.extract_word(0).unwrap()
+    }
+
+    fn parse(commands: &[Arc<LatexCommand>], env: &LatexEnvironmentInfo) -> Vec<Self> {
+        let bib_commands: Vec<_> = commands
+            .iter()
+            .filter(|command| command.name.text() == "\\bibitem" && command.has_word(0))
+            .collect();
+
+        let mut items = Vec::new();
+        for (i, command) in bib_commands.iter().enumerate() {
+            let start = command.end();
+            let environment_end = env
+                .environments
+                .iter()
+                .filter(|env| env.range().contains(start))
+                .filter(|env| {
+                    env.left
+                        .name()
+                        .map_or(false, |name| name.text() == "thebibliography")
+                })
+                .map(|env| env.right.start())
+                .next();
+
+            let next_start = bib_commands.get(i + 1).map(|next| next.start());
+            let end = match (next_start, environment_end) {
+                (Some(next_start), Some(environment_end)) if environment_end < next_start => {
+                    environment_end
+                }
+                (Some(next_start), _) => next_start,
+                (None, Some(environment_end)) => environment_end,
+                (None, None) => start,
+            };
+
+            items.push(Self {
+                command: Arc::clone(command),
+                content_range: Range::new(start, end),
+            });
+        }
+        items
+    }
+}
+
+impl SyntaxNode for LatexBibItem {
+    fn range(&self) -> Range {
+        self.command.range()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct LatexInclude {
     pub command: Arc<LatexCommand>,
@@ -256,6 +315,58 @@ impl LatexCommandDefinition {
         }
         definitions
     }
+
+    /// Returns the number of arguments that the defined command takes.
+    pub fn argument_count(&self) -> usize {
+        self.command
+            .options
+            .get(self.argument_count_index)
+            .and_then(|group| extract_group(group).trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Returns all parameter tokens (`#1` to `#9`) that occur in the implementation.
+    pub fn parameters(&self) -> Vec<LatexParameter> {
+        let mut words = Vec::new();
+        Self::collect_words(&self.implementation, &mut words);
+
+        let mut parameters = Vec::new();
+        for word in words {
+            let chars: Vec<char> = word.text().chars().collect();
+            for i in 0..chars.len().saturating_sub(1) {
+                if chars[i] == '#' && (i == 0 || chars[i - 1] != '#') {
+                    if let Some(number) = chars[i + 1].to_digit(10) {
+                        let start = word.start().character + i as u64;
+                        parameters.push(LatexParameter {
+                            number: number as usize,
+                            range: Range::new_simple(
+                                word.start().line,
+                                start,
+                                word.start().line,
+                                start + 2,
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+        parameters
+    }
+
+    fn collect_words<'a>(group: &'a LatexGroup, words: &mut Vec<&'a LatexToken>) {
+        for child in &group.children {
+            match child {
+                LatexContent::Group(group) => Self::collect_words(group, words),
+                LatexContent::Command(command) => {
+                    for group in &command.groups {
+                        Self::collect_words(group, words);
+                    }
+                }
+                LatexContent::Text(text) => words.extend(&text.words),
+                LatexContent::Comma(_) | LatexContent::Math(_) => (),
+            }
+        }
+    }
 }
 
 impl SyntaxNode for LatexCommandDefinition {
@@ -264,6 +375,12 @@ impl SyntaxNode for LatexCommandDefinition {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexParameter {
+    pub number: usize,
+    pub range: Range,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct LatexSyntaxTree {
     pub root: Arc<LatexRoot>,
@@ -273,6 +390,7 @@ pub struct LatexSyntaxTree {
     pub env: LatexEnvironmentInfo,
     pub structure: LatexStructureInfo,
     pub citations: Vec<LatexCitation>,
+    pub bib_items: Vec<LatexBibItem>,
     pub math: LatexMathInfo,
     pub command_definitions: Vec<LatexCommandDefinition>,
     pub glossary: LatexGlossaryInfo,
@@ -289,6 +407,7 @@ impl LatexSyntaxTree {
         let env = LatexEnvironmentInfo::parse(&commands);
         let structure = LatexStructureInfo::parse(&commands);
         let citations = LatexCitation::parse(&commands);
+        let bib_items = LatexBibItem::parse(&commands, &env);
         let math = LatexMathInfo::parse(Arc::clone(&root), &commands);
         let command_definitions = LatexCommandDefinition::parse(&commands);
         let glossary = LatexGlossaryInfo::parse(&commands);
@@ -300,6 +419,7 @@ impl LatexSyntaxTree {
             env,
             structure,
             citations,
+            bib_items,
             math,
             command_definitions,
             glossary,
@@ -312,6 +432,65 @@ impl LatexSyntaxTree {
         finder.results
     }
 
+    /// Returns the innermost node that contains the given position.
+    pub fn node_at(&self, position: Position) -> Option<LatexNode> {
+        self.find(position).pop()
+    }
+
+    /// Returns all nodes that contain the given position, starting with the innermost one.
+    pub fn ancestors(&self, position: Position) -> Vec<LatexNode> {
+        let mut nodes = self.find(position);
+        nodes.reverse();
+        nodes
+    }
+
+    /// Returns the innermost environment that contains the given position.
+    pub fn environment_at(&self, position: Position) -> Option<&LatexEnvironment> {
+        self.env
+            .environments
+            .iter()
+            .filter(|env| env.range().contains(position))
+            .max_by_key(|env| (env.start().line, env.start().character))
+    }
+
+    /// Returns the innermost math construct whose content contains the given position.
+    pub fn math_context_at(&self, position: Position) -> Option<LatexMathContext> {
+        self.env
+            .environments
+            .iter()
+            .filter(|env| env.left.is_math())
+            .map(LatexMathContext::Environment)
+            .chain(self.math.equations.iter().map(LatexMathContext::Equation))
+            .chain(self.math.inlines.iter().map(LatexMathContext::Inline))
+            .filter(|context| context.content_range().contains(position))
+            .max_by_key(|context| (context.start().line, context.start().character))
+    }
+
+    /// Returns the label name (definition or reference) at the given position.
+    pub fn label_name_at(&self, position: Position) -> Option<&LatexToken> {
+        self.structure
+            .labels
+            .iter()
+            .flat_map(LatexLabel::names)
+            .find(|name| name.range().contains(position))
+    }
+
+    /// Returns the citation key at the given position.
+    pub fn citation_key_at(&self, position: Position) -> Option<&LatexToken> {
+        self.citations
+            .iter()
+            .flat_map(LatexCitation::keys)
+            .find(|key| key.range().contains(position))
+    }
+
+    /// Returns the key of the `\bibitem` at the given position.
+    pub fn bib_item_key_at(&self, position: Position) -> Option<&LatexToken> {
+        self.bib_items
+            .iter()
+            .map(LatexBibItem::key)
+            .find(|key| key.range().contains(position))
+    }
+
     pub fn find_command_by_name(&self, position: Position) -> Option<Arc<LatexCommand>> {
         for result in self.find(position) {
             if let LatexNode::Command(command) = result {
@@ -354,6 +533,21 @@ impl LatexSyntaxTree {
                 .any(|env| env.range().contains(item.start()))
     }
 
+    pub fn is_math_mode(&self, position: Position) -> bool {
+        self.math_context_at(position).is_some()
+            && !self
+                .commands
+                .iter()
+                .filter(|command| {
+                    LANGUAGE_DATA
+                        .math_text_commands
+                        .iter()
+                        .any(|name| name == command.name.text())
+                })
+                .flat_map(|command| command.args.iter())
+                .any(|arg| arg.range().contains_exclusive(position))
+    }
+
     pub fn is_direct_child(&self, environment: &LatexEnvironment, position: Position) -> bool {
         environment.range().contains(position)
             && !self
@@ -377,3 +571,146 @@ pub fn extract_group(content: &LatexGroup) -> String {
     }
     printer.output
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_distro::Language;
+    use texlab_protocol::Options;
+
+    fn parse(text: &str) -> LatexSyntaxTree {
+        let uri = Uri::from_file_path(std::env::temp_dir().join("foo.tex")).unwrap();
+        LatexSyntaxTree::parse(SyntaxTreeInput {
+            options: &Options::default(),
+            resolver: &Resolver::default(),
+            uri: &uri,
+            text,
+            language: Language::Latex,
+        })
+    }
+
+    #[test]
+    fn node_at() {
+        let tree = parse("\\foo{\\bar}");
+        match tree.node_at(Position::new(0, 7)) {
+            Some(LatexNode::Command(command)) => assert_eq!(command.name.text(), "\\bar"),
+            node => panic!("Unexpected node: {:?}", node),
+        }
+    }
+
+    #[test]
+    fn ancestors() {
+        let tree = parse("\\foo{\\bar}");
+        let ancestors = tree.ancestors(Position::new(0, 7));
+        assert_eq!(ancestors.len(), 4);
+        match (&ancestors[0], &ancestors[3]) {
+            (LatexNode::Command(command), LatexNode::Root(_)) => {
+                assert_eq!(command.name.text(), "\\bar")
+            }
+            nodes => panic!("Unexpected nodes: {:?}", nodes),
+        }
+    }
+
+    #[test]
+    fn environment_at() {
+        let tree = parse("\\begin{a}\\begin{b}\n\\end{b}\\end{a}");
+        let env = tree.environment_at(Position::new(1, 0)).unwrap();
+        assert_eq!(env.left.name().unwrap().text(), "b");
+        assert!(tree.environment_at(Position::new(2, 0)).is_none());
+    }
+
+    #[test]
+    fn environment_definitions() {
+        let tree = parse(
+            "\\newenvironment{foo}{}{}\n\\NewDocumentEnvironment{bar}{m}{}{}\\newenvironment",
+        );
+        let names: Vec<&str> = tree
+            .env
+            .definitions
+            .iter()
+            .map(|definition| definition.name().text())
+            .collect();
+        assert_eq!(names, vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn math_context_at() {
+        let tree = parse("\\begin{equation}\\text{$x$}\\end{equation}");
+        match tree.math_context_at(Position::new(0, 24)) {
+            Some(LatexMathContext::Inline(_)) => (),
+            context => panic!("Unexpected context: {:?}", context),
+        }
+        match tree.math_context_at(Position::new(0, 17)) {
+            Some(LatexMathContext::Environment(_)) => (),
+            context => panic!("Unexpected context: {:?}", context),
+        }
+        assert!(tree.math_context_at(Position::new(0, 2)).is_none());
+    }
+
+    #[test]
+    fn bib_items() {
+        let tree = parse(
+            "\\begin{thebibliography}{9}\n\\bibitem{foo} Foo\n\\bibitem[Bar]{bar} Bar\n\\end{thebibliography}",
+        );
+        assert_eq!(tree.bib_items.len(), 2);
+        assert_eq!(tree.bib_items[0].key().text(), "foo");
+        assert_eq!(
+            tree.bib_items[0].content_range,
+            Range::new_simple(1, 13, 2, 0)
+        );
+        assert_eq!(tree.bib_items[1].key().text(), "bar");
+        assert_eq!(
+            tree.bib_items[1].content_range,
+            Range::new_simple(2, 18, 3, 0)
+        );
+        assert_eq!(
+            tree.bib_item_key_at(Position::new(2, 15)).unwrap().text(),
+            "bar"
+        );
+        assert!(tree.bib_item_key_at(Position::new(1, 16)).is_none());
+    }
+
+    #[test]
+    fn label_name_at() {
+        let tree = parse("\\label{foo}\\ref{bar}");
+        assert_eq!(
+            tree.label_name_at(Position::new(0, 9)).unwrap().text(),
+            "foo"
+        );
+        assert_eq!(
+            tree.label_name_at(Position::new(0, 18)).unwrap().text(),
+            "bar"
+        );
+        assert!(tree.label_name_at(Position::new(0, 2)).is_none());
+    }
+
+    #[test]
+    fn command_definition_parameters() {
+        let tree = parse("\\newcommand{\\foo}[2]{#1 \\bar{#3} ##1}");
+        let definition = &tree.command_definitions[0];
+        assert_eq!(definition.argument_count(), 2);
+        assert_eq!(
+            definition.parameters(),
+            vec![
+                LatexParameter {
+                    number: 1,
+                    range: Range::new_simple(0, 21, 0, 23),
+                },
+                LatexParameter {
+                    number: 3,
+                    range: Range::new_simple(0, 29, 0, 31),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn citation_key_at() {
+        let tree = parse("\\cite{foo,bar}");
+        assert_eq!(
+            tree.citation_key_at(Position::new(0, 12)).unwrap().text(),
+            "bar"
+        );
+        assert!(tree.citation_key_at(Position::new(0, 2)).is_none());
+    }
+}
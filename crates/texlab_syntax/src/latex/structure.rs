@@ -144,12 +144,30 @@ impl LatexLabelNumbering {
             }
         }
 
-        if command.name.text() != "\\newlabel" || !command.has_word(0) {
+        if !command.has_word(0) {
             return None;
         }
 
+        let group = match command.name.text() {
+            "\\newlabel" | "\\customlabel" => command.args.get(1)?,
+            "\\zref@newlabel" => {
+                command
+                    .args
+                    .get(1)?
+                    .children
+                    .iter()
+                    .find_map(|child| match child {
+                        LatexContent::Command(property) if property.name.text() == "\\default" => {
+                            property.args.get(0)
+                        }
+                        _ => None,
+                    })?
+            }
+            _ => return None,
+        };
+
         let mut analyzer = FirstText::default();
-        analyzer.visit_group(Arc::clone(command.args.get(1)?));
+        analyzer.visit_group(Arc::clone(group));
         let number = analyzer
             .text?
             .words
@@ -191,6 +209,48 @@ impl SyntaxNode for LatexCaption {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LatexMatterKind {
+    Front,
+    Main,
+    Back,
+    Appendix,
+}
+
+/// A command like `\frontmatter` or `\appendix` that changes the numbering of the sections.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexMatter {
+    pub command: Arc<LatexCommand>,
+    pub kind: LatexMatterKind,
+}
+
+impl LatexMatter {
+    fn parse(commands: &[Arc<LatexCommand>]) -> Vec<Self> {
+        let mut matters = Vec::new();
+        for command in commands {
+            let kind = match command.name.text() {
+                "\\frontmatter" => LatexMatterKind::Front,
+                "\\mainmatter" => LatexMatterKind::Main,
+                "\\backmatter" => LatexMatterKind::Back,
+                "\\appendix" => LatexMatterKind::Appendix,
+                _ => continue,
+            };
+
+            matters.push(Self {
+                command: Arc::clone(&command),
+                kind,
+            });
+        }
+        matters
+    }
+}
+
+impl SyntaxNode for LatexMatter {
+    fn range(&self) -> Range {
+        self.command.range()
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct LatexItem {
     pub command: Arc<LatexCommand>,
@@ -234,6 +294,7 @@ pub struct LatexStructureInfo {
     pub label_numberings: Vec<LatexLabelNumbering>,
     pub captions: Vec<LatexCaption>,
     pub items: Vec<LatexItem>,
+    pub matters: Vec<LatexMatter>,
 }
 
 impl LatexStructureInfo {
@@ -244,6 +305,7 @@ impl LatexStructureInfo {
             label_numberings: LatexLabelNumbering::parse(commands),
             captions: LatexCaption::parse(commands),
             items: LatexItem::parse(commands),
+            matters: LatexMatter::parse(commands),
         }
     }
 }
@@ -1,10 +1,12 @@
 mod bibtex;
+mod formatting;
 mod language;
 mod latex;
 mod lsp_kind;
 mod text;
 
 pub use self::bibtex::*;
+pub use self::formatting::*;
 pub use self::language::*;
 pub use self::latex::*;
 pub use self::lsp_kind::*;
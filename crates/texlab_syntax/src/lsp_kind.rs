@@ -24,6 +24,7 @@ pub enum Structure {
     Field,
     Argument,
     GlossaryEntry,
+    Parameter,
 }
 
 impl Structure {
@@ -56,6 +57,7 @@ impl Structure {
             Self::Field => CompletionItemKind::Field,
             Self::Argument => CompletionItemKind::Value,
             Self::GlossaryEntry => CompletionItemKind::Keyword,
+            Self::Parameter => CompletionItemKind::TypeParameter,
         }
     }
 
@@ -88,6 +90,7 @@ impl Structure {
             Self::Field => SymbolKind::Field,
             Self::Argument => SymbolKind::Number,
             Self::GlossaryEntry => unimplemented!(),
+            Self::Parameter => SymbolKind::TypeParameter,
         }
     }
 }
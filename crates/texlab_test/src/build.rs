@@ -1,5 +1,4 @@
-use super::capabilities::CLIENT_FULL_CAPABILITIES;
-use super::scenario::Scenario;
+use texlab::testing::{Scenario, CLIENT_FULL_CAPABILITIES};
 use texlab_distro::DistributionKind::*;
 use texlab_protocol::*;
 
@@ -33,7 +32,10 @@ pub async fn run_command(executable: &'static str, file: &'static str) -> Option
     match scenario.distribution.kind() {
         Texlive | Miktex => {
             let text_document = TextDocumentIdentifier::new(scenario.uri(file).into());
-            let params = BuildParams { text_document };
+            let params = BuildParams {
+                text_document,
+                variant: None,
+            };
             let result = scenario
                 .server
                 .execute(|svr| svr.build(params))
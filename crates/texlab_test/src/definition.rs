@@ -1,5 +1,4 @@
-use super::capabilities::{CLIENT_FULL_CAPABILITIES, CLIENT_NO_LINK_CAPABILITIES};
-use super::scenario::Scenario;
+use texlab::testing::{Scenario, CLIENT_FULL_CAPABILITIES, CLIENT_NO_LINK_CAPABILITIES};
 use texlab_protocol::*;
 
 pub async fn run(
@@ -1,6 +1,5 @@
-use super::capabilities::CLIENT_FULL_CAPABILITIES;
-use super::scenario::Scenario;
 use std::cmp::Reverse;
+use texlab::testing::{Scenario, CLIENT_FULL_CAPABILITIES};
 use texlab_protocol::*;
 
 pub async fn run(file: &'static str) -> Vec<FoldingRange> {
@@ -1,6 +1,5 @@
-use super::capabilities::CLIENT_FULL_CAPABILITIES;
-use super::scenario::Scenario;
 use std::collections::HashMap;
+use texlab::testing::{Scenario, CLIENT_FULL_CAPABILITIES};
 use texlab_protocol::*;
 
 pub async fn run_bibtex(
@@ -14,6 +13,8 @@ pub async fn run_bibtex(
         *scenario.client.options.lock().await = Options {
             bibtex: Some(BibtexOptions {
                 formatting: options,
+                hover: None,
+                dialect: None,
             }),
             latex: None,
         };
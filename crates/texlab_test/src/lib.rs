@@ -1,14 +1,9 @@
 pub mod build;
-mod capabilities;
-mod client;
 pub mod completion;
 pub mod definition;
 pub mod folding;
 pub mod formatting;
 pub mod hover;
-mod scenario;
 pub mod symbol;
 
-pub use self::capabilities::*;
-pub use self::client::*;
-pub use self::scenario::*;
+pub use texlab::testing::*;
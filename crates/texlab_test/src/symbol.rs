@@ -1,5 +1,4 @@
-use super::capabilities::CLIENT_FULL_CAPABILITIES;
-use super::scenario::Scenario;
+use texlab::testing::{Scenario, CLIENT_FULL_CAPABILITIES};
 use texlab_protocol::*;
 
 pub async fn run_hierarchical(file: &'static str) -> Vec<DocumentSymbol> {
@@ -27,8 +26,9 @@ pub async fn run_workspace(query: &'static str) -> (Scenario, Vec<SymbolInformat
     scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
     scenario.open("foo.tex").await;
     scenario.open("bar.bib").await;
-    let params = WorkspaceSymbolParams {
+    let params = WorkspaceSymbolRequestParams {
         query: query.into(),
+        partial_result_token: None,
     };
     let symbols = scenario
         .server
@@ -39,6 +39,27 @@ pub async fn run_workspace(query: &'static str) -> (Scenario, Vec<SymbolInformat
     (scenario, symbols)
 }
 
+pub async fn run_workspace_partial(
+    query: &'static str,
+) -> (Scenario, Vec<SymbolInformation>, Vec<PartialResultParams>) {
+    let scenario = Scenario::new("symbol/workspace", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+    scenario.open("bar.bib").await;
+    let params = WorkspaceSymbolRequestParams {
+        query: query.into(),
+        partial_result_token: Some(ProgressToken::String("symbols".into())),
+    };
+    let symbols = scenario
+        .server
+        .execute(|svr| svr.workspace_symbol(params))
+        .await
+        .unwrap();
+
+    let partial_results = scenario.client.partial_results.lock().await.clone();
+    (scenario, symbols, partial_results)
+}
+
 pub mod verify {
     use super::*;
 
@@ -69,6 +69,24 @@ impl Database {
             .any(|component| component.file_names.iter().any(|f| f == file_name))
     }
 
+    pub fn command_image(&self, name: &str) -> Option<&str> {
+        self.components
+            .iter()
+            .flat_map(|component| component.commands.iter())
+            .find(|command| command.name == name && command.image.is_some())
+            .and_then(|command| command.image.as_ref().map(AsRef::as_ref))
+    }
+
+    pub fn argument_image(&self, name: &str) -> Option<&str> {
+        self.components
+            .iter()
+            .flat_map(|component| component.commands.iter())
+            .flat_map(|command| command.parameters.iter())
+            .flat_map(|parameter| parameter.0.iter())
+            .find(|argument| argument.name == name && argument.image.is_some())
+            .and_then(|argument| argument.image.as_ref().map(AsRef::as_ref))
+    }
+
     pub fn documentation(&self, name: &str) -> Option<MarkupContent> {
         let metadata = self
             .metadata
@@ -128,6 +128,7 @@ pub struct FeatureSpec {
     pub files: Vec<FeatureSpecFile>,
     pub main_file: &'static str,
     pub position: Position,
+    pub range: Range,
     pub new_name: &'static str,
     pub include_declaration: bool,
     pub client_capabilities: ClientCapabilities,
@@ -141,6 +142,7 @@ impl Default for FeatureSpec {
             files: Vec::new(),
             main_file: "",
             position: Position::new(0, 0),
+            range: Range::new(Position::new(0, 0), Position::new(0, 0)),
             new_name: "",
             include_declaration: false,
             client_capabilities: ClientCapabilities::default(),
@@ -262,6 +264,20 @@ impl Into<FeatureRequest<DocumentSymbolParams>> for FeatureSpec {
     }
 }
 
+impl Into<FeatureRequest<CodeActionParams>> for FeatureSpec {
+    fn into(self) -> FeatureRequest<CodeActionParams> {
+        let params = CodeActionParams {
+            text_document: self.identifier(),
+            range: self.range,
+            context: CodeActionContext {
+                diagnostics: Vec::new(),
+                only: None,
+            },
+        };
+        self.request(params)
+    }
+}
+
 pub fn test_feature<F, P, O, S>(provider: F, spec: S) -> O
 where
     F: FeatureProvider<Params = P, Output = O>,
@@ -2,6 +2,7 @@ mod components;
 mod document;
 mod feature;
 mod outline;
+pub mod sandbox;
 mod workspace;
 
 pub use self::components::*;
@@ -4,6 +4,9 @@ use std::collections::HashSet;
 use texlab_protocol::*;
 use texlab_syntax::*;
 
+/// The prefix of the top-level sections after `\appendix`.
+pub const APPENDIX_PREFIX: &str = "Appendix";
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct Outline<'a> {
     sections: Vec<OutlineSection<'a>>,
@@ -11,12 +14,26 @@ pub struct Outline<'a> {
 
 impl<'a> Outline<'a> {
     pub fn find(&self, uri: &Uri, position: Position) -> Option<&'a LatexSection> {
+        self.find_section(uri, position).map(|sec| sec.item)
+    }
+
+    /// Returns the part of the document like the front matter or the appendix
+    /// that contains the section at the given position.
+    pub fn matter(&self, uri: &Uri, position: Position) -> Option<LatexMatterKind> {
+        self.find_section(uri, position).and_then(|sec| sec.matter)
+    }
+
+    fn find_section(&self, uri: &Uri, position: Position) -> Option<&OutlineSection<'a>> {
         self.sections
             .iter()
             .filter(|sec| sec.document.uri == *uri)
             .rev()
             .find(|sec| sec.item.end() <= position)
-            .map(|sec| sec.item)
+    }
+
+    /// Returns the sections of the project in document order.
+    pub fn sections(&self) -> impl Iterator<Item = (&'a Document, &'a LatexSection)> + '_ {
+        self.sections.iter().map(|sec| (sec.document, sec.item))
     }
 
     pub fn analyze(view: &'a DocumentView, options: &Options) -> Self {
@@ -42,11 +59,20 @@ impl<'a> Outline<'a> {
 struct OutlineSection<'a> {
     pub document: &'a Document,
     pub item: &'a LatexSection,
+    pub matter: Option<LatexMatterKind>,
 }
 
 impl<'a> OutlineSection<'a> {
-    fn new(document: &'a Document, item: &'a LatexSection) -> Self {
-        Self { document, item }
+    fn new(
+        document: &'a Document,
+        item: &'a LatexSection,
+        matter: Option<LatexMatterKind>,
+    ) -> Self {
+        Self {
+            document,
+            item,
+            matter,
+        }
     }
 }
 
@@ -54,6 +80,7 @@ impl<'a> OutlineSection<'a> {
 struct OutlineSectionFinder<'a> {
     visited: HashSet<&'a Uri>,
     sections: Vec<OutlineSection<'a>>,
+    matter: Option<LatexMatterKind>,
 }
 
 impl<'a> OutlineSectionFinder<'a> {
@@ -70,14 +97,20 @@ impl<'a> OutlineSectionFinder<'a> {
             for include in &tree.includes {
                 items.push(OutlineItem::Include(include));
             }
+            for matter in &tree.structure.matters {
+                items.push(OutlineItem::Matter(matter));
+            }
             items.sort_by_key(SyntaxNode::start);
 
             for item in items {
                 match item {
                     OutlineItem::Section(item) => {
-                        let section = OutlineSection::new(document, item);
+                        let section = OutlineSection::new(document, item, self.matter);
                         self.sections.push(section);
                     }
+                    OutlineItem::Matter(matter) => {
+                        self.matter = Some(matter.kind);
+                    }
                     OutlineItem::Include(item) => {
                         for document in &view.related_documents {
                             for targets in &item.all_targets {
@@ -98,6 +131,7 @@ impl<'a> OutlineSectionFinder<'a> {
 enum OutlineItem<'a> {
     Section(&'a LatexSection),
     Include(&'a LatexInclude),
+    Matter(&'a LatexMatter),
 }
 
 impl<'a> SyntaxNode for OutlineItem<'a> {
@@ -105,6 +139,7 @@ impl<'a> SyntaxNode for OutlineItem<'a> {
         match self {
             OutlineItem::Section(section) => section.range(),
             OutlineItem::Include(include) => include.range(),
+            OutlineItem::Matter(matter) => matter.range(),
         }
     }
 }
@@ -387,11 +422,23 @@ impl OutlineContext {
     fn find_section(view: &DocumentView, label: &LatexLabel, outline: &Outline) -> Option<Self> {
         let section = outline.find(&view.document.uri, label.start())?;
         let content = &section.command.args[section.index];
+        let number = Self::find_number(view, label);
+        let is_top_level = number
+            .as_ref()
+            .map_or(false, |number| !number.contains('.'));
+        let (prefix, number) = match outline.matter(&view.document.uri, label.start()) {
+            Some(LatexMatterKind::Front) | Some(LatexMatterKind::Back) => (section.prefix, None),
+            Some(LatexMatterKind::Appendix) if is_top_level => (APPENDIX_PREFIX, number),
+            Some(LatexMatterKind::Main) | Some(LatexMatterKind::Appendix) | None => {
+                (section.prefix, number)
+            }
+        };
+
         Some(Self {
             range: section.range(),
-            number: Self::find_number(view, label),
+            number,
             item: Section {
-                prefix: section.prefix,
+                prefix,
                 text: extract_group(content),
             },
         })
@@ -0,0 +1,61 @@
+use path_clean::PathClean;
+use std::path::{Path, PathBuf};
+
+/// The directory that contains the projects of all clients of the server.
+pub const SANDBOX_DIRECTORY: &str = "/minio";
+
+/// Resolves `.` and `..` without accessing the file system and returns the path
+/// if it is located inside of the given directory.
+///
+/// The path is cleaned before the comparison because `Path::starts_with`
+/// compares components, so that `/minio/../etc` would start with `/minio`.
+pub fn confine(path: &Path, directory: &Path) -> Option<PathBuf> {
+    let path = path.to_path_buf().clean();
+    if path.is_absolute() && path.starts_with(directory) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confine_to_sandbox(path: &str) -> Option<PathBuf> {
+        confine(Path::new(path), Path::new(SANDBOX_DIRECTORY))
+    }
+
+    #[test]
+    fn inside() {
+        assert_eq!(
+            confine_to_sandbox("/minio/foo/./bar.tex"),
+            Some(PathBuf::from("/minio/foo/bar.tex"))
+        );
+    }
+
+    #[test]
+    fn parent_inside() {
+        assert_eq!(
+            confine_to_sandbox("/minio/foo/../bar.tex"),
+            Some(PathBuf::from("/minio/bar.tex"))
+        );
+    }
+
+    #[test]
+    fn parent_outside() {
+        assert_eq!(confine_to_sandbox("/minio/../etc/cron.d/foo"), None);
+        assert_eq!(confine_to_sandbox("/minio/.."), None);
+    }
+
+    #[test]
+    fn outside() {
+        assert_eq!(confine_to_sandbox("/etc/passwd"), None);
+        assert_eq!(confine_to_sandbox("/minio2/foo"), None);
+    }
+
+    #[test]
+    fn relative() {
+        assert_eq!(confine_to_sandbox("minio/foo"), None);
+    }
+}
@@ -1,8 +1,9 @@
 use std::mem;
+use std::path::PathBuf;
 use std::sync::Mutex;
-use texlab_protocol::{ProgressToken, Uri};
+use texlab_protocol::{ArtifactsChangedParams, ProgressToken, Uri};
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum LintReason {
     Change,
     Save,
@@ -15,10 +16,14 @@ pub enum Action {
     LoadConfiguration,
     UpdateConfiguration(serde_json::Value),
     DetectRoot(Uri),
+    Preload(Vec<PathBuf>),
     PublishDiagnostics,
     RunLinter(Uri, LintReason),
+    SchedulePreview(Uri),
+    InstallMissingPackages(Uri),
     Build(Uri),
     CancelBuild(ProgressToken),
+    NotifyArtifacts(ArtifactsChangedParams),
 }
 
 #[derive(Debug, Default)]
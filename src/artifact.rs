@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use texlab_protocol::{ArtifactKind, Options};
+use texlab_workspace::sandbox;
+
+/// The minimum time between two scans of the build artifacts.
+const SCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The maximum number of bytes of a build log that are searched for warnings.
+const MAX_LOG_SIZE: u64 = 8 * 1024 * 1024;
+
+#[derive(Debug, Default)]
+pub struct ArtifactManager {
+    modified_by_path: Mutex<HashMap<PathBuf, Option<SystemTime>>>,
+    last_scan: Mutex<Option<Instant>>,
+}
+
+impl ArtifactManager {
+    /// Returns `true` if the artifacts should be scanned again
+    /// and restarts the interval in that case.
+    pub fn is_due(&self) -> bool {
+        let mut last_scan = self.last_scan.lock().unwrap();
+        let now = Instant::now();
+        match *last_scan {
+            Some(time) if now.duration_since(time) < SCAN_INTERVAL => false,
+            _ => {
+                *last_scan = Some(now);
+                true
+            }
+        }
+    }
+
+    /// Returns the build artifacts of the given document that changed since the last call.
+    ///
+    /// Artifacts that are seen for the first time are only recorded.
+    /// Artifacts outside of the sandbox are ignored.
+    pub fn update(&self, tex_path: &Path, options: &Options, sandbox: &Path) -> Vec<ArtifactKind> {
+        let mut modified_by_path = self.modified_by_path.lock().unwrap();
+        let mut artifacts = Vec::new();
+        for kind in &[ArtifactKind::Aux, ArtifactKind::Pdf, ArtifactKind::Log] {
+            /* Security Patch */
+            if let Some(path) = options
+                .resolve_output_file(tex_path, kind.extension())
+                .and_then(|path| sandbox::confine(&path, sandbox))
+            {
+                let modified = fs::metadata(&path).and_then(|data| data.modified()).ok();
+
+                match modified_by_path.insert(path, modified) {
+                    Some(old_modified) if old_modified != modified => artifacts.push(*kind),
+                    _ => (),
+                }
+            }
+        }
+        artifacts
+    }
+}
+
+/// Counts the warnings in the build log of the given document.
+///
+/// Only the beginning of a large log is searched and logs outside of the sandbox are ignored.
+pub fn count_log_warnings(tex_path: &Path, options: &Options, sandbox: &Path) -> Option<u64> {
+    /* Security Patch */
+    let path = options.resolve_output_file(tex_path, ArtifactKind::Log.extension())?;
+    let path = sandbox::confine(&path, sandbox)?;
+    let mut bytes = Vec::new();
+    File::open(path)
+        .ok()?
+        .take(MAX_LOG_SIZE)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    let log = String::from_utf8_lossy(&bytes);
+    let count = log.lines().filter(|line| line.contains("Warning:")).count();
+    Some(count as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn update_new_artifact() {
+        let directory = env::temp_dir().join("texlab-artifact-test");
+        fs::create_dir_all(&directory).unwrap();
+        let tex_path = directory.join("foo.tex");
+        let pdf_path = directory.join("foo.pdf");
+        let _ = fs::remove_file(&pdf_path);
+
+        let manager = ArtifactManager::default();
+        let options = Options::default();
+        assert!(manager.update(&tex_path, &options, &directory).is_empty());
+
+        fs::write(&pdf_path, "").unwrap();
+        assert_eq!(
+            manager.update(&tex_path, &options, &directory),
+            vec![ArtifactKind::Pdf]
+        );
+        assert!(manager.update(&tex_path, &options, &directory).is_empty());
+        fs::remove_file(&pdf_path).unwrap();
+    }
+
+    #[test]
+    fn count_warnings() {
+        let directory = env::temp_dir().join("texlab-artifact-warnings-test");
+        fs::create_dir_all(&directory).unwrap();
+        let tex_path = directory.join("foo.tex");
+        fs::write(
+            directory.join("foo.log"),
+            "LaTeX Warning: Reference `foo' undefined.\nfoo\nPackage bar Warning: baz\n",
+        )
+        .unwrap();
+
+        let options = Options::default();
+        assert_eq!(count_log_warnings(&tex_path, &options, &directory), Some(2));
+
+        let sandbox = directory.join("sandbox");
+        assert_eq!(count_log_warnings(&tex_path, &options, &sandbox), None);
+    }
+
+    #[test]
+    fn scan_interval() {
+        let manager = ArtifactManager::default();
+        assert!(manager.is_due());
+        assert!(!manager.is_due());
+    }
+}
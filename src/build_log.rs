@@ -0,0 +1,259 @@
+use once_cell::sync::Lazy;
+use path_clean::PathClean;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use texlab_protocol::*;
+
+/// The URI scheme of the virtual documents that contain build logs.
+///
+/// The path of such a URI is the path of the TeX file that was built.
+pub const SCHEME: &str = "texlab-log";
+
+const MAX_LINE_LENGTH: usize = 79;
+
+static PACKAGE_MESSAGE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\([a-zA-Z_\-]+\)\s*(?P<msg>.*)$").unwrap());
+
+static FILE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\((?P<file>[^\r\n()\s]+\.(tex|sty|cls|clo|cfg|def|fd|aux|bbl|toc|ltx))").unwrap()
+});
+
+static LINE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(^l\.(?P<line1>\d+)|on input line (?P<line2>\d+)|at lines? (?P<line3>\d+))")
+        .unwrap()
+});
+
+pub fn log_uri(tex_uri: &Uri) -> Option<Uri> {
+    Url::parse(&format!("{}://{}", SCHEME, tex_uri.path()))
+        .ok()
+        .map(Into::into)
+}
+
+pub fn tex_uri(log_uri: &Uri) -> Option<Uri> {
+    if log_uri.scheme() != SCHEME {
+        return None;
+    }
+
+    Url::parse(&format!("file://{}", log_uri.path()))
+        .ok()
+        .map(Into::into)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BuildLogLine {
+    pub text: String,
+    pub file: Option<PathBuf>,
+    pub line: Option<u64>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct BuildLog {
+    pub lines: Vec<BuildLogLine>,
+}
+
+impl BuildLog {
+    /// Parses a TeX log file that was created inside of `build_dir`.
+    ///
+    /// Lines that were wrapped by TeX are joined again and every line is attributed
+    /// to the file that was being read when the line was written.
+    pub fn parse(log: &str, build_dir: &Path) -> Self {
+        let mut stack: Vec<Option<PathBuf>> = Vec::new();
+        let mut lines = Vec::new();
+        for text in join_lines(log) {
+            let file = attribute_file(&text, build_dir, &mut stack);
+            if text.trim().is_empty() {
+                continue;
+            }
+
+            let line = LINE_REGEX.captures(&text).and_then(|captures| {
+                captures
+                    .name("line1")
+                    .or_else(|| captures.name("line2"))
+                    .or_else(|| captures.name("line3"))
+                    .and_then(|line| line.as_str().parse::<u64>().ok())
+            });
+
+            lines.push(BuildLogLine {
+                text,
+                file,
+                line: line.map(|line| line.saturating_sub(1)),
+            });
+        }
+        Self { lines }
+    }
+
+    /// Renders the log as a document and returns the locations that it refers to.
+    pub fn render(&self) -> (String, Vec<LocationLink>) {
+        let mut text = String::new();
+        let mut links = Vec::new();
+        let mut current_file = None;
+        let mut line_number = 0;
+        for line in &self.lines {
+            if line.file.is_some() && line.file != current_file {
+                current_file = line.file.clone();
+                if line_number > 0 {
+                    text.push('\n');
+                    line_number += 1;
+                }
+
+                let path = current_file.as_ref().unwrap();
+                text.push_str(&format!("==> {} <==\n", path.to_string_lossy()));
+                line_number += 1;
+            }
+
+            let target = line
+                .file
+                .as_ref()
+                .and_then(|file| Url::from_file_path(file).ok());
+
+            if let (Some(target_uri), Some(target_line)) = (target, line.line) {
+                let target_range = Range::new_simple(target_line, 0, target_line, 0);
+                let length = line.text.chars().count() as u64;
+                links.push(LocationLink {
+                    origin_selection_range: Some(Range::new_simple(
+                        line_number,
+                        0,
+                        line_number,
+                        length,
+                    )),
+                    target_uri,
+                    target_range,
+                    target_selection_range: target_range,
+                });
+            }
+
+            text.push_str(&line.text);
+            text.push('\n');
+            line_number += 1;
+        }
+        (text, links)
+    }
+}
+
+fn join_lines(log: &str) -> Vec<String> {
+    let mut old_lines = log.lines();
+    let mut new_lines: Vec<String> = Vec::new();
+    while let Some(line) = old_lines.next() {
+        if let Some(captures) = PACKAGE_MESSAGE_REGEX.captures(line) {
+            if let Some(last_line) = new_lines.last_mut() {
+                last_line.push(' ');
+                last_line.push_str(&captures["msg"]);
+                continue;
+            }
+        }
+
+        let mut new_line = line.to_owned();
+        let mut length = line.chars().count();
+        while length == MAX_LINE_LENGTH {
+            match old_lines.next() {
+                Some(next_line) => {
+                    new_line.push_str(next_line);
+                    length = next_line.chars().count();
+                }
+                None => break,
+            }
+        }
+        new_lines.push(new_line);
+    }
+    new_lines
+}
+
+fn attribute_file(
+    text: &str,
+    build_dir: &Path,
+    stack: &mut Vec<Option<PathBuf>>,
+) -> Option<PathBuf> {
+    let mut file = None;
+    let mut index = 0;
+    while let Some(c) = text[index..].chars().next() {
+        if c == '(' {
+            if let Some(captures) = FILE_REGEX.captures(&text[index..]) {
+                stack.push(Some(build_dir.join(&captures["file"]).clean()));
+                index += captures[0].len();
+                continue;
+            }
+            stack.push(None);
+        } else if c == ')' {
+            stack.pop();
+        } else if !c.is_whitespace() && file.is_none() {
+            file = Some(current_file(stack));
+        }
+        index += c.len_utf8();
+    }
+    file.unwrap_or_else(|| current_file(stack))
+}
+
+fn current_file(stack: &[Option<PathBuf>]) -> Option<PathBuf> {
+    stack.iter().rev().find_map(Clone::clone)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn join_wrapped_lines() {
+        let long_line = "a".repeat(MAX_LINE_LENGTH);
+        let log = format!("{}\nb\n(hyperref)   c\nd", long_line);
+        assert_eq!(
+            join_lines(&log),
+            vec![format!("{}b c", long_line), "d".to_owned()]
+        );
+    }
+
+    #[test]
+    fn file_stack() {
+        let build_dir = env::temp_dir();
+        let log = "(./foo.tex (./bar.tex\n\
+                   ! Undefined control sequence.\n\
+                   l.3 \\baz\n\
+                   ) (see the transcript file)\n\
+                   LaTeX Warning: Reference `qux' undefined on input line 5.\n\
+                   )";
+
+        let lines = BuildLog::parse(log, &build_dir).lines;
+        let foo = Some(build_dir.join("foo.tex"));
+        let bar = Some(build_dir.join("bar.tex"));
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0].file, bar);
+        assert_eq!(lines[1].file, bar);
+        assert_eq!(lines[2].file, bar);
+        assert_eq!(lines[2].line, Some(2));
+        assert_eq!(lines[3].file, foo);
+        assert_eq!(lines[4].file, foo);
+        assert_eq!(lines[4].line, Some(4));
+        assert_eq!(lines[5].file, None);
+    }
+
+    #[test]
+    fn render() {
+        let build_dir = env::temp_dir();
+        let log = "(./foo.tex\n\
+                   LaTeX Warning: Reference `bar' undefined on input line 2.\n\
+                   )";
+
+        let (text, links) = BuildLog::parse(log, &build_dir).render();
+        let path = build_dir.join("foo.tex");
+        assert_eq!(
+            text,
+            format!(
+                "==> {} <==\n(./foo.tex\n\
+                 LaTeX Warning: Reference `bar' undefined on input line 2.\n)\n",
+                path.to_string_lossy()
+            )
+        );
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].origin_selection_range.unwrap().start.line, 2);
+        assert_eq!(links[0].target_uri, Url::from_file_path(path).unwrap());
+        assert_eq!(links[0].target_range, Range::new_simple(1, 0, 1, 0));
+    }
+
+    #[test]
+    fn uri_roundtrip() {
+        let tex = Uri::from_file_path(env::temp_dir().join("foo.tex")).unwrap();
+        let log = log_uri(&tex).unwrap();
+        assert_eq!(log.scheme(), SCHEME);
+        assert_eq!(tex_uri(&log), Some(tex));
+    }
+}
@@ -0,0 +1,307 @@
+use crate::diagnostics::external_checker_command;
+use crate::policy::ToolPolicy;
+use std::env;
+use std::path::{Path, PathBuf};
+use texlab_distro::DistributionKind;
+use texlab_protocol::*;
+
+/// Lists the optional features of the session and the reasons why some of them are not active.
+pub fn report(
+    client_capabilities: &ClientCapabilities,
+    options: &Options,
+    distribution: DistributionKind,
+    tools: &ToolPolicy,
+) -> CapabilitiesReport {
+    let latex = options.latex.clone().unwrap_or_default();
+    let mut features = Vec::new();
+
+    features.push(if distribution == DistributionKind::Unknown {
+        disabled(
+            "distribution",
+            FeatureDisabledReason::MissingBinary,
+            "No TeX distribution was found",
+        )
+    } else {
+        enabled("distribution", Some(format!("{:?}", distribution)))
+    });
+
+    let build = latex.build.unwrap_or_default();
+    features.push(binary("build", &build.executable()));
+
+    let forward_search = latex.forward_search.and_then(|search| search.executable);
+    features.push(match forward_search {
+        Some(executable) => binary("forwardSearch", &executable),
+        None => config_off("forwardSearch", "latex.forwardSearch.executable is not set"),
+    });
+
+    let lint = latex.lint.unwrap_or_default();
+    features.push(match lint.tool() {
+        LatexLintTool::Chktex => binary("lint", "chktex"),
+        LatexLintTool::Lacheck => binary("lint", "lacheck"),
+        LatexLintTool::None => config_off("lint", "latex.lint.tool is none"),
+    });
+
+    features.push(binary("spellCheck", "hunspell"));
+
+    features.push(match &tools.language_tool {
+        Some(url) => enabled("grammarCheck", Some(url.to_string())),
+        None => config_off(
+            "grammarCheck",
+            "the server was started without --languagetool-url",
+        ),
+    });
+
+    features.push(match &latex.external_checker {
+        Some(checker) => match external_checker_command(checker, tools) {
+            Some(command) => binary("externalChecker", &command),
+            None => config_off("externalChecker", "latex.externalChecker is invalid"),
+        },
+        None => config_off("externalChecker", "latex.externalChecker is not set"),
+    });
+
+    let terminology = latex.terminology.unwrap_or_default();
+    features.push(if terminology.enabled() {
+        enabled("terminology", None)
+    } else {
+        config_off("terminology", "latex.terminology.enabled is false")
+    });
+
+    let unicode = latex.unicode.unwrap_or_default();
+    features.push(if unicode.enabled() {
+        enabled("unicode", None)
+    } else {
+        config_off("unicode", "latex.unicode.enabled is false")
+    });
+
+    let preview = latex.preview.unwrap_or_default();
+    features.push(if preview.enabled() {
+        enabled("preview", None)
+    } else {
+        config_off("preview", "latex.preview.enabled is false")
+    });
+
+    let abbreviations = latex
+        .completion
+        .and_then(|completion| completion.abbreviations)
+        .unwrap_or_default();
+    features.push(if abbreviations.enabled() {
+        enabled("abbreviations", None)
+    } else {
+        config_off(
+            "abbreviations",
+            "latex.completion.abbreviations.enabled is false",
+        )
+    });
+
+    let miktex = latex.miktex.unwrap_or_default();
+    features.push(if distribution != DistributionKind::Miktex {
+        disabled(
+            "installMissingPackages",
+            FeatureDisabledReason::MissingBinary,
+            "MiKTeX was not found",
+        )
+    } else if !tools.install_packages {
+        disabled(
+            "installMissingPackages",
+            FeatureDisabledReason::ConfigOff,
+            "the server was started without --install-missing-packages",
+        )
+    } else if miktex.install_missing_packages() {
+        enabled("installMissingPackages", None)
+    } else {
+        config_off(
+            "installMissingPackages",
+            "latex.miktex.installMissingPackages is false",
+        )
+    });
+
+    let capabilities = [
+        (
+            "definitionLinks",
+            client_capabilities.has_definition_link_support(),
+        ),
+        (
+            "hierarchicalDocumentSymbols",
+            client_capabilities.has_hierarchical_document_symbol_support(),
+        ),
+        (
+            "workDoneProgress",
+            client_capabilities.has_work_done_progress_support(),
+        ),
+        (
+            "pullConfiguration",
+            client_capabilities.has_pull_configuration_support(),
+        ),
+        (
+            "watchedFiles",
+            client_capabilities.has_watched_files_registration_support(),
+        ),
+    ];
+    for (name, is_supported) in &capabilities {
+        features.push(if *is_supported {
+            enabled(name, None)
+        } else {
+            disabled(
+                name,
+                FeatureDisabledReason::CapabilityOff,
+                "The client does not support this feature",
+            )
+        });
+    }
+
+    CapabilitiesReport { features }
+}
+
+fn enabled(name: &str, detail: Option<String>) -> FeatureReport {
+    FeatureReport {
+        name: name.into(),
+        enabled: true,
+        reason: None,
+        detail,
+    }
+}
+
+fn disabled(name: &str, reason: FeatureDisabledReason, detail: &str) -> FeatureReport {
+    FeatureReport {
+        name: name.into(),
+        enabled: false,
+        reason: Some(reason),
+        detail: Some(detail.into()),
+    }
+}
+
+fn config_off(name: &str, detail: &str) -> FeatureReport {
+    disabled(name, FeatureDisabledReason::ConfigOff, detail)
+}
+
+/// Reports the feature as active if the executable can be found.
+fn binary(name: &str, executable: &str) -> FeatureReport {
+    match find_executable(executable) {
+        Some(path) => enabled(name, Some(path.to_string_lossy().into_owned())),
+        None => disabled(
+            name,
+            FeatureDisabledReason::MissingBinary,
+            &format!("{} was not found", executable),
+        ),
+    }
+}
+
+/// Searches the directories of the `PATH` environment variable for the executable.
+fn find_executable(executable: &str) -> Option<PathBuf> {
+    let path = Path::new(executable);
+    if path.components().count() > 1 {
+        return Some(path.to_owned()).filter(|path| path.is_file());
+    }
+
+    let mut names = vec![executable.to_owned()];
+    if cfg!(windows) {
+        names.insert(0, format!("{}.exe", executable));
+    }
+
+    env::split_paths(&env::var_os("PATH")?)
+        .flat_map(|directory| names.iter().map(move |name| directory.join(name)))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn feature<'a>(report: &'a CapabilitiesReport, name: &str) -> &'a FeatureReport {
+        report
+            .features
+            .iter()
+            .find(|feature| feature.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn default_options() {
+        let report = report(
+            &ClientCapabilities::default(),
+            &Options::default(),
+            DistributionKind::Unknown,
+            &ToolPolicy::default(),
+        );
+
+        let distribution = feature(&report, "distribution");
+        assert!(!distribution.enabled);
+        assert_eq!(
+            distribution.reason,
+            Some(FeatureDisabledReason::MissingBinary)
+        );
+
+        let grammar_check = feature(&report, "grammarCheck");
+        assert_eq!(grammar_check.reason, Some(FeatureDisabledReason::ConfigOff));
+
+        let definition_links = feature(&report, "definitionLinks");
+        assert_eq!(
+            definition_links.reason,
+            Some(FeatureDisabledReason::CapabilityOff)
+        );
+    }
+
+    #[test]
+    fn configured_features() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                lint: Some(LatexLintOptions {
+                    tool: Some(LatexLintTool::None),
+                    ..LatexLintOptions::default()
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        let tools = ToolPolicy {
+            language_tool: Url::parse("http://localhost:8081").ok(),
+            ..ToolPolicy::default()
+        };
+        let report = report(
+            &ClientCapabilities::default(),
+            &options,
+            DistributionKind::Texlive,
+            &tools,
+        );
+
+        assert!(feature(&report, "distribution").enabled);
+        assert!(feature(&report, "grammarCheck").enabled);
+        assert_eq!(
+            feature(&report, "lint").reason,
+            Some(FeatureDisabledReason::ConfigOff)
+        );
+    }
+
+    #[test]
+    fn install_packages_policy() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                miktex: Some(LatexMiktexOptions {
+                    install_missing_packages: Some(true),
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        let capabilities = ClientCapabilities::default();
+        let report_with =
+            |tools: &ToolPolicy| report(&capabilities, &options, DistributionKind::Miktex, tools);
+
+        let install = report_with(&ToolPolicy::default());
+        assert_eq!(
+            feature(&install, "installMissingPackages").reason,
+            Some(FeatureDisabledReason::ConfigOff)
+        );
+
+        let install = report_with(&ToolPolicy {
+            install_packages: true,
+            ..ToolPolicy::default()
+        });
+        assert!(feature(&install, "installMissingPackages").enabled);
+    }
+
+    #[test]
+    fn missing_executable() {
+        assert_eq!(find_executable("texlab-missing-executable"), None);
+    }
+}
@@ -0,0 +1,150 @@
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+/// Groups the citations of a project by the sections in which they occur.
+pub fn analyze(view: &DocumentView, options: &Options) -> CitationSectionsResult {
+    let outline = Outline::analyze(view, options);
+    let mut entries = Vec::new();
+    for document in &view.related_documents {
+        match &document.tree {
+            SyntaxTree::Latex(tree) => {
+                for item in &tree.bib_items {
+                    entries.push(make_entry(item.key().text(), &document.uri));
+                }
+            }
+            SyntaxTree::Bibtex(tree) => {
+                for entry in tree.entries() {
+                    if entry.is_comment() {
+                        continue;
+                    }
+
+                    if let Some(key) = &entry.key {
+                        entries.push(make_entry(key.text(), &document.uri));
+                    }
+                }
+            }
+        }
+    }
+
+    let mut cited_sections = Vec::new();
+    for document in &view.related_documents {
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            for key in tree.citations.iter().flat_map(LatexCitation::keys) {
+                let entry = match entries.iter_mut().find(|entry| entry.key == key.text()) {
+                    Some(entry) => entry,
+                    None => continue,
+                };
+
+                entry.count += 1;
+                if let Some(section) = outline.find(&document.uri, key.start()) {
+                    let section = make_section(&document.uri, section);
+                    if !entry.sections.contains(&section) {
+                        entry.sections.push(section.clone());
+                    }
+                    cited_sections.push(section);
+                }
+            }
+        }
+    }
+
+    let uncited_sections = outline
+        .sections()
+        .map(|(document, section)| make_section(&document.uri, section))
+        .filter(|section| !cited_sections.contains(section))
+        .collect();
+
+    CitationSectionsResult {
+        entries,
+        uncited_sections,
+    }
+}
+
+fn make_entry(key: &str, uri: &Uri) -> EntryCitations {
+    EntryCitations {
+        key: key.to_owned(),
+        uri: uri.clone().into(),
+        count: 0,
+        sections: Vec::new(),
+    }
+}
+
+fn make_section(uri: &Uri, section: &LatexSection) -> CitationSection {
+    CitationSection {
+        uri: uri.clone().into(),
+        range: section.range(),
+        title: extract_group(&section.command.args[section.index]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn analyze_project(files: &[(&str, &str)]) -> CitationSectionsResult {
+        let mut builder = TestWorkspaceBuilder::new();
+        for (name, text) in files {
+            builder.add_document(name, text);
+        }
+        let document = Arc::clone(&builder.workspace.documents[0]);
+        let options = Options::default();
+        let view = DocumentView::new(Arc::new(builder.workspace), document, &options);
+        analyze(&view, &options)
+    }
+
+    #[test]
+    fn cited_by_section() {
+        let result = analyze_project(&[
+            (
+                "foo.tex",
+                "\\bibliography{bar}\n\
+                 \\section{Foo}\n\
+                 \\cite{foo}\\cite{foo, bar}\n\
+                 \\section{Bar}\n\
+                 \\cite{qux}\n\
+                 \\section{Baz}\n\
+                 \\cite{bar}",
+            ),
+            ("bar.bib", "@article{foo,}\n@article{bar,}\n@article{baz,}"),
+        ]);
+
+        let keys: Vec<&str> = result.entries.iter().map(|e| e.key.as_ref()).collect();
+        assert_eq!(keys, vec!["foo", "bar", "baz"]);
+
+        let foo = &result.entries[0];
+        assert_eq!(foo.count, 2);
+        let titles: Vec<&str> = foo.sections.iter().map(|s| s.title.as_ref()).collect();
+        assert_eq!(titles, vec!["Foo"]);
+
+        let bar = &result.entries[1];
+        assert_eq!(bar.count, 2);
+        let titles: Vec<&str> = bar.sections.iter().map(|s| s.title.as_ref()).collect();
+        assert_eq!(titles, vec!["Foo", "Baz"]);
+
+        assert_eq!(result.entries[2].count, 0);
+        assert!(result.entries[2].sections.is_empty());
+
+        let titles: Vec<&str> = result
+            .uncited_sections
+            .iter()
+            .map(|s| s.title.as_ref())
+            .collect();
+        assert_eq!(titles, vec!["Bar"]);
+    }
+
+    #[test]
+    fn bib_items() {
+        let result = analyze_project(&[(
+            "foo.tex",
+            "\\section{Foo}\n\
+             \\cite{foo}\n\
+             \\begin{thebibliography}{9}\n\
+             \\bibitem{foo} Foo\n\
+             \\end{thebibliography}",
+        )]);
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].sections[0].title, "Foo");
+        assert!(result.uncited_sections.is_empty());
+    }
+}
@@ -0,0 +1,202 @@
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+const TAB_SIZE: usize = 4;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum WrapperKind {
+    Center,
+    Figure,
+    Theorem,
+}
+
+impl WrapperKind {
+    const ALL: &'static [Self] = &[
+        WrapperKind::Center,
+        WrapperKind::Figure,
+        WrapperKind::Theorem,
+    ];
+
+    fn environment(self) -> &'static str {
+        match self {
+            WrapperKind::Center => "center",
+            WrapperKind::Figure => "figure",
+            WrapperKind::Theorem => "theorem",
+        }
+    }
+
+    /// The lines that are appended to the wrapped text.
+    ///
+    /// Workspace edits cannot contain snippets, so the placeholders are left empty.
+    fn footer(self) -> &'static [&'static str] {
+        match self {
+            WrapperKind::Center | WrapperKind::Theorem => &[],
+            WrapperKind::Figure => &["\\caption{}", "\\label{fig:}"],
+        }
+    }
+}
+
+/// Wraps the selected lines in an environment.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexEnvironmentCodeActionProvider;
+
+impl FeatureProvider for LatexEnvironmentCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        let tree = match &request.document().tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return Vec::new(),
+        };
+
+        let range = request.params.range;
+        if range.start == range.end || tree.is_math_mode(range.start) {
+            return Vec::new();
+        }
+
+        let lines: Vec<&str> = request.document().text.lines().collect();
+        let start_line = range.start.line as usize;
+        let mut end_line = range.end.line as usize;
+        if range.end.character == 0 && end_line > start_line {
+            end_line -= 1;
+        }
+
+        if end_line >= lines.len() {
+            return Vec::new();
+        }
+
+        let selected_lines = &lines[start_line..=end_line];
+        let edit_range = Range::new_simple(
+            start_line as u64,
+            0,
+            end_line as u64,
+            selected_lines[selected_lines.len() - 1].chars().count() as u64,
+        );
+
+        WrapperKind::ALL
+            .iter()
+            .map(|kind| {
+                let edits = vec![TextEdit::new(edit_range, wrap(selected_lines, *kind))];
+                let mut changes = HashMap::new();
+                changes.insert(request.document().uri.clone().into(), edits);
+                CodeAction {
+                    title: format!("Wrap in {} environment", kind.environment()),
+                    kind: Some(code_action_kind::REFACTOR_REWRITE.into()),
+                    edit: Some(WorkspaceEdit::new(changes)),
+                    ..CodeAction::default()
+                }
+            })
+            .collect()
+    }
+}
+
+fn wrap(lines: &[&str], kind: WrapperKind) -> String {
+    let base_indent: String = lines[0].chars().take_while(|c| c.is_whitespace()).collect();
+    let body_indent = format!(
+        "{}{}",
+        base_indent,
+        indentation(TAB_SIZE, !base_indent.starts_with('\t'))
+    );
+
+    let body = lines.iter().map(|line| {
+        if line.starts_with(&base_indent) {
+            &line[base_indent.len()..]
+        } else {
+            line.trim_start()
+        }
+    });
+
+    let mut text = format!("{}\\begin{{{}}}\n", base_indent, kind.environment());
+    text.push_str(&indent_lines(body, &body_indent));
+    text.push_str(&indent_lines(kind.footer().iter().copied(), &body_indent));
+    text.push_str(&format!("{}\\end{{{}}}", base_indent, kind.environment()));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edits<'a>(actions: &'a [CodeAction], title: &str) -> &'a Vec<TextEdit> {
+        let action = actions.iter().find(|action| action.title == title).unwrap();
+        &action.edit.as_ref().unwrap().changes.as_ref().unwrap()[&FeatureSpec::uri("foo.tex")]
+    }
+
+    #[test]
+    fn center() {
+        let actions = test_feature(
+            LatexEnvironmentCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "foo\n  bar\n\n  baz\nqux")],
+                main_file: "foo.tex",
+                range: Range::new_simple(1, 3, 4, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions.len(), 3);
+        assert_eq!(
+            *edits(&actions, "Wrap in center environment"),
+            vec![TextEdit::new(
+                Range::new_simple(1, 0, 3, 5),
+                "  \\begin{center}\n      bar\n\n      baz\n  \\end{center}".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn figure() {
+        let actions = test_feature(
+            LatexEnvironmentCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "\\includegraphics{foo}")],
+                main_file: "foo.tex",
+                range: Range::new_simple(0, 0, 0, 21),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            *edits(&actions, "Wrap in figure environment"),
+            vec![TextEdit::new(
+                Range::new_simple(0, 0, 0, 21),
+                "\\begin{figure}\n    \\includegraphics{foo}\n    \\caption{}\n    \\label{fig:}\n\\end{figure}"
+                    .into()
+            )]
+        );
+    }
+
+    #[test]
+    fn empty_selection() {
+        let actions = test_feature(
+            LatexEnvironmentCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "foo")],
+                main_file: "foo.tex",
+                range: Range::new_simple(0, 1, 0, 1),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn bibtex() {
+        let actions = test_feature(
+            LatexEnvironmentCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@article{foo, bar = {baz}}")],
+                main_file: "foo.bib",
+                range: Range::new_simple(0, 0, 0, 5),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(actions.is_empty());
+    }
+}
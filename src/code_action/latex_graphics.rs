@@ -0,0 +1,125 @@
+use crate::graphics::MissingGraphic;
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::sandbox::SANDBOX_DIRECTORY;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexGraphicsCodeActionProvider {
+    sandbox: PathBuf,
+}
+
+impl Default for LatexGraphicsCodeActionProvider {
+    fn default() -> Self {
+        Self {
+            sandbox: PathBuf::from(SANDBOX_DIRECTORY),
+        }
+    }
+}
+
+impl FeatureProvider for LatexGraphicsCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        let mut actions = Vec::new();
+        let document = request.document();
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            let range = request.params.range;
+            for graphic in
+                MissingGraphic::analyze(tree, &document.uri, &request.options, &self.sandbox)
+            {
+                let path_range = graphic.path.range();
+                let is_selected =
+                    range.contains(path_range.start) || path_range.contains(range.start);
+                if !is_selected {
+                    continue;
+                }
+
+                for suggestion in
+                    graphic.suggestions(tree, &document.uri, &request.options, &self.sandbox)
+                {
+                    let mut changes = HashMap::new();
+                    changes.insert(
+                        document.uri.clone().into(),
+                        vec![TextEdit::new(path_range, suggestion.clone())],
+                    );
+                    actions.push(CodeAction {
+                        title: format!("Replace with {}", suggestion),
+                        kind: Some(code_action_kind::QUICKFIX.into()),
+                        edit: Some(WorkspaceEdit::new(changes)),
+                        ..CodeAction::default()
+                    });
+                }
+            }
+        }
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use texlab_protocol::RangeExt;
+
+    #[test]
+    fn suggestions() {
+        let directory = env::temp_dir().join("texlab-graphics-action-test");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(directory.join("diagram.pdf"), "").unwrap();
+
+        let actions = test_feature(
+            LatexGraphicsCodeActionProvider {
+                sandbox: env::temp_dir(),
+            },
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "texlab-graphics-action-test/foo.tex",
+                    "\\includegraphics{diagam}",
+                )],
+                main_file: "texlab-graphics-action-test/foo.tex",
+                range: Range::new_simple(0, 18, 0, 18),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Replace with diagram");
+        let edits = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()
+            [&FeatureSpec::uri("texlab-graphics-action-test/foo.tex")];
+        assert_eq!(
+            *edits,
+            vec![TextEdit::new(
+                Range::new_simple(0, 17, 0, 23),
+                "diagram".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn outside_of_path() {
+        let actions = test_feature(
+            LatexGraphicsCodeActionProvider {
+                sandbox: env::temp_dir(),
+            },
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "texlab-graphics-action-test/bar.tex",
+                    "foo\n\\includegraphics{diagam}",
+                )],
+                main_file: "texlab-graphics-action-test/bar.tex",
+                range: Range::new_simple(0, 1, 0, 1),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(actions.is_empty());
+    }
+}
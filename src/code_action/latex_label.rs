@@ -0,0 +1,146 @@
+use crate::diagnostics::LabelPlacement;
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexLabelCodeActionProvider;
+
+impl FeatureProvider for LatexLabelCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        let mut actions = Vec::new();
+        if let SyntaxTree::Latex(tree) = &request.document().tree {
+            let range = request.params.range;
+            for placement in LabelPlacement::analyze(tree) {
+                let placement_range = placement.range();
+                let is_selected =
+                    range.contains(placement_range.start) || placement_range.contains(range.start);
+                if !is_selected {
+                    continue;
+                }
+
+                let (title, edits) = match placement {
+                    LabelPlacement::MissingLabel { kind, caption } => {
+                        let text = format!("\\label{{{}:}}", prefix(kind));
+                        let edits = vec![TextEdit::new(Range::new(caption.end, caption.end), text)];
+                        ("Insert label after caption".to_owned(), edits)
+                    }
+                    LabelPlacement::LabelBeforeCaption {
+                        label,
+                        text,
+                        caption,
+                    } => {
+                        let edits = vec![
+                            TextEdit::new(label, String::new()),
+                            TextEdit::new(Range::new(caption.end, caption.end), text),
+                        ];
+                        ("Move label after caption".to_owned(), edits)
+                    }
+                };
+
+                let mut changes = HashMap::new();
+                changes.insert(request.document().uri.clone().into(), edits);
+                actions.push(CodeAction {
+                    title,
+                    kind: Some(code_action_kind::QUICKFIX.into()),
+                    edit: Some(WorkspaceEdit::new(changes)),
+                    ..CodeAction::default()
+                });
+            }
+        }
+        actions
+    }
+}
+
+fn prefix(kind: OutlineCaptionKind) -> &'static str {
+    match kind {
+        OutlineCaptionKind::Figure => "fig",
+        OutlineCaptionKind::Table => "tab",
+        OutlineCaptionKind::Listing => "lst",
+        OutlineCaptionKind::Algorithm => "alg",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+
+    fn edits(actions: &[CodeAction]) -> &Vec<TextEdit> {
+        &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&FeatureSpec::uri("foo.tex")]
+    }
+
+    #[test]
+    fn missing_label() {
+        let actions = test_feature(
+            LatexLabelCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\begin{figure}\n\\caption{foo}\n\\end{figure}",
+                )],
+                main_file: "foo.tex",
+                range: Range::new_simple(1, 3, 1, 3),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            *edits(&actions),
+            vec![TextEdit::new(
+                Range::new_simple(1, 13, 1, 13),
+                "\\label{fig:}".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn label_before_caption() {
+        let actions = test_feature(
+            LatexLabelCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\begin{table}\n\\label{tab:foo}\n\\caption{foo}\n\\end{table}",
+                )],
+                main_file: "foo.tex",
+                range: Range::new_simple(1, 8, 1, 8),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(
+            *edits(&actions),
+            vec![
+                TextEdit::new(Range::new_simple(1, 0, 1, 15), String::new()),
+                TextEdit::new(Range::new_simple(2, 13, 2, 13), "\\label{tab:foo}".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn outside_range() {
+        let actions = test_feature(
+            LatexLabelCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\begin{figure}\n\\caption{foo}\n\\end{figure}",
+                )],
+                main_file: "foo.tex",
+                range: Range::new_simple(0, 3, 0, 3),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(actions.is_empty());
+    }
+}
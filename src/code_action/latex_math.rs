@@ -0,0 +1,106 @@
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexMathCodeActionProvider;
+
+impl FeatureProvider for LatexMathCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        let mut actions = Vec::new();
+        if let SyntaxTree::Latex(tree) = &request.document().tree {
+            let range = request.params.range;
+            for command in &tree.commands {
+                let is_selected =
+                    range.contains(command.start()) || command.range().contains(range.start);
+                if is_selected
+                    && LANGUAGE_DATA.is_math_command(command.name.text())
+                    && !tree.is_math_mode(command.start())
+                {
+                    let edits = vec![
+                        TextEdit::new(Range::new(command.start(), command.start()), "\\(".into()),
+                        TextEdit::new(Range::new(command.end(), command.end()), "\\)".into()),
+                    ];
+                    let mut changes = HashMap::new();
+                    changes.insert(request.document().uri.clone().into(), edits);
+                    actions.push(CodeAction {
+                        title: format!("Wrap {} in inline math", command.name.text()),
+                        kind: Some(code_action_kind::QUICKFIX.into()),
+                        edit: Some(WorkspaceEdit::new(changes)),
+                        ..CodeAction::default()
+                    });
+                }
+            }
+        }
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+
+    #[test]
+    fn outside_math() {
+        let actions = test_feature(
+            LatexMathCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "foo \\alpha bar")],
+                main_file: "foo.tex",
+                range: Range::new_simple(0, 6, 0, 6),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions.len(), 1);
+        let edits = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()
+            [&FeatureSpec::uri("foo.tex")];
+        assert_eq!(
+            *edits,
+            vec![
+                TextEdit::new(Range::new_simple(0, 4, 0, 4), "\\(".into()),
+                TextEdit::new(Range::new_simple(0, 10, 0, 10), "\\)".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn inside_math() {
+        let actions = test_feature(
+            LatexMathCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "$\\alpha$")],
+                main_file: "foo.tex",
+                range: Range::new_simple(0, 3, 0, 3),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(actions.is_empty());
+    }
+
+    #[test]
+    fn bibtex() {
+        let actions = test_feature(
+            LatexMathCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@article{foo, bar = {\\alpha}}",
+                )],
+                main_file: "foo.bib",
+                range: Range::new_simple(0, 23, 0, 23),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(actions.is_empty());
+    }
+}
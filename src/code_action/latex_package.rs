@@ -0,0 +1,136 @@
+use crate::diagnostics::{PackageConflict, PackageLoad};
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexPackageCodeActionProvider;
+
+impl FeatureProvider for LatexPackageCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        let mut actions = Vec::new();
+        let document = request.document();
+        let range = request.params.range;
+        for conflict in PackageConflict::analyze(request.workspace(), document, &request.options) {
+            let (package, required) = match &conflict {
+                PackageConflict::LoadOrder { package, required } => (package, required),
+                PackageConflict::OptionClash { .. } | PackageConflict::Engine { .. } => continue,
+            };
+
+            let package_range = package.name.range();
+            let is_selected =
+                range.contains(package_range.start) || package_range.contains(range.start);
+            if !is_selected || package.uri != document.uri || required.uri != document.uri {
+                continue;
+            }
+
+            if let Some(edits) = move_after(&document.text, package, required) {
+                let mut changes = HashMap::new();
+                changes.insert(document.uri.clone().into(), edits);
+                actions.push(CodeAction {
+                    title: format!(
+                        "Load {} after {}",
+                        package.name.text(),
+                        required.name.text()
+                    ),
+                    kind: Some(code_action_kind::QUICKFIX.into()),
+                    edit: Some(WorkspaceEdit::new(changes)),
+                    ..CodeAction::default()
+                });
+            }
+        }
+        actions
+    }
+}
+
+/// Moves the command that loads the package behind the command that loads the required package.
+///
+/// Commands that load several packages at once are left untouched.
+fn move_after(text: &str, package: &PackageLoad, required: &PackageLoad) -> Option<Vec<TextEdit>> {
+    if package.command.extract_comma_separated_words(0).len() != 1 {
+        return None;
+    }
+
+    let command_range = package.command.range();
+    let command_text = CharStream::extract(text, command_range);
+    let line = command_range.start.line;
+    let is_own_line = command_range.end.line == line
+        && text
+            .lines()
+            .nth(line as usize)
+            .map_or(false, |content| content.trim() == command_text);
+
+    let removal = if is_own_line {
+        Range::new_simple(line, 0, line + 1, 0)
+    } else {
+        command_range
+    };
+
+    let end = required.command.end();
+    Some(vec![
+        TextEdit::new(removal, String::new()),
+        TextEdit::new(Range::new(end, end), format!("\n{}", command_text)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_package() {
+        let actions = test_feature(
+            LatexPackageCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\usepackage{cleveref}\n\\usepackage[colorlinks]{hyperref}",
+                )],
+                main_file: "foo.tex",
+                range: Range::new_simple(0, 14, 0, 14),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Load cleveref after hyperref");
+
+        let uri = FeatureSpec::uri("foo.tex");
+        let edits = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(
+            *edits,
+            vec![
+                TextEdit::new(Range::new_simple(0, 0, 1, 0), "".into()),
+                TextEdit::new(
+                    Range::new_simple(1, 33, 1, 33),
+                    "\n\\usepackage{cleveref}".into()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn not_selected() {
+        let actions = test_feature(
+            LatexPackageCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\usepackage{cleveref}\n\\usepackage{hyperref}",
+                )],
+                main_file: "foo.tex",
+                range: Range::new_simple(1, 14, 1, 14),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(actions.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::diagnostics::TermVariant;
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexTerminologyCodeActionProvider;
+
+impl FeatureProvider for LatexTerminologyCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        let variants =
+            TermVariant::analyze(request.workspace(), request.document(), &request.options);
+        let range = request.params.range;
+        let mut actions = Vec::new();
+        for variant in variants
+            .iter()
+            .filter(|variant| variant.uri == request.document().uri)
+        {
+            let is_selected =
+                range.contains(variant.range.start) || variant.range.contains(range.start);
+            if !is_selected {
+                continue;
+            }
+
+            let mut changes = HashMap::new();
+            changes.insert(
+                variant.uri.clone().into(),
+                vec![TextEdit::new(variant.range, variant.replacement())],
+            );
+            actions.push(CodeAction {
+                title: format!("Replace with \"{}\"", variant.replacement()),
+                kind: Some(code_action_kind::QUICKFIX.into()),
+                edit: Some(WorkspaceEdit::new(changes)),
+                ..CodeAction::default()
+            });
+
+            let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+            for other in variants
+                .iter()
+                .filter(|other| other.dominant == variant.dominant)
+            {
+                changes
+                    .entry(other.uri.clone().into())
+                    .or_default()
+                    .push(TextEdit::new(other.range, other.replacement()));
+            }
+            actions.push(CodeAction {
+                title: format!("Use \"{}\" everywhere", variant.dominant),
+                kind: Some(code_action_kind::QUICKFIX.into()),
+                edit: Some(WorkspaceEdit::new(changes)),
+                ..CodeAction::default()
+            });
+        }
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+
+    fn actions(range: Range) -> Vec<CodeAction> {
+        test_feature(
+            LatexTerminologyCodeActionProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\input{bar}\nData set.\ndataset"),
+                    FeatureSpec::file("bar.tex", "dataset, data-set"),
+                ],
+                main_file: "foo.tex",
+                range,
+                options: Options {
+                    latex: Some(LatexOptions {
+                        terminology: Some(LatexTerminologyOptions {
+                            enabled: Some(true),
+                            variants: None,
+                        }),
+                        ..LatexOptions::default()
+                    }),
+                    bibtex: None,
+                },
+                ..FeatureSpec::default()
+            },
+        )
+    }
+
+    #[test]
+    fn unify_term() {
+        let actions = actions(Range::new_simple(1, 2, 1, 2));
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].title, "Replace with \"Dataset\"");
+        let changes = actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap();
+        assert_eq!(
+            changes[&FeatureSpec::uri("foo.tex")],
+            vec![TextEdit::new(
+                Range::new_simple(1, 0, 1, 8),
+                "Dataset".into()
+            )]
+        );
+
+        assert_eq!(actions[1].title, "Use \"dataset\" everywhere");
+        let changes = actions[1].edit.as_ref().unwrap().changes.as_ref().unwrap();
+        assert_eq!(changes.len(), 2);
+        assert_eq!(
+            changes[&FeatureSpec::uri("bar.tex")],
+            vec![TextEdit::new(
+                Range::new_simple(0, 9, 0, 17),
+                "dataset".into()
+            )]
+        );
+    }
+
+    #[test]
+    fn outside_range() {
+        assert!(actions(Range::new_simple(2, 2, 2, 2)).is_empty());
+    }
+}
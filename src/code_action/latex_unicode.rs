@@ -0,0 +1,107 @@
+use crate::diagnostics::UnicodeCharacter;
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LatexUnicodeCodeActionProvider;
+
+impl FeatureProvider for LatexUnicodeCodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        let document = request.document();
+        let characters = UnicodeCharacter::analyze(request.workspace(), document, &request.options);
+        let range = request.params.range;
+        let mut actions = Vec::new();
+        for character in &characters {
+            let is_selected =
+                range.contains(character.range.start) || character.range.contains(range.start);
+            if !is_selected {
+                continue;
+            }
+
+            let mut changes = HashMap::new();
+            changes.insert(
+                document.uri.clone().into(),
+                vec![TextEdit::new(
+                    character.range,
+                    character.replacement.clone(),
+                )],
+            );
+            actions.push(CodeAction {
+                title: format!("Replace with \"{}\"", character.replacement),
+                kind: Some(code_action_kind::QUICKFIX.into()),
+                edit: Some(WorkspaceEdit::new(changes)),
+                ..CodeAction::default()
+            });
+        }
+
+        if !actions.is_empty() && characters.len() > 1 {
+            let edits = characters
+                .iter()
+                .map(|character| TextEdit::new(character.range, character.replacement.clone()))
+                .collect();
+            let mut changes = HashMap::new();
+            changes.insert(document.uri.clone().into(), edits);
+            actions.push(CodeAction {
+                title: "Replace all non-ASCII characters with LaTeX commands".into(),
+                kind: Some(code_action_kind::QUICKFIX.into()),
+                edit: Some(WorkspaceEdit::new(changes)),
+                ..CodeAction::default()
+            });
+        }
+        actions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+
+    fn actions(text: &'static str, range: Range) -> Vec<CodeAction> {
+        test_feature(
+            LatexUnicodeCodeActionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", text)],
+                main_file: "foo.tex",
+                range,
+                ..FeatureSpec::default()
+            },
+        )
+    }
+
+    #[test]
+    fn replace_character() {
+        let actions = actions("Café\n$a≤b$", Range::new_simple(0, 3, 0, 3));
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].title, "Replace with \"\\'e\"");
+
+        let changes = actions[1].edit.as_ref().unwrap().changes.as_ref().unwrap();
+        assert_eq!(
+            changes[&FeatureSpec::uri("foo.tex")],
+            vec![
+                TextEdit::new(Range::new_simple(0, 3, 0, 4), "\\'e".into()),
+                TextEdit::new(Range::new_simple(1, 2, 1, 3), "\\leq".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn single_character() {
+        let actions = actions("Café", Range::new_simple(0, 3, 0, 4));
+        assert_eq!(actions.len(), 1);
+    }
+
+    #[test]
+    fn outside_range() {
+        assert!(actions("Café\nfoo", Range::new_simple(1, 1, 1, 1)).is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+mod latex_environment;
+mod latex_graphics;
+mod latex_label;
+mod latex_math;
+mod latex_package;
+mod latex_terminology;
+mod latex_unicode;
+
+use self::latex_environment::LatexEnvironmentCodeActionProvider;
+use self::latex_graphics::LatexGraphicsCodeActionProvider;
+use self::latex_label::LatexLabelCodeActionProvider;
+use self::latex_math::LatexMathCodeActionProvider;
+use self::latex_package::LatexPackageCodeActionProvider;
+use self::latex_terminology::LatexTerminologyCodeActionProvider;
+use self::latex_unicode::LatexUnicodeCodeActionProvider;
+use futures_boxed::boxed;
+use texlab_protocol::{CodeAction, CodeActionParams};
+use texlab_workspace::*;
+
+pub struct CodeActionProvider {
+    provider: ConcatProvider<CodeActionParams, CodeAction>,
+}
+
+impl CodeActionProvider {
+    pub fn new() -> Self {
+        Self {
+            provider: ConcatProvider::new(vec![
+                Box::new(LatexMathCodeActionProvider),
+                Box::new(LatexLabelCodeActionProvider),
+                Box::new(LatexGraphicsCodeActionProvider::default()),
+                Box::new(LatexPackageCodeActionProvider),
+                Box::new(LatexEnvironmentCodeActionProvider),
+                Box::new(LatexTerminologyCodeActionProvider),
+                Box::new(LatexUnicodeCodeActionProvider),
+            ]),
+        }
+    }
+}
+
+impl Default for CodeActionProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureProvider for CodeActionProvider {
+    type Params = CodeActionParams;
+    type Output = Vec<CodeAction>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<CodeActionParams>,
+    ) -> Vec<CodeAction> {
+        self.provider.execute(request).await
+    }
+}
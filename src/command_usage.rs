@@ -0,0 +1,70 @@
+use texlab_protocol::CommandUsage;
+use texlab_syntax::*;
+
+/// Counts the commands and environments of a document.
+///
+/// The delimiters of environments are only counted as environments.
+pub fn analyze(tree: &LatexSyntaxTree) -> CommandUsage {
+    let mut usage = CommandUsage::default();
+    for environment in &tree.env.environments {
+        if let Some(name) = environment.left.name() {
+            *usage
+                .environments
+                .entry(name.text().to_owned())
+                .or_default() += 1;
+        }
+    }
+
+    for command in &tree.commands {
+        let name = command.name.text();
+        if name != "\\begin" && name != "\\end" {
+            *usage.commands.entry(name.to_owned()).or_default() += 1;
+        }
+    }
+    usage
+}
+
+pub fn merge(project: &mut CommandUsage, usage: &CommandUsage) {
+    for (name, count) in &usage.commands {
+        *project.commands.entry(name.clone()).or_default() += count;
+    }
+
+    for (name, count) in &usage.environments {
+        *project.environments.entry(name.clone()).or_default() += count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn usage(text: &str) -> CommandUsage {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", text);
+        let document = builder.workspace.find(&uri).unwrap();
+        match &document.tree {
+            SyntaxTree::Latex(tree) => analyze(tree),
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn commands_and_environments() {
+        let usage = usage("\\begin{itemize}\\item \\bf foo \\item bar\\end{itemize}");
+        assert_eq!(usage.commands.len(), 2);
+        assert_eq!(usage.commands["\\item"], 2);
+        assert_eq!(usage.commands["\\bf"], 1);
+        assert_eq!(usage.environments.len(), 1);
+        assert_eq!(usage.environments["itemize"], 1);
+    }
+
+    #[test]
+    fn merge_documents() {
+        let mut project = usage("\\foo \\begin{bar}\\end{bar}");
+        merge(&mut project, &usage("\\foo \\baz"));
+        assert_eq!(project.commands["\\foo"], 2);
+        assert_eq!(project.commands["\\baz"], 1);
+        assert_eq!(project.environments["bar"], 1);
+    }
+}
@@ -0,0 +1,123 @@
+use texlab_protocol::*;
+use texlab_syntax::*;
+
+/// Returns the sections and environments that enclose the given position,
+/// e.g. `document`, `section: Results`, `figure` and `tikzpicture`.
+pub fn analyze(tree: &LatexSyntaxTree, position: Position) -> ContextAtResult {
+    let mut sections: Vec<&LatexSection> = Vec::new();
+    for section in &tree.structure.sections {
+        if section.start() > position {
+            break;
+        }
+
+        while sections
+            .last()
+            .map_or(false, |last| last.level >= section.level)
+        {
+            sections.pop();
+        }
+        sections.push(section);
+    }
+
+    let mut items: Vec<(Position, ContextItem)> = sections
+        .into_iter()
+        .map(|section| {
+            let name = section.command.name.text();
+            let item = ContextItem {
+                kind: ContextItemKind::Section,
+                name: name[1..].trim_end_matches('*').to_owned(),
+                title: Some(extract_group(&section.command.args[section.index])),
+                range: section.range(),
+            };
+            (section.start(), item)
+        })
+        .collect();
+
+    for environment in &tree.env.environments {
+        if !environment.range().contains(position) {
+            continue;
+        }
+
+        if let Some(name) = environment.left.name() {
+            let item = ContextItem {
+                kind: ContextItemKind::Environment,
+                name: name.text().to_owned(),
+                title: None,
+                range: environment.range(),
+            };
+            items.push((environment.start(), item));
+        }
+    }
+
+    items.sort_by_key(|(start, _)| *start);
+    ContextAtResult {
+        items: items.into_iter().map(|(_, item)| item).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn context(text: &str, position: Position) -> Vec<(ContextItemKind, String, Option<String>)> {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", text);
+        let document = builder.workspace.find(&uri).unwrap();
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        };
+        analyze(tree, position)
+            .items
+            .into_iter()
+            .map(|item| (item.kind, item.name, item.title))
+            .collect()
+    }
+
+    #[test]
+    fn nested_environments() {
+        let items = context(
+            "\\begin{document}\n\
+             \\section{Methods}\n\
+             \\subsection{Setup}\n\
+             \\section{Results}\n\
+             \\begin{figure}\n\
+             \\begin{tikzpicture}\n\
+             \\draw (0,0);\n\
+             \\end{tikzpicture}\n\
+             \\end{figure}\n\
+             \\end{document}",
+            Position::new(6, 2),
+        );
+        assert_eq!(
+            items,
+            vec![
+                (ContextItemKind::Environment, "document".into(), None),
+                (
+                    ContextItemKind::Section,
+                    "section".into(),
+                    Some("Results".into())
+                ),
+                (ContextItemKind::Environment, "figure".into(), None),
+                (ContextItemKind::Environment, "tikzpicture".into(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_sections() {
+        let items = context(
+            "\\chapter{Foo}\n\\section*{Bar}\n\\subsection{Baz}\nqux",
+            Position::new(3, 1),
+        );
+        let names: Vec<&str> = items.iter().map(|(_, name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["chapter", "section", "subsection"]);
+    }
+
+    #[test]
+    fn outside_of_environment() {
+        let items = context("\\begin{foo}\\end{foo}\nbar", Position::new(1, 1));
+        assert!(items.is_empty());
+    }
+}
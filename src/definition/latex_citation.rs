@@ -1,6 +1,5 @@
 use futures_boxed::boxed;
-use texlab_protocol::RangeExt;
-use texlab_protocol::{LocationLink, TextDocumentPositionParams};
+use texlab_protocol::{LocationLink, Range, TextDocumentPositionParams};
 use texlab_syntax::*;
 use texlab_workspace::*;
 
@@ -29,29 +28,42 @@ impl LatexCitationDefinitionProvider {
         reference: &LatexToken,
         links: &mut Vec<LocationLink>,
     ) {
-        if let SyntaxTree::Bibtex(tree) = &document.tree {
-            for entry in tree.entries() {
-                if let Some(key) = &entry.key {
+        match &document.tree {
+            SyntaxTree::Latex(tree) => {
+                for item in &tree.bib_items {
+                    let key = item.key();
                     if key.text() == reference.text() {
                         let link = LocationLink {
                             origin_selection_range: Some(reference.range()),
                             target_uri: document.uri.clone().into(),
-                            target_range: entry.range(),
+                            target_range: Range::new(item.start(), item.content_range.end),
                             target_selection_range: key.range(),
                         };
                         links.push(link);
                     }
                 }
             }
+            SyntaxTree::Bibtex(tree) => {
+                for entry in tree.entries() {
+                    if let Some(key) = &entry.key {
+                        if key.text() == reference.text() {
+                            let link = LocationLink {
+                                origin_selection_range: Some(reference.range()),
+                                target_uri: document.uri.clone().into(),
+                                target_range: entry.range(),
+                                target_selection_range: key.range(),
+                            };
+                            links.push(link);
+                        }
+                    }
+                }
+            }
         }
     }
 
     fn find_reference(request: &FeatureRequest<TextDocumentPositionParams>) -> Option<&LatexToken> {
         if let SyntaxTree::Latex(tree) = &request.document().tree {
-            tree.citations
-                .iter()
-                .flat_map(LatexCitation::keys)
-                .find(|key| key.range().contains(request.params.position))
+            tree.citation_key_at(request.params.position)
         } else {
             None
         }
@@ -61,7 +73,7 @@ impl LatexCitationDefinitionProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use texlab_protocol::{Position, Range};
+    use texlab_protocol::{Position, RangeExt};
 
     #[test]
     fn has_definition() {
@@ -89,6 +101,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bib_item() {
+        let links = test_feature(
+            LatexCitationDefinitionProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\cite{foo}\n\\begin{thebibliography}{9}\n\\bibitem{foo} Foo\n\\end{thebibliography}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(0, 7),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![LocationLink {
+                origin_selection_range: Some(Range::new_simple(0, 6, 0, 9)),
+                target_uri: FeatureSpec::uri("foo.tex"),
+                target_range: Range::new_simple(2, 0, 3, 0),
+                target_selection_range: Range::new_simple(2, 9, 2, 12)
+            }]
+        );
+    }
+
     #[test]
     fn no_definition_latex() {
         let links = test_feature(
@@ -13,7 +13,7 @@ impl FeatureProvider for LatexCommandDefinitionProvider {
     async fn execute<'a>(&'a self, request: &'a FeatureRequest<Self::Params>) -> Self::Output {
         let mut definitions = Vec::new();
         if let SyntaxTree::Latex(tree) = &request.document().tree {
-            if let Some(LatexNode::Command(command)) = tree.find(request.params.position).last() {
+            if let Some(LatexNode::Command(command)) = tree.node_at(request.params.position) {
                 for document in request.related_documents() {
                     if let SyntaxTree::Latex(tree) = &document.tree {
                         tree.command_definitions
@@ -66,11 +66,7 @@ impl LatexLabelDefinitionProvider {
 
     fn find_reference(request: &FeatureRequest<TextDocumentPositionParams>) -> Option<&LatexToken> {
         if let SyntaxTree::Latex(tree) = &request.document().tree {
-            tree.structure
-                .labels
-                .iter()
-                .flat_map(LatexLabel::names)
-                .find(|label| label.range().contains(request.params.position))
+            tree.label_name_at(request.params.position)
         } else {
             None
         }
@@ -11,6 +11,7 @@ pub enum BibtexErrorCode {
     MissingAssign,
     MissingContent,
     MissingQuote,
+    UnterminatedEntry,
 }
 
 impl BibtexErrorCode {
@@ -23,6 +24,9 @@ impl BibtexErrorCode {
             BibtexErrorCode::MissingAssign => "Expecting an equals sign: \"=\"",
             BibtexErrorCode::MissingContent => "Expecting content",
             BibtexErrorCode::MissingQuote => "Expecting a quote: '\"'",
+            BibtexErrorCode::UnterminatedEntry => {
+                "Unterminated entry, parsing continues with the next entry"
+            }
         }
     }
 }
@@ -30,12 +34,16 @@ impl BibtexErrorCode {
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct BibtexError {
     code: BibtexErrorCode,
-    position: Position,
+    range: Range,
 }
 
 impl BibtexError {
     pub fn new(code: BibtexErrorCode, position: Position) -> Self {
-        Self { code, position }
+        Self::with_range(code, Range::new(position, position))
+    }
+
+    pub fn with_range(code: BibtexErrorCode, range: Range) -> Self {
+        Self { code, range }
     }
 
     pub fn analyze(tree: &BibtexSyntaxTree) -> Vec<Self> {
@@ -99,10 +107,26 @@ impl BibtexError {
             }
 
             if entry.right.is_none() {
-                errors.push(BibtexError::new(
-                    BibtexErrorCode::MissingEndBrace,
-                    entry.end(),
-                ));
+                let is_recovered = tree
+                    .root
+                    .children
+                    .iter()
+                    .any(|declaration| match declaration {
+                        BibtexDeclaration::Comment(_) => false,
+                        _ => declaration.start() >= entry.end(),
+                    });
+
+                if is_recovered {
+                    errors.push(BibtexError::with_range(
+                        BibtexErrorCode::UnterminatedEntry,
+                        entry.range(),
+                    ));
+                } else {
+                    errors.push(BibtexError::new(
+                        BibtexErrorCode::MissingEndBrace,
+                        entry.end(),
+                    ));
+                }
                 continue;
             }
         }
@@ -158,7 +182,7 @@ impl Into<Diagnostic> for BibtexError {
     fn into(self) -> Diagnostic {
         Diagnostic {
             source: Some("bibtex".into()),
-            range: Range::new(self.position, self.position),
+            range: self.range,
             message: self.code.message().into(),
             severity: Some(DiagnosticSeverity::Error),
             code: None,
@@ -186,6 +210,7 @@ impl BibtexDiagnosticsProvider {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use texlab_protocol::RangeExt;
 
     #[test]
     fn begin_brace() {
@@ -311,6 +336,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn entry_unterminated() {
+        let text = "@article{foo, bar = {baz\n@article{qux, bar = {quux}}";
+        let errors = BibtexError::analyze(&text.into());
+        assert_eq!(
+            errors,
+            vec![
+                BibtexError::new(BibtexErrorCode::MissingEndBrace, Position::new(0, 24)),
+                BibtexError::with_range(
+                    BibtexErrorCode::UnterminatedEntry,
+                    Range::new_simple(0, 0, 0, 24)
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn entry_valid() {
         let text = "@article{foo, bar = \"baz {qux}\" # quux}";
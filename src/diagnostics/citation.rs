@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, Options};
+use texlab_syntax::*;
+use texlab_workspace::{Document, Workspace};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct CitationDiagnosticsProvider;
+
+impl CitationDiagnosticsProvider {
+    pub fn get(
+        self,
+        workspace: &Workspace,
+        document: &Document,
+        options: &Options,
+    ) -> Vec<Diagnostic> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return Vec::new(),
+        };
+
+        if tree.citations.is_empty() {
+            return Vec::new();
+        }
+
+        let keys = match defined_keys(workspace, document, options) {
+            Some(keys) => keys,
+            None => return Vec::new(),
+        };
+
+        let mut diagnostics = Vec::new();
+        for key in tree.citations.iter().flat_map(LatexCitation::keys) {
+            if key.text() != "*" && !keys.contains(key.text()) {
+                diagnostics.push(Diagnostic {
+                    source: Some("latex".into()),
+                    range: key.range(),
+                    message: format!("Undefined citation: {}", key.text()),
+                    severity: Some(DiagnosticSeverity::Warning),
+                    code: None,
+                    related_information: None,
+                });
+            }
+        }
+        diagnostics
+    }
+}
+
+/// Collects the citation keys of the project.
+///
+/// Returns `None` if the project does not define any keys
+/// or if one of its bibliographies has not been loaded yet.
+fn defined_keys(
+    workspace: &Workspace,
+    document: &Document,
+    options: &Options,
+) -> Option<HashSet<String>> {
+    let mut has_definitions = false;
+    let mut keys = HashSet::new();
+    for related in workspace.related_documents(&document.uri, options) {
+        match &related.tree {
+            SyntaxTree::Latex(tree) => {
+                let has_missing_bibliography = tree
+                    .includes
+                    .iter()
+                    .filter(|include| include.kind == LatexIncludeKind::Bibliography)
+                    .flat_map(|include| &include.all_targets)
+                    .any(|targets| {
+                        targets
+                            .iter()
+                            .all(|target| workspace.find(target).is_none())
+                    });
+
+                if has_missing_bibliography {
+                    return None;
+                }
+
+                for item in &tree.bib_items {
+                    has_definitions = true;
+                    keys.insert(item.key().text().to_owned());
+                }
+            }
+            SyntaxTree::Bibtex(tree) => {
+                has_definitions = true;
+                for entry in tree.entries() {
+                    if let Some(key) = &entry.key {
+                        keys.insert(key.text().to_owned());
+                    }
+                }
+            }
+        }
+    }
+
+    if has_definitions {
+        Some(keys)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::test_diagnostics;
+    use texlab_protocol::{Range, RangeExt};
+
+    fn diagnostics(files: &[(&str, &str)], name: &str) -> Vec<Diagnostic> {
+        test_diagnostics(files, name, |workspace, document| {
+            CitationDiagnosticsProvider.get(workspace, document, &Options::default())
+        })
+    }
+
+    #[test]
+    fn undefined_bib_item() {
+        let diagnostics = diagnostics(&[(
+            "foo.tex",
+            "\\cite{foo, bar}\n\\begin{thebibliography}{9}\n\\bibitem{foo} Foo\n\\end{thebibliography}",
+        )], "foo.tex");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 11, 0, 14));
+        assert_eq!(diagnostics[0].message, "Undefined citation: bar");
+    }
+
+    #[test]
+    fn undefined_entry() {
+        let diagnostics = diagnostics(
+            &[
+                ("foo.tex", "\\bibliography{bar}\n\\cite{foo}\n\\nocite{*}"),
+                ("bar.bib", "@article{bar,}"),
+            ],
+            "foo.tex",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 6, 1, 9));
+    }
+
+    #[test]
+    fn no_definitions() {
+        assert!(diagnostics(&[("foo.tex", "\\cite{foo}")], "foo.tex").is_empty());
+    }
+
+    #[test]
+    fn missing_bibliography() {
+        let diagnostics = diagnostics(&[(
+            "foo.tex",
+            "\\bibliography{bar}\n\\cite{foo}\n\\begin{thebibliography}{9}\n\\bibitem{baz} Baz\n\\end{thebibliography}",
+        )], "foo.tex");
+        assert!(diagnostics.is_empty());
+    }
+}
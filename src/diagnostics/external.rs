@@ -0,0 +1,273 @@
+use crate::policy::ToolPolicy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use texlab_protocol::*;
+use texlab_workspace::Document;
+use tokio::process::Command;
+
+/// Matches the output of `textidote --output singleline`,
+/// e.g. `foo.tex(L1C5-L1C9): Possible spelling mistake found. "..."`.
+const TEXTIDOTE_PATTERN: &str =
+    r"\(L(?P<line>\d+)C(?P<column>\d+)-L(?P<end_line>\d+)C(?P<end_column>\d+)\): (?P<message>.*)$";
+
+/// Reports the findings of a command line linter that is selected by the user.
+///
+/// The linters are either built-in presets or defined by the operator of the server.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ExternalCheckerProvider {
+    diagnostics_by_uri: HashMap<Uri, Vec<Diagnostic>>,
+}
+
+impl ExternalCheckerProvider {
+    pub fn get(&self, document: &Document) -> Vec<Diagnostic> {
+        self.diagnostics_by_uri
+            .get(&document.uri)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn set(&mut self, uri: Uri, diagnostics: Vec<Diagnostic>) {
+        self.diagnostics_by_uri.insert(uri, diagnostics);
+    }
+
+    pub fn remove(&mut self, uri: &Uri) {
+        self.diagnostics_by_uri.remove(uri);
+    }
+}
+
+/// The command line of an external checker and the format of its output.
+#[derive(Debug, Clone)]
+struct Checker {
+    command: String,
+    args: Vec<String>,
+    pattern: Regex,
+    source: String,
+}
+
+impl Checker {
+    /// Returns `None` if the checker is not configured or the preset is unknown.
+    ///
+    /// The checkers of the server take precedence over the built-in presets.
+    fn new(options: &LatexExternalCheckerOptions, tools: &ToolPolicy) -> Option<Self> {
+        /* Security Patch */
+        let name = options.preset()?;
+        let (command, args, pattern) = match tools.external_checkers.get(name) {
+            Some(checker) => (
+                checker.command.clone(),
+                checker.args.clone(),
+                Regex::new(&checker.pattern).ok()?,
+            ),
+            None if name == "textidote" => (
+                "textidote".to_owned(),
+                vec!["--output".into(), "singleline".into(), "{file}".into()],
+                Regex::new(TEXTIDOTE_PATTERN).unwrap(),
+            ),
+            None => return None,
+        };
+
+        let source = options.source.clone().unwrap_or_else(|| command.clone());
+        Some(Self {
+            command,
+            args,
+            pattern,
+            source,
+        })
+    }
+
+    /// Replaces the `{file}` placeholder with the path of the document.
+    ///
+    /// The path is appended if the arguments do not contain the placeholder.
+    fn args(&self, path: &Path) -> Vec<String> {
+        let path = path.to_string_lossy();
+        let mut args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| arg.replace("{file}", &path))
+            .collect();
+
+        if !self.args.iter().any(|arg| arg.contains("{file}")) {
+            args.push(path.into_owned());
+        }
+        args
+    }
+
+    /// Converts a line of output into a diagnostic.
+    ///
+    /// Lines and columns are one-based. The range ends at `end_column` (inclusive)
+    /// or after `length` characters and defaults to a single character.
+    fn parse_line(&self, line: &str) -> Option<Diagnostic> {
+        let captures = self.pattern.captures(line)?;
+        let number = |name: &str| {
+            captures
+                .name(name)
+                .and_then(|value| value.as_str().parse::<u64>().ok())
+        };
+        let text = |name: &str| captures.name(name).map(|value| value.as_str());
+
+        let start_line = number("line")?.saturating_sub(1);
+        let start_character = number("column").unwrap_or(1).saturating_sub(1);
+        let end_line = number("end_line")
+            .map(|line| line.saturating_sub(1))
+            .unwrap_or(start_line);
+        let mut end_character = match (number("end_column"), number("length")) {
+            (Some(column), _) => column,
+            (None, Some(length)) => start_character + length,
+            (None, None) => start_character + 1,
+        };
+        if end_line <= start_line && end_character <= start_character {
+            end_character = start_character + 1;
+        }
+
+        Some(Diagnostic {
+            source: Some(self.source.clone()),
+            range: Range::new_simple(start_line, start_character, end_line, end_character),
+            message: text("message").unwrap_or(line).trim().to_owned(),
+            severity: Some(severity(text("severity"))),
+            code: text("code").map(|code| NumberOrString::String(code.to_owned())),
+            related_information: None,
+        })
+    }
+}
+
+fn severity(text: Option<&str>) -> DiagnosticSeverity {
+    let text = text.unwrap_or_default().to_lowercase();
+    if text.starts_with("err") {
+        DiagnosticSeverity::Error
+    } else if text.starts_with("info") || text.starts_with("note") {
+        DiagnosticSeverity::Information
+    } else if text.starts_with("hint") {
+        DiagnosticSeverity::Hint
+    } else {
+        DiagnosticSeverity::Warning
+    }
+}
+
+/// Returns the command of the external checker or `None` if it is not configured correctly.
+pub fn external_checker_command(
+    options: &LatexExternalCheckerOptions,
+    tools: &ToolPolicy,
+) -> Option<String> {
+    Checker::new(options, tools).map(|checker| checker.command)
+}
+
+/// Runs the external checker on the saved document without blocking the executor.
+pub async fn run_external_checker(
+    uri: &Uri,
+    options: &LatexExternalCheckerOptions,
+    tools: &ToolPolicy,
+) -> Option<Vec<Diagnostic>> {
+    let checker = Checker::new(options, tools)?;
+    let path = uri.to_file_path().ok()?;
+    let directory = path.parent()?;
+
+    let process = Command::new(&checker.command)
+        .args(checker.args(&path))
+        .current_dir(directory)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .ok()?;
+
+    let output = process.wait_with_output().await.ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(
+        stdout
+            .lines()
+            .filter_map(|line| checker.parse_line(line))
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::ExternalCheckerDefinition;
+    use std::path::PathBuf;
+
+    fn preset(name: &str) -> LatexExternalCheckerOptions {
+        LatexExternalCheckerOptions {
+            preset: Some(name.into()),
+            ..LatexExternalCheckerOptions::default()
+        }
+    }
+
+    fn textidote() -> Checker {
+        Checker::new(&preset("textidote"), &ToolPolicy::default()).unwrap()
+    }
+
+    fn server_checker(args: &[&str]) -> ToolPolicy {
+        let checker = ExternalCheckerDefinition {
+            command: "lint".into(),
+            args: args.iter().map(|&arg| arg.into()).collect(),
+            pattern: r"^(?P<line>\d+):(?P<column>\d+):(?P<length>\d+): (?P<severity>\w+) (?P<code>\w+): (?P<message>.*)$".into(),
+        };
+        let mut tools = ToolPolicy::default();
+        tools.external_checkers.insert("lint".into(), checker);
+        tools
+    }
+
+    #[test]
+    fn textidote_output() {
+        let diagnostic = textidote()
+            .parse_line("foo.tex(L3C5-L3C9): Possible spelling mistake found. \"Thsi\"")
+            .unwrap();
+        assert_eq!(diagnostic.range, Range::new_simple(2, 4, 2, 9));
+        assert_eq!(
+            diagnostic.message,
+            "Possible spelling mistake found. \"Thsi\""
+        );
+        assert_eq!(diagnostic.source, Some("textidote".into()));
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::Warning));
+    }
+
+    #[test]
+    fn textidote_summary() {
+        assert_eq!(textidote().parse_line("Found 3 warning(s)"), None);
+    }
+
+    #[test]
+    fn custom_pattern() {
+        let options = LatexExternalCheckerOptions {
+            preset: Some("lint".into()),
+            source: Some("custom".into()),
+        };
+        let checker = Checker::new(&options, &server_checker(&[])).unwrap();
+
+        let diagnostic = checker.parse_line("2:1:3: error E42: Foo").unwrap();
+        assert_eq!(diagnostic.range, Range::new_simple(1, 0, 1, 3));
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::Error));
+        assert_eq!(diagnostic.code, Some(NumberOrString::String("E42".into())));
+        assert_eq!(diagnostic.message, "Foo");
+        assert_eq!(diagnostic.source, Some("custom".into()));
+    }
+
+    #[test]
+    fn file_placeholder() {
+        let path = PathBuf::from("/foo/bar.tex");
+        assert_eq!(
+            textidote().args(&path),
+            vec!["--output", "singleline", "/foo/bar.tex"]
+        );
+
+        let tools = server_checker(&["--check", "en"]);
+        let checker = Checker::new(&preset("lint"), &tools).unwrap();
+        assert_eq!(checker.args(&path), vec!["--check", "en", "/foo/bar.tex"]);
+    }
+
+    #[test]
+    fn invalid_configuration() {
+        let tools = ToolPolicy::default();
+        assert!(Checker::new(&preset("foo"), &tools).is_none());
+        assert!(Checker::new(&preset("lint"), &tools).is_none());
+
+        let missing_preset = LatexExternalCheckerOptions {
+            source: Some("lint".into()),
+            ..LatexExternalCheckerOptions::default()
+        };
+        assert!(Checker::new(&missing_preset, &server_checker(&[])).is_none());
+    }
+}
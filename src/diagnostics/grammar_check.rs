@@ -0,0 +1,438 @@
+use super::spell_check::extract_prose;
+use super::LinterInput;
+use crate::action::LintReason;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::Document;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time;
+
+const SOURCE: &str = "LanguageTool";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The maximum number of bytes of a response of the LanguageTool server.
+const MAX_RESPONSE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Checks the grammar of the documents with a LanguageTool server.
+///
+/// The server is queried without holding the diagnostics manager, so the provider
+/// only prepares the checks and stores their results.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct GrammarCheckProvider {
+    diagnostics_by_uri: HashMap<Uri, Vec<Diagnostic>>,
+    last_lint_time: u64,
+}
+
+impl GrammarCheckProvider {
+    pub fn get(&self, document: &Document) -> Vec<Diagnostic> {
+        self.diagnostics_by_uri
+            .get(&document.uri)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Returns the check of the document unless the grammar checker
+    /// already ran within the configured delay.
+    ///
+    /// Saving the document always triggers the grammar checker.
+    pub fn update(
+        &mut self,
+        document: &Document,
+        reason: LintReason,
+        options: &LatexGrammarCheckOptions,
+    ) -> Option<GrammarCheck> {
+        let current_timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        let delay = options.delay();
+        if reason == LintReason::Save || current_timestamp > self.last_lint_time + delay {
+            self.last_lint_time = current_timestamp;
+            GrammarCheck::new(document, options)
+        } else {
+            None
+        }
+    }
+
+    pub fn set(&mut self, uri: Uri, diagnostics: Vec<Diagnostic>) {
+        self.diagnostics_by_uri.insert(uri, diagnostics);
+    }
+
+    pub fn remove(&mut self, uri: &Uri) {
+        self.diagnostics_by_uri.remove(uri);
+    }
+}
+
+/// The prose of a document that is sent to the LanguageTool server.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GrammarCheck {
+    pub uri: Uri,
+    text: PlainText,
+    language: String,
+    disabled_rules: Vec<String>,
+    notice: Option<Diagnostic>,
+}
+
+impl GrammarCheck {
+    pub fn new(document: &Document, options: &LatexGrammarCheckOptions) -> Option<Self> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return None,
+        };
+
+        let prose = extract_prose(tree, &document.text, &LatexSpellCheckOptions::default());
+        let input = LinterInput::new(&prose, options.max_input_size());
+        Some(Self {
+            uri: document.uri.clone(),
+            text: PlainText::new(&input.text),
+            language: options.language(),
+            disabled_rules: options.disabled_rules(),
+            notice: input.notice(SOURCE),
+        })
+    }
+
+    /// Sends the prose to the server and converts its matches into diagnostics.
+    pub async fn run(self, url: &Url) -> Vec<Diagnostic> {
+        let mut diagnostics = self.lint(url).await.unwrap_or_default();
+        diagnostics.extend(self.notice);
+        diagnostics
+    }
+
+    async fn lint(&self, url: &Url) -> Option<Vec<Diagnostic>> {
+        if self.text.text.trim().is_empty() {
+            return Some(Vec::new());
+        }
+
+        let endpoint = url.join("v2/check").ok()?;
+        let mut body = format!(
+            "language={}&text={}",
+            encode(&self.language),
+            encode(&self.text.text)
+        );
+        if !self.disabled_rules.is_empty() {
+            body.push_str("&disabledRules=");
+            body.push_str(&encode(&self.disabled_rules.join(",")));
+        }
+
+        let response = post(&endpoint, &body).await?;
+        parse_response(&response, &self.text)
+    }
+}
+
+/// The prose of a document without its markup.
+///
+/// Every UTF-16 code unit of the text knows its position in the document
+/// because LanguageTool reports the offsets of its matches in code units.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+struct PlainText {
+    text: String,
+    positions: Vec<Position>,
+    gap: Option<Position>,
+    line_breaks: usize,
+}
+
+impl PlainText {
+    fn new(prose: &str) -> Self {
+        let mut text = Self::default();
+        for (line, content) in prose.split('\n').enumerate() {
+            let chars: Vec<char> = content.chars().collect();
+            let mut i = 0;
+            while i < chars.len() {
+                let position = Position::new(line as u64, i as u64);
+                match chars[i] {
+                    '%' => break,
+                    '\\' => match chars.get(i + 1) {
+                        Some(c) if c.is_ascii_alphabetic() => {
+                            i += 1;
+                            while i + 1 < chars.len() && chars[i + 1].is_ascii_alphabetic() {
+                                i += 1;
+                            }
+                            text.space(position);
+                        }
+                        Some('\\') | None => {
+                            i += 1;
+                            text.space(position);
+                        }
+                        Some(c) => {
+                            i += 1;
+                            text.push(*c, Position::new(line as u64, i as u64));
+                        }
+                    },
+                    '{' | '}' => (),
+                    '~' => text.space(position),
+                    c if c.is_whitespace() => text.space(position),
+                    c => text.push(c, position),
+                }
+                i += 1;
+            }
+
+            text.space(Position::new(line as u64, chars.len() as u64));
+            text.line_breaks += 1;
+        }
+        text
+    }
+
+    fn space(&mut self, position: Position) {
+        if self.gap.is_none() {
+            self.gap = Some(position);
+        }
+    }
+
+    fn push(&mut self, c: char, position: Position) {
+        if let Some(gap) = self.gap.take() {
+            if !self.text.is_empty() {
+                let separator = if self.line_breaks >= 2 { "\n\n" } else { " " };
+                for c in separator.chars() {
+                    self.push_char(c, gap);
+                }
+            }
+        }
+        self.line_breaks = 0;
+        self.push_char(c, position);
+    }
+
+    fn push_char(&mut self, c: char, position: Position) {
+        self.text.push(c);
+        for _ in 0..c.len_utf16() {
+            self.positions.push(position);
+        }
+    }
+
+    /// Maps a range of UTF-16 code units to the document.
+    fn range(&self, offset: usize, length: usize) -> Option<Range> {
+        let start = *self.positions.get(offset)?;
+        let last = *self.positions.get(offset + length.max(1) - 1)?;
+        Some(Range::new(
+            start,
+            Position::new(last.line, last.character + 1),
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckResponse {
+    matches: Vec<CheckMatch>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckMatch {
+    message: String,
+    offset: usize,
+    length: usize,
+    #[serde(default)]
+    replacements: Vec<CheckReplacement>,
+    rule: Option<CheckRule>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckReplacement {
+    value: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRule {
+    id: String,
+}
+
+impl CheckMatch {
+    fn to_diagnostic(&self, text: &PlainText) -> Option<Diagnostic> {
+        let message = match self.replacements.first() {
+            Some(replacement) => format!("{} Suggestion: {}", self.message, replacement.value),
+            None => self.message.clone(),
+        };
+
+        Some(Diagnostic {
+            source: Some(SOURCE.into()),
+            code: self
+                .rule
+                .as_ref()
+                .map(|rule| NumberOrString::String(rule.id.clone())),
+            message,
+            severity: Some(DiagnosticSeverity::Information),
+            range: text.range(self.offset, self.length)?,
+            related_information: None,
+        })
+    }
+}
+
+fn parse_response(body: &str, text: &PlainText) -> Option<Vec<Diagnostic>> {
+    let response: CheckResponse = serde_json::from_str(body).ok()?;
+    Some(
+        response
+            .matches
+            .iter()
+            .filter_map(|check_match| check_match.to_diagnostic(text))
+            .collect(),
+    )
+}
+
+/// Sends a form to a plain HTTP server and returns the body of the response.
+///
+/// Responses that exceed `MAX_RESPONSE_SIZE` are discarded.
+async fn post(url: &Url, body: &str) -> Option<String> {
+    if url.scheme() != "http" {
+        return None;
+    }
+
+    let host = url.host_str()?;
+    let port = url.port_or_known_default()?;
+    let connect = TcpStream::connect((host, port));
+    let mut stream = time::timeout(CONNECT_TIMEOUT, connect).await.ok()?.ok()?;
+    let request = format!(
+        "POST {} HTTP/1.1\r\n\
+         Host: {}\r\n\
+         Content-Type: application/x-www-form-urlencoded\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        url.path(),
+        host,
+        body.len(),
+        body
+    );
+
+    let mut response = Vec::new();
+    let exchange = async {
+        stream.write_all(request.as_bytes()).await?;
+        let limit = MAX_RESPONSE_SIZE as u64 + 1;
+        (&mut stream).take(limit).read_to_end(&mut response).await
+    };
+    time::timeout(READ_TIMEOUT, exchange).await.ok()?.ok()?;
+    parse_http_response(&response)
+}
+
+/// Extracts the body of a successful response.
+fn parse_http_response(response: &[u8]) -> Option<String> {
+    if response.len() > MAX_RESPONSE_SIZE {
+        return None;
+    }
+
+    let response = String::from_utf8_lossy(response);
+    let separator = response.find("\r\n\r\n")?;
+    let (head, content) = (&response[..separator], &response[separator + 4..]);
+    if !head.starts_with("HTTP/1.1 200") && !head.starts_with("HTTP/1.0 200") {
+        return None;
+    }
+
+    let is_chunked = head.to_lowercase().contains("transfer-encoding: chunked");
+    if is_chunked {
+        decode_chunks(content)
+    } else {
+        Some(content.to_owned())
+    }
+}
+
+fn decode_chunks(mut content: &str) -> Option<String> {
+    let mut body = String::new();
+    loop {
+        let line_end = content.find("\r\n")?;
+        let size_text = content[..line_end].split(';').next()?.trim();
+        let size = usize::from_str_radix(size_text, 16).ok()?;
+        if size == 0 {
+            return Some(body);
+        }
+
+        let start = line_end + 2;
+        body.push_str(content.get(start..start + size)?);
+        content = content.get(start + size + 2..)?;
+    }
+}
+
+/// Encodes a value of an `application/x-www-form-urlencoded` form.
+fn encode(value: &str) -> String {
+    let mut encoded = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'*' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+
+    #[test]
+    fn plain_text() {
+        let text = PlainText::new(
+            "This is \\textbf{bold}~text. % comment\n\\emph{Next}\n\nParagraph 50\\%.",
+        );
+        assert_eq!(text.text, "This is bold text. Next\n\nParagraph 50%.");
+        assert_eq!(text.range(8, 4), Some(Range::new_simple(0, 16, 0, 20)));
+        assert_eq!(text.range(19, 4), Some(Range::new_simple(1, 6, 1, 10)));
+        assert_eq!(text.range(37, 1), Some(Range::new_simple(3, 13, 3, 14)));
+        assert_eq!(text.range(100, 1), None);
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        let text = PlainText::new("😀 foo");
+        assert_eq!(text.range(3, 3), Some(Range::new_simple(0, 2, 0, 5)));
+    }
+
+    #[test]
+    fn response() {
+        let text = PlainText::new("This are \\emph{wrong}.");
+        let body = r#"{
+            "matches": [{
+                "message": "The verb does not agree with the subject.",
+                "offset": 5,
+                "length": 3,
+                "replacements": [{ "value": "is" }],
+                "rule": { "id": "AGREEMENT" }
+            }]
+        }"#;
+        let diagnostics = parse_response(body, &text).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 5, 0, 8));
+        assert_eq!(
+            diagnostics[0].message,
+            "The verb does not agree with the subject. Suggestion: is"
+        );
+        assert_eq!(
+            diagnostics[0].code,
+            Some(NumberOrString::String("AGREEMENT".into()))
+        );
+    }
+
+    #[test]
+    fn chunked_response() {
+        assert_eq!(
+            decode_chunks("4\r\nfoo \r\n3\r\nbar\r\n0\r\n\r\n"),
+            Some("foo bar".into())
+        );
+    }
+
+    #[test]
+    fn http_response() {
+        let response = b"HTTP/1.1 200 OK\r\nContent-Length: 3\r\n\r\nfoo";
+        assert_eq!(parse_http_response(response), Some("foo".into()));
+
+        let response = b"HTTP/1.1 500 Internal Server Error\r\n\r\nfoo";
+        assert_eq!(parse_http_response(response), None);
+    }
+
+    #[test]
+    fn oversized_http_response() {
+        let mut response = b"HTTP/1.1 200 OK\r\n\r\n".to_vec();
+        response.resize(MAX_RESPONSE_SIZE + 1, b' ');
+        assert_eq!(parse_http_response(&response), None);
+    }
+
+    #[test]
+    fn form_encoding() {
+        assert_eq!(encode("foo bar&ä"), "foo+bar%26%C3%A4");
+    }
+}
@@ -0,0 +1,57 @@
+use crate::graphics::MissingGraphic;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, Options, Uri};
+use texlab_syntax::*;
+use texlab_workspace::sandbox::SANDBOX_DIRECTORY;
+use texlab_workspace::Document;
+
+/// Reports the graphics that do not exist.
+///
+/// Looking up the files is expensive, so the diagnostics are only updated
+/// when a document is opened or saved.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GraphicsDiagnosticsProvider {
+    diagnostics_by_uri: HashMap<Uri, Vec<Diagnostic>>,
+    sandbox: PathBuf,
+}
+
+impl Default for GraphicsDiagnosticsProvider {
+    fn default() -> Self {
+        Self {
+            diagnostics_by_uri: HashMap::new(),
+            sandbox: PathBuf::from(SANDBOX_DIRECTORY),
+        }
+    }
+}
+
+impl GraphicsDiagnosticsProvider {
+    pub fn get(&self, document: &Document) -> Vec<Diagnostic> {
+        match self.diagnostics_by_uri.get(&document.uri) {
+            Some(diagnostics) => diagnostics.to_owned(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn update(&mut self, document: &Document, options: &Options) {
+        let mut diagnostics = Vec::new();
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            for graphic in MissingGraphic::analyze(tree, &document.uri, options, &self.sandbox) {
+                diagnostics.push(Diagnostic {
+                    source: Some("latex".into()),
+                    range: graphic.path.range(),
+                    message: format!("Graphics file not found: {}", graphic.path.text()),
+                    severity: Some(DiagnosticSeverity::Warning),
+                    code: None,
+                    related_information: None,
+                });
+            }
+        }
+        self.diagnostics_by_uri
+            .insert(document.uri.clone(), diagnostics);
+    }
+
+    pub fn remove(&mut self, uri: &Uri) {
+        self.diagnostics_by_uri.remove(uri);
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, Uri};
+use texlab_syntax::*;
+use texlab_workspace::{Document, Workspace};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct IncludeCycleDiagnosticsProvider;
+
+impl IncludeCycleDiagnosticsProvider {
+    pub fn get(self, workspace: &Workspace, document: &Document) -> Vec<Diagnostic> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return Vec::new(),
+        };
+
+        let mut diagnostics = Vec::new();
+        for include in latex_includes(tree) {
+            for (path, targets) in include.paths().into_iter().zip(&include.all_targets) {
+                let child = match targets.iter().find_map(|target| workspace.find(target)) {
+                    Some(child) => child,
+                    None => continue,
+                };
+
+                if Self::is_reachable(workspace, &child.uri, &document.uri) {
+                    diagnostics.push(Diagnostic {
+                        source: Some("latex".into()),
+                        range: path.range(),
+                        message: format!(
+                            "Cyclic include: \"{}\" includes this file again",
+                            path.text()
+                        ),
+                        severity: Some(DiagnosticSeverity::Error),
+                        code: None,
+                        related_information: None,
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+
+    fn is_reachable(workspace: &Workspace, start: &Uri, target: &Uri) -> bool {
+        let mut visited = HashSet::new();
+        let mut stack = vec![start.clone()];
+        while let Some(uri) = stack.pop() {
+            if &uri == target {
+                return true;
+            }
+
+            if !visited.insert(uri.clone()) {
+                continue;
+            }
+
+            if let Some(document) = workspace.find(&uri) {
+                if let SyntaxTree::Latex(tree) = &document.tree {
+                    for include in latex_includes(tree) {
+                        stack.extend(include.all_targets.iter().filter_map(|targets| {
+                            targets
+                                .iter()
+                                .find_map(|target| workspace.find(target))
+                                .map(|child| child.uri.clone())
+                        }));
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+fn latex_includes(tree: &LatexSyntaxTree) -> impl Iterator<Item = &LatexInclude> {
+    tree.includes
+        .iter()
+        .filter(|include| include.kind == LatexIncludeKind::Latex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Range, RangeExt};
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn diagnostics(builder: &TestWorkspaceBuilder, uri: &Uri) -> Vec<Diagnostic> {
+        let document = builder.workspace.find(uri).unwrap();
+        IncludeCycleDiagnosticsProvider.get(&builder.workspace, &document)
+    }
+
+    #[test]
+    fn cycle() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "\\input{bar}");
+        let uri2 = builder.add_document("bar.tex", "\\include{baz}");
+        let uri3 = builder.add_document("baz.tex", "\\input{foo.tex}");
+        for uri in &[uri1, uri2, uri3.clone()] {
+            assert_eq!(diagnostics(&builder, uri).len(), 1);
+        }
+
+        let diagnostics = diagnostics(&builder, &uri3);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 7, 0, 14));
+    }
+
+    #[test]
+    fn self_include() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", "\\input{foo}");
+        assert_eq!(diagnostics(&builder, &uri).len(), 1);
+    }
+
+    #[test]
+    fn no_cycle() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri1 = builder.add_document("foo.tex", "\\input{bar}\n\\input{baz}");
+        let uri2 = builder.add_document("bar.tex", "\\input{baz}");
+        let uri3 = builder.add_document("baz.tex", "");
+        for uri in &[uri1, uri2, uri3] {
+            assert!(diagnostics(&builder, uri).is_empty());
+        }
+    }
+
+    #[test]
+    fn bibtex() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.bib", "");
+        assert!(diagnostics(&builder, &uri).is_empty());
+    }
+}
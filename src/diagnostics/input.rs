@@ -0,0 +1,98 @@
+use std::borrow::Cow;
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, Position, Range};
+
+/// The text that is passed to an external linter.
+///
+/// Large documents are reduced to their first and last lines.
+/// The lines in between are left empty so that the reported positions stay valid.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LinterInput<'a> {
+    pub text: Cow<'a, str>,
+    pub skipped_lines: Option<(u64, u64)>,
+}
+
+impl<'a> LinterInput<'a> {
+    pub fn new(text: &'a str, max_size: usize) -> Self {
+        if text.len() <= max_size {
+            return Self {
+                text: Cow::Borrowed(text),
+                skipped_lines: None,
+            };
+        }
+
+        let lines: Vec<&str> = text.split('\n').collect();
+        let budget = max_size / 2;
+
+        let mut head_size = 0;
+        let mut head_end = 0;
+        while head_end < lines.len() && head_size + lines[head_end].len() + 1 <= budget {
+            head_size += lines[head_end].len() + 1;
+            head_end += 1;
+        }
+
+        let mut tail_size = 0;
+        let mut tail_start = lines.len();
+        while tail_start > head_end && tail_size + lines[tail_start - 1].len() + 1 <= budget {
+            tail_size += lines[tail_start - 1].len() + 1;
+            tail_start -= 1;
+        }
+
+        let text = lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                if i < head_end || i >= tail_start {
+                    *line
+                } else {
+                    ""
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Self {
+            text: Cow::Owned(text),
+            skipped_lines: Some((head_end as u64, tail_start as u64)),
+        }
+    }
+
+    /// Tells the user that the linter did not check the whole document.
+    pub fn notice(&self, source: &str) -> Option<Diagnostic> {
+        let (start, _) = self.skipped_lines?;
+        Some(Diagnostic {
+            source: Some(source.into()),
+            code: None,
+            message: format!(
+                "The document is too large for {}. Only its first and last lines were checked.",
+                source
+            ),
+            severity: Some(DiagnosticSeverity::Information),
+            range: Range::new(Position::new(start, 0), Position::new(start, 0)),
+            related_information: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_document() {
+        let input = LinterInput::new("foo\nbar", 7);
+        assert_eq!(input.text, "foo\nbar");
+        assert_eq!(input.skipped_lines, None);
+        assert_eq!(input.notice("chktex"), None);
+    }
+
+    #[test]
+    fn large_document() {
+        let input = LinterInput::new("foo\nbar\nbaz\nqux\nquux", 14);
+        assert_eq!(input.text, "foo\n\n\n\nquux");
+        assert_eq!(input.skipped_lines, Some((1, 4)));
+
+        let notice = input.notice("chktex").unwrap();
+        assert_eq!(notice.range.start, Position::new(1, 0));
+        assert_eq!(notice.severity, Some(DiagnosticSeverity::Information));
+    }
+}
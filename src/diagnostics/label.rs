@@ -0,0 +1,240 @@
+use std::collections::HashSet;
+use texlab_protocol::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, Options,
+};
+use texlab_syntax::*;
+use texlab_workspace::{Document, Workspace};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LabelDiagnosticsProvider;
+
+impl LabelDiagnosticsProvider {
+    pub fn get(
+        self,
+        workspace: &Workspace,
+        document: &Document,
+        options: &Options,
+    ) -> Vec<Diagnostic> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return Vec::new(),
+        };
+
+        let mut diagnostics = undefined_references(workspace, document, tree, options);
+        diagnostics.append(&mut duplicate_definitions(
+            workspace, document, tree, options,
+        ));
+        diagnostics
+    }
+}
+
+fn undefined_references(
+    workspace: &Workspace,
+    document: &Document,
+    tree: &LatexSyntaxTree,
+    options: &Options,
+) -> Vec<Diagnostic> {
+    let mut references = tree
+        .structure
+        .labels
+        .iter()
+        .filter(|label| label.kind.is_reference())
+        .peekable();
+
+    if references.peek().is_none() {
+        return Vec::new();
+    }
+
+    let names = match defined_labels(workspace, document, options) {
+        Some(names) => names,
+        None => return Vec::new(),
+    };
+
+    let mut diagnostics = Vec::new();
+    for name in references.flat_map(LatexLabel::names) {
+        if !names.contains(name.text()) {
+            diagnostics.push(Diagnostic {
+                source: Some("latex".into()),
+                range: name.range(),
+                message: format!("Undefined reference: {}", name.text()),
+                severity: Some(DiagnosticSeverity::Warning),
+                code: None,
+                related_information: None,
+            });
+        }
+    }
+    diagnostics
+}
+
+/// Reports the labels that are also defined elsewhere in the project.
+fn duplicate_definitions(
+    workspace: &Workspace,
+    document: &Document,
+    tree: &LatexSyntaxTree,
+    options: &Options,
+) -> Vec<Diagnostic> {
+    let names: Vec<&LatexToken> = definitions(tree).collect();
+    if names.is_empty() {
+        return Vec::new();
+    }
+
+    let related_documents = workspace.related_documents(&document.uri, options);
+    let mut diagnostics = Vec::new();
+    for name in names {
+        let mut others = Vec::new();
+        for related in &related_documents {
+            if let SyntaxTree::Latex(related_tree) = &related.tree {
+                for other in definitions(related_tree) {
+                    let is_same = related.uri == document.uri && other.range() == name.range();
+                    if other.text() == name.text() && !is_same {
+                        others.push(DiagnosticRelatedInformation {
+                            location: Location::new(related.uri.clone().into(), other.range()),
+                            message: "Also defined here".into(),
+                        });
+                    }
+                }
+            }
+        }
+
+        if !others.is_empty() {
+            diagnostics.push(Diagnostic {
+                source: Some("latex".into()),
+                range: name.range(),
+                message: format!("Duplicate label: {}", name.text()),
+                severity: Some(DiagnosticSeverity::Warning),
+                code: None,
+                related_information: Some(others),
+            });
+        }
+    }
+    diagnostics
+}
+
+fn definitions(tree: &LatexSyntaxTree) -> impl Iterator<Item = &LatexToken> {
+    tree.structure
+        .labels
+        .iter()
+        .filter(|label| !label.kind.is_reference())
+        .flat_map(LatexLabel::names)
+}
+
+/// Collects the label definitions of the project.
+///
+/// Returns `None` if one of the included documents has not been loaded yet.
+fn defined_labels(
+    workspace: &Workspace,
+    document: &Document,
+    options: &Options,
+) -> Option<HashSet<String>> {
+    let mut names = HashSet::new();
+    for related in workspace.related_documents(&document.uri, options) {
+        if let SyntaxTree::Latex(tree) = &related.tree {
+            let has_missing_include = tree
+                .includes
+                .iter()
+                .filter(|include| include.kind == LatexIncludeKind::Latex)
+                .flat_map(|include| &include.all_targets)
+                .any(|targets| {
+                    targets
+                        .iter()
+                        .all(|target| workspace.find(target).is_none())
+                });
+
+            if has_missing_include {
+                return None;
+            }
+
+            for label in &tree.structure.labels {
+                if !label.kind.is_reference() {
+                    names.extend(label.names().iter().map(|name| name.text().to_owned()));
+                }
+            }
+        }
+    }
+    Some(names)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::test_diagnostics;
+    use texlab_protocol::{Range, RangeExt};
+
+    fn diagnostics(files: &[(&str, &str)], name: &str) -> Vec<Diagnostic> {
+        test_diagnostics(files, name, |workspace, document| {
+            LabelDiagnosticsProvider.get(workspace, document, &Options::default())
+        })
+    }
+
+    #[test]
+    fn undefined_reference() {
+        let diagnostics = diagnostics(
+            &[(
+                "foo.tex",
+                "\\label{foo}\n\\ref{foo}\n\\eqref{bar}\n\\cref{foo,baz}",
+            )],
+            "foo.tex",
+        );
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].range, Range::new_simple(2, 7, 2, 10));
+        assert_eq!(diagnostics[0].message, "Undefined reference: bar");
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Warning));
+        assert_eq!(diagnostics[1].range, Range::new_simple(3, 10, 3, 13));
+    }
+
+    #[test]
+    fn defined_in_included_document() {
+        let diagnostics = diagnostics(
+            &[
+                ("foo.tex", "\\include{bar}\n\\ref{baz}"),
+                ("bar.tex", "\\label{baz}"),
+            ],
+            "foo.tex",
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn duplicate_in_related_document() {
+        let diagnostics = diagnostics(
+            &[
+                ("foo.tex", "\\include{bar}\n\\label{baz}"),
+                ("bar.tex", "\\label{baz}"),
+            ],
+            "foo.tex",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Duplicate label: baz");
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 7, 1, 10));
+
+        let related_information = diagnostics[0].related_information.as_ref().unwrap();
+        assert_eq!(related_information.len(), 1);
+        assert_eq!(
+            related_information[0].location.range,
+            Range::new_simple(0, 7, 0, 10)
+        );
+        assert!(related_information[0]
+            .location
+            .uri
+            .as_str()
+            .ends_with("bar.tex"));
+    }
+
+    #[test]
+    fn duplicate_in_same_document() {
+        let diagnostics = diagnostics(
+            &[("foo.tex", "\\label{foo}\n\\label{foo}\n\\label{bar}")],
+            "foo.tex",
+        );
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics
+            .iter()
+            .all(|diagnostic| diagnostic.message == "Duplicate label: foo"));
+    }
+
+    #[test]
+    fn missing_include() {
+        let diagnostics = diagnostics(&[("foo.tex", "\\include{bar}\n\\ref{baz}")], "foo.tex");
+        assert!(diagnostics.is_empty());
+    }
+}
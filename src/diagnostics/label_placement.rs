@@ -0,0 +1,169 @@
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, Range};
+use texlab_syntax::*;
+use texlab_workspace::{Document, OutlineCaptionKind};
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum LabelPlacement {
+    MissingLabel {
+        kind: OutlineCaptionKind,
+        caption: Range,
+    },
+    LabelBeforeCaption {
+        label: Range,
+        text: String,
+        caption: Range,
+    },
+}
+
+impl LabelPlacement {
+    /// Finds floats with a caption that either lack a label or define it before the caption.
+    pub fn analyze(tree: &LatexSyntaxTree) -> Vec<Self> {
+        let mut placements = Vec::new();
+        for environment in &tree.env.environments {
+            let kind = match environment
+                .left
+                .name()
+                .and_then(|name| OutlineCaptionKind::parse(name.text()))
+            {
+                Some(kind) => kind,
+                None => continue,
+            };
+
+            let caption = match tree
+                .structure
+                .captions
+                .iter()
+                .find(|caption| tree.is_direct_child(environment, caption.start()))
+            {
+                Some(caption) => caption,
+                None => continue,
+            };
+
+            let labels: Vec<_> = tree
+                .structure
+                .labels
+                .iter()
+                .filter(|label| label.kind == LatexLabelKind::Definition)
+                .filter(|label| tree.is_direct_child(environment, label.start()))
+                .collect();
+
+            if labels.is_empty() {
+                placements.push(LabelPlacement::MissingLabel {
+                    kind,
+                    caption: caption.range(),
+                });
+            } else if labels.iter().all(|label| label.start() < caption.start()) {
+                let label = labels[0];
+                let names: Vec<_> = label.names().into_iter().map(LatexToken::text).collect();
+                placements.push(LabelPlacement::LabelBeforeCaption {
+                    label: label.range(),
+                    text: format!("{}{{{}}}", label.command.name.text(), names.join(",")),
+                    caption: caption.range(),
+                });
+            }
+        }
+        placements
+    }
+
+    pub fn range(&self) -> Range {
+        match self {
+            LabelPlacement::MissingLabel { caption, .. } => *caption,
+            LabelPlacement::LabelBeforeCaption { label, .. } => *label,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct LabelPlacementDiagnosticsProvider;
+
+impl LabelPlacementDiagnosticsProvider {
+    pub fn get(self, document: &Document) -> Vec<Diagnostic> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return Vec::new(),
+        };
+
+        LabelPlacement::analyze(tree)
+            .into_iter()
+            .map(|placement| {
+                let message = match &placement {
+                    LabelPlacement::MissingLabel { kind, .. } => {
+                        format!("{} has a caption but no label", kind.as_str())
+                    }
+                    LabelPlacement::LabelBeforeCaption { .. } => {
+                        "Label should be placed after the caption to get the correct number"
+                            .to_owned()
+                    }
+                };
+
+                Diagnostic {
+                    source: Some("latex".into()),
+                    range: placement.range(),
+                    message,
+                    severity: Some(DiagnosticSeverity::Hint),
+                    code: None,
+                    related_information: None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn analyze(text: &str) -> Vec<LabelPlacement> {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", text);
+        let document = builder.workspace.find(&uri).unwrap();
+        match &document.tree {
+            SyntaxTree::Latex(tree) => LabelPlacement::analyze(tree),
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn missing_label() {
+        let placements = analyze("\\begin{figure}\n\\caption{foo}\n\\end{figure}");
+        assert_eq!(
+            placements,
+            vec![LabelPlacement::MissingLabel {
+                kind: OutlineCaptionKind::Figure,
+                caption: Range::new_simple(1, 0, 1, 13),
+            }]
+        );
+    }
+
+    #[test]
+    fn label_before_caption() {
+        let placements = analyze("\\begin{table}\n\\label{tab:foo}\n\\caption{foo}\n\\end{table}");
+        assert_eq!(
+            placements,
+            vec![LabelPlacement::LabelBeforeCaption {
+                label: Range::new_simple(1, 0, 1, 15),
+                text: "\\label{tab:foo}".into(),
+                caption: Range::new_simple(2, 0, 2, 13),
+            }]
+        );
+    }
+
+    #[test]
+    fn label_after_caption() {
+        let placements = analyze("\\begin{figure}\n\\caption{foo}\\label{fig:foo}\n\\end{figure}");
+        assert!(placements.is_empty());
+    }
+
+    #[test]
+    fn label_inside_caption() {
+        let placements = analyze("\\begin{figure}\n\\caption{foo\\label{fig:foo}}\n\\end{figure}");
+        assert!(placements.is_empty());
+    }
+
+    #[test]
+    fn without_caption() {
+        assert!(analyze("\\begin{figure}\n\\end{figure}").is_empty());
+    }
+}
@@ -1,11 +1,23 @@
+use super::LinterInput;
+use crate::action::LintReason;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
-use std::io::{Read, Write};
-use std::process::{Command, Stdio};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use texlab_protocol::*;
+use texlab_workspace::sandbox::{self, SANDBOX_DIRECTORY};
 use texlab_workspace::Document;
-use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time;
+use uuid::Uuid;
+
+/// The time after which a linter is stopped.
+pub(super) const LINTER_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct LatexDiagnosticsProvider {
@@ -21,49 +33,161 @@ impl LatexDiagnosticsProvider {
         }
     }
 
-    pub fn update(&mut self, uri: &Uri, text: &str) {
-        if uri.scheme() != "file" {
-            return;
-        }
+    /// Returns `true` if the linter should run on the document
+    /// and restarts the delay in that case.
+    ///
+    /// Saving the document always triggers the linter.
+    pub fn is_due(&mut self, reason: LintReason, options: &LatexLintOptions) -> bool {
         let current_time = SystemTime::now();
         let since_the_epoch = current_time.duration_since(UNIX_EPOCH).expect("Time went backwards");
         let current_timestamp = since_the_epoch.as_secs();
-        /* Every one minute */
-        if current_timestamp > self.last_lint_time + 60 {
+        let delay = options.delay();
+        if reason == LintReason::Save || current_timestamp > self.last_lint_time + delay {
             self.last_lint_time = current_timestamp;
-            self.diagnostics_by_uri
-            .insert(uri.clone(), lint(text).unwrap_or_default());
+            true
+        } else {
+            false
         }
     }
+
+    pub fn set(&mut self, uri: Uri, diagnostics: Vec<Diagnostic>) {
+        self.diagnostics_by_uri.insert(uri, diagnostics);
+    }
+
+    pub fn remove(&mut self, uri: &Uri) {
+        self.diagnostics_by_uri.remove(uri);
+    }
 }
 
 pub static LINE_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new("(\\d+):(\\d+):(\\d+):(\\w+):(\\w+):(.*)").unwrap());
 
-fn lint(text: &str) -> Option<Vec<Diagnostic>> {
-    let mut process = Command::new("chktex")
-        .args(&["-I0", "-f%l:%c:%d:%k:%n:%m\n"])
+static LACHECK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^\"(.*)\", line (\\d+): (.*)$").unwrap());
+
+/// Runs the configured linter on the text.
+pub async fn run_linter(
+    uri: &Uri,
+    text: &str,
+    options: &LatexLintOptions,
+) -> Option<Vec<Diagnostic>> {
+    match options.tool() {
+        LatexLintTool::Chktex => run_chktex(uri, text, options).await,
+        LatexLintTool::Lacheck => run_lacheck(uri, text, options).await,
+        LatexLintTool::None => Some(Vec::new()),
+    }
+}
+
+/// Returns the directory of the document if it exists on disk.
+fn document_directory(uri: &Uri) -> Option<PathBuf> {
+    uri.to_file_path().ok().and_then(|mut path| {
+        path.pop();
+        Some(path).filter(|path| path.is_dir())
+    })
+}
+
+/// Finds the nearest `.chktexrc` in the directory of the document or one of its ancestors.
+///
+/// The search stops at the sandbox, so that no configuration of the host is used.
+fn find_chktexrc(directory: &Path, sandbox: &Path) -> Option<PathBuf> {
+    /* Security Patch */
+    sandbox::confine(directory, sandbox)?
+        .ancestors()
+        .take_while(|directory| directory.starts_with(sandbox))
+        .map(|directory| directory.join(".chktexrc"))
+        .find(|path| path.is_file())
+}
+
+/// Keeps the custom arguments of `chktex` that only enable or disable a warning,
+/// e.g. `-n1` or `-w 24`.
+///
+/// Any other argument could make `chktex` read or write arbitrary files.
+fn chktex_warning_args(custom_args: &[String]) -> Vec<String> {
+    /* Security Patch */
+    fn is_switch(arg: &str) -> bool {
+        ["-n", "-w", "-e", "-m"].contains(&arg)
+    }
+
+    fn is_number(arg: &str) -> bool {
+        !arg.is_empty() && arg.chars().all(|c| c.is_ascii_digit())
+    }
+
+    let mut args = Vec::new();
+    let mut custom_args = custom_args.iter().peekable();
+    while let Some(arg) = custom_args.next() {
+        if is_switch(arg) {
+            if let Some(value) = custom_args.peek().filter(|value| is_number(value)) {
+                args.push(arg.clone());
+                args.push((*value).clone());
+                custom_args.next();
+            }
+        } else if arg.len() > 2 && arg.is_char_boundary(2) {
+            let (switch, value) = arg.split_at(2);
+            if is_switch(switch) && is_number(value) {
+                args.push(arg.clone());
+            }
+        }
+    }
+    args
+}
+
+/// Runs `chktex` on the text without blocking the executor.
+///
+/// Only the custom arguments that toggle warnings are passed on
+/// and the output format is always set by the server.
+/// The process is killed if it does not finish within the timeout.
+async fn run_chktex(uri: &Uri, text: &str, options: &LatexLintOptions) -> Option<Vec<Diagnostic>> {
+    let directory = document_directory(uri);
+
+    let mut args = chktex_warning_args(&options.chktex_args());
+    let sandbox = Path::new(SANDBOX_DIRECTORY);
+    if let Some(chktexrc) = directory
+        .as_deref()
+        .and_then(|directory| find_chktexrc(directory, sandbox))
+    {
+        args.push("-l".into());
+        args.push(chktexrc.to_string_lossy().into_owned());
+    }
+    args.push("-I0".into());
+    args.push("-f%l:%c:%d:%k:%n:%m\n".into());
+
+    let mut command = Command::new("chktex");
+    if let Some(directory) = &directory {
+        command.current_dir(directory);
+    }
+
+    let mut process = command
+        .args(&args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::null())
+        .kill_on_drop(true)
         .spawn()
         .ok()?;
 
-    process
-        .stdin
-        .take()
-        .unwrap()
-        .write_all(text.as_bytes())
-        .ok()?;
-
-    let mut stdout = String::new();
-    process
-        .stdout
-        .take()
-        .unwrap()
-        .read_to_string(&mut stdout)
-        .ok()?;
+    // The output is read while the input is written so that neither pipe can fill up.
+    let input = LinterInput::new(text, options.max_input_size());
+    let mut stdin = process.stdin.take()?;
+    let bytes = input.text.as_bytes();
+    let write = async move {
+        let _ = stdin.write_all(bytes).await;
+    };
+    let run = async {
+        let (_, output) = tokio::join!(write, process.wait_with_output());
+        output
+    };
+    let output = time::timeout(LINTER_TIMEOUT, run).await.ok()?.ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut diagnostics = parse_chktex(&stdout, options);
+    diagnostics.extend(input.notice("chktex"));
+    Some(diagnostics)
+}
 
+/// Converts the output of `chktex` into diagnostics.
+///
+/// The configured severities replace the severity that `chktex` reports
+/// and the warnings that are turned off are dropped.
+fn parse_chktex(stdout: &str, options: &LatexLintOptions) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
     for line in stdout.lines() {
         if let Some(captures) = LINE_REGEX.captures(line) {
@@ -74,10 +198,17 @@ fn lint(text: &str) -> Option<Vec<Diagnostic>> {
             let code = &captures[5];
             let message = captures[6].to_owned();
             let range = Range::new_simple(line, character, line, character + digit);
-            let severity = match kind {
-                "Message" => DiagnosticSeverity::Information,
-                "Warning" => DiagnosticSeverity::Warning,
-                _ => DiagnosticSeverity::Error,
+            let severity = match options.severity(code) {
+                Some(LatexLintSeverity::Error) => DiagnosticSeverity::Error,
+                Some(LatexLintSeverity::Warning) => DiagnosticSeverity::Warning,
+                Some(LatexLintSeverity::Info) => DiagnosticSeverity::Information,
+                Some(LatexLintSeverity::Hint) => DiagnosticSeverity::Hint,
+                Some(LatexLintSeverity::Off) => continue,
+                None => match kind {
+                    "Message" => DiagnosticSeverity::Information,
+                    "Warning" => DiagnosticSeverity::Warning,
+                    _ => DiagnosticSeverity::Error,
+                },
             };
 
             diagnostics.push(Diagnostic {
@@ -90,5 +221,154 @@ fn lint(text: &str) -> Option<Vec<Diagnostic>> {
             })
         }
     }
+    diagnostics
+}
+
+/// Runs `lacheck` on a temporary copy of the text because it cannot read from stdin.
+///
+/// The copy is checked from the directory of the document so that included files are found.
+async fn run_lacheck(uri: &Uri, text: &str, options: &LatexLintOptions) -> Option<Vec<Diagnostic>> {
+    let input = LinterInput::new(text, options.max_input_size());
+    let path = env::temp_dir().join(format!("texlab-{}.tex", Uuid::new_v4()));
+    fs::write(&path, input.text.as_bytes()).await.ok()?;
+
+    let mut command = Command::new("lacheck");
+    if let Some(directory) = document_directory(uri) {
+        command.current_dir(directory);
+    }
+
+    let output = match command
+        .arg(&path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(process) => time::timeout(LINTER_TIMEOUT, process.wait_with_output())
+            .await
+            .ok()
+            .and_then(Result::ok),
+        Err(_) => None,
+    };
+    let _ = fs::remove_file(&path).await;
+
+    let stdout = String::from_utf8_lossy(&output?.stdout).into_owned();
+    let mut diagnostics = parse_lacheck(&stdout, &path.to_string_lossy(), &input.text);
+    diagnostics.extend(input.notice("lacheck"));
     Some(diagnostics)
 }
+
+/// Parses the warnings of `lacheck` that refer to the given file.
+///
+/// Since `lacheck` does not report columns, the warnings span the whole line.
+fn parse_lacheck(output: &str, file: &str, text: &str) -> Vec<Diagnostic> {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let mut diagnostics = Vec::new();
+    for line in output.lines() {
+        if let Some(captures) = LACHECK_REGEX.captures(line) {
+            if &captures[1] != file {
+                continue;
+            }
+
+            let line = captures[2].parse::<u64>().unwrap().saturating_sub(1);
+            let length = lines
+                .get(line as usize)
+                .map(|content| content.trim_end().chars().count())
+                .unwrap_or(0);
+            diagnostics.push(Diagnostic {
+                source: Some("lacheck".into()),
+                code: None,
+                message: captures[3].trim().to_owned(),
+                severity: Some(DiagnosticSeverity::Warning),
+                range: Range::new_simple(line, 0, line, length as u64),
+                related_information: None,
+            });
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn chktexrc_in_ancestor() {
+        let root = tempdir().unwrap();
+        let directory = root.path().join("foo").join("bar");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(root.path().join(".chktexrc"), "").unwrap();
+        assert_eq!(
+            find_chktexrc(&directory, root.path()),
+            Some(root.path().join(".chktexrc"))
+        );
+    }
+
+    #[test]
+    fn chktexrc_outside_of_sandbox() {
+        let root = tempdir().unwrap();
+        let sandbox = root.path().join("foo");
+        let directory = sandbox.join("bar");
+        fs::create_dir_all(&directory).unwrap();
+        fs::write(root.path().join(".chktexrc"), "").unwrap();
+        assert_eq!(find_chktexrc(&directory, &sandbox), None);
+    }
+
+    #[test]
+    fn chktex_args() {
+        let args = |args: &[&str]| -> Vec<String> {
+            chktex_warning_args(&args.iter().map(|&arg| arg.into()).collect::<Vec<_>>())
+        };
+        let warnings = ["-n1", "-w", "24", "-e8"];
+        assert_eq!(args(&warnings), warnings);
+        assert!(args(&["-m", "foo", "-n"]).is_empty());
+        assert!(args(&["-o", "/tmp/out", "-l/etc/chktexrc", "-I1"]).is_empty());
+    }
+
+    #[test]
+    fn lacheck_output() {
+        let output = "\"/tmp/foo.tex\", line 2: possible unwanted space at \"{\"\n\
+                      \"bar.tex\", line 1: Whitespace before punctation mark in \" .\"\n";
+        let diagnostics = parse_lacheck(output, "/tmp/foo.tex", "foo\nbar {baz}  \n");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 0, 1, 9));
+        assert_eq!(diagnostics[0].message, "possible unwanted space at \"{\"");
+        assert_eq!(diagnostics[0].source, Some("lacheck".into()));
+    }
+
+    #[test]
+    fn chktex_severities() {
+        let mut severities = HashMap::new();
+        severities.insert("24".to_owned(), LatexLintSeverity::Error);
+        severities.insert("1".to_owned(), LatexLintSeverity::Off);
+        let options = LatexLintOptions {
+            severities: Some(severities),
+            ..LatexLintOptions::default()
+        };
+
+        let output = "1:5:1:Warning:24:Delete this space to maintain correct pagereferences.\n\
+                      2:1:3:Warning:1:Command terminated with space.\n\
+                      3:2:1:Warning:8:Wrong length of dash may have been used.\n";
+        let diagnostics = parse_chktex(output, &options);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Error));
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 4, 0, 5));
+        assert_eq!(diagnostics[1].severity, Some(DiagnosticSeverity::Warning));
+        assert_eq!(
+            diagnostics[1].code,
+            Some(NumberOrString::String("8".into()))
+        );
+    }
+
+    #[test]
+    fn in_memory_document() {
+        let uri: Uri = Url::parse("untitled:foo.tex").unwrap().into();
+        assert_eq!(document_directory(&uri), None);
+
+        let mut provider = LatexDiagnosticsProvider::default();
+        assert!(provider.is_due(LintReason::Save, &LatexLintOptions::default()));
+    }
+}
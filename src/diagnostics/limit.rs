@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+use texlab_protocol::{Diagnostic, DiagnosticSeverity};
+
+pub const RAISE_DIAGNOSTICS_LIMIT_COMMAND: &str = "texlab.raiseDiagnosticsLimit";
+
+/// Keeps at most `max` diagnostics of every source and summarizes the remaining ones.
+pub fn limit_diagnostics(diagnostics: Vec<Diagnostic>, max: usize) -> Vec<Diagnostic> {
+    let mut counts: HashMap<Option<String>, usize> = HashMap::new();
+    let mut overflows: Vec<Diagnostic> = Vec::new();
+    let mut limited = Vec::new();
+    for diagnostic in diagnostics {
+        let count = counts.entry(diagnostic.source.clone()).or_default();
+        *count += 1;
+        if *count <= max {
+            limited.push(diagnostic);
+        } else if !overflows
+            .iter()
+            .any(|overflow| overflow.source == diagnostic.source)
+        {
+            overflows.push(diagnostic);
+        }
+    }
+
+    for mut overflow in overflows {
+        let suppressed = counts[&overflow.source] - max;
+        let kind = match overflow.source.as_deref() {
+            Some("Spell Checker") => "spelling",
+            Some(source) => source,
+            None => "other",
+        };
+        overflow.message = format!("{} more {} issues suppressed", suppressed, kind);
+        overflow.severity = Some(DiagnosticSeverity::Information);
+        overflow.code = None;
+        overflow.related_information = None;
+        limited.push(overflow);
+    }
+    limited
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Range, RangeExt};
+
+    fn diagnostic(source: &str, line: u64) -> Diagnostic {
+        Diagnostic {
+            source: Some(source.into()),
+            code: None,
+            message: "foo".into(),
+            severity: Some(DiagnosticSeverity::Warning),
+            range: Range::new_simple(line, 0, line, 1),
+            related_information: None,
+        }
+    }
+
+    #[test]
+    fn below_limit() {
+        let diagnostics = vec![diagnostic("chktex", 0), diagnostic("chktex", 1)];
+        assert_eq!(limit_diagnostics(diagnostics.clone(), 2), diagnostics);
+    }
+
+    #[test]
+    fn overflow_per_source() {
+        let diagnostics = vec![
+            diagnostic("Spell Checker", 0),
+            diagnostic("chktex", 1),
+            diagnostic("Spell Checker", 2),
+            diagnostic("Spell Checker", 3),
+            diagnostic("Spell Checker", 4),
+        ];
+        let limited = limit_diagnostics(diagnostics, 2);
+        assert_eq!(limited.len(), 4);
+        assert_eq!(limited[2].range, Range::new_simple(2, 0, 2, 1));
+        assert_eq!(limited[3].range, Range::new_simple(3, 0, 3, 1));
+        assert_eq!(limited[3].message, "2 more spelling issues suppressed");
+        assert_eq!(limited[3].severity, Some(DiagnosticSeverity::Information));
+    }
+}
@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, Uri};
+use texlab_syntax::*;
+use texlab_workspace::sandbox::{self, SANDBOX_DIRECTORY};
+use texlab_workspace::{Document, Workspace};
+
+/// Reports the included documents that can neither be found in the workspace nor on disk.
+///
+/// Missing images are reported by the graphics diagnostics, which also consider `\graphicspath`.
+/// Files outside of the sandbox are never looked up.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MissingFileDiagnosticsProvider {
+    sandbox: PathBuf,
+}
+
+impl Default for MissingFileDiagnosticsProvider {
+    fn default() -> Self {
+        Self {
+            sandbox: PathBuf::from(SANDBOX_DIRECTORY),
+        }
+    }
+}
+
+impl MissingFileDiagnosticsProvider {
+    pub fn get(&self, workspace: &Workspace, document: &Document) -> Vec<Diagnostic> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) if document.is_file() => tree,
+            _ => return Vec::new(),
+        };
+
+        let mut diagnostics = Vec::new();
+        for include in &tree.includes {
+            match include.kind {
+                LatexIncludeKind::Latex
+                | LatexIncludeKind::Bibliography
+                | LatexIncludeKind::Svg
+                | LatexIncludeKind::Pdf => (),
+                LatexIncludeKind::Package
+                | LatexIncludeKind::Class
+                | LatexIncludeKind::Image
+                | LatexIncludeKind::Everything => continue,
+            }
+
+            for (path, targets) in include.paths().into_iter().zip(&include.all_targets) {
+                if !exists(workspace, targets, &self.sandbox) {
+                    diagnostics.push(Diagnostic {
+                        source: Some("latex".into()),
+                        range: path.range(),
+                        message: format!("File not found: {}", path.text()),
+                        severity: Some(DiagnosticSeverity::Warning),
+                        code: None,
+                        related_information: None,
+                    });
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+fn exists(workspace: &Workspace, targets: &[Uri], sandbox: &Path) -> bool {
+    /* Security Patch */
+    targets.iter().any(|target| {
+        workspace.find(target).is_some()
+            || target
+                .to_file_path()
+                .ok()
+                .and_then(|path| sandbox::confine(&path, sandbox))
+                .map_or(false, |path| path.is_file())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::test_diagnostics;
+    use std::env;
+    use std::fs;
+    use tempfile::tempdir;
+    use texlab_protocol::{Range, RangeExt};
+
+    fn diagnostics_in_sandbox(
+        sandbox: &Path,
+        files: &[(&str, &str)],
+        name: &str,
+    ) -> Vec<Diagnostic> {
+        test_diagnostics(files, name, |workspace, document| {
+            let provider = MissingFileDiagnosticsProvider {
+                sandbox: sandbox.to_owned(),
+            };
+            provider.get(workspace, document)
+        })
+    }
+
+    fn diagnostics(files: &[(&str, &str)], name: &str) -> Vec<Diagnostic> {
+        diagnostics_in_sandbox(&env::temp_dir(), files, name)
+    }
+
+    #[test]
+    fn missing_document() {
+        let diagnostics = diagnostics(
+            &[
+                ("foo.tex", "\\input{bar}\n\\include{texlab-missing-chapter}"),
+                ("bar.tex", ""),
+            ],
+            "foo.tex",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "File not found: texlab-missing-chapter"
+        );
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 9, 1, 31));
+    }
+
+    #[test]
+    fn missing_document_in_second_file() {
+        let files = [
+            ("foo.tex", "\\input{bar}"),
+            ("bar.tex", "\\input{texlab-missing-section}"),
+        ];
+        assert!(diagnostics(&files, "foo.tex").is_empty());
+        assert_eq!(diagnostics(&files, "bar.tex").len(), 1);
+    }
+
+    #[test]
+    fn missing_bibliography() {
+        let diagnostics = diagnostics(
+            &[("foo.tex", "\\bibliography{texlab-missing-bibliography}")],
+            "foo.tex",
+        );
+        assert_eq!(diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn file_on_disk() {
+        let directory = tempdir().unwrap();
+        fs::write(directory.path().join("bar.tex"), "").unwrap();
+
+        let path = directory.path().join("foo.tex");
+        let name = path.to_str().unwrap();
+        let files = [(name, "\\input{bar}")];
+        assert!(diagnostics_in_sandbox(directory.path(), &files, name).is_empty());
+    }
+
+    #[test]
+    fn file_outside_of_sandbox() {
+        let directory = tempdir().unwrap();
+        fs::write(directory.path().join("bar.tex"), "").unwrap();
+
+        let path = directory.path().join("foo.tex");
+        let name = path.to_str().unwrap();
+        let files = [(name, "\\input{bar}")];
+        let sandbox = directory.path().join("sandbox");
+        assert_eq!(diagnostics_in_sandbox(&sandbox, &files, name).len(), 1);
+    }
+
+    #[test]
+    fn ignore_packages_and_images() {
+        let diagnostics = diagnostics(
+            &[(
+                "foo.tex",
+                "\\usepackage{texlab-missing-package}\n\\includegraphics{texlab-missing-image}",
+            )],
+            "foo.tex",
+        );
+        assert!(diagnostics.is_empty());
+    }
+}
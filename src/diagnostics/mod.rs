@@ -1,33 +1,198 @@
 mod bibtex;
 // mod build;
+mod citation;
+mod external;
 mod latex;
-mod english;
+mod grammar_check;
+mod graphics;
+mod include_cycle;
+mod input;
+mod label;
+mod label_placement;
+mod limit;
+mod missing_file;
+mod package_conflict;
+mod parameter;
+mod spell_check;
+mod style;
+mod suppression;
+mod terminology;
+mod unicode;
+mod unused;
 
 pub use self::bibtex::BibtexErrorCode;
+pub use self::external::{external_checker_command, run_external_checker};
+pub use self::grammar_check::GrammarCheck;
+pub use self::input::LinterInput;
+pub use self::label_placement::LabelPlacement;
+pub use self::latex::run_linter;
+pub use self::limit::RAISE_DIAGNOSTICS_LIMIT_COMMAND;
+pub use self::package_conflict::{PackageConflict, PackageLoad};
+pub use self::spell_check::{extract_words, SpellCheck, ADD_TO_DICTIONARY_COMMAND};
+pub use self::suppression::Suppressions;
+pub use self::terminology::TermVariant;
+pub use self::unicode::UnicodeCharacter;
 
 use self::bibtex::BibtexDiagnosticsProvider;
 // use self::build::BuildDiagnosticsProvider;
-use self::english::EnglishDiagnosticsProvider;
+use self::citation::CitationDiagnosticsProvider;
+use self::external::ExternalCheckerProvider;
+use self::grammar_check::GrammarCheckProvider;
+use self::graphics::GraphicsDiagnosticsProvider;
+use self::include_cycle::IncludeCycleDiagnosticsProvider;
+use self::label::LabelDiagnosticsProvider;
+use self::label_placement::LabelPlacementDiagnosticsProvider;
 use self::latex::LatexDiagnosticsProvider;
-use texlab_protocol::Diagnostic;
-use texlab_workspace::Document;
+use self::limit::limit_diagnostics;
+use self::missing_file::MissingFileDiagnosticsProvider;
+use self::package_conflict::PackageConflictDiagnosticsProvider;
+use self::parameter::ParameterDiagnosticsProvider;
+use self::spell_check::SpellCheckProvider;
+use self::style::StyleDiagnosticsProvider;
+use self::terminology::TerminologyDiagnosticsProvider;
+use self::unicode::UnicodeDiagnosticsProvider;
+use self::unused::UnusedDiagnosticsProvider;
+use std::collections::HashSet;
+use texlab_protocol::{Diagnostic, Options, Range, Uri};
+use texlab_syntax::SyntaxTree;
+use texlab_workspace::{Document, Workspace};
 
 
+/// Blanks out the lines outside of the range while keeping the line numbers intact.
+pub fn lines_in_range(text: &str, range: Range) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(line, text)| {
+            let line = line as u64;
+            if line >= range.start.line && line <= range.end.line {
+                text
+            } else {
+                ""
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Runs the provider on the document with the given name
+/// in a test workspace that contains the files.
+#[cfg(test)]
+fn test_diagnostics<F>(files: &[(&str, &str)], name: &str, provider: F) -> Vec<Diagnostic>
+where
+    F: FnOnce(&Workspace, &Document) -> Vec<Diagnostic>,
+{
+    let mut builder = texlab_workspace::TestWorkspaceBuilder::new();
+    let mut uri = None;
+    for (file, text) in files {
+        let file_uri = builder.add_document(file, text);
+        if *file == name {
+            uri = Some(file_uri);
+        }
+    }
+    let document = builder.workspace.find(&uri.unwrap()).unwrap();
+    provider(&builder.workspace, &document)
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Default)]
 pub struct DiagnosticsManager {
     // pub build: BuildDiagnosticsProvider,
     pub latex: LatexDiagnosticsProvider,
     pub bibtex: BibtexDiagnosticsProvider,
-    pub english: EnglishDiagnosticsProvider,
+    pub spell_check: SpellCheckProvider,
+    pub grammar_check: GrammarCheckProvider,
+    pub external_checker: ExternalCheckerProvider,
+    pub include_cycle: IncludeCycleDiagnosticsProvider,
+    pub missing_file: MissingFileDiagnosticsProvider,
+    pub parameter: ParameterDiagnosticsProvider,
+    pub label_placement: LabelPlacementDiagnosticsProvider,
+    pub style: StyleDiagnosticsProvider,
+    pub graphics: GraphicsDiagnosticsProvider,
+    pub citation: CitationDiagnosticsProvider,
+    pub label: LabelDiagnosticsProvider,
+    pub package_conflict: PackageConflictDiagnosticsProvider,
+    pub unused: UnusedDiagnosticsProvider,
+    pub terminology: TerminologyDiagnosticsProvider,
+    pub unicode: UnicodeDiagnosticsProvider,
+    unlimited: HashSet<Uri>,
 }
 
 impl DiagnosticsManager {
-    pub fn get(&self, document: &Document) -> Vec<Diagnostic> {
+    pub fn get(
+        &self,
+        workspace: &Workspace,
+        document: &Document,
+        options: &Options,
+    ) -> Vec<Diagnostic> {
+        let style_options = options
+            .latex
+            .as_ref()
+            .and_then(|latex| latex.style.clone())
+            .unwrap_or_default();
+
         let mut diagnostics = Vec::new();
         // diagnostics.append(&mut self.build.get(document));
         diagnostics.append(&mut self.latex.get(document));
         diagnostics.append(&mut self.bibtex.get(document));
-        diagnostics.append(&mut self.english.get(document));
-        diagnostics
+        diagnostics.append(&mut self.spell_check.get(document));
+        diagnostics.append(&mut self.grammar_check.get(document));
+        diagnostics.append(&mut self.external_checker.get(document));
+        diagnostics.append(&mut self.include_cycle.get(workspace, document));
+        diagnostics.append(&mut self.missing_file.get(workspace, document));
+        diagnostics.append(&mut self.parameter.get(document));
+        diagnostics.append(&mut self.label_placement.get(document));
+        diagnostics.append(&mut self.style.get(document, &style_options));
+        diagnostics.append(&mut self.graphics.get(document));
+        diagnostics.append(&mut self.citation.get(workspace, document, options));
+        diagnostics.append(&mut self.label.get(workspace, document, options));
+        diagnostics.append(&mut self.package_conflict.get(workspace, document, options));
+        diagnostics.append(&mut self.unused.get(workspace, document, options));
+        diagnostics.append(&mut self.terminology.get(workspace, document, options));
+        diagnostics.append(&mut self.unicode.get(workspace, document, options));
+        if let SyntaxTree::Latex(_) = &document.tree {
+            Suppressions::parse(&document.text).apply(&mut diagnostics);
+        }
+
+        if self.unlimited.contains(&document.uri) {
+            diagnostics
+        } else {
+            let lint_options = options
+                .latex
+                .as_ref()
+                .and_then(|latex| latex.lint.clone())
+                .unwrap_or_default();
+            limit_diagnostics(diagnostics, lint_options.max_diagnostics())
+        }
+    }
+
+    /// Reports every diagnostic of the document until it is closed.
+    pub fn raise_limit(&mut self, uri: Uri) {
+        self.unlimited.insert(uri);
+    }
+
+    pub fn reset_limit(&mut self, uri: &Uri) {
+        self.unlimited.remove(uri);
+    }
+
+    /// Forgets the diagnostics of a document that no longer exists.
+    pub fn remove(&mut self, uri: &Uri) {
+        self.latex.remove(uri);
+        self.spell_check.remove(uri);
+        self.grammar_check.remove(uri);
+        self.external_checker.remove(uri);
+        self.graphics.remove(uri);
+        self.unlimited.remove(uri);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+
+    #[test]
+    fn keep_line_numbers() {
+        let text = "foo\nbar\nbaz\nqux";
+        let range = Range::new_simple(1, 2, 2, 0);
+        assert_eq!(lines_in_range(text, range), "\nbar\nbaz\n");
     }
 }
@@ -0,0 +1,373 @@
+use crate::magic_comment::MagicComments;
+use std::collections::HashSet;
+use std::mem;
+use std::sync::Arc;
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, Options, Uri};
+use texlab_syntax::*;
+use texlab_workspace::{Document, Workspace};
+
+/// Pairs of packages where the first package has to be loaded before the second one.
+const LOAD_ORDER: &[(&str, &str)] = &[
+    ("amsmath", "cleveref"),
+    ("hyperref", "cleveref"),
+    ("hyperref", "glossaries"),
+    ("hyperref", "bookmark"),
+];
+
+/// Packages that only work with the 8-bit engines.
+const LEGACY_PACKAGES: &[&str] = &["inputenc"];
+
+const UNICODE_ENGINES: &[(&str, &[&str])] = &[
+    ("xelatex", &["-xelatex", "-pdfxe", "-xe"]),
+    ("lualatex", &["-lualatex", "-pdflua", "-lua"]),
+];
+
+/// A package that is loaded by the preamble of a project.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PackageLoad {
+    pub uri: Uri,
+    pub command: Arc<LatexCommand>,
+    pub name: LatexToken,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PackageConflict {
+    LoadOrder {
+        package: PackageLoad,
+        required: PackageLoad,
+    },
+    OptionClash {
+        package: PackageLoad,
+        options: Vec<String>,
+    },
+    Engine {
+        package: PackageLoad,
+        engine: String,
+    },
+}
+
+impl PackageConflict {
+    /// Finds the conflicts of the packages that are loaded by the project of the document.
+    ///
+    /// The packages are visited in the order in which LaTeX loads them,
+    /// starting at the parent document and following the included files.
+    pub fn analyze(workspace: &Workspace, document: &Document, options: &Options) -> Vec<Self> {
+        let parent = match workspace
+            .find_parent(&document.uri, options)
+            .or_else(|| workspace.find(&document.uri))
+        {
+            Some(parent) => parent,
+            None => return Vec::new(),
+        };
+
+        let mut loads = Vec::new();
+        collect_packages(workspace, &parent, &mut HashSet::new(), &mut loads);
+
+        let mut conflicts = Vec::new();
+        for (required, name) in LOAD_ORDER {
+            let package = loads.iter().position(|load| load.name.text() == *name);
+            let required = loads.iter().position(|load| load.name.text() == *required);
+            if let (Some(package), Some(required)) = (package, required) {
+                if required > package {
+                    conflicts.push(PackageConflict::LoadOrder {
+                        package: loads[package].clone(),
+                        required: loads[required].clone(),
+                    });
+                }
+            }
+        }
+
+        for (i, load) in loads.iter().enumerate() {
+            if let Some(first) = loads[..i]
+                .iter()
+                .find(|first| first.name.text() == load.name.text())
+            {
+                let options: Vec<String> = load
+                    .options
+                    .iter()
+                    .filter(|option| !first.options.contains(option))
+                    .cloned()
+                    .collect();
+
+                if !options.is_empty() {
+                    conflicts.push(PackageConflict::OptionClash {
+                        package: load.clone(),
+                        options,
+                    });
+                }
+            }
+        }
+
+        if let Some(engine) = unicode_engine(&parent, options) {
+            for load in &loads {
+                if LEGACY_PACKAGES.contains(&load.name.text()) {
+                    conflicts.push(PackageConflict::Engine {
+                        package: load.clone(),
+                        engine: engine.to_owned(),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    pub fn package(&self) -> &PackageLoad {
+        match self {
+            PackageConflict::LoadOrder { package, .. }
+            | PackageConflict::OptionClash { package, .. }
+            | PackageConflict::Engine { package, .. } => package,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            PackageConflict::LoadOrder { package, required } => format!(
+                "The {} package has to be loaded after {}",
+                package.name.text(),
+                required.name.text()
+            ),
+            PackageConflict::OptionClash { package, options } => format!(
+                "Option clash for package {}: {} cannot be applied because the package is already loaded",
+                package.name.text(),
+                options.join(", ")
+            ),
+            PackageConflict::Engine { package, engine } => format!(
+                "The {} package should not be used with {}",
+                package.name.text(),
+                engine
+            ),
+        }
+    }
+}
+
+fn collect_packages(
+    workspace: &Workspace,
+    document: &Document,
+    visited: &mut HashSet<Uri>,
+    loads: &mut Vec<PackageLoad>,
+) {
+    if !visited.insert(document.uri.clone()) {
+        return;
+    }
+
+    if let SyntaxTree::Latex(tree) = &document.tree {
+        for include in &tree.includes {
+            match include.kind {
+                LatexIncludeKind::Package => {
+                    let options = package_options(&include.command);
+                    for name in include.paths() {
+                        loads.push(PackageLoad {
+                            uri: document.uri.clone(),
+                            command: Arc::clone(&include.command),
+                            name: name.clone(),
+                            options: options.clone(),
+                        });
+                    }
+                }
+                LatexIncludeKind::Latex => {
+                    for targets in &include.all_targets {
+                        if let Some(child) =
+                            targets.iter().find_map(|target| workspace.find(target))
+                        {
+                            collect_packages(workspace, &child, visited, loads);
+                        }
+                    }
+                }
+                LatexIncludeKind::Bibliography
+                | LatexIncludeKind::Image
+                | LatexIncludeKind::Svg
+                | LatexIncludeKind::Pdf
+                | LatexIncludeKind::Everything
+                | LatexIncludeKind::Class => (),
+            }
+        }
+    }
+}
+
+fn package_options(command: &LatexCommand) -> Vec<String> {
+    let mut options = Vec::new();
+    let mut option = String::new();
+    if let Some(group) = command.options.get(0) {
+        for child in &group.children {
+            match child {
+                LatexContent::Text(text) => {
+                    for word in &text.words {
+                        option.push_str(word.text());
+                    }
+                }
+                LatexContent::Comma(_) => {
+                    if !option.is_empty() {
+                        options.push(mem::replace(&mut option, String::new()));
+                    }
+                }
+                LatexContent::Group(_) | LatexContent::Command(_) | LatexContent::Math(_) => (),
+            }
+        }
+    }
+
+    if !option.is_empty() {
+        options.push(option);
+    }
+    options
+}
+
+/// Returns the Unicode engine that compiles the project, if any.
+pub(super) fn unicode_engine(parent: &Document, options: &Options) -> Option<&'static str> {
+    if let Some(engine) = MagicComments::parse(&parent.text).engine() {
+        return UNICODE_ENGINES
+            .iter()
+            .map(|(unicode_engine, _)| *unicode_engine)
+            .find(|unicode_engine| *unicode_engine == engine);
+    }
+
+    let build_options = options
+        .latex
+        .as_ref()
+        .and_then(|latex| latex.build.clone())
+        .unwrap_or_default();
+
+    let executable = build_options.executable().to_lowercase();
+    let args = build_options.args();
+    UNICODE_ENGINES
+        .iter()
+        .find(|(engine, flags)| {
+            executable == *engine || args.iter().any(|arg| flags.contains(&arg.as_str()))
+        })
+        .map(|(engine, _)| *engine)
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct PackageConflictDiagnosticsProvider;
+
+impl PackageConflictDiagnosticsProvider {
+    pub fn get(
+        self,
+        workspace: &Workspace,
+        document: &Document,
+        options: &Options,
+    ) -> Vec<Diagnostic> {
+        if let SyntaxTree::Bibtex(_) = &document.tree {
+            return Vec::new();
+        }
+
+        PackageConflict::analyze(workspace, document, options)
+            .into_iter()
+            .filter(|conflict| conflict.package().uri == document.uri)
+            .map(|conflict| Diagnostic {
+                source: Some("latex".into()),
+                range: conflict.package().name.range(),
+                message: conflict.message(),
+                severity: Some(DiagnosticSeverity::Warning),
+                code: None,
+                related_information: None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::test_diagnostics;
+    use texlab_protocol::{Range, RangeExt};
+
+    fn diagnostics(files: &[(&str, &str)], name: &str, options: &Options) -> Vec<Diagnostic> {
+        test_diagnostics(files, name, |workspace, document| {
+            PackageConflictDiagnosticsProvider.get(workspace, document, options)
+        })
+    }
+
+    #[test]
+    fn load_order() {
+        let diagnostics = diagnostics(
+            &[(
+                "foo.tex",
+                "\\usepackage{cleveref}\n\\usepackage{hyperref}\n\\begin{document}\\end{document}",
+            )],
+            "foo.tex",
+            &Options::default(),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 12, 0, 20));
+        assert_eq!(
+            diagnostics[0].message,
+            "The cleveref package has to be loaded after hyperref"
+        );
+    }
+
+    #[test]
+    fn load_order_across_files() {
+        let diagnostics = diagnostics(
+            &[
+                ("foo.tex", "\\usepackage{cleveref}"),
+                (
+                    "bar.tex",
+                    "\\input{foo}\n\\usepackage{amsmath}\n\\begin{document}\\end{document}",
+                ),
+            ],
+            "foo.tex",
+            &Options::default(),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "The cleveref package has to be loaded after amsmath"
+        );
+    }
+
+    #[test]
+    fn valid_order() {
+        let diagnostics = diagnostics(
+            &[(
+                "foo.tex",
+                "\\usepackage{amsmath,hyperref}\n\\usepackage{cleveref}",
+            )],
+            "foo.tex",
+            &Options::default(),
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn option_clash() {
+        let diagnostics = diagnostics(
+            &[(
+                "foo.tex",
+                "\\usepackage[a4paper]{geometry}\n\\usepackage[a4paper, margin=1in]{geometry}",
+            )],
+            "foo.tex",
+            &Options::default(),
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 33, 1, 41));
+        assert_eq!(
+            diagnostics[0].message,
+            "Option clash for package geometry: margin=1in cannot be applied because the package is already loaded"
+        );
+    }
+
+    #[test]
+    fn inputenc_with_xelatex() {
+        let files = [(
+            "foo.tex",
+            "% !TEX program = xelatex\n\\usepackage[utf8]{inputenc}",
+        )];
+        let diagnostics = diagnostics(&files, "foo.tex", &Options::default());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "The inputenc package should not be used with xelatex"
+        );
+    }
+
+    #[test]
+    fn pdflatex() {
+        let diagnostics = diagnostics(
+            &[("foo.tex", "\\usepackage[utf8]{inputenc}")],
+            "foo.tex",
+            &Options::default(),
+        );
+        assert!(diagnostics.is_empty());
+    }
+}
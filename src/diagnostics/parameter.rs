@@ -0,0 +1,66 @@
+use texlab_protocol::{Diagnostic, DiagnosticSeverity};
+use texlab_syntax::*;
+use texlab_workspace::Document;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ParameterDiagnosticsProvider;
+
+impl ParameterDiagnosticsProvider {
+    pub fn get(self, document: &Document) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            for definition in &tree.command_definitions {
+                let count = definition.argument_count();
+                for parameter in definition.parameters() {
+                    if parameter.number > count {
+                        diagnostics.push(Diagnostic {
+                            source: Some("latex".into()),
+                            range: parameter.range,
+                            message: format!(
+                                "Parameter #{} exceeds the number of arguments of {} ({})",
+                                parameter.number,
+                                definition.definition.name.text(),
+                                count
+                            ),
+                            severity: Some(DiagnosticSeverity::Warning),
+                            code: None,
+                            related_information: None,
+                        });
+                    }
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Range, RangeExt};
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn diagnostics(text: &str) -> Vec<Diagnostic> {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", text);
+        let document = builder.workspace.find(&uri).unwrap();
+        ParameterDiagnosticsProvider.get(&document)
+    }
+
+    #[test]
+    fn exceeding_parameter() {
+        let diagnostics = diagnostics("\\newcommand{\\foo}[1]{#1 #2}");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 24, 0, 26));
+    }
+
+    #[test]
+    fn valid_parameters() {
+        assert!(diagnostics("\\newcommand{\\foo}[2]{#1 #2}").is_empty());
+    }
+
+    #[test]
+    fn without_arguments() {
+        assert_eq!(diagnostics("\\newcommand{\\foo}{#1}").len(), 1);
+    }
+}
@@ -0,0 +1,1107 @@
+use super::latex::LINTER_TIMEOUT;
+use super::{lines_in_range, LinterInput};
+use crate::action::LintReason;
+use crate::magic_comment::MagicComments;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::sandbox::{self, SANDBOX_DIRECTORY};
+use texlab_workspace::Document;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::time;
+
+const EXCLUDED_ENVIRONMENTS: &[&str] =
+    &["verbatim", "lstlisting", "minted", "comment", "tikzpicture"];
+
+pub(super) const SOURCE: &str = "Spell Checker";
+
+const FIX_ALL_SPELLING_KIND: &str = "source.fixAll.spelling";
+
+pub const ADD_TO_DICTIONARY_COMMAND: &str = "texlab.addToDictionary";
+
+const EXCLUDED_COMMANDS: &[&str] = &[
+    "\\begin",
+    "\\end",
+    "\\documentclass",
+    "\\url",
+    "\\href",
+    "\\texttt",
+    "\\verb",
+    "\\includegraphics",
+    "\\bibliographystyle",
+    "\\newcommand",
+    "\\renewcommand",
+];
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct SpellingError {
+    range: Range,
+    word: String,
+    suggestions: Vec<String>,
+}
+
+impl SpellingError {
+    fn to_diagnostic(&self) -> Diagnostic {
+        let message = match self.suggestions.first() {
+            Some(suggestion) => "Maybe a spelling error, suggestion: ".to_owned() + suggestion,
+            None => "Maybe a spelling error".to_owned(),
+        };
+
+        Diagnostic {
+            source: Some(SOURCE.into()),
+            code: None,
+            message,
+            severity: Some(DiagnosticSeverity::Information),
+            range: self.range,
+            related_information: None,
+        }
+    }
+}
+
+/// The lines that differ between two versions of a document.
+///
+/// The ends are exclusive and refer to the old and the new text respectively.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct LineChange {
+    start: u64,
+    old_end: u64,
+    new_end: u64,
+}
+
+impl LineChange {
+    fn new(old_text: &str, new_text: &str) -> Option<Self> {
+        let old_lines: Vec<&str> = old_text.split('\n').collect();
+        let new_lines: Vec<&str> = new_text.split('\n').collect();
+        let prefix = old_lines
+            .iter()
+            .zip(&new_lines)
+            .take_while(|(old, new)| old == new)
+            .count();
+        if prefix == old_lines.len() && prefix == new_lines.len() {
+            return None;
+        }
+
+        let suffix = old_lines
+            .iter()
+            .rev()
+            .zip(new_lines.iter().rev())
+            .take(old_lines.len().min(new_lines.len()) - prefix)
+            .take_while(|(old, new)| old == new)
+            .count();
+
+        Some(Self {
+            start: prefix as u64,
+            old_end: (old_lines.len() - suffix) as u64,
+            new_end: (new_lines.len() - suffix) as u64,
+        })
+    }
+
+    /// Maps a line of the old text to the new text.
+    ///
+    /// Lines inside of the change are moved to its end if the change removed them.
+    fn shift(self, line: u64) -> u64 {
+        if line >= self.old_end {
+            line - self.old_end + self.new_end
+        } else {
+            line.min(self.new_end)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpellCheckProvider {
+    errors_by_uri: HashMap<Uri, Vec<SpellingError>>,
+    notices_by_uri: HashMap<Uri, Diagnostic>,
+    changed_lines_by_uri: HashMap<Uri, (u64, u64)>,
+    dictionary: HashSet<String>,
+    last_lint_time: u64,
+    sandbox: PathBuf,
+}
+
+impl Default for SpellCheckProvider {
+    fn default() -> Self {
+        Self {
+            errors_by_uri: HashMap::new(),
+            notices_by_uri: HashMap::new(),
+            changed_lines_by_uri: HashMap::new(),
+            dictionary: HashSet::new(),
+            last_lint_time: 0,
+            sandbox: PathBuf::from(SANDBOX_DIRECTORY),
+        }
+    }
+}
+
+impl SpellCheckProvider {
+    pub fn get(&self, document: &Document) -> Vec<Diagnostic> {
+        self.errors(document)
+            .map(SpellingError::to_diagnostic)
+            .chain(self.notices_by_uri.get(&document.uri).cloned())
+            .collect()
+    }
+
+    fn errors<'a>(&'a self, document: &Document) -> impl Iterator<Item = &'a SpellingError> {
+        self.errors_by_uri
+            .get(&document.uri)
+            .into_iter()
+            .flatten()
+            .filter(move |error| !self.dictionary.contains(&error.word))
+    }
+
+    /// Words of the personal dictionary are no longer reported.
+    pub fn set_dictionary(&mut self, words: &[String]) {
+        self.dictionary = words.iter().cloned().collect();
+    }
+
+    /// Creates quick fixes that add the selected misspelled words to the personal dictionary.
+    pub fn add_to_dictionary(&self, document: &Document, range: Range) -> Vec<CodeAction> {
+        let mut words = Vec::new();
+        let mut actions = Vec::new();
+        for error in self.errors(document) {
+            let is_selected =
+                range.contains(error.range.start) || error.range.contains(range.start);
+            if !is_selected || words.contains(&error.word) {
+                continue;
+            }
+
+            words.push(error.word.clone());
+            let title = format!("Add \"{}\" to dictionary", error.word);
+            actions.push(CodeAction {
+                title: title.clone(),
+                kind: Some(code_action_kind::QUICKFIX.into()),
+                diagnostics: Some(vec![error.to_diagnostic()]),
+                command: Some(texlab_protocol::Command {
+                    title,
+                    command: ADD_TO_DICTIONARY_COMMAND.into(),
+                    arguments: Some(vec![error.word.clone().into()]),
+                }),
+                ..CodeAction::default()
+            });
+        }
+        actions
+    }
+
+    /// Creates quick fixes that replace the selected misspelled words with the suggestions
+    /// of the spell checker.
+    pub fn suggestions(&self, document: &Document, range: Range) -> Vec<CodeAction> {
+        let mut actions = Vec::new();
+        for error in self.errors(document) {
+            let is_selected =
+                range.contains(error.range.start) || error.range.contains(range.start);
+            if !is_selected || CharStream::extract(&document.text, error.range) != error.word {
+                continue;
+            }
+
+            for suggestion in &error.suggestions {
+                let mut changes = HashMap::new();
+                changes.insert(
+                    document.uri.clone().into(),
+                    vec![TextEdit::new(error.range, suggestion.clone())],
+                );
+                actions.push(CodeAction {
+                    title: format!("Replace \"{}\" with \"{}\"", error.word, suggestion),
+                    kind: Some(code_action_kind::QUICKFIX.into()),
+                    diagnostics: Some(vec![error.to_diagnostic()]),
+                    edit: Some(WorkspaceEdit::new(changes)),
+                    ..CodeAction::default()
+                });
+            }
+        }
+        actions
+    }
+
+    /// Creates a source action that replaces every misspelled word of the document
+    /// with the first suggestion of the spell checker.
+    ///
+    /// Words that were edited since the last check are left untouched.
+    pub fn fix_all(&self, document: &Document) -> Option<CodeAction> {
+        let edits: Vec<TextEdit> = self
+            .errors(document)
+            .filter(|error| CharStream::extract(&document.text, error.range) == error.word)
+            .filter_map(|error| {
+                let suggestion = error.suggestions.first()?;
+                Some(TextEdit::new(error.range, suggestion.clone()))
+            })
+            .collect();
+
+        if edits.is_empty() {
+            return None;
+        }
+
+        let mut changes = HashMap::new();
+        changes.insert(document.uri.clone().into(), edits);
+        Some(CodeAction {
+            title: "Fix all spelling errors in file".into(),
+            kind: Some(FIX_ALL_SPELLING_KIND.into()),
+            edit: Some(WorkspaceEdit::new(changes)),
+            ..CodeAction::default()
+        })
+    }
+
+    /// Returns the check of the document unless the spell checker
+    /// already ran within the configured delay.
+    ///
+    /// Saving the document always triggers the spell checker.
+    pub fn update(
+        &mut self,
+        document: &Document,
+        reason: LintReason,
+        options: &LatexSpellCheckOptions,
+    ) -> Option<SpellCheck> {
+        let current_time = SystemTime::now();
+        let since_the_epoch = current_time.duration_since(UNIX_EPOCH).expect("Time went backwards");
+        let current_timestamp = since_the_epoch.as_secs();
+        let delay = options.delay();
+        if reason == LintReason::Save {
+            self.last_lint_time = current_timestamp;
+            self.check(document, options)
+        } else if current_timestamp > self.last_lint_time + delay {
+            self.last_lint_time = current_timestamp;
+            self.check_changes(document, options)
+        } else {
+            None
+        }
+    }
+
+    /// Moves the results of the last check along with the edited lines
+    /// and remembers the lines that need to be checked again.
+    pub fn track_change(&mut self, uri: &Uri, old_text: &str, new_text: &str) {
+        let errors = match self.errors_by_uri.get_mut(uri) {
+            Some(errors) => errors,
+            None => return,
+        };
+
+        let change = match LineChange::new(old_text, new_text) {
+            Some(change) => change,
+            None => return,
+        };
+
+        errors.retain(|error| {
+            let line = error.range.start.line;
+            line < change.start || line >= change.old_end
+        });
+        for error in errors.iter_mut() {
+            error.range.start.line = change.shift(error.range.start.line);
+            error.range.end.line = change.shift(error.range.end.line);
+        }
+
+        let lines = match self.changed_lines_by_uri.get(uri) {
+            Some((start, end)) => (
+                change.shift(*start).min(change.start),
+                change.shift(*end).max(change.new_end),
+            ),
+            None => (change.start, change.new_end),
+        };
+        self.changed_lines_by_uri.insert(uri.clone(), lines);
+    }
+
+    /// Returns the check of the whole document.
+    pub fn check(
+        &mut self,
+        document: &Document,
+        options: &LatexSpellCheckOptions,
+    ) -> Option<SpellCheck> {
+        self.changed_lines_by_uri.remove(&document.uri);
+        self.prepare(document, None, options)
+    }
+
+    /// Returns the check of the paragraphs that changed since the last check,
+    /// so that the results of the other lines can be kept.
+    ///
+    /// Large documents whose input is truncated are always checked as a whole.
+    fn check_changes(
+        &mut self,
+        document: &Document,
+        options: &LatexSpellCheckOptions,
+    ) -> Option<SpellCheck> {
+        let uri = &document.uri;
+        let is_incremental =
+            self.errors_by_uri.contains_key(uri) && !self.notices_by_uri.contains_key(uri);
+        if !is_incremental {
+            return self.check(document, options);
+        }
+
+        let (start, end) = self.changed_lines_by_uri.remove(uri)?;
+        let range = paragraph_range(&document.text, start, end);
+        self.prepare(document, Some(range), options)
+    }
+
+    /// Returns the check of the lines of the range,
+    /// whose results do not replace the results of the last check.
+    pub fn check_range(
+        &self,
+        document: &Document,
+        range: Range,
+        options: &LatexSpellCheckOptions,
+    ) -> Option<SpellCheck> {
+        self.prepare(document, Some(range), options)
+    }
+
+    fn prepare(
+        &self,
+        document: &Document,
+        range: Option<Range>,
+        options: &LatexSpellCheckOptions,
+    ) -> Option<SpellCheck> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return None,
+        };
+
+        let (words, notice) = words_to_check(tree, &document.text, range, options);
+        Some(SpellCheck {
+            uri: document.uri.clone(),
+            range,
+            words,
+            languages: languages(document, options),
+            search_path: dictionary_search_path(options, &self.sandbox, env::var_os("DICPATH")),
+            notice,
+        })
+    }
+
+    /// Stores the results of a check of the whole document or of its changed paragraphs.
+    pub fn set(&mut self, result: SpellCheckResult) {
+        let SpellCheckResult {
+            uri,
+            range,
+            errors,
+            notice,
+        } = result;
+
+        let range = match range {
+            Some(range) => range,
+            None => {
+                self.errors_by_uri
+                    .insert(uri.clone(), errors.unwrap_or_default());
+                match notice {
+                    Some(notice) => self.notices_by_uri.insert(uri, notice),
+                    None => self.notices_by_uri.remove(&uri),
+                };
+                return;
+            }
+        };
+
+        let new_errors = match errors {
+            Some(errors) => errors,
+            None => return,
+        };
+
+        let errors = self.errors_by_uri.entry(uri).or_default();
+        errors.retain(|error| {
+            let line = error.range.start.line;
+            line < range.start.line || line > range.end.line
+        });
+        errors.extend(new_errors);
+        errors.sort_by_key(|error| (error.range.start.line, error.range.start.character));
+    }
+
+    /// Converts the results of a check into diagnostics without storing them.
+    pub fn diagnostics(&self, result: &SpellCheckResult) -> Vec<Diagnostic> {
+        result
+            .errors
+            .iter()
+            .flatten()
+            .filter(|error| !self.dictionary.contains(&error.word))
+            .map(SpellingError::to_diagnostic)
+            .chain(result.notice.clone())
+            .collect()
+    }
+
+    pub fn remove(&mut self, uri: &Uri) {
+        self.errors_by_uri.remove(uri);
+        self.notices_by_uri.remove(uri);
+        self.changed_lines_by_uri.remove(uri);
+    }
+}
+
+/// The words of a document that are checked with `hunspell`.
+///
+/// The spell checker runs without holding the diagnostics manager,
+/// so the provider only prepares the checks and stores their results.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpellCheck {
+    pub uri: Uri,
+    range: Option<Range>,
+    words: Vec<LatexToken>,
+    languages: Vec<String>,
+    search_path: Option<OsString>,
+    notice: Option<Diagnostic>,
+}
+
+impl SpellCheck {
+    pub async fn run(self) -> SpellCheckResult {
+        let errors = lint(&self.words, &self.languages, self.search_path).await;
+        SpellCheckResult {
+            uri: self.uri,
+            range: self.range,
+            errors,
+            notice: self.notice,
+        }
+    }
+}
+
+/// The misspelled words that a spell check found.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SpellCheckResult {
+    uri: Uri,
+    range: Option<Range>,
+    errors: Option<Vec<SpellingError>>,
+    notice: Option<Diagnostic>,
+}
+
+/// Extends the changed lines to the paragraphs around them.
+///
+/// The range includes its last line.
+fn paragraph_range(text: &str, start: u64, end: u64) -> Range {
+    let lines: Vec<&str> = text.split('\n').collect();
+    let is_blank = |line: usize| lines[line].trim().is_empty();
+    let mut start = (start as usize).min(lines.len() - 1);
+    let mut end = (end as usize).max(start + 1).min(lines.len());
+    while start > 0 && !is_blank(start - 1) {
+        start -= 1;
+    }
+    while end < lines.len() && !is_blank(end) {
+        end += 1;
+    }
+    Range::new_simple(start as u64, 0, (end - 1) as u64, 0)
+}
+
+/// Returns `true` if the dictionary is a plain name like `en_US` and not a path.
+fn is_dictionary_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Returns the dictionary of the `% !TeX spellcheck` comment or the configured dictionaries.
+///
+/// Dictionaries are only selected by name, so that `hunspell` cannot be pointed
+/// to arbitrary files.
+fn languages(document: &Document, options: &LatexSpellCheckOptions) -> Vec<String> {
+    /* Security Patch */
+    let languages: Vec<String> = MagicComments::parse(&document.text)
+        .spellcheck
+        .filter(|language| is_dictionary_name(language))
+        .map(|language| vec![language])
+        .unwrap_or_else(|| options.languages())
+        .into_iter()
+        .filter(|language| is_dictionary_name(language))
+        .collect();
+
+    if languages.is_empty() {
+        LatexSpellCheckOptions::default().languages()
+    } else {
+        languages
+    }
+}
+
+/// Returns the ranges of the math, verbatim code, references and excluded commands.
+fn excluded_ranges(tree: &LatexSyntaxTree, options: &LatexSpellCheckOptions) -> Vec<Range> {
+    let excluded_environments = options.excluded_environments();
+    let excluded_commands = options.excluded_commands();
+
+    let mut ranges: Vec<Range> = tree
+        .env
+        .environments
+        .iter()
+        .filter(|env| {
+            env.left.is_math()
+                || env.left.name().map_or(false, |name| {
+                    EXCLUDED_ENVIRONMENTS.contains(&name.text())
+                        || excluded_environments
+                            .iter()
+                            .any(|excluded| excluded == name.text())
+                })
+        })
+        .map(|env| env.range())
+        .collect();
+
+    ranges.extend(tree.math.equations.iter().map(|eq| eq.range()));
+    ranges.extend(tree.math.inlines.iter().map(|inline| inline.range()));
+    ranges.extend(tree.citations.iter().map(|citation| citation.range()));
+    ranges.extend(tree.includes.iter().map(|include| include.range()));
+    ranges.extend(tree.structure.labels.iter().map(|label| label.range()));
+    ranges.extend(
+        tree.commands
+            .iter()
+            .filter(|command| {
+                let name = command.name.text();
+                EXCLUDED_COMMANDS.contains(&name)
+                    || excluded_commands.iter().any(|excluded| excluded == name)
+            })
+            .flat_map(|command| command.args.iter().map(|arg| arg.range())),
+    );
+    ranges
+}
+
+/// Replaces everything that should not be spell checked with whitespace.
+///
+/// Line breaks are kept so that the positions reported by the spell checker
+/// still refer to the original document.
+pub(super) fn extract_prose(
+    tree: &LatexSyntaxTree,
+    text: &str,
+    options: &LatexSpellCheckOptions,
+) -> String {
+    let mut lines: Vec<Vec<char>> = text
+        .split('\n')
+        .map(|line| line.chars().collect())
+        .collect();
+    for range in excluded_ranges(tree, options) {
+        for line in range.start.line..=range.end.line {
+            if let Some(chars) = lines.get_mut(line as usize) {
+                let start = if line == range.start.line {
+                    range.start.character as usize
+                } else {
+                    0
+                };
+                let end = if line == range.end.line {
+                    (range.end.character as usize).min(chars.len())
+                } else {
+                    chars.len()
+                };
+                for c in chars.iter_mut().take(end).skip(start) {
+                    if !c.is_whitespace() {
+                        *c = ' ';
+                    }
+                }
+            }
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|line| line.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Collects the words of the text nodes, leaving out command names and optional arguments.
+#[derive(Debug, Default)]
+struct WordCollector {
+    words: Vec<LatexToken>,
+}
+
+impl LatexVisitor for WordCollector {
+    fn visit_root(&mut self, root: Arc<LatexRoot>) {
+        LatexWalker::walk_root(self, root);
+    }
+
+    fn visit_group(&mut self, group: Arc<LatexGroup>) {
+        LatexWalker::walk_group(self, group);
+    }
+
+    fn visit_command(&mut self, command: Arc<LatexCommand>) {
+        for arg in &command.args {
+            self.visit_group(Arc::clone(arg));
+        }
+    }
+
+    fn visit_text(&mut self, text: Arc<LatexText>) {
+        self.words.extend(text.words.iter().cloned());
+    }
+
+    fn visit_comma(&mut self, comma: Arc<LatexComma>) {
+        LatexWalker::walk_comma(self, comma);
+    }
+
+    fn visit_math(&mut self, math: Arc<LatexMath>) {
+        LatexWalker::walk_math(self, math);
+    }
+}
+
+/// Returns the words of the prose outside of math, verbatim code and excluded commands.
+pub fn extract_words(tree: &LatexSyntaxTree, options: &LatexSpellCheckOptions) -> Vec<LatexToken> {
+    let excluded_ranges = excluded_ranges(tree, options);
+    let mut collector = WordCollector::default();
+    collector.visit_root(Arc::clone(&tree.root));
+    collector
+        .words
+        .into_iter()
+        .filter(|word| word.text().chars().any(char::is_alphabetic))
+        .filter(|word| {
+            let start = word.start();
+            !excluded_ranges
+                .iter()
+                .any(|range| range.start <= start && start < range.end)
+        })
+        .collect()
+}
+
+/// Returns the words of the lines in the range that fit into the input size
+/// and the notice about the skipped lines of a large document.
+fn words_to_check(
+    tree: &LatexSyntaxTree,
+    text: &str,
+    range: Option<Range>,
+    options: &LatexSpellCheckOptions,
+) -> (Vec<LatexToken>, Option<Diagnostic>) {
+    let mut prose = extract_prose(tree, text, options);
+    if let Some(range) = range {
+        prose = lines_in_range(&prose, range);
+    }
+
+    let input = LinterInput::new(&prose, options.max_input_size());
+    let words = extract_words(tree, options)
+        .into_iter()
+        .filter(|word| {
+            let line = word.start().line;
+            let is_in_range = range.map_or(true, |range| {
+                line >= range.start.line && line <= range.end.line
+            });
+            let is_skipped = input
+                .skipped_lines
+                .map_or(false, |(start, end)| line >= start && line < end);
+            is_in_range && !is_skipped
+        })
+        .collect();
+    (words, input.notice(SOURCE))
+}
+
+/// Matches the misses of `hunspell -a`, e.g. `& Teh 2 0: The, Tea` or `# Texlab 0`.
+pub static LINE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new("^[&#] (\\S+)(?: ([0-9]+))? ([0-9]+)(?:: (.*))?$").unwrap());
+
+/// Prepends the configured dictionary directories to the search path of `hunspell`.
+///
+/// Only the directories inside of the sandbox are used.
+fn dictionary_search_path(
+    options: &LatexSpellCheckOptions,
+    sandbox: &Path,
+    search_path: Option<OsString>,
+) -> Option<OsString> {
+    /* Security Patch */
+    let mut paths: Vec<PathBuf> = options
+        .dictionary_paths()
+        .iter()
+        .filter_map(|path| sandbox::confine(path, sandbox))
+        .collect();
+    if paths.is_empty() {
+        return None;
+    }
+
+    if let Some(search_path) = search_path {
+        paths.extend(env::split_paths(&search_path));
+    }
+    env::join_paths(paths).ok()
+}
+
+/// Splits the comma-separated suggestions of a `hunspell` result.
+fn parse_suggestions(text: &str) -> Vec<String> {
+    text.split(", ")
+        .map(str::trim)
+        .filter(|suggestion| !suggestion.is_empty())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+/// Checks the words with `hunspell`, one word per line, without blocking the executor.
+///
+/// Every line starts with `^` so that words are never mistaken for commands of the pipe mode.
+/// The process is killed if it does not finish within the timeout.
+async fn lint(
+    words: &[LatexToken],
+    languages: &[String],
+    search_path: Option<OsString>,
+) -> Option<Vec<SpellingError>> {
+    if words.is_empty() {
+        return Some(Vec::new());
+    }
+
+    let mut command = Command::new("hunspell");
+    command.arg("-a").arg("-d").arg(languages.join(","));
+    if let Some(search_path) = search_path {
+        command.env("DICPATH", search_path);
+    }
+
+    let mut process = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .ok()?;
+
+    let mut feed = String::new();
+    for word in words {
+        feed.push('^');
+        feed.push_str(word.text());
+        feed.push('\n');
+    }
+
+    // The results are read while the words are written so that neither pipe can fill up.
+    let mut stdin = process.stdin.take()?;
+    let write = async move {
+        let _ = stdin.write_all(feed.as_bytes()).await;
+    };
+    let run = async {
+        let (_, output) = tokio::join!(write, process.wait_with_output());
+        output
+    };
+    let output = time::timeout(LINTER_TIMEOUT, run).await.ok()?.ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Some(parse_output(&stdout, words))
+}
+
+/// Maps the misses of `hunspell` back to the words of the document.
+///
+/// Each line of input is answered by its results and an empty line.
+fn parse_output(output: &str, words: &[LatexToken]) -> Vec<SpellingError> {
+    let mut errors = Vec::new();
+    let mut index = 0;
+    for line in output.lines().skip_while(|line| line.starts_with('@')) {
+        if line.is_empty() {
+            index += 1;
+            continue;
+        }
+
+        let word = match words.get(index) {
+            Some(word) => word,
+            None => break,
+        };
+
+        if let Some(captures) = LINE_REGEX.captures(line) {
+            let misspelled = &captures[1];
+            if let Some(offset) = word.text().find(misspelled) {
+                let start = word.start();
+                let character = start.character + word.text()[..offset].chars().count() as u64;
+                let length = misspelled.chars().count() as u64;
+                errors.push(SpellingError {
+                    range: Range::new_simple(start.line, character, start.line, character + length),
+                    word: misspelled.to_owned(),
+                    suggestions: captures
+                        .get(4)
+                        .map(|suggestions| parse_suggestions(suggestions.as_str()))
+                        .unwrap_or_default(),
+                });
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_distro::{Language, Resolver};
+
+    fn prose(text: &str, options: &LatexSpellCheckOptions) -> String {
+        let document = document(text);
+        match &document.tree {
+            SyntaxTree::Latex(tree) => extract_prose(tree, text, options),
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        }
+    }
+
+    fn document(text: &str) -> Document {
+        let uri = Uri::from_file_path(std::env::temp_dir().join("foo.tex")).unwrap();
+        Document::parse(
+            uri,
+            text.into(),
+            Language::Latex,
+            &Options::default(),
+            &Resolver::default(),
+        )
+    }
+
+    fn error(range: Range, word: &str, suggestion: &str) -> SpellingError {
+        SpellingError {
+            range,
+            word: word.into(),
+            suggestions: vec![suggestion.into()],
+        }
+    }
+
+    #[test]
+    fn fix_all_errors() {
+        let document = document("Teh quick fox jumpd over teh dog");
+        let mut provider = SpellCheckProvider::default();
+        provider.errors_by_uri.insert(
+            document.uri.clone(),
+            vec![
+                error(Range::new_simple(0, 0, 0, 3), "Teh", "The"),
+                error(Range::new_simple(0, 14, 0, 19), "jumpd", "jumped"),
+                error(Range::new_simple(0, 25, 0, 28), "teh", "the"),
+            ],
+        );
+
+        let action = provider.fix_all(&document).unwrap();
+        assert_eq!(action.kind, Some(FIX_ALL_SPELLING_KIND.into()));
+        let uri: Url = document.uri.clone().into();
+        let edits = &action.edit.unwrap().changes.unwrap()[&uri];
+        assert_eq!(
+            *edits,
+            vec![
+                TextEdit::new(Range::new_simple(0, 0, 0, 3), "The".into()),
+                TextEdit::new(Range::new_simple(0, 14, 0, 19), "jumped".into()),
+                TextEdit::new(Range::new_simple(0, 25, 0, 28), "the".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn fix_all_skips_stale_errors() {
+        let document = document("The quick fox");
+        let mut provider = SpellCheckProvider::default();
+        provider.errors_by_uri.insert(
+            document.uri.clone(),
+            vec![error(Range::new_simple(0, 0, 0, 3), "Teh", "The")],
+        );
+        assert!(provider.fix_all(&document).is_none());
+    }
+
+    #[test]
+    fn dictionary() {
+        let document = document("Teh texlab server");
+        let mut provider = SpellCheckProvider::default();
+        provider.errors_by_uri.insert(
+            document.uri.clone(),
+            vec![
+                error(Range::new_simple(0, 0, 0, 3), "Teh", "The"),
+                error(Range::new_simple(0, 4, 0, 10), "texlab", "textual"),
+            ],
+        );
+
+        let actions = provider.add_to_dictionary(&document, Range::new_simple(0, 5, 0, 5));
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].title, "Add \"texlab\" to dictionary");
+        let command = actions[0].command.as_ref().unwrap();
+        assert_eq!(command.command, ADD_TO_DICTIONARY_COMMAND);
+        assert_eq!(command.arguments, Some(vec!["texlab".into()]));
+
+        provider.set_dictionary(&["texlab".into()]);
+        let diagnostics = provider.get(&document);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 0, 0, 3));
+        assert!(provider
+            .add_to_dictionary(&document, Range::new_simple(0, 5, 0, 5))
+            .is_empty());
+    }
+
+    #[test]
+    fn default_exclusions() {
+        let text = "Foo $x$ \\cite{bar}\n\\begin{verbatim}\nbaz\n\\end{verbatim}";
+        let expected = "Foo               \n                \n   \n              ";
+        assert_eq!(prose(text, &LatexSpellCheckOptions::default()), expected);
+    }
+
+    #[test]
+    fn configured_exclusions() {
+        let text = "\\begin{foo}bar\\end{foo} \\baz{qux} quux";
+        let options = LatexSpellCheckOptions {
+            excluded_environments: Some(vec!["foo".into()]),
+            excluded_commands: Some(vec!["baz".into()]),
+            ..LatexSpellCheckOptions::default()
+        };
+        let expected = "                        \\baz      quux";
+        assert_eq!(prose(text, &options), expected);
+    }
+
+    #[test]
+    fn no_exclusions() {
+        let text = "\\section{Foo} bar";
+        let expected = "\\section{Foo} bar";
+        assert_eq!(prose(text, &LatexSpellCheckOptions::default()), expected);
+    }
+
+    fn words(text: &str) -> Vec<String> {
+        let document = document(text);
+        match &document.tree {
+            SyntaxTree::Latex(tree) => extract_words(tree, &LatexSpellCheckOptions::default())
+                .iter()
+                .map(|word| word.text().to_owned())
+                .collect(),
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn prose_words() {
+        let text = "Foo $x$ \\cite{bar} \\textbf{baz}[qux] \\\\\n\\begin{verbatim}\nquux\n\\end{verbatim} 42";
+        assert_eq!(words(text), vec!["Foo", "baz"]);
+    }
+
+    #[test]
+    fn hunspell_output() {
+        let document = document("Foo \\emph{Teh}, bar.\nTexlab");
+        let words = match &document.tree {
+            SyntaxTree::Latex(tree) => extract_words(tree, &LatexSpellCheckOptions::default()),
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        };
+        let output = "@(#) International Ispell Version 3.2.06 (but really Hunspell 1.7.0)\n\
+                      *\n\n& Teh 2 0: The, Tea\n\n*\n\n# Texlab 0\n\n";
+        assert_eq!(
+            parse_output(output, &words),
+            vec![
+                SpellingError {
+                    range: Range::new_simple(0, 10, 0, 13),
+                    word: "Teh".into(),
+                    suggestions: vec!["The".into(), "Tea".into()],
+                },
+                SpellingError {
+                    range: Range::new_simple(1, 0, 1, 6),
+                    word: "Texlab".into(),
+                    suggestions: Vec::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn dictionary_paths() {
+        let options = LatexSpellCheckOptions {
+            dictionary_paths: Some(vec!["/minio/foo".into()]),
+            ..LatexSpellCheckOptions::default()
+        };
+        let sandbox = Path::new(SANDBOX_DIRECTORY);
+        let expected = env::join_paths(vec!["/minio/foo", "/bar"]).ok();
+        assert_eq!(
+            dictionary_search_path(&options, sandbox, Some("/bar".into())),
+            expected
+        );
+        let options = LatexSpellCheckOptions::default();
+        assert_eq!(
+            dictionary_search_path(&options, sandbox, Some("/bar".into())),
+            None
+        );
+    }
+
+    #[test]
+    fn dictionary_paths_outside_of_sandbox() {
+        let options = LatexSpellCheckOptions {
+            dictionary_paths: Some(vec!["/etc".into(), "/minio/../tmp".into()]),
+            ..LatexSpellCheckOptions::default()
+        };
+        let sandbox = Path::new(SANDBOX_DIRECTORY);
+        assert_eq!(dictionary_search_path(&options, sandbox, None), None);
+    }
+
+    #[test]
+    fn dictionary_names() {
+        let options = LatexSpellCheckOptions {
+            languages: Some(vec!["../foo".into(), "de_DE".into()]),
+            ..LatexSpellCheckOptions::default()
+        };
+        let path = document("% !TeX spellcheck = /tmp/foo\nfoo");
+        assert_eq!(languages(&path, &options), vec!["de_DE"]);
+
+        let name = document("% !TeX spellcheck = en_GB\nfoo");
+        assert_eq!(languages(&name, &options), vec!["en_GB"]);
+    }
+
+    #[test]
+    fn parse_suggestion() {
+        let line = "& Rechtschreibfeher 2 1: Rechtschreibfehler, Rechtschreiber";
+        let captures = LINE_REGEX.captures(line).unwrap();
+        assert_eq!(&captures[1], "Rechtschreibfeher");
+        assert_eq!(
+            parse_suggestions(&captures[4]),
+            vec!["Rechtschreibfehler", "Rechtschreiber"]
+        );
+    }
+
+    #[test]
+    fn suggestions() {
+        let document = document("Teh quick fox");
+        let mut provider = SpellCheckProvider::default();
+        provider.errors_by_uri.insert(
+            document.uri.clone(),
+            vec![SpellingError {
+                range: Range::new_simple(0, 0, 0, 3),
+                word: "Teh".into(),
+                suggestions: vec!["The".into(), "Tea".into()],
+            }],
+        );
+
+        let actions = provider.suggestions(&document, Range::new_simple(0, 1, 0, 1));
+        let titles: Vec<&str> = actions.iter().map(|action| action.title.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "Replace \"Teh\" with \"The\"",
+                "Replace \"Teh\" with \"Tea\""
+            ]
+        );
+        let uri: Url = document.uri.clone().into();
+        let edits = &actions[1].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri];
+        assert_eq!(
+            *edits,
+            vec![TextEdit::new(Range::new_simple(0, 0, 0, 3), "Tea".into())]
+        );
+        assert!(provider
+            .suggestions(&document, Range::new_simple(0, 5, 0, 5))
+            .is_empty());
+    }
+
+    #[test]
+    fn line_changes() {
+        assert_eq!(LineChange::new("foo\nbar", "foo\nbar"), None);
+        assert_eq!(
+            LineChange::new("foo\nbar\nbaz", "foo\nqux\nquux\nbaz"),
+            Some(LineChange {
+                start: 1,
+                old_end: 2,
+                new_end: 3,
+            })
+        );
+        assert_eq!(
+            LineChange::new("foo\nfoo", "foo"),
+            Some(LineChange {
+                start: 1,
+                old_end: 2,
+                new_end: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn paragraphs() {
+        let text = "foo\nbar\n\nbaz\nqux\n\nquux";
+        assert_eq!(paragraph_range(text, 4, 5), Range::new_simple(3, 0, 4, 0));
+        assert_eq!(paragraph_range(text, 1, 1), Range::new_simple(0, 0, 1, 0));
+        assert_eq!(paragraph_range(text, 10, 10), Range::new_simple(6, 0, 6, 0));
+    }
+
+    #[test]
+    fn track_changes() {
+        let old_text = "Teh fox\n\nA dgo\n\nA cta";
+        let new_text = "Teh fox\n\nA dog\nand more\n\nA cta";
+        let document = document(old_text);
+        let mut provider = SpellCheckProvider::default();
+        provider.errors_by_uri.insert(
+            document.uri.clone(),
+            vec![
+                error(Range::new_simple(0, 0, 0, 3), "Teh", "The"),
+                error(Range::new_simple(2, 2, 2, 5), "dgo", "dog"),
+                error(Range::new_simple(4, 2, 4, 5), "cta", "cat"),
+            ],
+        );
+
+        provider.track_change(&document.uri, old_text, new_text);
+        let ranges: Vec<Range> = provider.errors_by_uri[&document.uri]
+            .iter()
+            .map(|error| error.range)
+            .collect();
+        assert_eq!(
+            ranges,
+            vec![Range::new_simple(0, 0, 0, 3), Range::new_simple(5, 2, 5, 5)]
+        );
+        assert_eq!(provider.changed_lines_by_uri[&document.uri], (2, 4));
+
+        provider.track_change(
+            &document.uri,
+            new_text,
+            "Teh fox\n\nA dog\nand more\n\nA cat",
+        );
+        assert_eq!(provider.changed_lines_by_uri[&document.uri], (2, 6));
+    }
+}
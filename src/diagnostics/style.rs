@@ -0,0 +1,95 @@
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, LatexStyleOptions, Range};
+use texlab_syntax::*;
+use texlab_workspace::Document;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct StyleDiagnosticsProvider;
+
+impl StyleDiagnosticsProvider {
+    pub fn get(self, document: &Document, options: &LatexStyleOptions) -> Vec<Diagnostic> {
+        let discouraged_commands = options.discouraged_commands();
+        if discouraged_commands.is_empty() {
+            return Vec::new();
+        }
+
+        let mut diagnostics = Vec::new();
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            for command in &tree.commands {
+                let name = command.name.text();
+                if discouraged_commands
+                    .iter()
+                    .any(|discouraged| discouraged == name)
+                {
+                    diagnostics.push(make_diagnostic(command.name.range(), name));
+                }
+            }
+
+            for inline in &tree.math.inlines {
+                let delimiter = inline.left.token.text();
+                if discouraged_commands
+                    .iter()
+                    .any(|discouraged| discouraged == delimiter)
+                {
+                    diagnostics.push(make_diagnostic(inline.left.range(), delimiter));
+                    diagnostics.push(make_diagnostic(inline.right.range(), delimiter));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+fn make_diagnostic(range: Range, name: &str) -> Diagnostic {
+    Diagnostic {
+        source: Some("latex".into()),
+        range,
+        message: format!("Use of discouraged command {}", name),
+        severity: Some(DiagnosticSeverity::Information),
+        code: None,
+        related_information: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn diagnostics(text: &str, discouraged_commands: &[&str]) -> Vec<Diagnostic> {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", text);
+        let document = builder.workspace.find(&uri).unwrap();
+        let options = LatexStyleOptions {
+            discouraged_commands: Some(
+                discouraged_commands
+                    .iter()
+                    .map(|name| (*name).to_owned())
+                    .collect(),
+            ),
+        };
+        StyleDiagnosticsProvider.get(&document, &options)
+    }
+
+    #[test]
+    fn discouraged_command() {
+        let diagnostics = diagnostics("{\\bf foo} \\textbf{bar} {\\it baz}", &["bf", "it"]);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 1, 0, 4));
+        assert_eq!(diagnostics[0].message, "Use of discouraged command \\bf");
+        assert_eq!(diagnostics[1].range, Range::new_simple(0, 24, 0, 27));
+    }
+
+    #[test]
+    fn discouraged_math_delimiter() {
+        let diagnostics = diagnostics("$x$ $$y$$", &["$$"]);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].range, Range::new_simple(0, 4, 0, 6));
+        assert_eq!(diagnostics[1].range, Range::new_simple(0, 7, 0, 9));
+    }
+
+    #[test]
+    fn nothing_discouraged() {
+        assert!(diagnostics("{\\bf foo} $$x$$", &[]).is_empty());
+    }
+}
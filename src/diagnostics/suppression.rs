@@ -0,0 +1,194 @@
+use super::spell_check::SOURCE as SPELL_CHECK_SOURCE;
+use texlab_protocol::{Diagnostic, NumberOrString};
+
+/// The diagnostics that are turned off by comments inside of the document:
+///
+/// - `% chktex-ignore-line` hides the diagnostics of its line.
+/// - `% spellcheck: off` and `% spellcheck: on` enclose a region without spelling errors.
+/// - `% texlab: disable=<code>,...` hides the given codes on its line
+///   or, if the comment is on a line of its own, until the end of the document.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Suppressions {
+    ignored_lines: Vec<u64>,
+    spell_check_regions: Vec<(u64, u64)>,
+    disabled_codes: Vec<DisabledCode>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct DisabledCode {
+    code: String,
+    start_line: u64,
+    end_line: u64,
+}
+
+impl Suppressions {
+    pub fn parse(text: &str) -> Self {
+        let mut suppressions = Self::default();
+        let mut spell_check_start = None;
+        for (line, text) in text.lines().enumerate() {
+            let line = line as u64;
+            let (index, comment) = match find_comment(text) {
+                Some(comment) => comment,
+                None => continue,
+            };
+
+            if comment.starts_with("chktex-ignore-line") {
+                suppressions.ignored_lines.push(line);
+            } else if let Some(value) = directive(comment, "spellcheck:") {
+                match value {
+                    "off" if spell_check_start.is_none() => spell_check_start = Some(line),
+                    "on" => {
+                        if let Some(start) = spell_check_start.take() {
+                            suppressions.spell_check_regions.push((start, line));
+                        }
+                    }
+                    _ => (),
+                }
+            } else if let Some(value) = directive(comment, "texlab:") {
+                if !value.starts_with("disable=") {
+                    continue;
+                }
+
+                let end_line = if text[..index].trim().is_empty() {
+                    u64::max_value()
+                } else {
+                    line
+                };
+                for code in value["disable=".len()..].split(',').map(str::trim) {
+                    if !code.is_empty() {
+                        suppressions.disabled_codes.push(DisabledCode {
+                            code: code.to_owned(),
+                            start_line: line,
+                            end_line,
+                        });
+                    }
+                }
+            }
+        }
+
+        if let Some(start) = spell_check_start {
+            suppressions
+                .spell_check_regions
+                .push((start, u64::max_value()));
+        }
+        suppressions
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ignored_lines.is_empty()
+            && self.spell_check_regions.is_empty()
+            && self.disabled_codes.is_empty()
+    }
+
+    pub fn is_suppressed(&self, diagnostic: &Diagnostic) -> bool {
+        let line = diagnostic.range.start.line;
+        if self.ignored_lines.contains(&line) {
+            return true;
+        }
+
+        if diagnostic.source.as_deref() == Some(SPELL_CHECK_SOURCE)
+            && self
+                .spell_check_regions
+                .iter()
+                .any(|(start, end)| line >= *start && line <= *end)
+        {
+            return true;
+        }
+
+        let code = match &diagnostic.code {
+            Some(NumberOrString::String(code)) => code.clone(),
+            Some(NumberOrString::Number(code)) => code.to_string(),
+            None => return false,
+        };
+        self.disabled_codes.iter().any(|disabled| {
+            disabled.code == code && line >= disabled.start_line && line <= disabled.end_line
+        })
+    }
+
+    /// Removes the diagnostics that are turned off.
+    pub fn apply(&self, diagnostics: &mut Vec<Diagnostic>) {
+        if !self.is_empty() {
+            diagnostics.retain(|diagnostic| !self.is_suppressed(diagnostic));
+        }
+    }
+}
+
+/// Returns the position of the `%` sign and the text of the comment on the given line.
+fn find_comment(line: &str) -> Option<(usize, &str)> {
+    let mut is_escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            '\\' => is_escaped = !is_escaped,
+            '%' if !is_escaped => {
+                let comment = line[index..].trim_start_matches('%').trim();
+                return Some((index, comment));
+            }
+            _ => is_escaped = false,
+        }
+    }
+    None
+}
+
+fn directive<'a>(comment: &'a str, key: &str) -> Option<&'a str> {
+    let prefix = comment.get(..key.len())?;
+    if prefix.eq_ignore_ascii_case(key) {
+        Some(comment[key.len()..].trim())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Range, RangeExt};
+
+    fn diagnostic(line: u64, source: &str, code: Option<&str>) -> Diagnostic {
+        Diagnostic {
+            source: Some(source.into()),
+            range: Range::new_simple(line, 0, line, 1),
+            message: String::new(),
+            severity: None,
+            code: code.map(|code| NumberOrString::String(code.into())),
+            related_information: None,
+        }
+    }
+
+    #[test]
+    fn ignore_line() {
+        let suppressions =
+            Suppressions::parse("foo\nbar % chktex-ignore-line\n\\% chktex-ignore-line");
+        assert!(!suppressions.is_suppressed(&diagnostic(0, "chktex", Some("8"))));
+        assert!(suppressions.is_suppressed(&diagnostic(1, "chktex", Some("8"))));
+        assert!(!suppressions.is_suppressed(&diagnostic(2, "chktex", Some("8"))));
+    }
+
+    #[test]
+    fn spell_check_region() {
+        let suppressions =
+            Suppressions::parse("foo\n% spellcheck: off\nbar\n% spellcheck: on\nbaz");
+        assert!(!suppressions.is_suppressed(&diagnostic(0, SPELL_CHECK_SOURCE, None)));
+        assert!(suppressions.is_suppressed(&diagnostic(2, SPELL_CHECK_SOURCE, None)));
+        assert!(!suppressions.is_suppressed(&diagnostic(2, "chktex", None)));
+        assert!(!suppressions.is_suppressed(&diagnostic(4, SPELL_CHECK_SOURCE, None)));
+    }
+
+    #[test]
+    fn unterminated_spell_check_region() {
+        let suppressions = Suppressions::parse("foo\n%spellcheck: off\nbar\nbaz");
+        assert!(suppressions.is_suppressed(&diagnostic(3, SPELL_CHECK_SOURCE, None)));
+    }
+
+    #[test]
+    fn disable_codes() {
+        let suppressions =
+            Suppressions::parse("foo\nbar % texlab: disable=8\n% texlab: disable=1, 24\nbaz");
+        assert!(!suppressions.is_suppressed(&diagnostic(0, "chktex", Some("8"))));
+        assert!(suppressions.is_suppressed(&diagnostic(1, "chktex", Some("8"))));
+        assert!(!suppressions.is_suppressed(&diagnostic(3, "chktex", Some("8"))));
+        assert!(!suppressions.is_suppressed(&diagnostic(1, "chktex", Some("24"))));
+        assert!(suppressions.is_suppressed(&diagnostic(3, "chktex", Some("24"))));
+        assert!(suppressions.is_suppressed(&diagnostic(3, "chktex", Some("1"))));
+        assert!(!suppressions.is_suppressed(&diagnostic(3, "chktex", None)));
+    }
+}
@@ -0,0 +1,348 @@
+use super::spell_check::extract_words;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::{Document, Workspace};
+
+/// British spellings and their American counterparts.
+const SPELLING_VARIANTS: &[(&str, &str)] = &[
+    ("analyse", "analyze"),
+    ("behaviour", "behavior"),
+    ("catalogue", "catalog"),
+    ("centre", "center"),
+    ("colour", "color"),
+    ("defence", "defense"),
+    ("favour", "favor"),
+    ("grey", "gray"),
+    ("honour", "honor"),
+    ("labelled", "labeled"),
+    ("labour", "labor"),
+    ("licence", "license"),
+    ("metre", "meter"),
+    ("modelling", "modeling"),
+    ("neighbour", "neighbor"),
+    ("optimise", "optimize"),
+    ("organise", "organize"),
+    ("recognise", "recognize"),
+    ("travelling", "traveling"),
+];
+
+/// Words that form a different term when they are joined with the next word,
+/// e.g. `may be` and `maybe`.
+const FUNCTION_WORDS: &[&str] = &[
+    "a", "all", "an", "any", "at", "be", "by", "can", "every", "for", "in", "into", "it", "may",
+    "no", "not", "of", "on", "out", "over", "per", "some", "the", "to", "up", "with",
+];
+
+/// A spelling of a term that differs from the spelling that the project uses most often.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TermVariant {
+    pub uri: Uri,
+    pub range: Range,
+    pub text: String,
+    pub dominant: String,
+}
+
+impl TermVariant {
+    /// Finds the inconsistent spellings in the documents that are related to the given document.
+    ///
+    /// Spaces and hyphens inside of a term as well as British and American spellings
+    /// are treated as variants of the same term.
+    pub fn analyze(workspace: &Workspace, document: &Document, options: &Options) -> Vec<Self> {
+        let latex_options = options.latex.clone().unwrap_or_default();
+        let terminology_options = latex_options.terminology.unwrap_or_default();
+        if !terminology_options.enabled() {
+            return Vec::new();
+        }
+
+        let spell_check_options = latex_options.spell_check.unwrap_or_default();
+        let groups = variant_groups(&terminology_options);
+        let mut occurrences_by_term: HashMap<String, Vec<Occurrence>> = HashMap::new();
+        for document in workspace.related_documents(&document.uri, options) {
+            if let SyntaxTree::Latex(tree) = &document.tree {
+                for occurrence in occurrences(&document, tree, &spell_check_options) {
+                    occurrences_by_term
+                        .entry(normalize(&occurrence.text, &groups))
+                        .or_default()
+                        .push(occurrence);
+                }
+            }
+        }
+
+        let mut variants = Vec::new();
+        for occurrences in occurrences_by_term.values() {
+            let mut counts: Vec<(String, usize)> = Vec::new();
+            for occurrence in occurrences {
+                let form = occurrence.text.to_lowercase();
+                match counts.iter_mut().find(|(other, _)| *other == form) {
+                    Some((_, count)) => *count += 1,
+                    None => counts.push((form, 1)),
+                }
+            }
+
+            if counts.len() < 2 {
+                continue;
+            }
+
+            let mut dominant = &counts[0];
+            for count in &counts[1..] {
+                if count.1 > dominant.1 {
+                    dominant = count;
+                }
+            }
+
+            for occurrence in occurrences {
+                if occurrence.text.to_lowercase() != dominant.0 {
+                    variants.push(Self {
+                        uri: occurrence.uri.clone(),
+                        range: occurrence.range,
+                        text: occurrence.text.clone(),
+                        dominant: dominant.0.clone(),
+                    });
+                }
+            }
+        }
+
+        variants.sort_by_key(|variant| {
+            (
+                variant.uri.as_str().to_owned(),
+                variant.range.start.line,
+                variant.range.start.character,
+            )
+        });
+        variants
+    }
+
+    /// The dominant spelling with the capitalization of the variant.
+    pub fn replacement(&self) -> String {
+        let mut chars = self.dominant.chars();
+        match (self.text.chars().next(), chars.next()) {
+            (Some(first), Some(c)) if first.is_uppercase() => {
+                c.to_uppercase().chain(chars).collect()
+            }
+            _ => self.dominant.clone(),
+        }
+    }
+
+    fn to_diagnostic(&self) -> Diagnostic {
+        Diagnostic {
+            source: Some("latex".into()),
+            range: self.range,
+            message: format!(
+                "Inconsistent spelling: \"{}\" is mostly written as \"{}\"",
+                self.text,
+                self.replacement()
+            ),
+            severity: Some(DiagnosticSeverity::Information),
+            code: None,
+            related_information: None,
+        }
+    }
+}
+
+/// Reports the spellings of a term that deviate from the rest of the project.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct TerminologyDiagnosticsProvider;
+
+impl TerminologyDiagnosticsProvider {
+    pub fn get(
+        self,
+        workspace: &Workspace,
+        document: &Document,
+        options: &Options,
+    ) -> Vec<Diagnostic> {
+        TermVariant::analyze(workspace, document, options)
+            .into_iter()
+            .filter(|variant| variant.uri == document.uri)
+            .map(|variant| variant.to_diagnostic())
+            .collect()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Occurrence {
+    uri: Uri,
+    range: Range,
+    text: String,
+}
+
+/// Returns the words of the prose and the pairs of adjacent words
+/// that could also be written as one word.
+fn occurrences(
+    document: &Document,
+    tree: &LatexSyntaxTree,
+    options: &LatexSpellCheckOptions,
+) -> Vec<Occurrence> {
+    let words: Vec<(String, Range)> = extract_words(tree, options)
+        .iter()
+        .filter_map(trim_word)
+        .collect();
+
+    let mut occurrences = Vec::new();
+    for (i, (text, range)) in words.iter().enumerate() {
+        occurrences.push(Occurrence {
+            uri: document.uri.clone(),
+            range: *range,
+            text: text.clone(),
+        });
+
+        if let Some((next_text, next_range)) = words.get(i + 1) {
+            let gap = CharStream::extract(&document.text, Range::new(range.end, next_range.start));
+            let is_pair = !gap.is_empty()
+                && gap.chars().all(char::is_whitespace)
+                && gap.matches('\n').count() <= 1
+                && is_compound_part(text)
+                && is_compound_part(next_text);
+            if is_pair {
+                occurrences.push(Occurrence {
+                    uri: document.uri.clone(),
+                    range: Range::new(range.start, next_range.end),
+                    text: format!("{} {}", text, next_text),
+                });
+            }
+        }
+    }
+    occurrences
+}
+
+/// Strips the punctuation around a word.
+///
+/// Returns `None` if the word contains characters that are not part of a term.
+fn trim_word(word: &LatexToken) -> Option<(String, Range)> {
+    let text = word.text();
+    let leading = text.chars().take_while(|c| !c.is_alphanumeric()).count();
+    let trimmed: String = text.chars().skip(leading).collect();
+    let trimmed = trimmed.trim_end_matches(|c: char| !c.is_alphanumeric());
+    let is_term = trimmed.chars().any(char::is_alphabetic)
+        && !trimmed.contains("--")
+        && trimmed
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '\'');
+    if !is_term {
+        return None;
+    }
+
+    let start = Position::new(word.start().line, word.start().character + leading as u64);
+    let end = Position::new(start.line, start.character + trimmed.chars().count() as u64);
+    Some((trimmed.to_owned(), Range::new(start, end)))
+}
+
+fn is_compound_part(word: &str) -> bool {
+    word.chars().all(char::is_alphabetic) && !FUNCTION_WORDS.contains(&word.to_lowercase().as_str())
+}
+
+/// Maps the configured spellings to the term of their group.
+fn variant_groups(options: &LatexTerminologyOptions) -> HashMap<String, String> {
+    let mut groups = HashMap::new();
+    for group in options.variants() {
+        if let Some(first) = group.first() {
+            let term = normalize(first, &HashMap::new());
+            for variant in &group {
+                groups.insert(strip(variant), term.clone());
+            }
+        }
+    }
+    groups
+}
+
+/// Returns the term that a spelling belongs to.
+fn normalize(text: &str, groups: &HashMap<String, String>) -> String {
+    let key = strip(text);
+    if let Some(term) = groups.get(&key) {
+        return term.clone();
+    }
+
+    for (british, american) in SPELLING_VARIANTS {
+        if key == *british {
+            return (*american).to_owned();
+        } else if key.starts_with(british) && &key[british.len()..] == "s" {
+            return format!("{}s", american);
+        }
+    }
+    key
+}
+
+fn strip(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn variants(files: &[(&str, &str)], variants: Option<Vec<Vec<String>>>) -> Vec<TermVariant> {
+        let mut builder = TestWorkspaceBuilder::new();
+        for (name, text) in files {
+            builder.add_document(name, text);
+        }
+        let uri = builder.workspace.documents[0].uri.clone();
+        let document = builder.workspace.find(&uri).unwrap();
+        let options = Options {
+            latex: Some(LatexOptions {
+                terminology: Some(LatexTerminologyOptions {
+                    enabled: Some(true),
+                    variants,
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        TermVariant::analyze(&builder.workspace, &document, &options)
+    }
+
+    #[test]
+    fn compound_words() {
+        let variants = variants(
+            &[
+                ("foo.tex", "The dataset.\n\\input{bar}\nA data-set."),
+                ("bar.tex", "Data set and dataset."),
+            ],
+            None,
+        );
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0].text, "Data set");
+        assert_eq!(variants[0].range, Range::new_simple(0, 0, 0, 8));
+        assert_eq!(variants[0].replacement(), "Dataset");
+        assert_eq!(variants[1].text, "data-set");
+        assert_eq!(variants[1].range, Range::new_simple(2, 2, 2, 10));
+        assert_eq!(variants[1].dominant, "dataset");
+    }
+
+    #[test]
+    fn british_spelling() {
+        let variants = variants(&[("foo.tex", "color, colour, \\emph{color}")], None);
+        assert_eq!(variants.len(), 1);
+        assert_eq!(variants[0].text, "colour");
+        assert_eq!(variants[0].dominant, "color");
+    }
+
+    #[test]
+    fn configured_variants() {
+        let groups = vec![vec!["toolkit".into(), "toolbox".into()]];
+        let variants = variants(&[("foo.tex", "toolbox toolkit toolbox")], Some(groups));
+        assert_eq!(variants.len(), 1);
+        assert_eq!(variants[0].text, "toolkit");
+        assert_eq!(variants[0].dominant, "toolbox");
+    }
+
+    #[test]
+    fn function_words() {
+        let variants = variants(&[("foo.tex", "It may be. Maybe.")], None);
+        assert!(variants.is_empty());
+    }
+
+    #[test]
+    fn disabled() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", "color colour");
+        let document = builder.workspace.find(&uri).unwrap();
+        let diagnostics =
+            TerminologyDiagnosticsProvider.get(&builder.workspace, &document, &Options::default());
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,272 @@
+use super::package_conflict::unicode_engine;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::{Document, Workspace};
+
+/// Characters with their replacements inside of text and math mode.
+///
+/// Characters without a replacement for the current mode are left alone.
+const REPLACEMENTS: &[(char, Option<&str>, Option<&str>)] = &[
+    ('à', Some("\\`a"), None),
+    ('á', Some("\\'a"), None),
+    ('â', Some("\\^a"), None),
+    ('ã', Some("\\~a"), None),
+    ('ä', Some("\\\"a"), None),
+    ('å', Some("\\aa{}"), None),
+    ('æ', Some("\\ae{}"), None),
+    ('ç', Some("\\c{c}"), None),
+    ('è', Some("\\`e"), None),
+    ('é', Some("\\'e"), None),
+    ('ê', Some("\\^e"), None),
+    ('ë', Some("\\\"e"), None),
+    ('ì', Some("\\`i"), None),
+    ('í', Some("\\'i"), None),
+    ('î', Some("\\^i"), None),
+    ('ï', Some("\\\"i"), None),
+    ('ñ', Some("\\~n"), None),
+    ('ò', Some("\\`o"), None),
+    ('ó', Some("\\'o"), None),
+    ('ô', Some("\\^o"), None),
+    ('õ', Some("\\~o"), None),
+    ('ö', Some("\\\"o"), None),
+    ('ø', Some("\\o{}"), None),
+    ('œ', Some("\\oe{}"), None),
+    ('ß', Some("\\ss{}"), None),
+    ('ù', Some("\\`u"), None),
+    ('ú', Some("\\'u"), None),
+    ('û', Some("\\^u"), None),
+    ('ü', Some("\\\"u"), None),
+    ('ý', Some("\\'y"), None),
+    ('À', Some("\\`A"), None),
+    ('Á', Some("\\'A"), None),
+    ('Ä', Some("\\\"A"), None),
+    ('Å', Some("\\AA{}"), None),
+    ('Æ', Some("\\AE{}"), None),
+    ('Ç', Some("\\c{C}"), None),
+    ('È', Some("\\`E"), None),
+    ('É', Some("\\'E"), None),
+    ('Ñ', Some("\\~N"), None),
+    ('Ó', Some("\\'O"), None),
+    ('Ö', Some("\\\"O"), None),
+    ('Ø', Some("\\O{}"), None),
+    ('Œ', Some("\\OE{}"), None),
+    ('Ü', Some("\\\"U"), None),
+    ('\u{A0}', Some("~"), None),
+    ('\u{2013}', Some("--"), None),
+    ('\u{2014}', Some("---"), None),
+    ('\u{2018}', Some("`"), None),
+    ('\u{2019}', Some("'"), None),
+    ('\u{201C}', Some("``"), None),
+    ('\u{201D}', Some("''"), None),
+    ('\u{2026}', Some("\\ldots{}"), Some("\\ldots")),
+    ('«', Some("\\guillemotleft{}"), None),
+    ('»', Some("\\guillemotright{}"), None),
+    ('§', Some("\\S{}"), None),
+    ('¶', Some("\\P{}"), None),
+    ('©', Some("\\textcopyright{}"), None),
+    ('®', Some("\\textregistered{}"), None),
+    ('™', Some("\\texttrademark{}"), None),
+    ('€', Some("\\texteuro{}"), None),
+    ('°', Some("\\textdegree{}"), Some("^\\circ")),
+    ('×', Some("$\\times$"), Some("\\times")),
+    ('÷', Some("$\\div$"), Some("\\div")),
+    ('±', Some("$\\pm$"), Some("\\pm")),
+    ('·', Some("$\\cdot$"), Some("\\cdot")),
+    ('≤', Some("$\\leq$"), Some("\\leq")),
+    ('≥', Some("$\\geq$"), Some("\\geq")),
+    ('≠', Some("$\\neq$"), Some("\\neq")),
+    ('≈', Some("$\\approx$"), Some("\\approx")),
+    ('∞', Some("$\\infty$"), Some("\\infty")),
+    ('∈', Some("$\\in$"), Some("\\in")),
+    ('→', Some("$\\rightarrow$"), Some("\\to")),
+    ('←', Some("$\\leftarrow$"), Some("\\leftarrow")),
+    ('⇒', Some("$\\Rightarrow$"), Some("\\Rightarrow")),
+    ('α', Some("$\\alpha$"), Some("\\alpha")),
+    ('β', Some("$\\beta$"), Some("\\beta")),
+    ('γ', Some("$\\gamma$"), Some("\\gamma")),
+    ('δ', Some("$\\delta$"), Some("\\delta")),
+    ('ε', Some("$\\varepsilon$"), Some("\\varepsilon")),
+    ('θ', Some("$\\theta$"), Some("\\theta")),
+    ('λ', Some("$\\lambda$"), Some("\\lambda")),
+    ('μ', Some("$\\mu$"), Some("\\mu")),
+    ('π', Some("$\\pi$"), Some("\\pi")),
+    ('σ', Some("$\\sigma$"), Some("\\sigma")),
+    ('ω', Some("$\\omega$"), Some("\\omega")),
+    ('Δ', Some("$\\Delta$"), Some("\\Delta")),
+    ('Σ', Some("$\\Sigma$"), Some("\\Sigma")),
+    ('Ω', Some("$\\Omega$"), Some("\\Omega")),
+];
+
+/// A non-ASCII character that has a canonical LaTeX encoding.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UnicodeCharacter {
+    pub range: Range,
+    pub character: char,
+    pub replacement: String,
+}
+
+impl UnicodeCharacter {
+    /// Finds the characters that should be written as LaTeX commands.
+    ///
+    /// Projects that are compiled with XeLaTeX or LuaLaTeX accept UTF-8 input,
+    /// so nothing is reported for them.
+    pub fn analyze(workspace: &Workspace, document: &Document, options: &Options) -> Vec<Self> {
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return Vec::new(),
+        };
+
+        let unicode_options = options
+            .latex
+            .as_ref()
+            .and_then(|latex| latex.unicode.clone())
+            .unwrap_or_default();
+        if !unicode_options.enabled() {
+            return Vec::new();
+        }
+
+        let parent = workspace.find_parent(&document.uri, options);
+        let parent = parent.as_ref().map_or(document, |parent| &**parent);
+        if unicode_engine(parent, options).is_some() {
+            return Vec::new();
+        }
+
+        let allowed: Vec<char> = unicode_options
+            .allowed()
+            .iter()
+            .flat_map(|text| text.chars())
+            .collect();
+
+        let mut characters = Vec::new();
+        for (line, text) in document.text.lines().enumerate() {
+            let mut is_escaped = false;
+            for (character, c) in text.chars().enumerate() {
+                match c {
+                    '\\' => is_escaped = !is_escaped,
+                    '%' if !is_escaped => break,
+                    _ => is_escaped = false,
+                }
+
+                if c.is_ascii() || allowed.contains(&c) {
+                    continue;
+                }
+
+                let start = Position::new(line as u64, character as u64);
+                let replacement = REPLACEMENTS
+                    .iter()
+                    .find(|(other, _, _)| *other == c)
+                    .and_then(|(_, text, math)| {
+                        if tree.is_math_mode(start) {
+                            *math
+                        } else {
+                            *text
+                        }
+                    });
+
+                if let Some(replacement) = replacement {
+                    characters.push(Self {
+                        range: Range::new(start, Position::new(start.line, start.character + 1)),
+                        character: c,
+                        replacement: replacement.to_owned(),
+                    });
+                }
+            }
+        }
+        characters
+    }
+
+    fn to_diagnostic(&self) -> Diagnostic {
+        Diagnostic {
+            source: Some("latex".into()),
+            range: self.range,
+            message: format!(
+                "\"{}\" can be written as \"{}\"",
+                self.character, self.replacement
+            ),
+            severity: Some(DiagnosticSeverity::Information),
+            code: None,
+            related_information: None,
+        }
+    }
+}
+
+/// Reports the non-ASCII characters of documents that are compiled with an 8-bit engine.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct UnicodeDiagnosticsProvider;
+
+impl UnicodeDiagnosticsProvider {
+    pub fn get(
+        self,
+        workspace: &Workspace,
+        document: &Document,
+        options: &Options,
+    ) -> Vec<Diagnostic> {
+        UnicodeCharacter::analyze(workspace, document, options)
+            .iter()
+            .map(UnicodeCharacter::to_diagnostic)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn characters(text: &str, options: &Options) -> Vec<UnicodeCharacter> {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", text);
+        let document = builder.workspace.find(&uri).unwrap();
+        UnicodeCharacter::analyze(&builder.workspace, &document, options)
+    }
+
+    #[test]
+    fn text_and_math() {
+        let characters = characters("Café – 2×3, $2×3$ % é", &Options::default());
+        let replacements: Vec<&str> = characters
+            .iter()
+            .map(|character| character.replacement.as_str())
+            .collect();
+        assert_eq!(replacements, vec!["\\'e", "--", "$\\times$", "\\times"]);
+        assert_eq!(characters[0].range, Range::new_simple(0, 3, 0, 4));
+    }
+
+    #[test]
+    fn lualatex() {
+        let text = "% !TEX program = lualatex\nCafé";
+        assert!(characters(text, &Options::default()).is_empty());
+    }
+
+    #[test]
+    fn allowed_characters() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                unicode: Some(LatexUnicodeOptions {
+                    enabled: None,
+                    allowed: Some(vec!["é–".into()]),
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        let characters = characters("Café – ×", &options);
+        assert_eq!(characters.len(), 1);
+        assert_eq!(characters[0].character, '×');
+    }
+
+    #[test]
+    fn disabled() {
+        let options = Options {
+            latex: Some(LatexOptions {
+                unicode: Some(LatexUnicodeOptions {
+                    enabled: Some(false),
+                    allowed: None,
+                }),
+                ..LatexOptions::default()
+            }),
+            bibtex: None,
+        };
+        assert!(characters("Café", &options).is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::HashSet;
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, Options};
+use texlab_syntax::*;
+use texlab_workspace::{Document, Workspace};
+
+/// Hints at labels that are never referenced and entries that are never cited.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct UnusedDiagnosticsProvider;
+
+impl UnusedDiagnosticsProvider {
+    pub fn get(
+        self,
+        workspace: &Workspace,
+        document: &Document,
+        options: &Options,
+    ) -> Vec<Diagnostic> {
+        let usages = match Usages::new(workspace, document, options) {
+            Some(usages) => usages,
+            None => return Vec::new(),
+        };
+
+        match &document.tree {
+            SyntaxTree::Latex(tree) => unused_labels(tree, &usages),
+            SyntaxTree::Bibtex(tree) => unused_entries(tree, &usages),
+        }
+    }
+}
+
+fn unused_labels(tree: &LatexSyntaxTree, usages: &Usages) -> Vec<Diagnostic> {
+    tree.structure
+        .labels
+        .iter()
+        .filter(|label| !label.kind.is_reference())
+        .flat_map(LatexLabel::names)
+        .filter(|name| !usages.labels.contains(name.text()))
+        .map(|name| Diagnostic {
+            source: Some("latex".into()),
+            range: name.range(),
+            message: format!("Unused label: {}", name.text()),
+            severity: Some(DiagnosticSeverity::Hint),
+            code: None,
+            related_information: None,
+        })
+        .collect()
+}
+
+fn unused_entries(tree: &BibtexSyntaxTree, usages: &Usages) -> Vec<Diagnostic> {
+    if !usages.has_citations || usages.keys.contains("*") {
+        return Vec::new();
+    }
+
+    tree.entries()
+        .into_iter()
+        .filter(|entry| !entry.is_comment())
+        .filter_map(|entry| entry.key.as_ref())
+        .filter(|key| !usages.keys.contains(key.text()))
+        .map(|key| Diagnostic {
+            source: Some("bibtex".into()),
+            range: key.range(),
+            message: format!("Unused entry: {}", key.text()),
+            severity: Some(DiagnosticSeverity::Hint),
+            code: None,
+            related_information: None,
+        })
+        .collect()
+}
+
+/// The labels and citation keys that are referenced in the project.
+struct Usages {
+    labels: HashSet<String>,
+    keys: HashSet<String>,
+    has_citations: bool,
+}
+
+impl Usages {
+    /// Returns `None` if one of the included documents has not been loaded yet
+    /// because it might use the remaining labels and entries.
+    fn new(workspace: &Workspace, document: &Document, options: &Options) -> Option<Self> {
+        let mut usages = Self {
+            labels: HashSet::new(),
+            keys: HashSet::new(),
+            has_citations: false,
+        };
+
+        for related in workspace.related_documents(&document.uri, options) {
+            if let SyntaxTree::Latex(tree) = &related.tree {
+                let has_missing_include = tree
+                    .includes
+                    .iter()
+                    .filter(|include| include.kind == LatexIncludeKind::Latex)
+                    .flat_map(|include| &include.all_targets)
+                    .any(|targets| {
+                        targets
+                            .iter()
+                            .all(|target| workspace.find(target).is_none())
+                    });
+
+                if has_missing_include {
+                    return None;
+                }
+
+                for label in &tree.structure.labels {
+                    if label.kind.is_reference() {
+                        usages
+                            .labels
+                            .extend(label.names().iter().map(|name| name.text().to_owned()));
+                    }
+                }
+
+                for citation in &tree.citations {
+                    usages.has_citations = true;
+                    usages
+                        .keys
+                        .extend(citation.keys().iter().map(|key| key.text().to_owned()));
+                }
+            }
+        }
+        Some(usages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostics::test_diagnostics;
+    use texlab_protocol::{Range, RangeExt};
+
+    fn diagnostics(files: &[(&str, &str)], name: &str) -> Vec<Diagnostic> {
+        test_diagnostics(files, name, |workspace, document| {
+            UnusedDiagnosticsProvider.get(workspace, document, &Options::default())
+        })
+    }
+
+    #[test]
+    fn unused_label() {
+        let diagnostics = diagnostics(
+            &[("foo.tex", "\\label{foo}\n\\label{bar}\n\\ref{foo}")],
+            "foo.tex",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Unused label: bar");
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 7, 1, 10));
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::Hint));
+    }
+
+    #[test]
+    fn label_referenced_in_included_document() {
+        let diagnostics = diagnostics(
+            &[
+                ("foo.tex", "\\include{bar}\n\\label{baz}"),
+                ("bar.tex", "\\ref{baz}"),
+            ],
+            "foo.tex",
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn missing_include() {
+        let diagnostics = diagnostics(&[("foo.tex", "\\include{bar}\n\\label{baz}")], "foo.tex");
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn unused_entry() {
+        let diagnostics = diagnostics(
+            &[
+                ("foo.bib", "@article{foo,}\n@article{bar,}\n@comment{baz,}"),
+                ("main.tex", "\\addbibresource{foo.bib}\n\\cite{foo}"),
+            ],
+            "foo.bib",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Unused entry: bar");
+        assert_eq!(diagnostics[0].range, Range::new_simple(1, 9, 1, 12));
+    }
+
+    #[test]
+    fn nocite_everything() {
+        let diagnostics = diagnostics(
+            &[
+                ("foo.bib", "@article{foo,}\n@article{bar,}"),
+                ("main.tex", "\\addbibresource{foo.bib}\n\\nocite{*}"),
+            ],
+            "foo.bib",
+        );
+        assert!(diagnostics.is_empty());
+    }
+
+    #[test]
+    fn standalone_bibliography() {
+        let diagnostics = diagnostics(&[("foo.bib", "@article{foo,}")], "foo.bib");
+        assert!(diagnostics.is_empty());
+    }
+}
@@ -0,0 +1,139 @@
+use encoding_rs::{Encoding, GBK, UTF_8, WINDOWS_1252};
+use once_cell::sync::Lazy;
+use regex::bytes::Regex;
+
+static INPUTENC_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\\usepackage\s*\[([^\]]*)\]\s*\{inputenc\}").unwrap());
+
+static CJK_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\\begin\s*\{CJK\*?\}\s*(?:\[[^\]]*\])?\s*\{([^}]*)\}").unwrap());
+
+/// Decodes the contents of a file that was loaded from disk.
+///
+/// Files that are valid UTF-8 are passed through unchanged.
+/// Otherwise, the encoding declared via `inputenc` or a `CJK` environment is used
+/// and if there is none, the encoding is guessed from the contents.
+/// Since the text is stored as UTF-8 internally and positions are expressed
+/// in characters, edits coming from the client map onto the decoded text directly.
+pub fn decode(bytes: &[u8]) -> String {
+    if let Some((encoding, _)) = Encoding::for_bom(bytes) {
+        let (text, _) = encoding.decode_with_bom_removal(bytes);
+        return text.into_owned();
+    }
+
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return text.to_owned();
+    }
+
+    let encoding = declared_encoding(bytes).unwrap_or_else(|| sniff_encoding(bytes));
+    let (text, _) = encoding.decode_without_bom_handling(bytes);
+    text.into_owned()
+}
+
+fn declared_encoding(bytes: &[u8]) -> Option<&'static Encoding> {
+    let options = INPUTENC_REGEX
+        .captures_iter(bytes)
+        .chain(CJK_REGEX.captures_iter(bytes))
+        .filter_map(|captures| std::str::from_utf8(&captures[1]).ok().map(str::to_owned))
+        .collect::<Vec<_>>();
+
+    options
+        .iter()
+        .flat_map(|options| options.split(','))
+        .map(str::trim)
+        .find_map(encoding_by_name)
+}
+
+fn encoding_by_name(name: &str) -> Option<&'static Encoding> {
+    match name.to_lowercase().as_str() {
+        "utf8" | "utf8x" | "utf-8" => Some(UTF_8),
+        "latin1" | "latin9" | "ansinew" | "cp1252" => Some(WINDOWS_1252),
+        "gbk" | "gb" | "cp936" => Some(GBK),
+        "latin2" => Encoding::for_label(b"iso-8859-2"),
+        "latin3" => Encoding::for_label(b"iso-8859-3"),
+        "latin4" => Encoding::for_label(b"iso-8859-4"),
+        "latin5" => Encoding::for_label(b"iso-8859-9"),
+        "latin10" => Encoding::for_label(b"iso-8859-16"),
+        "cp1250" => Encoding::for_label(b"windows-1250"),
+        "cp1251" => Encoding::for_label(b"windows-1251"),
+        "cp1257" => Encoding::for_label(b"windows-1257"),
+        "koi8-r" => Encoding::for_label(b"koi8-r"),
+        "bg5" | "big5" => Encoding::for_label(b"big5"),
+        "sjis" => Encoding::for_label(b"shift_jis"),
+        "euc-jp" => Encoding::for_label(b"euc-jp"),
+        "euc-kr" => Encoding::for_label(b"euc-kr"),
+        name => Encoding::for_label(name.as_bytes()),
+    }
+}
+
+fn sniff_encoding(bytes: &[u8]) -> &'static Encoding {
+    let (_, _, has_errors) = GBK.decode(bytes);
+    if !has_errors && looks_like_gbk(bytes) {
+        GBK
+    } else {
+        WINDOWS_1252
+    }
+}
+
+fn looks_like_gbk(bytes: &[u8]) -> bool {
+    let mut pairs = 0;
+    let mut singles = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] >= 0x81 && bytes[i] <= 0xFE && i + 1 < bytes.len() {
+            if bytes[i + 1] >= 0x40 && bytes[i + 1] != 0x7F {
+                pairs += 1;
+                i += 2;
+                continue;
+            }
+        } else if bytes[i] >= 0x80 {
+            singles += 1;
+        }
+        i += 1;
+    }
+    pairs > 0 && pairs >= singles * 4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn utf8() {
+        let text = "\\section{Übersicht}";
+        assert_eq!(decode(text.as_bytes()), text);
+    }
+
+    #[test]
+    fn utf8_bom() {
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(b"foo");
+        assert_eq!(decode(&bytes), "foo");
+    }
+
+    #[test]
+    fn inputenc_latin1() {
+        let mut bytes = b"\\usepackage[latin1]{inputenc}\n".to_vec();
+        bytes.push(0xDC);
+        assert_eq!(decode(&bytes), "\\usepackage[latin1]{inputenc}\nÜ");
+    }
+
+    #[test]
+    fn utf8_with_stale_inputenc() {
+        let text = "\\usepackage[latin1]{inputenc}\n\\section{Übersicht}";
+        assert_eq!(decode(text.as_bytes()), text);
+    }
+
+    #[test]
+    fn cjk_gbk() {
+        let mut bytes = b"\\begin{CJK}{GBK}{song}".to_vec();
+        bytes.extend_from_slice(&[0xD6, 0xD0, 0xCE, 0xC4]);
+        assert_eq!(decode(&bytes), "\\begin{CJK}{GBK}{song}中文");
+    }
+
+    #[test]
+    fn sniff_latin1() {
+        let bytes = b"caf\xE9";
+        assert_eq!(decode(bytes), "café");
+    }
+}
@@ -0,0 +1,123 @@
+use futures_boxed::boxed;
+use texlab_protocol::{FoldingRange, FoldingRangeKind, FoldingRangeParams};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexCommentFoldingProvider;
+
+impl FeatureProvider for LatexCommentFoldingProvider {
+    type Params = FoldingRangeParams;
+    type Output = Vec<FoldingRange>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<FoldingRangeParams>,
+    ) -> Vec<FoldingRange> {
+        let document = request.document();
+        if let SyntaxTree::Latex(_) = &document.tree {
+            fold_comments(&document.text)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Folds blocks of consecutive lines that only contain a comment.
+fn fold_comments(text: &str) -> Vec<FoldingRange> {
+    let mut foldings: Vec<FoldingRange> = Vec::new();
+    for (line, content) in text.lines().enumerate() {
+        let line = line as u64;
+        let trimmed = content.trim_start();
+        if !trimmed.starts_with('%') {
+            continue;
+        }
+
+        let end_character = content.chars().count() as u64;
+        match foldings.last_mut() {
+            Some(folding) if folding.end_line + 1 == line => {
+                folding.end_line = line;
+                folding.end_character = Some(end_character);
+            }
+            _ => {
+                let start_character = (content.chars().count() - trimmed.chars().count()) as u64;
+                foldings.push(FoldingRange {
+                    start_line: line,
+                    start_character: Some(start_character),
+                    end_line: line,
+                    end_character: Some(end_character),
+                    kind: Some(FoldingRangeKind::Comment),
+                });
+            }
+        }
+    }
+
+    foldings.retain(|folding| folding.end_line > folding.start_line);
+    foldings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn consecutive_comments() {
+        let foldings = test_feature(
+            LatexCommentFoldingProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "% foo\n  % bar\n\\baz % qux\n%a\n%b\n%c",
+                )],
+                main_file: "foo.tex",
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            foldings,
+            vec![
+                FoldingRange {
+                    start_line: 0,
+                    start_character: Some(0),
+                    end_line: 1,
+                    end_character: Some(7),
+                    kind: Some(FoldingRangeKind::Comment),
+                },
+                FoldingRange {
+                    start_line: 3,
+                    start_character: Some(0),
+                    end_line: 5,
+                    end_character: Some(2),
+                    kind: Some(FoldingRangeKind::Comment),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn single_comment() {
+        let foldings = test_feature(
+            LatexCommentFoldingProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "% foo\nbar")],
+                main_file: "foo.tex",
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(foldings.is_empty());
+    }
+
+    #[test]
+    fn bibtex() {
+        let foldings = test_feature(
+            LatexCommentFoldingProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "% foo\n% bar")],
+                main_file: "foo.bib",
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(foldings.is_empty());
+    }
+}
@@ -0,0 +1,94 @@
+use futures_boxed::boxed;
+use texlab_protocol::{FoldingRange, FoldingRangeKind, FoldingRangeParams};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexPreambleFoldingProvider;
+
+impl FeatureProvider for LatexPreambleFoldingProvider {
+    type Params = FoldingRangeParams;
+    type Output = Vec<FoldingRange>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<FoldingRangeParams>,
+    ) -> Vec<FoldingRange> {
+        let mut foldings = Vec::new();
+        if let SyntaxTree::Latex(tree) = &request.document().tree {
+            let class = tree
+                .commands
+                .iter()
+                .find(|command| command.name.text() == "\\documentclass");
+
+            let document = tree.env.environments.iter().find(|environment| {
+                environment
+                    .left
+                    .name()
+                    .map_or(false, |name| name.text() == "document")
+            });
+
+            if let (Some(class), Some(document)) = (class, document) {
+                let start = class.end();
+                let end = document.left.command.start();
+                if start < end {
+                    foldings.push(FoldingRange {
+                        start_line: start.line,
+                        start_character: Some(start.character),
+                        end_line: end.line,
+                        end_character: Some(end.character),
+                        kind: Some(FoldingRangeKind::Imports),
+                    });
+                }
+            }
+        }
+        foldings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preamble() {
+        let foldings = test_feature(
+            LatexPreambleFoldingProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\documentclass{article}\n\\usepackage{amsmath}\n\\begin{document}\n\\end{document}",
+                )],
+                main_file: "foo.tex",
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            foldings,
+            vec![FoldingRange {
+                start_line: 0,
+                start_character: Some(23),
+                end_line: 2,
+                end_character: Some(0),
+                kind: Some(FoldingRangeKind::Imports),
+            }]
+        );
+    }
+
+    #[test]
+    fn no_document_environment() {
+        let foldings = test_feature(
+            LatexPreambleFoldingProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\documentclass{article}\n\\usepackage{amsmath}",
+                )],
+                main_file: "foo.tex",
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(foldings.is_empty());
+    }
+}
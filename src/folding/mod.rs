@@ -1,12 +1,16 @@
 mod bibtex_declaration;
+mod latex_comment;
 mod latex_environment;
+mod latex_preamble;
 mod latex_section;
 
 use self::bibtex_declaration::BibtexDeclarationFoldingProvider;
+use self::latex_comment::LatexCommentFoldingProvider;
 use self::latex_environment::LatexEnvironmentFoldingProvider;
+use self::latex_preamble::LatexPreambleFoldingProvider;
 use self::latex_section::LatexSectionFoldingProvider;
 use futures_boxed::boxed;
-use texlab_protocol::{FoldingRange, FoldingRangeParams};
+use texlab_protocol::{ClientCapabilities, FoldingRange, FoldingRangeParams};
 use texlab_workspace::*;
 
 pub struct FoldingProvider {
@@ -18,7 +22,9 @@ impl FoldingProvider {
         Self {
             provider: ConcatProvider::new(vec![
                 Box::new(BibtexDeclarationFoldingProvider),
+                Box::new(LatexCommentFoldingProvider),
                 Box::new(LatexEnvironmentFoldingProvider),
+                Box::new(LatexPreambleFoldingProvider),
                 Box::new(LatexSectionFoldingProvider),
             ]),
         }
@@ -40,6 +46,97 @@ impl FeatureProvider for FoldingProvider {
         &'a self,
         request: &'a FeatureRequest<FoldingRangeParams>,
     ) -> Vec<FoldingRange> {
-        self.provider.execute(request).await
+        let foldings = self.provider.execute(request).await;
+        adapt_to_client(foldings, &request.client_capabilities)
+    }
+}
+
+/// Drops the character offsets if the client only folds entire lines
+/// and truncates the result to the maximum number of ranges of the client.
+fn adapt_to_client(
+    mut foldings: Vec<FoldingRange>,
+    capabilities: &ClientCapabilities,
+) -> Vec<FoldingRange> {
+    let capabilities = match capabilities
+        .text_document
+        .as_ref()
+        .and_then(|cap| cap.folding_range.as_ref())
+    {
+        Some(capabilities) => capabilities,
+        None => return foldings,
+    };
+
+    if capabilities.line_folding_only == Some(true) {
+        foldings.retain(|folding| folding.end_line > folding.start_line);
+        for folding in &mut foldings {
+            folding.start_character = None;
+            folding.end_character = None;
+        }
+    }
+
+    if let Some(limit) = capabilities.range_limit {
+        foldings.truncate(limit as usize);
+    }
+    foldings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{FoldingRangeCapability, TextDocumentClientCapabilities};
+
+    fn capabilities(
+        range_limit: Option<u64>,
+        line_folding_only: Option<bool>,
+    ) -> ClientCapabilities {
+        ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                folding_range: Some(FoldingRangeCapability {
+                    dynamic_registration: None,
+                    range_limit,
+                    line_folding_only,
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        }
+    }
+
+    #[test]
+    fn line_folding_only() {
+        let foldings = test_feature(
+            FoldingProvider::new(),
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\begin{foo}\\end{foo}\n\\begin{bar}\n\\end{bar}",
+                )],
+                main_file: "foo.tex",
+                client_capabilities: capabilities(None, Some(true)),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(foldings.len(), 1);
+        assert_eq!(foldings[0].start_line, 1);
+        assert_eq!(foldings[0].start_character, None);
+        assert_eq!(foldings[0].end_line, 2);
+        assert_eq!(foldings[0].end_character, None);
+    }
+
+    #[test]
+    fn range_limit() {
+        let foldings = test_feature(
+            FoldingProvider::new(),
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\begin{foo}\n\\end{foo}\n\\begin{bar}\n\\end{bar}",
+                )],
+                main_file: "foo.tex",
+                client_capabilities: capabilities(Some(1), None),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(foldings.len(), 1);
     }
 }
@@ -0,0 +1,283 @@
+use texlab_workspace::sandbox;
+use std::path::{Path, PathBuf};
+use texlab_protocol::{Options, Uri};
+use texlab_syntax::*;
+use walkdir::WalkDir;
+
+const MAX_SEARCH_DEPTH: usize = 3;
+
+const MAX_SUGGESTIONS: usize = 5;
+
+/// An argument of `\includegraphics` that does not refer to an existing file.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MissingGraphic<'a> {
+    pub include: &'a LatexInclude,
+    pub path: &'a LatexToken,
+}
+
+impl<'a> MissingGraphic<'a> {
+    /// Finds the missing graphics of a document.
+    ///
+    /// Only the directories inside of the sandbox are searched.
+    pub fn analyze(
+        tree: &'a LatexSyntaxTree,
+        uri: &Uri,
+        options: &Options,
+        sandbox: &Path,
+    ) -> Vec<Self> {
+        let base_directory = match base_directory(uri, options, sandbox) {
+            Some(base_directory) => base_directory,
+            None => return Vec::new(),
+        };
+
+        let graphics_directories = graphics_directories(tree, &base_directory, sandbox);
+        let mut missing = Vec::new();
+        for include in &tree.includes {
+            if include.kind != LatexIncludeKind::Image {
+                continue;
+            }
+
+            for (path, targets) in include.paths().into_iter().zip(&include.all_targets) {
+                let exists = targets
+                    .iter()
+                    .filter_map(|target| target.to_file_path().ok())
+                    .any(|target| target.is_file())
+                    || graphics_directories
+                        .iter()
+                        .any(|directory| resolve(directory, path.text()).is_some());
+
+                if !exists {
+                    missing.push(Self { include, path });
+                }
+            }
+        }
+        missing
+    }
+
+    /// Returns the existing images whose paths are most similar to the missing path.
+    pub fn suggestions(
+        &self,
+        tree: &LatexSyntaxTree,
+        uri: &Uri,
+        options: &Options,
+        sandbox: &Path,
+    ) -> Vec<String> {
+        let base_directory = match base_directory(uri, options, sandbox) {
+            Some(base_directory) => base_directory,
+            None => return Vec::new(),
+        };
+
+        let name = self.path.text();
+        let has_extension = Path::new(name).extension().is_some();
+        let max_distance = (name.chars().count() / 3).max(2);
+        let mut directories = vec![base_directory.clone()];
+        directories.extend(graphics_directories(tree, &base_directory, sandbox));
+
+        let mut candidates = Vec::new();
+        for directory in &directories {
+            for entry in WalkDir::new(directory)
+                .min_depth(1)
+                .max_depth(MAX_SEARCH_DEPTH)
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_type().is_file() && is_image(entry.path()))
+            {
+                let mut path = match entry.path().strip_prefix(directory) {
+                    Ok(path) => path.to_owned(),
+                    Err(_) => continue,
+                };
+
+                if !has_extension {
+                    path.set_extension("");
+                }
+
+                let candidate = path.to_string_lossy().replace('\\', "/");
+                let distance = levenshtein(&name.to_lowercase(), &candidate.to_lowercase());
+                if distance > 0 && distance <= max_distance {
+                    candidates.push((distance, candidate));
+                }
+            }
+        }
+
+        candidates.sort();
+        candidates.dedup_by(|(_, a), (_, b)| a == b);
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+}
+
+fn base_directory(uri: &Uri, options: &Options, sandbox: &Path) -> Option<PathBuf> {
+    if uri.scheme() != "file" {
+        return None;
+    }
+
+    /* Security Patch */
+    options
+        .latex
+        .as_ref()
+        .and_then(|latex| latex.root_directory.clone())
+        .or_else(|| {
+            uri.to_file_path()
+                .ok()
+                .and_then(|path| path.parent().map(ToOwned::to_owned))
+        })
+        .and_then(|directory| sandbox::confine(&directory, sandbox))
+}
+
+/// Returns the directories inside of the sandbox that are listed by `\graphicspath`.
+fn graphics_directories(
+    tree: &LatexSyntaxTree,
+    base_directory: &Path,
+    sandbox: &Path,
+) -> Vec<PathBuf> {
+    let mut directories = Vec::new();
+    for command in &tree.commands {
+        if command.name.text() != "\\graphicspath" || command.args.is_empty() {
+            continue;
+        }
+
+        for child in &command.args[0].children {
+            if let LatexContent::Group(group) = child {
+                let directory: String = group
+                    .children
+                    .iter()
+                    .filter_map(|child| match child {
+                        LatexContent::Text(text) => Some(text),
+                        _ => None,
+                    })
+                    .flat_map(|text| text.words.iter().map(LatexToken::text))
+                    .collect();
+
+                if directory.is_empty() {
+                    continue;
+                }
+
+                /* Security Patch */
+                if let Some(directory) = sandbox::confine(&base_directory.join(directory), sandbox)
+                {
+                    directories.push(directory);
+                }
+            }
+        }
+    }
+    directories
+}
+
+fn resolve(directory: &Path, name: &str) -> Option<PathBuf> {
+    let path = directory.join(name);
+    if path.is_file() {
+        return Some(path);
+    }
+
+    LatexIncludeKind::Image
+        .extensions()
+        .unwrap()
+        .iter()
+        .map(|extension| directory.join(format!("{}.{}", name, extension)))
+        .find(|path| path.is_file())
+}
+
+fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| {
+            LatexIncludeKind::Image
+                .extensions()
+                .unwrap()
+                .contains(&extension.to_lowercase().as_str())
+        })
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut distances: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous = distances[0];
+        distances[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let current = distances[j + 1];
+            distances[j + 1] = if a_char == *b_char {
+                previous
+            } else {
+                1 + previous.min(current).min(distances[j])
+            };
+            previous = current;
+        }
+    }
+    distances[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn missing_paths(directory: &str, text: &str) -> (Vec<String>, Vec<Vec<String>>) {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document(&format!("{}/foo.tex", directory), text);
+        let document = builder.workspace.find(&uri).unwrap();
+        let options = Options::default();
+        let sandbox = env::temp_dir().join(directory);
+        match &document.tree {
+            SyntaxTree::Latex(tree) => {
+                let missing = MissingGraphic::analyze(tree, &uri, &options, &sandbox);
+                let paths = missing
+                    .iter()
+                    .map(|graphic| graphic.path.text().to_owned())
+                    .collect();
+                let suggestions = missing
+                    .iter()
+                    .map(|graphic| graphic.suggestions(tree, &uri, &options, &sandbox))
+                    .collect();
+                (paths, suggestions)
+            }
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn levenshtein_distance() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("plot", "plot"), 0);
+    }
+
+    #[test]
+    fn existing_and_missing_graphics() {
+        let directory = env::temp_dir().join("texlab-graphics-test");
+        fs::create_dir_all(directory.join("figures")).unwrap();
+        fs::write(directory.join("figures").join("plot.png"), "").unwrap();
+        fs::write(directory.join("logo.pdf"), "").unwrap();
+
+        let (paths, suggestions) = missing_paths(
+            "texlab-graphics-test",
+            "\\graphicspath{{figures/}}\n\
+             \\includegraphics{logo}\n\
+             \\includegraphics{plot.png}\n\
+             \\includegraphics{figures/plt}\n\
+             \\includegraphics{unrelated}",
+        );
+        assert_eq!(paths, vec!["figures/plt", "unrelated"]);
+        assert_eq!(suggestions[0], vec!["figures/plot"]);
+        assert!(suggestions[1].is_empty());
+    }
+
+    #[test]
+    fn outside_of_sandbox() {
+        let directory = env::temp_dir().join("texlab-graphics-sandbox-test");
+        fs::create_dir_all(directory.join("project")).unwrap();
+        fs::write(directory.join("chart.png"), "").unwrap();
+
+        let (paths, suggestions) = missing_paths(
+            "texlab-graphics-sandbox-test/project",
+            "\\graphicspath{{../}}\n\\includegraphics{chart}\n\\includegraphics{chrt}",
+        );
+        assert_eq!(paths, vec!["chart", "chrt"]);
+        assert!(suggestions.iter().all(Vec::is_empty));
+    }
+}
@@ -0,0 +1,115 @@
+use futures_boxed::boxed;
+use texlab_protocol::{DocumentHighlight, DocumentHighlightKind, TextDocumentPositionParams};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BibtexStringHighlightProvider;
+
+impl FeatureProvider for BibtexStringHighlightProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<DocumentHighlight>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<TextDocumentPositionParams>,
+    ) -> Vec<DocumentHighlight> {
+        let mut highlights = Vec::new();
+        if let SyntaxTree::Bibtex(tree) = &request.document().tree {
+            if let Some(name) = tree.string_name_at(request.params.position) {
+                for string in tree.strings() {
+                    if let Some(string_name) = &string.name {
+                        if string_name.text() == name.text() {
+                            highlights.push(DocumentHighlight {
+                                range: string_name.range(),
+                                kind: Some(DocumentHighlightKind::Write),
+                            });
+                        }
+                    }
+                }
+
+                for reference in tree.string_references() {
+                    if reference.text() == name.text() {
+                        highlights.push(DocumentHighlight {
+                            range: reference.range(),
+                            kind: Some(DocumentHighlightKind::Read),
+                        });
+                    }
+                }
+            }
+        }
+        highlights
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::RangeExt;
+    use texlab_protocol::{Position, Range};
+
+    #[test]
+    fn string() {
+        let highlights = test_feature(
+            BibtexStringHighlightProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@string{foo = {Foo}}\n@string{bar = {Bar}}\n@article{baz, author = foo # bar, title = foo}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 9),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            highlights,
+            vec![
+                DocumentHighlight {
+                    range: Range::new_simple(0, 8, 0, 11),
+                    kind: Some(DocumentHighlightKind::Write),
+                },
+                DocumentHighlight {
+                    range: Range::new_simple(2, 23, 2, 26),
+                    kind: Some(DocumentHighlightKind::Read),
+                },
+                DocumentHighlight {
+                    range: Range::new_simple(2, 42, 2, 45),
+                    kind: Some(DocumentHighlightKind::Read),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn field_name() {
+        let highlights = test_feature(
+            BibtexStringHighlightProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@string{foo = {Foo}}\n@article{baz, author = foo}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(1, 15),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(highlights.is_empty());
+    }
+
+    #[test]
+    fn latex() {
+        let highlights = test_feature(
+            BibtexStringHighlightProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "")],
+                main_file: "foo.tex",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(highlights.is_empty());
+    }
+}
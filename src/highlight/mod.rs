@@ -1,5 +1,7 @@
+mod bibtex_string;
 mod latex_label;
 
+use self::bibtex_string::BibtexStringHighlightProvider;
 use self::latex_label::LatexLabelHighlightProvider;
 use futures_boxed::boxed;
 use texlab_protocol::{DocumentHighlight, TextDocumentPositionParams};
@@ -12,7 +14,10 @@ pub struct HighlightProvider {
 impl HighlightProvider {
     pub fn new() -> Self {
         Self {
-            provider: ConcatProvider::new(vec![Box::new(LatexLabelHighlightProvider)]),
+            provider: ConcatProvider::new(vec![
+                Box::new(BibtexStringHighlightProvider),
+                Box::new(LatexLabelHighlightProvider),
+            ]),
         }
     }
 }
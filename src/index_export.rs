@@ -0,0 +1,232 @@
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use texlab_protocol::{Options, Range, SymbolInformation, Uri};
+use texlab_syntax::*;
+use texlab_workspace::Workspace;
+
+pub const EXPORT_INDEX_COMMAND: &str = "texlab.exportIndex";
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportIndexParams {
+    pub path: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndexLanguage {
+    Latex,
+    Bibtex,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexDocument {
+    pub uri: Uri,
+    pub language: IndexLanguage,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexLabel {
+    pub uri: Uri,
+    pub name: String,
+    pub kind: LatexLabelKind,
+    pub range: Range,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexCitation {
+    pub uri: Uri,
+    pub key: String,
+    pub range: Range,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexInclude {
+    pub uri: Uri,
+    pub path: String,
+    pub kind: LatexIncludeKind,
+    pub range: Range,
+    /// The documents of the workspace that the path resolves to.
+    pub targets: Vec<Uri>,
+}
+
+/// A flat snapshot of the workspace for external tools.
+///
+/// Every table refers to its document by URI, which makes it easy to load the export
+/// into a database.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceIndex {
+    pub documents: Vec<IndexDocument>,
+    pub labels: Vec<IndexLabel>,
+    pub citations: Vec<IndexCitation>,
+    pub includes: Vec<IndexInclude>,
+    pub symbols: Vec<SymbolInformation>,
+}
+
+impl WorkspaceIndex {
+    /// Indexes the documents of the workspace in the order of the project structure.
+    ///
+    /// The symbols are computed asynchronously and need to be added by the caller.
+    pub fn new(workspace: &Workspace, options: &Options) -> Self {
+        let mut index = Self::default();
+        for document in texlab_symbol::ordered_documents(workspace, options) {
+            let uri = &document.uri;
+            let tree = match &document.tree {
+                SyntaxTree::Latex(tree) => tree,
+                SyntaxTree::Bibtex(_) => {
+                    index.documents.push(IndexDocument {
+                        uri: uri.clone(),
+                        language: IndexLanguage::Bibtex,
+                    });
+                    continue;
+                }
+            };
+
+            index.documents.push(IndexDocument {
+                uri: uri.clone(),
+                language: IndexLanguage::Latex,
+            });
+
+            for label in &tree.structure.labels {
+                for name in label.names() {
+                    index.labels.push(IndexLabel {
+                        uri: uri.clone(),
+                        name: name.text().to_owned(),
+                        kind: label.kind,
+                        range: name.range(),
+                    });
+                }
+            }
+
+            for citation in &tree.citations {
+                for key in citation.keys() {
+                    index.citations.push(IndexCitation {
+                        uri: uri.clone(),
+                        key: key.text().to_owned(),
+                        range: key.range(),
+                    });
+                }
+            }
+
+            for include in &tree.includes {
+                for (path, targets) in include.paths().into_iter().zip(&include.all_targets) {
+                    index.includes.push(IndexInclude {
+                        uri: uri.clone(),
+                        path: path.text().to_owned(),
+                        kind: include.kind,
+                        range: path.range(),
+                        targets: targets
+                            .iter()
+                            .filter(|target| workspace.find(target).is_some())
+                            .cloned()
+                            .collect(),
+                    });
+                }
+            }
+        }
+        index
+    }
+
+    /// Writes the index to a JSON file.
+    ///
+    /// An existing file is only replaced if it contains an index,
+    /// so that the export cannot overwrite the documents of the project.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if path.extension().and_then(OsStr::to_str) != Some("json") {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the index must be saved as a .json file",
+            ));
+        }
+
+        if path.exists() && Self::load(path).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "the file exists and is not an index",
+            ));
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+
+    fn load(path: &Path) -> Option<Self> {
+        let bytes = fs::read(path).ok()?;
+        serde_json::from_slice(&bytes).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    #[test]
+    fn latex() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let foo = builder.add_document(
+            "foo.tex",
+            "\\documentclass{article}\n\\include{bar}\n\\label{baz}\n\\cite{qux,quux}",
+        );
+        let bar = builder.add_document("bar.tex", "\\ref{baz}");
+        let index = WorkspaceIndex::new(&builder.workspace, &Options::default());
+
+        assert_eq!(
+            index.documents,
+            vec![
+                IndexDocument {
+                    uri: foo.clone(),
+                    language: IndexLanguage::Latex,
+                },
+                IndexDocument {
+                    uri: bar.clone(),
+                    language: IndexLanguage::Latex,
+                },
+            ]
+        );
+
+        let labels: Vec<(&Uri, &str)> = index
+            .labels
+            .iter()
+            .map(|label| (&label.uri, label.name.as_str()))
+            .collect();
+        assert_eq!(labels, vec![(&foo, "baz"), (&bar, "baz")]);
+        assert_eq!(index.labels[0].range, Range::new_simple(2, 7, 2, 10));
+
+        let keys: Vec<&str> = index
+            .citations
+            .iter()
+            .map(|citation| citation.key.as_str())
+            .collect();
+        assert_eq!(keys, vec!["qux", "quux"]);
+
+        assert_eq!(index.includes.len(), 1);
+        assert_eq!(index.includes[0].path, "bar");
+        assert_eq!(index.includes[0].targets, vec![bar]);
+    }
+
+    #[test]
+    fn bibtex() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let foo = builder.add_document("foo.bib", "@article{foo, bar = {baz}}");
+        let index = WorkspaceIndex::new(&builder.workspace, &Options::default());
+        assert_eq!(
+            index.documents,
+            vec![IndexDocument {
+                uri: foo,
+                language: IndexLanguage::Bibtex,
+            }]
+        );
+        assert!(index.labels.is_empty());
+    }
+}
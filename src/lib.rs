@@ -1,13 +1,43 @@
 #![recursion_limit = "128"]
 
 pub mod action;
+pub mod artifact;
+pub mod build_log;
+pub mod capabilities_report;
+pub mod citation_sections;
+pub mod code_action;
+pub mod command_usage;
 pub mod config;
+pub mod context_at;
 pub mod definition;
 pub mod diagnostics;
+pub mod encoding;
 pub mod folding;
+pub mod graphics;
 pub mod highlight;
+pub mod index_export;
+pub mod limits;
 pub mod link;
+pub mod log_tail;
+pub mod low_bandwidth;
+pub mod lsif;
+pub mod magic_comment;
+pub mod matching_environment;
+pub mod moniker;
+pub mod output;
+pub mod paste;
+pub mod policy;
+pub mod presence;
+pub mod preview;
+pub mod project_stats;
 pub mod reference;
+pub mod registration;
 pub mod rename;
+pub mod root;
 pub mod server;
+pub mod shared_workspace;
+pub mod signature_help;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod user_state;
 pub mod workspace_manager;
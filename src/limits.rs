@@ -0,0 +1,93 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// The resources that a single client connection may use.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ConnectionLimits {
+    pub max_documents: Option<usize>,
+    pub max_processes: Option<usize>,
+}
+
+/// Counts the active connections of the server.
+#[derive(Debug, Default)]
+pub struct ConnectionCounter {
+    active: AtomicUsize,
+    max: Option<usize>,
+}
+
+impl ConnectionCounter {
+    pub fn new(max: Option<usize>) -> Self {
+        Self {
+            active: AtomicUsize::new(0),
+            max,
+        }
+    }
+
+    /// Registers a new connection unless the limit has been reached.
+    ///
+    /// The connection is released when the returned guard is dropped.
+    pub fn try_acquire(self: &Arc<Self>) -> Option<ConnectionGuard> {
+        let mut active = self.active.load(Ordering::SeqCst);
+        loop {
+            if self.max.map_or(false, |max| active >= max) {
+                return None;
+            }
+
+            match self.active.compare_exchange(
+                active,
+                active + 1,
+                Ordering::SeqCst,
+                Ordering::SeqCst,
+            ) {
+                Ok(_) => {
+                    return Some(ConnectionGuard {
+                        counter: Arc::clone(self),
+                    })
+                }
+                Err(current) => active = current,
+            }
+        }
+    }
+
+    pub fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+#[derive(Debug)]
+pub struct ConnectionGuard {
+    counter: Arc<ConnectionCounter>,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.counter.active.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit() {
+        let counter = Arc::new(ConnectionCounter::new(Some(2)));
+        let first = counter.try_acquire();
+        let second = counter.try_acquire();
+        assert!(first.is_some());
+        assert!(second.is_some());
+        assert!(counter.try_acquire().is_none());
+
+        drop(first);
+        assert_eq!(counter.active(), 1);
+        assert!(counter.try_acquire().is_some());
+    }
+
+    #[test]
+    fn unlimited() {
+        let counter = Arc::new(ConnectionCounter::new(None));
+        let guards: Vec<_> = (0..10).map(|_| counter.try_acquire()).collect();
+        assert!(guards.iter().all(Option::is_some));
+        assert_eq!(counter.active(), 10);
+    }
+}
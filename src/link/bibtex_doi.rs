@@ -0,0 +1,79 @@
+use super::LinkData;
+use futures_boxed::boxed;
+use texlab_protocol::{DocumentLinkParams, ResolvableDocumentLink};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct BibtexDoiLinkProvider;
+
+impl FeatureProvider for BibtexDoiLinkProvider {
+    type Params = DocumentLinkParams;
+    type Output = Vec<ResolvableDocumentLink>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<DocumentLinkParams>,
+    ) -> Vec<ResolvableDocumentLink> {
+        let mut links = Vec::new();
+        if let SyntaxTree::Bibtex(tree) = &request.document().tree {
+            for entry in tree.entries() {
+                if let Some(content) = entry.field("doi").and_then(|field| field.content.as_ref()) {
+                    let doi: String = format_content(content, &BibtexFormattingParams::default())
+                        .chars()
+                        .filter(|c| !c.is_whitespace() && *c != '{' && *c != '}' && *c != '"')
+                        .collect();
+
+                    if !doi.is_empty() {
+                        links.push(LinkData::Doi { doi }.into_link(content.range()));
+                    }
+                }
+            }
+        }
+        links
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Position, Range, RangeExt};
+
+    #[test]
+    fn has_links() {
+        let links = test_feature(
+            BibtexDoiLinkProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.bib",
+                    "@article{foo, doi = {10.1000/xyz123}}",
+                )],
+                main_file: "foo.bib",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![LinkData::Doi {
+                doi: "10.1000/xyz123".into()
+            }
+            .into_link(Range::new_simple(0, 20, 0, 36))]
+        );
+    }
+
+    #[test]
+    fn no_links_latex() {
+        let links = test_feature(
+            BibtexDoiLinkProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", "")],
+                main_file: "foo.tex",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(links.is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+use super::LinkData;
+use futures_boxed::boxed;
+use texlab_protocol::{DocumentLinkParams, ResolvableDocumentLink};
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LatexPackageLinkProvider;
+
+impl FeatureProvider for LatexPackageLinkProvider {
+    type Params = DocumentLinkParams;
+    type Output = Vec<ResolvableDocumentLink>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<DocumentLinkParams>,
+    ) -> Vec<ResolvableDocumentLink> {
+        if let SyntaxTree::Latex(tree) = &request.document().tree {
+            return tree
+                .includes
+                .iter()
+                .flat_map(|include| Self::collect(request, include))
+                .collect();
+        }
+        Vec::new()
+    }
+}
+
+impl LatexPackageLinkProvider {
+    fn collect(
+        request: &FeatureRequest<DocumentLinkParams>,
+        include: &LatexInclude,
+    ) -> Vec<ResolvableDocumentLink> {
+        let extension = match include.kind {
+            LatexIncludeKind::Package => "sty",
+            LatexIncludeKind::Class => "cls",
+            _ => return Vec::new(),
+        };
+
+        let mut links = Vec::new();
+        for (path, targets) in include.paths().into_iter().zip(&include.all_targets) {
+            let is_local = targets
+                .iter()
+                .any(|target| request.workspace().find(target).is_some());
+
+            if !is_local {
+                let name = format!("{}.{}", path.text(), extension);
+                links.push(LinkData::Component { name }.into_link(path.range()));
+            }
+        }
+        links
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Position, Range, RangeExt};
+
+    #[test]
+    fn has_links() {
+        let links = test_feature(
+            LatexPackageLinkProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\documentclass{article}\n\\usepackage{amsmath}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            links,
+            vec![
+                LinkData::Component {
+                    name: "article.cls".into()
+                }
+                .into_link(Range::new_simple(0, 15, 0, 22)),
+                LinkData::Component {
+                    name: "amsmath.sty".into()
+                }
+                .into_link(Range::new_simple(1, 12, 1, 19)),
+            ]
+        );
+        assert!(links.iter().all(|link| link.target.is_none()));
+    }
+
+    #[test]
+    fn local_package() {
+        let links = test_feature(
+            LatexPackageLinkProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file("foo.tex", "\\usepackage{bar}"),
+                    FeatureSpec::file("bar.sty", ""),
+                ],
+                main_file: "foo.tex",
+                position: Position::new(0, 0),
+                ..FeatureSpec::default()
+            },
+        );
+        assert!(links.is_empty());
+    }
+}
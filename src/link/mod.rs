@@ -1,18 +1,61 @@
+mod bibtex_doi;
 mod latex_include;
+mod latex_package;
 
+use crate::link::bibtex_doi::BibtexDoiLinkProvider;
 use crate::link::latex_include::LatexIncludeLinkProvider;
+use crate::link::latex_package::LatexPackageLinkProvider;
 use futures_boxed::boxed;
-use texlab_protocol::{DocumentLink, DocumentLinkParams};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use texlab_distro::Resolver;
+use texlab_protocol::{DocumentLink, DocumentLinkParams, Range, ResolvableDocumentLink, Url};
 use texlab_workspace::*;
 
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum LinkData {
+    Component { name: String },
+    Doi { doi: String },
+}
+
+impl LinkData {
+    pub fn resolve(&self, resolver: &Resolver) -> Option<Url> {
+        match self {
+            LinkData::Component { name } => resolver
+                .files_by_name
+                .get(name)
+                .and_then(|path| Url::from_file_path(path).ok())
+                .or_else(|| {
+                    let stem = Path::new(name).file_stem()?.to_str()?;
+                    Url::parse(&format!("https://ctan.org/pkg/{}", stem)).ok()
+                }),
+            LinkData::Doi { doi } => Url::parse(&format!("https://doi.org/{}", doi)).ok(),
+        }
+    }
+
+    pub fn into_link(self, range: Range) -> ResolvableDocumentLink {
+        ResolvableDocumentLink {
+            range,
+            target: None,
+            data: serde_json::to_value(self).ok(),
+        }
+    }
+}
+
 pub struct LinkProvider {
     provider: ConcatProvider<DocumentLinkParams, DocumentLink>,
+    lazy_provider: ConcatProvider<DocumentLinkParams, ResolvableDocumentLink>,
 }
 
 impl LinkProvider {
     pub fn new() -> Self {
         Self {
             provider: ConcatProvider::new(vec![Box::new(LatexIncludeLinkProvider)]),
+            lazy_provider: ConcatProvider::new(vec![
+                Box::new(LatexPackageLinkProvider),
+                Box::new(BibtexDoiLinkProvider),
+            ]),
         }
     }
 }
@@ -25,13 +68,62 @@ impl Default for LinkProvider {
 
 impl FeatureProvider for LinkProvider {
     type Params = DocumentLinkParams;
-    type Output = Vec<DocumentLink>;
+    type Output = Vec<ResolvableDocumentLink>;
 
     #[boxed]
     async fn execute<'a>(
         &'a self,
         request: &'a FeatureRequest<DocumentLinkParams>,
-    ) -> Vec<DocumentLink> {
-        self.provider.execute(request).await
+    ) -> Vec<ResolvableDocumentLink> {
+        let mut links: Vec<ResolvableDocumentLink> = self
+            .provider
+            .execute(request)
+            .await
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        links.extend(self.lazy_provider.execute(request).await);
+        links
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::env;
+
+    #[test]
+    fn resolve_component() {
+        let path = env::temp_dir().join("amsmath.sty");
+        let mut files_by_name = HashMap::new();
+        files_by_name.insert("amsmath.sty".to_owned(), path.clone());
+        let resolver = Resolver::new(files_by_name);
+        let data = LinkData::Component {
+            name: "amsmath.sty".into(),
+        };
+        assert_eq!(data.resolve(&resolver), Url::from_file_path(path).ok());
+    }
+
+    #[test]
+    fn resolve_component_ctan() {
+        let data = LinkData::Component {
+            name: "amsmath.sty".into(),
+        };
+        assert_eq!(
+            data.resolve(&Resolver::default()).unwrap().as_str(),
+            "https://ctan.org/pkg/amsmath"
+        );
+    }
+
+    #[test]
+    fn resolve_doi() {
+        let data = LinkData::Doi {
+            doi: "10.1000/xyz123".into(),
+        };
+        assert_eq!(
+            data.resolve(&Resolver::default()).unwrap().as_str(),
+            "https://doi.org/10.1000/xyz123"
+        );
     }
 }
@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// The time between two checks of a subscribed build log.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The maximum number of build logs that a client can follow at the same time.
+pub const MAX_SUBSCRIPTIONS: usize = 8;
+
+/// Follows a build log like `tail -f`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LogTail {
+    path: PathBuf,
+    offset: u64,
+    partial_line: Vec<u8>,
+}
+
+impl LogTail {
+    /// Starts at the current end of the log.
+    pub fn new(path: PathBuf) -> Self {
+        let offset = fs::metadata(&path).map(|data| data.len()).unwrap_or(0);
+        Self {
+            path,
+            offset,
+            partial_line: Vec::new(),
+        }
+    }
+
+    /// Returns the complete lines that were appended since the last call.
+    ///
+    /// A new build truncates the log, in which case the tail starts over.
+    pub fn poll(&mut self) -> Vec<String> {
+        let length = match fs::metadata(&self.path) {
+            Ok(data) => data.len(),
+            Err(_) => return Vec::new(),
+        };
+
+        if length < self.offset {
+            self.offset = 0;
+            self.partial_line.clear();
+        }
+
+        if length == self.offset {
+            return Vec::new();
+        }
+
+        let mut bytes = Vec::new();
+        let read = File::open(&self.path).and_then(|mut file| {
+            file.seek(SeekFrom::Start(self.offset))?;
+            file.take(length - self.offset).read_to_end(&mut bytes)
+        });
+        if read.is_err() {
+            return Vec::new();
+        }
+
+        self.offset += bytes.len() as u64;
+        self.partial_line.extend(bytes);
+
+        let mut lines = Vec::new();
+        while let Some(end) = self.partial_line.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.partial_line.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            lines.push(line.trim_end_matches(&['\r', '\n'][..]).to_owned());
+        }
+        lines
+    }
+}
+
+/// The build logs that the client follows.
+#[derive(Debug, Default)]
+pub struct LogSubscriptions {
+    ids: Mutex<HashSet<String>>,
+}
+
+impl LogSubscriptions {
+    /// Returns `None` if the client already follows `MAX_SUBSCRIPTIONS` logs.
+    pub fn subscribe(&self) -> Option<String> {
+        let mut ids = self.ids.lock().unwrap();
+        if ids.len() >= MAX_SUBSCRIPTIONS {
+            return None;
+        }
+
+        let id = Uuid::new_v4().to_string();
+        ids.insert(id.clone());
+        Some(id)
+    }
+
+    /// Returns `false` if there is no subscription with the given id.
+    pub fn unsubscribe(&self, id: &str) -> bool {
+        self.ids.lock().unwrap().remove(id)
+    }
+
+    pub fn is_active(&self, id: &str) -> bool {
+        self.ids.lock().unwrap().contains(id)
+    }
+
+    pub fn clear(&self) {
+        self.ids.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+
+    fn append(path: &PathBuf, text: &str) {
+        let mut file = OpenOptions::new().append(true).open(path).unwrap();
+        file.write_all(text.as_bytes()).unwrap();
+    }
+
+    #[test]
+    fn appended_lines() {
+        let directory = env::temp_dir().join("texlab-log-tail-test");
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("foo.log");
+        fs::write(&path, "old\n").unwrap();
+
+        let mut tail = LogTail::new(path.clone());
+        assert!(tail.poll().is_empty());
+
+        append(&path, "foo\r\nbar\nba");
+        assert_eq!(tail.poll(), vec!["foo", "bar"]);
+
+        append(&path, "z\n");
+        assert_eq!(tail.poll(), vec!["baz"]);
+
+        fs::write(&path, "new\n").unwrap();
+        assert_eq!(tail.poll(), vec!["new"]);
+    }
+
+    #[test]
+    fn subscriptions() {
+        let subscriptions = LogSubscriptions::default();
+        let id = subscriptions.subscribe().unwrap();
+        assert!(subscriptions.is_active(&id));
+        assert!(subscriptions.unsubscribe(&id));
+        assert!(!subscriptions.is_active(&id));
+        assert!(!subscriptions.unsubscribe(&id));
+    }
+
+    #[test]
+    fn too_many_subscriptions() {
+        let subscriptions = LogSubscriptions::default();
+        let ids: Vec<_> = (0..MAX_SUBSCRIPTIONS)
+            .map(|_| subscriptions.subscribe().unwrap())
+            .collect();
+        assert_eq!(subscriptions.subscribe(), None);
+
+        subscriptions.unsubscribe(&ids[0]);
+        assert!(subscriptions.subscribe().is_some());
+
+        subscriptions.clear();
+        assert!(ids.iter().all(|id| !subscriptions.is_active(id)));
+    }
+}
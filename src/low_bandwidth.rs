@@ -0,0 +1,85 @@
+use texlab_protocol::{
+    CompletionItem, LatexGrammarCheckOptions, LatexLintOptions, LatexOptions,
+    LatexSpellCheckOptions,
+};
+
+/// The factor by which the periodic linters are slowed down.
+pub const LINT_INTERVAL_FACTOR: u64 = 3;
+
+const MAX_FILTER_TEXT_LENGTH: usize = 64;
+
+/// Multiplies the delays of the periodic linters by `LINT_INTERVAL_FACTOR`.
+///
+/// Only the options of the slow client are changed,
+/// so that the other clients of a shared workspace are not slowed down.
+pub fn slow_down_linters(options: &mut LatexOptions) {
+    let lint = options.lint.get_or_insert_with(LatexLintOptions::default);
+    lint.delay = Some(lint.delay() * LINT_INTERVAL_FACTOR);
+
+    let spell_check = options
+        .spell_check
+        .get_or_insert_with(LatexSpellCheckOptions::default);
+    spell_check.delay = Some(spell_check.delay() * LINT_INTERVAL_FACTOR);
+
+    let grammar_check = options
+        .grammar_check
+        .get_or_insert_with(LatexGrammarCheckOptions::default);
+    grammar_check.delay = Some(grammar_check.delay() * LINT_INTERVAL_FACTOR);
+}
+
+/// Removes the documentation of a completion item and shortens its filter text.
+pub fn strip_completion_item(item: &mut CompletionItem) {
+    item.documentation = None;
+    if let Some(filter_text) = &mut item.filter_text {
+        if let Some((index, _)) = filter_text.char_indices().nth(MAX_FILTER_TEXT_LENGTH) {
+            filter_text.truncate(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::Documentation;
+
+    #[test]
+    fn strip() {
+        let mut item = CompletionItem {
+            label: "foo".into(),
+            documentation: Some(Documentation::String("bar".into())),
+            filter_text: Some("ä".repeat(100)),
+            ..CompletionItem::default()
+        };
+        strip_completion_item(&mut item);
+        assert_eq!(item.documentation, None);
+        assert_eq!(item.filter_text, Some("ä".repeat(MAX_FILTER_TEXT_LENGTH)));
+    }
+
+    #[test]
+    fn short_filter_text() {
+        let mut item = CompletionItem {
+            label: "foo".into(),
+            filter_text: Some("foo bar".into()),
+            ..CompletionItem::default()
+        };
+        strip_completion_item(&mut item);
+        assert_eq!(item.filter_text, Some("foo bar".into()));
+    }
+
+    #[test]
+    fn slow_down() {
+        let mut options = LatexOptions {
+            lint: Some(LatexLintOptions {
+                delay: Some(5),
+                ..LatexLintOptions::default()
+            }),
+            ..LatexOptions::default()
+        };
+        slow_down_linters(&mut options);
+        assert_eq!(options.lint.unwrap().delay, Some(5 * LINT_INTERVAL_FACTOR));
+        assert_eq!(
+            options.spell_check.unwrap().delay,
+            Some(LatexSpellCheckOptions::default().delay() * LINT_INTERVAL_FACTOR)
+        );
+    }
+}
@@ -0,0 +1,386 @@
+use crate::definition::DefinitionProvider;
+use crate::reference::ReferenceProvider;
+use crate::server::project_files;
+use crate::workspace_manager::WorkspaceManager;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+use texlab_distro::Distribution;
+use texlab_hover::HoverProvider;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+pub const LSIF_VERSION: &str = "0.4.3";
+
+type RangeKey = (Uri, u64, u64, u64, u64);
+
+/// Collects the vertices and edges of an LSIF dump.
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LsifWriter {
+    elements: Vec<Value>,
+    next_id: u64,
+    documents: HashMap<Uri, u64>,
+    ranges: HashMap<RangeKey, u64>,
+    contained_ranges: Vec<(u64, Vec<u64>)>,
+}
+
+impl LsifWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn emit(&mut self, mut element: Value) -> u64 {
+        self.next_id += 1;
+        element["id"] = self.next_id.into();
+        self.elements.push(element);
+        self.next_id
+    }
+
+    fn vertex(&mut self, label: &str, mut vertex: Value) -> u64 {
+        vertex["type"] = "vertex".into();
+        vertex["label"] = label.into();
+        self.emit(vertex)
+    }
+
+    fn edge(&mut self, label: &str, mut edge: Value) -> u64 {
+        edge["type"] = "edge".into();
+        edge["label"] = label.into();
+        self.emit(edge)
+    }
+
+    pub fn document(&mut self, document: &Document) -> u64 {
+        let language_id = match document.tree {
+            SyntaxTree::Latex(_) => "latex",
+            SyntaxTree::Bibtex(_) => "bibtex",
+        };
+
+        let id = self.vertex(
+            "document",
+            json!({ "uri": document.uri.as_str(), "languageId": language_id }),
+        );
+        self.documents.insert(document.uri.clone(), id);
+        self.contained_ranges.push((id, Vec::new()));
+        id
+    }
+
+    /// Returns the range vertex of the location or `None` if its document is not part of the dump.
+    pub fn range(&mut self, uri: &Uri, range: Range) -> Option<u64> {
+        let key = (
+            uri.clone(),
+            range.start.line,
+            range.start.character,
+            range.end.line,
+            range.end.character,
+        );
+        if let Some(id) = self.ranges.get(&key) {
+            return Some(*id);
+        }
+
+        let document_id = *self.documents.get(uri)?;
+        let id = self.vertex(
+            "range",
+            json!({
+                "start": { "line": range.start.line, "character": range.start.character },
+                "end": { "line": range.end.line, "character": range.end.character },
+            }),
+        );
+        self.ranges.insert(key, id);
+        if let Some((_, ranges)) = self
+            .contained_ranges
+            .iter_mut()
+            .find(|(document, _)| *document == document_id)
+        {
+            ranges.push(id);
+        }
+        Some(id)
+    }
+
+    pub fn definitions(&mut self, range_id: u64, targets: &[Location]) {
+        let targets = self.locations(targets);
+        if targets.is_empty() {
+            return;
+        }
+
+        let result_id = self.vertex("definitionResult", json!({}));
+        self.edge(
+            "textDocument/definition",
+            json!({ "outV": range_id, "inV": result_id }),
+        );
+        for (document_id, range_ids) in targets {
+            self.edge(
+                "item",
+                json!({ "outV": result_id, "inVs": range_ids, "document": document_id }),
+            );
+        }
+    }
+
+    pub fn references(&mut self, range_id: u64, definitions: &[Location], references: &[Location]) {
+        let definitions = self.locations(definitions);
+        let references = self.locations(references);
+        if definitions.is_empty() && references.is_empty() {
+            return;
+        }
+
+        let result_id = self.vertex("referenceResult", json!({}));
+        self.edge(
+            "textDocument/references",
+            json!({ "outV": range_id, "inV": result_id }),
+        );
+
+        let groups = vec![("definitions", definitions), ("references", references)];
+        for (property, targets) in groups {
+            for (document_id, range_ids) in targets {
+                self.edge(
+                    "item",
+                    json!({
+                        "outV": result_id,
+                        "inVs": range_ids,
+                        "document": document_id,
+                        "property": property,
+                    }),
+                );
+            }
+        }
+    }
+
+    pub fn hover(&mut self, range_id: u64, hover: &Hover) {
+        let result_id = self.vertex("hoverResult", json!({ "result": hover }));
+        self.edge(
+            "textDocument/hover",
+            json!({ "outV": range_id, "inV": result_id }),
+        );
+    }
+
+    /// Groups the range vertices of the locations by document.
+    fn locations(&mut self, locations: &[Location]) -> Vec<(u64, Vec<u64>)> {
+        let mut groups: Vec<(u64, Vec<u64>)> = Vec::new();
+        for location in locations {
+            let uri: Uri = location.uri.clone().into();
+            let range_id = match self.range(&uri, location.range) {
+                Some(range_id) => range_id,
+                None => continue,
+            };
+
+            let document_id = self.documents[&uri];
+            match groups.iter_mut().find(|(id, _)| *id == document_id) {
+                Some((_, range_ids)) => range_ids.push(range_id),
+                None => groups.push((document_id, vec![range_id])),
+            }
+        }
+        groups
+    }
+
+    /// Emits the edges that connect the project, its documents and their ranges.
+    pub fn finish(mut self, project_id: u64) -> Vec<Value> {
+        let document_ids: Vec<u64> = self.contained_ranges.iter().map(|(id, _)| *id).collect();
+        if !document_ids.is_empty() {
+            self.edge(
+                "contains",
+                json!({ "outV": project_id, "inVs": document_ids }),
+            );
+        }
+
+        for (document_id, range_ids) in std::mem::take(&mut self.contained_ranges) {
+            if !range_ids.is_empty() {
+                self.edge(
+                    "contains",
+                    json!({ "outV": document_id, "inVs": range_ids }),
+                );
+            }
+        }
+        self.elements
+    }
+}
+
+/// Creates an LSIF dump of the labels, citations and entries of the workspace.
+pub async fn index(
+    workspace: Arc<Workspace>,
+    distribution: Arc<Box<dyn Distribution>>,
+    root_directory: &Path,
+) -> Vec<Value> {
+    let options = Options::default();
+    let client_capabilities = Arc::new(ClientCapabilities::default());
+    let definition_provider = DefinitionProvider::new();
+    let reference_provider = ReferenceProvider::new();
+    let hover_provider = HoverProvider::new();
+
+    let mut writer = LsifWriter::new();
+    let project_root = Url::from_directory_path(root_directory)
+        .map(|uri| uri.to_string())
+        .unwrap_or_default();
+    writer.vertex(
+        "metaData",
+        json!({
+            "version": LSIF_VERSION,
+            "projectRoot": project_root,
+            "positionEncoding": "utf-16",
+            "toolInfo": { "name": "texlab", "version": env!("CARGO_PKG_VERSION") },
+        }),
+    );
+    let project_id = writer.vertex("project", json!({ "kind": "latex" }));
+
+    for document in &workspace.documents {
+        writer.document(document);
+    }
+
+    for document in &workspace.documents {
+        let request = |position| FeatureRequest {
+            params: TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(document.uri.clone().into()),
+                position,
+            ),
+            view: DocumentView::new(Arc::clone(&workspace), Arc::clone(document), &options),
+            client_capabilities: Arc::clone(&client_capabilities),
+            distribution: Arc::clone(&distribution),
+            options: options.clone(),
+        };
+
+        for range in symbol_ranges(document) {
+            let range_id = match writer.range(&document.uri, range) {
+                Some(range_id) => range_id,
+                None => continue,
+            };
+
+            let definition_request = request(range.start);
+            let definitions: Vec<Location> = definition_provider
+                .execute(&definition_request)
+                .await
+                .into_iter()
+                .map(|link| Location::new(link.target_uri, link.target_selection_range))
+                .collect();
+            writer.definitions(range_id, &definitions);
+
+            let reference_request = FeatureRequest {
+                params: ReferenceParams {
+                    text_document_position: definition_request.params.clone(),
+                    context: ReferenceContext {
+                        include_declaration: false,
+                    },
+                },
+                view: definition_request.view.clone(),
+                client_capabilities: Arc::clone(&client_capabilities),
+                distribution: Arc::clone(&distribution),
+                options: options.clone(),
+            };
+            let references = reference_provider.execute(&reference_request).await;
+            writer.references(range_id, &definitions, &references);
+
+            if let Some(hover) = hover_provider.execute(&definition_request).await {
+                writer.hover(range_id, &hover);
+            }
+        }
+    }
+    writer.finish(project_id)
+}
+
+/// Loads the project below the root directory and writes its LSIF dump to the output file.
+pub async fn dump(
+    root_directory: &Path,
+    distribution: Arc<Box<dyn Distribution>>,
+    output: &Path,
+) -> io::Result<()> {
+    let workspace_manager = WorkspaceManager::new(Arc::clone(&distribution));
+    workspace_manager.preload(&project_files(root_directory), &Options::default(), None);
+    let elements = index(workspace_manager.get(), distribution, root_directory).await;
+
+    let mut writer = BufWriter::new(File::create(output)?);
+    for element in elements {
+        serde_json::to_writer(&mut writer, &element)?;
+        writeln!(writer)?;
+    }
+    writer.flush()
+}
+
+/// The ranges of the labels and citation keys that are part of the dump.
+fn symbol_ranges(document: &Document) -> Vec<Range> {
+    let mut ranges = Vec::new();
+    match &document.tree {
+        SyntaxTree::Latex(tree) => {
+            for label in &tree.structure.labels {
+                ranges.extend(label.names().into_iter().map(SyntaxNode::range));
+            }
+
+            for citation in &tree.citations {
+                ranges.extend(citation.keys().into_iter().map(SyntaxNode::range));
+            }
+
+            ranges.extend(tree.bib_items.iter().map(|item| item.key().range()));
+        }
+        SyntaxTree::Bibtex(tree) => {
+            for entry in tree.entries() {
+                if let Some(key) = &entry.key {
+                    ranges.push(key.range());
+                }
+            }
+        }
+    }
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use texlab_distro::UnknownDistribution;
+
+    fn elements(files: &[(&str, &str)]) -> Vec<Value> {
+        let mut builder = TestWorkspaceBuilder::new();
+        for (name, text) in files {
+            builder.add_document(name, text);
+        }
+        let distribution: Box<dyn Distribution> = Box::new(UnknownDistribution::default());
+        block_on(index(
+            Arc::new(builder.workspace),
+            Arc::new(distribution),
+            &std::env::temp_dir(),
+        ))
+    }
+
+    fn labeled<'a>(elements: &'a [Value], label: &str) -> Vec<&'a Value> {
+        elements
+            .iter()
+            .filter(|element| element["label"] == label)
+            .collect()
+    }
+
+    #[test]
+    fn label() {
+        let elements = elements(&[("foo.tex", "\\label{foo}\n\\ref{foo}")]);
+        assert_eq!(elements[0]["label"], "metaData");
+        assert_eq!(labeled(&elements, "document").len(), 1);
+        assert_eq!(labeled(&elements, "range").len(), 2);
+
+        assert!(!labeled(&elements, "textDocument/definition").is_empty());
+
+        let contains = labeled(&elements, "contains");
+        assert_eq!(contains.len(), 2);
+        assert_eq!(contains[1]["inVs"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn citation() {
+        let elements = elements(&[
+            ("foo.tex", "\\addbibresource{bar.bib}\n\\cite{baz}"),
+            ("bar.bib", "@article{baz, title = {Baz}}"),
+        ]);
+        assert_eq!(labeled(&elements, "document").len(), 2);
+        assert_eq!(labeled(&elements, "range").len(), 2);
+        assert!(!labeled(&elements, "textDocument/references").is_empty());
+    }
+
+    #[test]
+    fn shared_ranges() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", "");
+        let mut writer = LsifWriter::new();
+        writer.document(&builder.workspace.documents[0]);
+        let range = Range::new_simple(0, 0, 0, 1);
+        assert_eq!(writer.range(&uri, range), writer.range(&uri, range));
+        let other = Uri::from_file_path(std::env::temp_dir().join("bar.tex")).unwrap();
+        assert_eq!(writer.range(&other, range), None);
+    }
+}
@@ -0,0 +1,110 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static MAGIC_COMMENT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^%\s*!\s*(?i:TEX)\s+(?P<key>[a-zA-Z\-]+)\s*=\s*(?P<value>.*)$").unwrap()
+});
+
+/// The `% !TEX key = value` comments at the top of a document.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct MagicComments {
+    pub program: Option<String>,
+    pub root: Option<String>,
+    pub spellcheck: Option<String>,
+}
+
+impl MagicComments {
+    pub fn parse(text: &str) -> Self {
+        let mut comments = Self::default();
+        for line in text
+            .lines()
+            .map(str::trim)
+            .take_while(|line| line.is_empty() || line.starts_with('%'))
+        {
+            if let Some(captures) = MAGIC_COMMENT_REGEX.captures(line) {
+                let value = captures["value"].trim().to_owned();
+                match captures["key"].to_lowercase().as_str() {
+                    "program" | "ts-program" if !value.is_empty() => {
+                        comments.program = Some(value);
+                    }
+                    "root" if !value.is_empty() => {
+                        comments.root = Some(value);
+                    }
+                    "spellcheck" if !value.is_empty() => {
+                        comments.spellcheck = Some(value);
+                    }
+                    _ => (),
+                }
+            }
+        }
+        comments
+    }
+
+    /// Returns the engine that is selected with `% !TEX program`.
+    ///
+    /// Only the engines in `TEX_ENGINES` are accepted; any other program is ignored.
+    pub fn engine(&self) -> Option<&'static str> {
+        let program = self.program.as_ref()?.to_lowercase();
+        TEX_ENGINES
+            .iter()
+            .find(|engine| **engine == program)
+            .copied()
+    }
+}
+
+/// The engines that may be selected with `% !TEX program`.
+const TEX_ENGINES: &[&str] = &["pdflatex", "xelatex", "lualatex", "latex"];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_program() {
+        let comments = MagicComments::parse("% !TEX program = xelatex\n\\documentclass{article}");
+        assert_eq!(comments.program, Some("xelatex".into()));
+    }
+
+    #[test]
+    fn parse_root() {
+        let comments = MagicComments::parse("%!TEX root = ../main.tex\n\\section{Foo}");
+        assert_eq!(comments.root, Some("../main.tex".into()));
+    }
+
+    #[test]
+    fn parse_spellcheck() {
+        let comments = MagicComments::parse("% !TeX spellcheck = de_DE\n\\section{Foo}");
+        assert_eq!(comments.spellcheck, Some("de_DE".into()));
+    }
+
+    #[test]
+    fn parse_after_preamble() {
+        let comments = MagicComments::parse("\\documentclass{article}\n% !TEX program = xelatex");
+        assert_eq!(comments.program, None);
+    }
+
+    #[test]
+    fn engine() {
+        let comments = MagicComments {
+            program: Some("XeLaTeX".into()),
+            root: None,
+            spellcheck: None,
+        };
+        assert_eq!(comments.engine(), Some("xelatex"));
+    }
+
+    #[test]
+    fn unknown_engine() {
+        let comments = MagicComments {
+            program: Some("/minio/evil.sh".into()),
+            root: None,
+            spellcheck: None,
+        };
+        assert_eq!(comments.engine(), None);
+    }
+
+    #[test]
+    fn engine_without_comments() {
+        assert_eq!(MagicComments::default().engine(), None);
+    }
+}
@@ -1,17 +1,60 @@
 use clap::{app_from_crate, crate_authors, crate_description, crate_name, crate_version, Arg};
-use futures::channel::mpsc;
+use futures::channel::{mpsc, oneshot};
+use futures::future::{self, Either};
+use futures::lock::Mutex;
 use futures::prelude::*;
-use jsonrpc::MessageHandler;
+use jsonrpc::{Message, MessageHandler};
+use log::*;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
 use std::error::Error;
+use std::fmt::Display;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use stderrlog::{ColorChoice, Timestamp};
+use texlab::limits::{ConnectionCounter, ConnectionGuard, ConnectionLimits};
+use texlab::output::OutputQueue;
+use texlab::policy::{ExternalCheckerDefinition, ToolPolicy};
+use texlab::presence::PresenceHub;
 use texlab::server::LatexLspServer;
-use texlab_distro::Distribution;
-use texlab_protocol::{LatexLspClient, LspCodec};
+use texlab::shared_workspace::WorkspaceRegistry;
+use texlab_distro::{Distribution, SharedDistribution};
+use texlab_protocol::{LatexLspClient, LspCodec, Url};
+use tokio::io::{self, AsyncRead, AsyncWrite};
 use tokio::net::TcpListener;
-use tokio::net::TcpStream;
+use tokio::time;
+use tokio_rustls::rustls::internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys};
+use tokio_rustls::rustls::{NoClientAuth, ServerConfig};
+use tokio_rustls::TlsAcceptor;
 use tokio_util::codec::{FramedRead, FramedWrite};
 
+/// The time that a client has to complete the TLS handshake.
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+static DISTRIBUTION: Lazy<Mutex<Option<Arc<Box<dyn Distribution>>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+static PRESENCE_HUB: Lazy<Arc<PresenceHub>> = Lazy::new(|| Arc::new(PresenceHub::new()));
+
+static WORKSPACE_REGISTRY: Lazy<Arc<WorkspaceRegistry>> =
+    Lazy::new(|| Arc::new(WorkspaceRegistry::new()));
+
+/// The settings that apply to every connection.
+#[derive(Debug)]
+struct ConnectionSettings {
+    shared: bool,
+    shared_workspace: bool,
+    limits: ConnectionLimits,
+    tools: ToolPolicy,
+    counter: Arc<ConnectionCounter>,
+    exit_on_disconnect: bool,
+    ping_interval: Option<Duration>,
+    shutdown: mpsc::UnboundedSender<()>,
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     let matches = app_from_crate!()
@@ -28,6 +71,106 @@ async fn main() -> Result<(), Box<dyn Error>> {
                 .short("q")
                 .help("No output printed to stderr"),
         )
+        .arg(
+            Arg::with_name("shared")
+                .long("shared")
+                .help("Relay the cursors of clients that open the same workspace"),
+        )
+        .arg(
+            Arg::with_name("shared-workspace")
+                .long("shared-workspace")
+                .help("Share documents and diagnostics between clients of the same workspace"),
+        )
+        .arg(
+            Arg::with_name("listen")
+                .long("listen")
+                .value_name("ADDRESS")
+                .takes_value(true)
+                .default_value("127.0.0.1:9998")
+                .help("Listen on this TCP address"),
+        )
+        .arg(
+            Arg::with_name("socket")
+                .long("socket")
+                .value_name("PATH")
+                .takes_value(true)
+                .help("Listen on a Unix domain socket instead of a TCP port"),
+        )
+        .arg(
+            Arg::with_name("tls-cert")
+                .long("tls-cert")
+                .value_name("FILE")
+                .takes_value(true)
+                .requires("tls-key")
+                .help("Encrypt TCP connections using the PEM certificate chain"),
+        )
+        .arg(
+            Arg::with_name("tls-key")
+                .long("tls-key")
+                .value_name("FILE")
+                .takes_value(true)
+                .requires("tls-cert")
+                .help("Private key of the TLS certificate in PEM format"),
+        )
+        .arg(
+            Arg::with_name("max-connections")
+                .long("max-connections")
+                .value_name("COUNT")
+                .takes_value(true)
+                .help("Reject clients once this many clients are connected"),
+        )
+        .arg(
+            Arg::with_name("max-documents")
+                .long("max-documents")
+                .value_name("COUNT")
+                .takes_value(true)
+                .help("Maximum number of documents that a client can open"),
+        )
+        .arg(
+            Arg::with_name("max-processes")
+                .long("max-processes")
+                .value_name("COUNT")
+                .takes_value(true)
+                .help("Maximum number of external tools that run for a client at the same time"),
+        )
+        .arg(
+            Arg::with_name("ping-interval")
+                .long("ping-interval")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .help("Disconnect clients that do not answer a ping within this interval"),
+        )
+        .arg(
+            Arg::with_name("install-missing-packages")
+                .long("install-missing-packages")
+                .help("Allow clients to install missing packages with the MiKTeX package manager"),
+        )
+        .arg(
+            Arg::with_name("languagetool-url")
+                .long("languagetool-url")
+                .value_name("URL")
+                .takes_value(true)
+                .help("Check the grammar of the documents with this LanguageTool server"),
+        )
+        .arg(
+            Arg::with_name("external-checkers")
+                .long("external-checkers")
+                .value_name("FILE")
+                .takes_value(true)
+                .help("Let clients select the external checkers defined in this JSON file"),
+        )
+        .arg(
+            Arg::with_name("index")
+                .long("index")
+                .value_name("PATH")
+                .takes_value(true)
+                .help("Write an LSIF dump of the project in the current directory and exit"),
+        )
+        .arg(
+            Arg::with_name("exit-on-disconnect")
+                .long("exit-on-disconnect")
+                .help("Stop the server once the last client has disconnected"),
+        )
         .get_matches();
 
     stderrlog::new()
@@ -48,60 +191,322 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .init()
         .unwrap();
 
-    let mut listener = TcpListener::bind("127.0.0.1:9998").await?;
+    if let Some(path) = matches.value_of("index") {
+        let root_directory = std::env::current_dir()?;
+        texlab::lsif::dump(&root_directory, distribution().await, Path::new(path)).await?;
+        return Ok(());
+    }
+
+    let parse_count = |name: &str| -> Result<Option<usize>, Box<dyn Error>> {
+        match matches.value_of(name) {
+            Some(value) => Ok(Some(value.parse()?)),
+            None => Ok(None),
+        }
+    };
+
+    let (shutdown_tx, mut shutdown_rx) = mpsc::unbounded();
+    let settings = Arc::new(ConnectionSettings {
+        shared: matches.is_present("shared"),
+        shared_workspace: matches.is_present("shared-workspace"),
+        limits: ConnectionLimits {
+            max_documents: parse_count("max-documents")?,
+            max_processes: parse_count("max-processes")?,
+        },
+        tools: ToolPolicy {
+            install_packages: matches.is_present("install-missing-packages"),
+            language_tool: match matches.value_of("languagetool-url") {
+                Some(url) => Some(Url::parse(url)?),
+                None => None,
+            },
+            external_checkers: match matches.value_of("external-checkers") {
+                Some(path) => ExternalCheckerDefinition::parse_all(&std::fs::read(path)?)?,
+                None => HashMap::new(),
+            },
+        },
+        counter: Arc::new(ConnectionCounter::new(parse_count("max-connections")?)),
+        exit_on_disconnect: matches.is_present("exit-on-disconnect"),
+        ping_interval: parse_count("ping-interval")?.map(|secs| Duration::from_secs(secs as u64)),
+        shutdown: shutdown_tx,
+    });
+
+    if let Some(path) = matches.value_of("socket") {
+        return listen_unix(path, settings, shutdown_rx).await;
+    }
+
+    let tls_acceptor = match (matches.value_of("tls-cert"), matches.value_of("tls-key")) {
+        (Some(cert), Some(key)) => Some(tls_acceptor(cert, key)?),
+        _ => None,
+    };
+
+    let mut listener = TcpListener::bind(matches.value_of("listen").unwrap()).await?;
 
     loop {
-        let (socket, addr) = listener.accept().await?;
-        tokio::spawn(accept_connection(socket, addr));
+        let (socket, addr) =
+            match future::select(Box::pin(listener.accept()), shutdown_rx.next()).await {
+                Either::Left((result, _)) => result?,
+                Either::Right(_) => return Ok(()),
+            };
+
+        // The slot is taken before the handshake so that pending handshakes count as well.
+        let connection = match acquire_connection(&settings, &addr) {
+            Some(connection) => connection,
+            None => continue,
+        };
+
+        let settings = Arc::clone(&settings);
+        match tls_acceptor.clone() {
+            Some(acceptor) => {
+                tokio::spawn(async move {
+                    match time::timeout(TLS_HANDSHAKE_TIMEOUT, acceptor.accept(socket)).await {
+                        Ok(Ok(stream)) => {
+                            accept_connection(stream, addr, connection, settings).await
+                        }
+                        Ok(Err(why)) => warn!("TLS handshake with {} failed: {}", addr, why),
+                        Err(_) => warn!("TLS handshake with {} timed out", addr),
+                    }
+                });
+            }
+            None => {
+                tokio::spawn(accept_connection(socket, addr, connection, settings));
+            }
+        }
+    }
+}
+
+fn tls_acceptor(cert_path: &str, key_path: &str) -> Result<TlsAcceptor, Box<dyn Error>> {
+    let cert_chain = certs(&mut BufReader::new(File::open(cert_path)?))
+        .map_err(|()| format!("Invalid certificate: {}", cert_path))?;
+
+    let mut keys = pkcs8_private_keys(&mut BufReader::new(File::open(key_path)?))
+        .map_err(|()| format!("Invalid private key: {}", key_path))?;
+    if keys.is_empty() {
+        keys = rsa_private_keys(&mut BufReader::new(File::open(key_path)?))
+            .map_err(|()| format!("Invalid private key: {}", key_path))?;
+    }
+    let key = keys
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("No private key found: {}", key_path))?;
+
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config.set_single_cert(cert_chain, key)?;
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+#[cfg(unix)]
+async fn listen_unix(
+    path: &str,
+    settings: Arc<ConnectionSettings>,
+    mut shutdown_rx: mpsc::UnboundedReceiver<()>,
+) -> Result<(), Box<dyn Error>> {
+    use std::os::unix::fs::FileTypeExt;
+    use tokio::net::UnixListener;
+
+    // Remove the socket of a previous run that did not shut down cleanly.
+    if let Ok(metadata) = std::fs::symlink_metadata(path) {
+        if metadata.file_type().is_socket() {
+            std::fs::remove_file(path)?;
+        }
+    }
+
+    let mut listener = UnixListener::bind(path)?;
+    loop {
+        let socket = match future::select(Box::pin(listener.accept()), shutdown_rx.next()).await {
+            Either::Left((result, _)) => result?.0,
+            Either::Right(_) => break,
+        };
+
+        let connection = match acquire_connection(&settings, path) {
+            Some(connection) => connection,
+            None => continue,
+        };
+
+        tokio::spawn(accept_connection(
+            socket,
+            path.to_owned(),
+            connection,
+            Arc::clone(&settings),
+        ));
+    }
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn listen_unix(
+    _path: &str,
+    _settings: Arc<ConnectionSettings>,
+    _shutdown_rx: mpsc::UnboundedReceiver<()>,
+) -> Result<(), Box<dyn Error>> {
+    Err("Unix domain sockets are not supported on this platform".into())
+}
+
+async fn distribution() -> Arc<Box<dyn Distribution>> {
+    let mut guard = DISTRIBUTION.lock().await;
+    if guard.is_none() {
+        let distro: Box<dyn Distribution> = Box::new(SharedDistribution::detect().await);
+        *guard = Some(Arc::new(distro));
     }
+    Arc::clone(guard.as_ref().unwrap())
 }
 
-async fn accept_connection(mut socket: TcpStream, addr: std::net::SocketAddr) {
+fn acquire_connection<A: Display>(
+    settings: &ConnectionSettings,
+    addr: A,
+) -> Option<ConnectionGuard> {
+    let connection = settings.counter.try_acquire();
+    if connection.is_none() {
+        warn!("Connection limit reached, rejecting {}", addr);
+    }
+    connection
+}
+
+async fn accept_connection<S, A>(
+    socket: S,
+    addr: A,
+    connection: ConnectionGuard,
+    settings: Arc<ConnectionSettings>,
+) where
+    S: AsyncRead + AsyncWrite,
+    A: Display,
+{
     println!("hello there! start serving {}", addr);
-    let (reader, writer) = socket.split();
+    let (reader, writer) = io::split(socket);
     let mut stdout = FramedWrite::new(writer, LspCodec);
     let mut stdin = FramedRead::new(reader, LspCodec);
-    let (stdout_tx, mut stdout_rx) = mpsc::channel(0);
-    let distro = Arc::new(Distribution::detect().await);
+    let (stdout_tx, mut stdout_rx) = mpsc::channel::<Message>(64);
+    let (mut wakeup_tx, mut wakeup_rx) = mpsc::channel::<()>(1);
+    let queue = Mutex::new(OutputQueue::new());
+    let distro = distribution().await;
     let client = Arc::new(LatexLspClient::new(stdout_tx.clone()));
     let server = Arc::new(LatexLspServer::new(
         Arc::clone(&client),
         Arc::clone(&distro),
     ));
-    let mut stdout_tx_shutdown = stdout_tx.clone();
-    let mut handler = MessageHandler {
-        server: Arc::clone(&server),
-        client: Arc::clone(&client),
-        output: stdout_tx,
-    };
+    server.set_limits(settings.limits);
+    server.set_tool_policy(settings.tools.clone());
+    if settings.shared {
+        server.share_workspace(Arc::clone(&PRESENCE_HUB));
+    }
+    if settings.shared_workspace {
+        server.use_workspace_registry(Arc::clone(&WORKSPACE_REGISTRY));
+    }
+    let mut handler = MessageHandler::new(Arc::clone(&server), Arc::clone(&client), stdout_tx);
+    let (timeout_tx, timeout_rx) = mpsc::unbounded();
+    let mut exit = stream::select(server.exit_signal(), timeout_rx);
+    let (stop_heartbeat_tx, stop_heartbeat_rx) = oneshot::channel::<()>();
+    let (kill_tx, mut kill_rx) = oneshot::channel::<()>();
+    let (overflow_tx, overflow_rx) = oneshot::channel::<()>();
+    let ping_interval = settings.ping_interval;
+    let addr = &addr;
+    let queue = &queue;
 
-    tokio::join!(
-        async move {
-            loop {
-                let message = stdout_rx.next().await.unwrap();
-                if message == "kill" {
-                    break;
+    let tasks = async move {
+        tokio::join!(
+            async move {
+                // Keep accepting messages while the client is not reading.
+                loop {
+                    let message = match future::select(stdout_rx.next(), &mut kill_rx).await {
+                        Either::Left((Some(message), _)) => message,
+                        Either::Left((None, _)) | Either::Right(_) => break,
+                    };
+
+                    if !queue.lock().await.push(message) {
+                        warn!("{} stopped reading, closing the connection", addr);
+                        let _ = overflow_tx.send(());
+                        return;
+                    }
+                    let _ = wakeup_tx.try_send(());
                 }
-                let status = stdout.send(message).await;
-                match status {
-                    Ok(_) => {}
-                    Err(_) => break,
+
+                // Flush the messages that were sent before the client disconnected.
+                let mut queue = queue.lock().await;
+                while let Ok(Some(message)) = stdout_rx.try_next() {
+                    queue.push(message);
                 }
-            }
-        },
-        async move {
-            while let Some(json) = stdin.next().await {
-                match &json {
-                    Ok(jsonmsg) => handler.handle(jsonmsg).await,
-                    Err(_) => {
-                        break;
+                let _ = wakeup_tx.try_send(());
+            },
+            async move {
+                while wakeup_rx.next().await.is_some() {
+                    loop {
+                        let message = match queue.lock().await.pop() {
+                            Some(message) => serde_json::to_string(&message).unwrap(),
+                            None => break,
+                        };
+
+                        // A client that does not read for a whole ping interval is considered dead.
+                        let status = match ping_interval {
+                            Some(deadline) => time::timeout(deadline, stdout.send(message))
+                                .await
+                                .unwrap_or_else(|elapsed| {
+                                    Err(io::Error::new(io::ErrorKind::TimedOut, elapsed))
+                                }),
+                            None => stdout.send(message).await,
+                        };
+
+                        if let Err(why) = status {
+                            error!("Failed to send a message to {}: {}", addr, why);
+                            return;
+                        }
+                    }
+                }
+            },
+            async move {
+                loop {
+                    // Stop reading once the client has sent the `exit` notification.
+                    let json = match future::select(stdin.next(), exit.next()).await {
+                        Either::Left((Some(json), _)) => json,
+                        Either::Left((None, _)) | Either::Right(_) => break,
+                    };
+
+                    match &json {
+                        Ok(jsonmsg) => handler.handle(jsonmsg).await,
+                        Err(why) => {
+                            error!("Failed to read a message from {}: {}", addr, why);
+                            break;
+                        }
                     }
                 }
+                drop(stop_heartbeat_tx);
+                let _ = kill_tx.send(());
+                println!("Connection break {}", addr);
+            },
+            async move {
+                let interval = match ping_interval {
+                    Some(interval) => interval,
+                    None => return,
+                };
+
+                let heartbeat = async {
+                    loop {
+                        time::delay_for(interval).await;
+                        if time::timeout(interval, client.ping(())).await.is_err() {
+                            warn!("{} did not answer the ping in time", addr);
+                            let _ = timeout_tx.unbounded_send(());
+                            break;
+                        }
+                    }
+                };
+                future::select(Box::pin(heartbeat), stop_heartbeat_rx).await;
             }
-            stdout_tx_shutdown.send("kill".to_string()).await.unwrap();
-            println!("Connection break {}", addr);
+        )
+    };
+
+    // Dropping the tasks closes the connection even if the writer is stuck.
+    let overflow = async move {
+        if overflow_rx.await.is_err() {
+            future::pending::<()>().await;
         }
-    );
+    };
+    future::select(Box::pin(tasks), Box::pin(overflow)).await;
 
+    server.stop_sharing();
+    server.unsubscribe_logs();
     println!("Connection cleanup! {}", addr);
+
+    drop(connection);
+    if settings.exit_on_disconnect && settings.counter.active() == 0 {
+        let _ = settings.shutdown.unbounded_send(());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,111 @@
+use texlab_protocol::*;
+use texlab_syntax::*;
+
+pub const GOTO_MATCHING_ENVIRONMENT_COMMAND: &str = "texlab.gotoMatchingEnvironment";
+
+/// Returns the delimiter that matches the innermost environment or equation
+/// that encloses the given position.
+///
+/// The cursor jumps from `\begin` to `\end` and from everywhere else
+/// inside of the environment back to `\begin`.
+pub fn find(tree: &LatexSyntaxTree, position: Position) -> Option<Range> {
+    let environments = tree
+        .env
+        .environments
+        .iter()
+        .map(|env| (env.left.range(), env.right.range()));
+    let equations = tree
+        .math
+        .equations
+        .iter()
+        .map(|equation| (equation.left.range(), equation.right.range()));
+    let inlines = tree
+        .math
+        .inlines
+        .iter()
+        .map(|inline| (inline.left.range(), inline.right.range()));
+
+    let (left, right) = environments
+        .chain(equations)
+        .chain(inlines)
+        .filter(|(left, right)| Range::new(left.start, right.end).contains(position))
+        .max_by_key(|(left, _)| left.start)?;
+
+    if left.contains(position) && !right.contains(position) {
+        Some(right)
+    } else {
+        Some(left)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn find_in(text: &str, position: Position) -> Option<Range> {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", text);
+        let document = builder.workspace.find(&uri).unwrap();
+        match &document.tree {
+            SyntaxTree::Latex(tree) => find(tree, position),
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn begin_to_end() {
+        let text = "\\begin{foo}\n\\begin{bar}\n\\end{bar}\n\\end{foo}";
+        assert_eq!(
+            find_in(text, Position::new(0, 3)),
+            Some(Range::new_simple(3, 0, 3, 9))
+        );
+        assert_eq!(
+            find_in(text, Position::new(1, 3)),
+            Some(Range::new_simple(2, 0, 2, 9))
+        );
+    }
+
+    #[test]
+    fn end_to_begin() {
+        let text = "\\begin{foo}\n\\begin{bar}\n\\end{bar}\n\\end{foo}";
+        assert_eq!(
+            find_in(text, Position::new(3, 3)),
+            Some(Range::new_simple(0, 0, 0, 11))
+        );
+    }
+
+    #[test]
+    fn inside_of_environment() {
+        let text = "\\begin{foo}\nbar\n\\end{foo}";
+        assert_eq!(
+            find_in(text, Position::new(1, 1)),
+            Some(Range::new_simple(0, 0, 0, 11))
+        );
+    }
+
+    #[test]
+    fn equation() {
+        let text = "\\begin{foo}\n\\[\n  x\n\\]\n\\(y\\)\n\\end{foo}";
+        assert_eq!(
+            find_in(text, Position::new(1, 1)),
+            Some(Range::new_simple(3, 0, 3, 2))
+        );
+        assert_eq!(
+            find_in(text, Position::new(2, 2)),
+            Some(Range::new_simple(1, 0, 1, 2))
+        );
+        assert_eq!(
+            find_in(text, Position::new(4, 1)),
+            Some(Range::new_simple(4, 3, 4, 5))
+        );
+    }
+
+    #[test]
+    fn outside_of_environment() {
+        assert_eq!(
+            find_in("foo\n\\begin{bar}\\end{bar}", Position::new(0, 1)),
+            None
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use futures_boxed::boxed;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+pub const LABEL_SCHEME: &str = "latex-label";
+
+pub const BIBTEX_KEY_SCHEME: &str = "bibtex-key";
+
+/// Provides the monikers of labels and citation keys.
+///
+/// Labels are only unique within a project, while citation keys usually refer
+/// to the same entry across projects that share a bibliography.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MonikerProvider;
+
+impl FeatureProvider for MonikerProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Vec<Moniker>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<TextDocumentPositionParams>,
+    ) -> Vec<Moniker> {
+        let position = request.params.position;
+        let moniker = match &request.document().tree {
+            SyntaxTree::Latex(tree) => label_moniker(tree, position)
+                .or_else(|| citation_moniker(tree, position))
+                .or_else(|| {
+                    tree.bib_item_key_at(position)
+                        .map(|key| key_moniker(key.text(), MonikerKind::Export))
+                }),
+            SyntaxTree::Bibtex(tree) => tree
+                .entries()
+                .into_iter()
+                .filter_map(|entry| entry.key.as_ref())
+                .find(|key| key.range().contains(position))
+                .map(|key| key_moniker(key.text(), MonikerKind::Export)),
+        };
+        moniker.into_iter().collect()
+    }
+}
+
+fn label_moniker(tree: &LatexSyntaxTree, position: Position) -> Option<Moniker> {
+    for label in &tree.structure.labels {
+        if let Some(name) = label
+            .names()
+            .into_iter()
+            .find(|name| name.range().contains(position))
+        {
+            let kind = match label.kind {
+                LatexLabelKind::Definition => MonikerKind::Export,
+                LatexLabelKind::Reference(_) => MonikerKind::Import,
+            };
+
+            return Some(Moniker {
+                scheme: LABEL_SCHEME.into(),
+                identifier: name.text().into(),
+                unique: UniquenessLevel::Project,
+                kind: Some(kind),
+            });
+        }
+    }
+    None
+}
+
+fn citation_moniker(tree: &LatexSyntaxTree, position: Position) -> Option<Moniker> {
+    tree.citations
+        .iter()
+        .flat_map(LatexCitation::keys)
+        .find(|key| key.range().contains(position))
+        .map(|key| key_moniker(key.text(), MonikerKind::Import))
+}
+
+fn key_moniker(key: &str, kind: MonikerKind) -> Moniker {
+    Moniker {
+        scheme: BIBTEX_KEY_SCHEME.into(),
+        identifier: key.into(),
+        unique: UniquenessLevel::Scheme,
+        kind: Some(kind),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monikers(file: &'static str, text: &'static str, position: Position) -> Vec<Moniker> {
+        test_feature(
+            MonikerProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(file, text)],
+                main_file: file,
+                position,
+                ..FeatureSpec::default()
+            },
+        )
+    }
+
+    #[test]
+    fn label_definition() {
+        assert_eq!(
+            monikers("foo.tex", "\\label{foo}\n\\ref{foo}", Position::new(0, 8)),
+            vec![Moniker {
+                scheme: LABEL_SCHEME.into(),
+                identifier: "foo".into(),
+                unique: UniquenessLevel::Project,
+                kind: Some(MonikerKind::Export),
+            }]
+        );
+    }
+
+    #[test]
+    fn label_reference() {
+        let monikers = monikers("foo.tex", "\\label{foo}\n\\ref{foo}", Position::new(1, 6));
+        assert_eq!(monikers.len(), 1);
+        assert_eq!(monikers[0].kind, Some(MonikerKind::Import));
+    }
+
+    #[test]
+    fn citation() {
+        assert_eq!(
+            monikers("foo.tex", "\\cite{foo,bar}", Position::new(0, 11)),
+            vec![Moniker {
+                scheme: BIBTEX_KEY_SCHEME.into(),
+                identifier: "bar".into(),
+                unique: UniquenessLevel::Scheme,
+                kind: Some(MonikerKind::Import),
+            }]
+        );
+    }
+
+    #[test]
+    fn entry() {
+        let monikers = monikers(
+            "foo.bib",
+            "@article{foo, bar = {baz}}",
+            Position::new(0, 10),
+        );
+        assert_eq!(monikers.len(), 1);
+        assert_eq!(monikers[0].identifier, "foo");
+        assert_eq!(monikers[0].kind, Some(MonikerKind::Export));
+    }
+
+    #[test]
+    fn no_symbol() {
+        assert!(monikers("foo.tex", "\\section{foo}", Position::new(0, 10)).is_empty());
+    }
+}
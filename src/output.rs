@@ -0,0 +1,176 @@
+use jsonrpc::Message;
+use std::collections::VecDeque;
+
+/// The maximum number of queued messages.
+///
+/// Progress reports are dropped first once the limit is reached.
+/// If there are none left, the client has stopped reading.
+pub const MAX_QUEUED_MESSAGES: usize = 256;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum MessageKind {
+    /// A `report` of a work done progress that is superseded by the next report.
+    ProgressReport(serde_json::Value),
+    /// Diagnostics that are superseded by the next diagnostics of the document.
+    Diagnostics(serde_json::Value),
+    Other,
+}
+
+impl MessageKind {
+    fn of(message: &Message) -> Self {
+        let notification = match message {
+            Message::Notification(notification) => notification,
+            Message::Request(_) | Message::Response(_) => return MessageKind::Other,
+        };
+
+        let params = &notification.params;
+        match notification.method.as_str() {
+            "$/progress" if params["value"]["kind"] == "report" => {
+                MessageKind::ProgressReport(params["token"].clone())
+            }
+            "textDocument/publishDiagnostics" => MessageKind::Diagnostics(params["uri"].clone()),
+            _ => MessageKind::Other,
+        }
+    }
+
+    fn is_superseded_by(&self, other: &Self) -> bool {
+        *self != MessageKind::Other && self == other
+    }
+}
+
+/// Buffers the messages for the client while it is not reading.
+///
+/// Outdated progress reports and diagnostics are merged with newer ones.
+/// If the client falls behind, progress reports are dropped first.
+/// The messages are kept unserialized, so that they can be classified without parsing them.
+#[derive(Debug, Default)]
+pub struct OutputQueue {
+    messages: VecDeque<(MessageKind, Message)>,
+}
+
+impl OutputQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `false` if the queue is full and the connection should be closed.
+    pub fn push(&mut self, message: Message) -> bool {
+        let kind = MessageKind::of(&message);
+        if let Some(queued) = self
+            .messages
+            .iter_mut()
+            .find(|(queued_kind, _)| queued_kind.is_superseded_by(&kind))
+        {
+            queued.1 = message;
+            return true;
+        }
+
+        if self.messages.len() >= MAX_QUEUED_MESSAGES {
+            let report = self.messages.iter().position(|(kind, _)| match kind {
+                MessageKind::ProgressReport(_) => true,
+                MessageKind::Diagnostics(_) | MessageKind::Other => false,
+            });
+
+            match (report, &kind) {
+                (Some(index), _) => {
+                    self.messages.remove(index);
+                }
+                (None, MessageKind::ProgressReport(_)) => return true,
+                (None, _) => return false,
+            }
+        }
+        self.messages.push_back((kind, message));
+        true
+    }
+
+    pub fn pop(&mut self) -> Option<Message> {
+        self.messages.pop_front().map(|(_, message)| message)
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpc::{Id, Notification, Response};
+    use serde_json::json;
+
+    fn progress(token: &str, kind: &str, percentage: u64) -> Message {
+        let params = json!({
+            "token": token,
+            "value": { "kind": kind, "percentage": percentage }
+        });
+        Message::Notification(Notification::new("$/progress".into(), params))
+    }
+
+    fn diagnostics(uri: &str, count: usize) -> Message {
+        let params = json!({ "uri": uri, "diagnostics": vec![json!({}); count] });
+        Message::Notification(Notification::new(
+            "textDocument/publishDiagnostics".into(),
+            params,
+        ))
+    }
+
+    fn response(id: u64) -> Message {
+        Message::Response(Response::result(serde_json::Value::Null, Id::Number(id)))
+    }
+
+    #[test]
+    fn merge_progress_reports() {
+        let mut queue = OutputQueue::new();
+        queue.push(progress("foo", "begin", 0));
+        queue.push(progress("foo", "report", 10));
+        queue.push(progress("bar", "report", 20));
+        queue.push(progress("foo", "report", 30));
+        queue.push(progress("foo", "end", 100));
+
+        assert_eq!(queue.pop(), Some(progress("foo", "begin", 0)));
+        assert_eq!(queue.pop(), Some(progress("foo", "report", 30)));
+        assert_eq!(queue.pop(), Some(progress("bar", "report", 20)));
+        assert_eq!(queue.pop(), Some(progress("foo", "end", 100)));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn merge_diagnostics() {
+        let mut queue = OutputQueue::new();
+        queue.push(diagnostics("file:///foo.tex", 1));
+        queue.push(response(1));
+        queue.push(diagnostics("file:///foo.tex", 2));
+
+        assert_eq!(queue.pop(), Some(diagnostics("file:///foo.tex", 2)));
+        assert_eq!(queue.pop(), Some(response(1)));
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn drop_progress_reports_first() {
+        let mut queue = OutputQueue::new();
+        queue.push(progress("foo", "report", 0));
+        for id in 1..MAX_QUEUED_MESSAGES as u64 {
+            queue.push(response(id));
+        }
+        queue.push(progress("bar", "report", 0));
+        assert_eq!(queue.len(), MAX_QUEUED_MESSAGES);
+        assert_eq!(queue.pop(), Some(response(1)));
+
+        queue.push(response(0));
+        queue.push(response(0));
+        queue.push(progress("baz", "report", 0));
+        assert_eq!(queue.len(), MAX_QUEUED_MESSAGES);
+        assert!(queue
+            .messages
+            .iter()
+            .all(|(kind, _)| *kind == MessageKind::Other));
+
+        assert!(!queue.push(response(0)));
+        assert_eq!(queue.len(), MAX_QUEUED_MESSAGES);
+    }
+}
@@ -0,0 +1,441 @@
+use texlab_protocol::PasteFormat;
+
+/// Converts text from the clipboard to LaTeX.
+pub fn convert(text: &str, format: Option<PasteFormat>) -> String {
+    let text = text.replace("\r\n", "\n");
+    match format.unwrap_or_else(|| detect_format(&text)) {
+        PasteFormat::PlainText => TextConverter::default().convert(&text),
+        PasteFormat::Html => HtmlConverter::default().convert(&text),
+    }
+}
+
+fn detect_format(text: &str) -> PasteFormat {
+    let text = text.trim();
+    if text.starts_with('<') && text.ends_with('>') && text.contains("</") {
+        PasteFormat::Html
+    } else {
+        PasteFormat::PlainText
+    }
+}
+
+/// Escapes the special characters and replaces the typographic characters
+/// with their LaTeX counterparts.
+#[derive(Debug, Default)]
+struct TextConverter {
+    is_quote_open: bool,
+}
+
+impl TextConverter {
+    fn convert(&mut self, text: &str) -> String {
+        let mut output = String::new();
+        for c in text.chars() {
+            match c {
+                '\\' => output.push_str("\\textbackslash{}"),
+                '{' | '}' | '$' | '&' | '%' | '#' | '_' => {
+                    output.push('\\');
+                    output.push(c);
+                }
+                '~' => output.push_str("\\textasciitilde{}"),
+                '^' => output.push_str("\\textasciicircum{}"),
+                '"' => {
+                    output.push_str(if self.is_quote_open { "''" } else { "``" });
+                    self.is_quote_open = !self.is_quote_open;
+                }
+                '\u{201C}' => output.push_str("``"),
+                '\u{201D}' => output.push_str("''"),
+                '\u{2018}' => output.push('`'),
+                '\u{2019}' => output.push('\''),
+                '\u{2013}' => output.push_str("--"),
+                '\u{2014}' => output.push_str("---"),
+                '\u{2026}' => output.push_str("\\ldots{}"),
+                '\u{A0}' => output.push('~'),
+                _ => output.push(c),
+            }
+        }
+        output
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+enum HtmlToken {
+    Text(String),
+    Start(String),
+    End(String),
+}
+
+/// Splits the markup into text and tags while dropping comments and declarations.
+fn tokenize(html: &str) -> Vec<HtmlToken> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let start = match rest.find('<') {
+            Some(start) => start,
+            None => {
+                tokens.push(HtmlToken::Text(rest.to_owned()));
+                break;
+            }
+        };
+
+        if start > 0 {
+            tokens.push(HtmlToken::Text(rest[..start].to_owned()));
+        }
+        rest = &rest[start..];
+
+        if rest.starts_with("<!--") {
+            rest = match rest.find("-->") {
+                Some(end) => &rest[end + 3..],
+                None => "",
+            };
+            continue;
+        }
+
+        let end = match rest.find('>') {
+            Some(end) => end,
+            None => {
+                tokens.push(HtmlToken::Text(rest.to_owned()));
+                break;
+            }
+        };
+
+        let tag = &rest[1..end];
+        let is_end = tag.starts_with('/');
+        let name: String = tag
+            .trim_start_matches('/')
+            .chars()
+            .take_while(char::is_ascii_alphanumeric)
+            .collect::<String>()
+            .to_lowercase();
+        if !name.is_empty() {
+            tokens.push(if is_end {
+                HtmlToken::End(name)
+            } else {
+                HtmlToken::Start(name)
+            });
+        }
+        rest = &rest[end + 1..];
+    }
+    tokens
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = match rest.find(';') {
+            Some(end) if end <= 10 => end,
+            _ => {
+                output.push('&');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+
+        let entity = &rest[1..end];
+        let c = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{A0}'),
+            "ndash" => Some('\u{2013}'),
+            "mdash" => Some('\u{2014}'),
+            "hellip" => Some('\u{2026}'),
+            _ if entity.starts_with("#x") || entity.starts_with("#X") => {
+                u32::from_str_radix(&entity[2..], 16)
+                    .ok()
+                    .and_then(std::char::from_u32)
+            }
+            _ if entity.starts_with('#') => entity[1..]
+                .parse::<u32>()
+                .ok()
+                .and_then(std::char::from_u32),
+            _ => None,
+        };
+
+        match c {
+            Some(c) => {
+                output.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                output.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+#[derive(Debug, Default)]
+struct HtmlTable {
+    rows: Vec<Vec<String>>,
+    cell: Option<String>,
+}
+
+impl HtmlTable {
+    fn render(&self) -> String {
+        let columns = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut output = format!("\\begin{{tabular}}{{{}}}\n", "l".repeat(columns));
+        for row in self.rows.iter().filter(|row| !row.is_empty()) {
+            let mut cells = row.clone();
+            cells.resize(columns, String::new());
+            output.push_str(&cells.join(" & "));
+            output.push_str(" \\\\\n");
+        }
+        output.push_str("\\end{tabular}\n");
+        output
+    }
+}
+
+/// Maps simple formatting, lists and tables to LaTeX and drops the remaining markup.
+#[derive(Debug, Default)]
+struct HtmlConverter {
+    output: String,
+    text: TextConverter,
+    groups: Vec<String>,
+    tables: Vec<HtmlTable>,
+    skipped: usize,
+}
+
+impl HtmlConverter {
+    fn convert(mut self, html: &str) -> String {
+        for token in tokenize(html) {
+            match token {
+                HtmlToken::Text(text) => self.visit_text(&text),
+                HtmlToken::Start(name) => self.visit_start(&name),
+                HtmlToken::End(name) => self.visit_end(&name),
+            }
+        }
+
+        self.close_groups();
+        self.output.trim().to_owned()
+    }
+
+    /// Returns the table cell or the document to which the output is written.
+    fn buffer(&mut self) -> Option<&mut String> {
+        match self.tables.last_mut() {
+            Some(table) => table.cell.as_mut(),
+            None => Some(&mut self.output),
+        }
+    }
+
+    fn write(&mut self, text: &str) {
+        if let Some(buffer) = self.buffer() {
+            buffer.push_str(text);
+        }
+    }
+
+    fn newline(&mut self) {
+        if let Some(buffer) = self.buffer() {
+            let length = buffer.trim_end_matches(' ').len();
+            buffer.truncate(length);
+            if !buffer.is_empty() && !buffer.ends_with('\n') {
+                buffer.push('\n');
+            }
+        }
+    }
+
+    fn paragraph(&mut self) {
+        self.close_groups();
+        self.newline();
+        if let Some(buffer) = self.buffer() {
+            if !buffer.is_empty() && !buffer.ends_with("\n\n") {
+                buffer.push('\n');
+            }
+        }
+    }
+
+    fn visit_text(&mut self, text: &str) {
+        if self.skipped > 0 {
+            return;
+        }
+
+        let mut text = decode_entities(text)
+            .split(|c: char| c.is_whitespace() && c != '\u{A0}')
+            .collect::<Vec<_>>()
+            .join(" ");
+        while text.contains("  ") {
+            text = text.replace("  ", " ");
+        }
+
+        let is_line_start = match self.buffer() {
+            Some(buffer) => buffer.is_empty() || buffer.ends_with('\n') || buffer.ends_with(' '),
+            None => return,
+        };
+        let text = if is_line_start {
+            text.trim_start()
+        } else {
+            &text
+        };
+
+        let converted = self.text.convert(text);
+        self.write(&converted);
+    }
+
+    fn visit_start(&mut self, name: &str) {
+        match name {
+            "script" | "style" | "head" => self.skipped += 1,
+            "em" | "i" => self.open_group(name, "\\emph{"),
+            "strong" | "b" => self.open_group(name, "\\textbf{"),
+            "code" | "tt" => self.open_group(name, "\\texttt{"),
+            "br" => {
+                self.write("\\\\");
+                self.newline();
+            }
+            "p" | "div" => self.paragraph(),
+            "ul" | "ol" => {
+                self.newline();
+                self.write(if name == "ul" {
+                    "\\begin{itemize}"
+                } else {
+                    "\\begin{enumerate}"
+                });
+                self.newline();
+            }
+            "li" => {
+                self.close_groups();
+                self.newline();
+                self.write("\\item ");
+            }
+            "table" => self.tables.push(HtmlTable::default()),
+            "tr" => {
+                if let Some(table) = self.tables.last_mut() {
+                    table.rows.push(Vec::new());
+                }
+            }
+            "td" | "th" => {
+                if let Some(table) = self.tables.last_mut() {
+                    if table.rows.is_empty() {
+                        table.rows.push(Vec::new());
+                    }
+                    table.cell = Some(String::new());
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn visit_end(&mut self, name: &str) {
+        match name {
+            "script" | "style" | "head" => self.skipped = self.skipped.saturating_sub(1),
+            "em" | "i" | "strong" | "b" | "code" | "tt" => self.close_group(name),
+            "p" | "div" => self.paragraph(),
+            "ul" | "ol" => {
+                self.close_groups();
+                self.newline();
+                self.write(if name == "ul" {
+                    "\\end{itemize}"
+                } else {
+                    "\\end{enumerate}"
+                });
+                self.newline();
+            }
+            "td" | "th" => {
+                self.close_groups();
+                if let Some(table) = self.tables.last_mut() {
+                    if let Some(cell) = table.cell.take() {
+                        let cell = cell.trim().to_owned();
+                        table.rows.last_mut().unwrap().push(cell);
+                    }
+                }
+            }
+            "table" => {
+                if let Some(table) = self.tables.pop() {
+                    self.newline();
+                    self.write(&table.render());
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn open_group(&mut self, name: &str, prefix: &str) {
+        self.groups.push(name.to_owned());
+        self.write(prefix);
+    }
+
+    fn close_groups(&mut self) {
+        while self.groups.pop().is_some() {
+            self.write("}");
+        }
+    }
+
+    /// Closes the group of the tag and the groups that were opened after it.
+    fn close_group(&mut self, name: &str) {
+        if !self.groups.iter().any(|group| group == name) {
+            return;
+        }
+
+        while let Some(group) = self.groups.pop() {
+            self.write("}");
+            if group == name {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn special_characters() {
+        assert_eq!(
+            convert("50% of $5 & #1_a {b} ~ ^ \\", Some(PasteFormat::PlainText)),
+            "50\\% of \\$5 \\& \\#1\\_a \\{b\\} \\textasciitilde{} \\textasciicircum{} \\textbackslash{}"
+        );
+    }
+
+    #[test]
+    fn typography() {
+        assert_eq!(
+            convert(
+                "\"Foo\" \u{201C}bar\u{201D} \u{2018}baz\u{2019} 1\u{2013}2 a\u{2014}b\u{2026}",
+                None
+            ),
+            "``Foo'' ``bar'' `baz' 1--2 a---b\\ldots{}"
+        );
+    }
+
+    #[test]
+    fn html_formatting() {
+        assert_eq!(
+            convert(
+                "<p>An <em>important</em> and <b>bold &amp; <i>nested</i></b> claim.</p>\
+                 <!-- comment --><p>Second&nbsp;paragraph</p>",
+                None
+            ),
+            "An \\emph{important} and \\textbf{bold \\& \\emph{nested}} claim.\n\nSecond~paragraph"
+        );
+    }
+
+    #[test]
+    fn html_list() {
+        assert_eq!(
+            convert("<ul>\n<li>foo</li>\n<li>bar</li>\n</ul>", None),
+            "\\begin{itemize}\n\\item foo\n\\item bar\n\\end{itemize}"
+        );
+    }
+
+    #[test]
+    fn html_table() {
+        assert_eq!(
+            convert(
+                "<table><tr><th>Name</th><th>Value</th></tr>\
+                 <tr><td>a_1</td><td>1</td></tr><tr><td>b</td></tr></table>",
+                Some(PasteFormat::Html)
+            ),
+            "\\begin{tabular}{ll}\nName & Value \\\\\na\\_1 & 1 \\\\\nb &  \\\\\n\\end{tabular}"
+        );
+    }
+
+    #[test]
+    fn unbalanced_tags() {
+        assert_eq!(convert("<p><b>foo</i></p>", None), "\\textbf{foo}");
+    }
+}
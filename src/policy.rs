@@ -0,0 +1,75 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::error::Error;
+use texlab_protocol::Url;
+
+/// The external tools that the operator of the server allows the clients to use.
+///
+/// Unlike the options of a client, the policy is passed on the command line,
+/// so that a client cannot make the server run programs on its own.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct ToolPolicy {
+    /// Allows clients to install missing packages with the MiKTeX package manager.
+    pub install_packages: bool,
+
+    /// The base URL of the LanguageTool server that checks the grammar,
+    /// e.g. `http://localhost:8081`.
+    pub language_tool: Option<Url>,
+
+    /// The external checkers that clients can select by name
+    /// in addition to the built-in presets.
+    pub external_checkers: HashMap<String, ExternalCheckerDefinition>,
+}
+
+/// The command line of an external checker and the format of its output.
+///
+/// The `{file}` placeholder of the arguments is replaced with the path of the document.
+/// The pattern captures the `line` of a finding and optionally its `column`, `end_line`,
+/// `end_column`, `length`, `severity`, `code` and `message`.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize)]
+pub struct ExternalCheckerDefinition {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub pattern: String,
+}
+
+impl ExternalCheckerDefinition {
+    /// Parses a JSON object that maps the names of the checkers to their definitions.
+    pub fn parse_all(json: &[u8]) -> Result<HashMap<String, Self>, Box<dyn Error>> {
+        let checkers: HashMap<String, Self> = serde_json::from_slice(json)?;
+        for (name, checker) in &checkers {
+            if checker.command.is_empty() {
+                return Err(format!("The external checker {} has no command", name).into());
+            }
+
+            Regex::new(&checker.pattern).map_err(|why| {
+                format!("Invalid pattern of the external checker {}: {}", name, why)
+            })?;
+        }
+        Ok(checkers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_external_checkers() {
+        let json = br#"{ "lint": { "command": "lint", "args": ["{file}"], "pattern": "^(?P<line>\\d+)" } }"#;
+        let checkers = ExternalCheckerDefinition::parse_all(json).unwrap();
+        assert_eq!(checkers["lint"].command, "lint");
+        assert_eq!(checkers["lint"].args, vec!["{file}"]);
+    }
+
+    #[test]
+    fn invalid_external_checkers() {
+        let json = br#"{ "lint": { "command": "lint", "pattern": "(" } }"#;
+        assert!(ExternalCheckerDefinition::parse_all(json).is_err());
+
+        let json = br#"{ "lint": { "command": "", "pattern": "^" } }"#;
+        assert!(ExternalCheckerDefinition::parse_all(json).is_err());
+    }
+}
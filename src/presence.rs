@@ -0,0 +1,150 @@
+use futures::channel::mpsc;
+use std::sync::Mutex;
+use texlab_protocol::{PresenceParams, Uri};
+
+#[derive(Debug)]
+struct Participant {
+    session: String,
+    workspace: Uri,
+    sender: mpsc::UnboundedSender<PresenceParams>,
+}
+
+/// Relays the cursors of the participants that share a workspace.
+///
+/// Participants share a workspace if they were initialized with the same root.
+/// Clients without a root do not take part because they have nothing in common.
+#[derive(Debug, Default)]
+pub struct PresenceHub {
+    participants: Mutex<Vec<Participant>>,
+}
+
+impl PresenceHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn join(
+        &self,
+        session: String,
+        workspace: Option<Uri>,
+        sender: mpsc::UnboundedSender<PresenceParams>,
+    ) {
+        let mut participants = self.participants.lock().unwrap();
+        participants.retain(|participant| participant.session != session);
+        let workspace = match workspace {
+            Some(workspace) => workspace,
+            None => return,
+        };
+
+        participants.push(Participant {
+            session,
+            workspace,
+            sender,
+        });
+    }
+
+    pub fn leave(&self, session: &str) {
+        self.relay(PresenceParams {
+            session: session.to_owned(),
+            uri: None,
+            selections: Vec::new(),
+        });
+
+        let mut participants = self.participants.lock().unwrap();
+        participants.retain(|participant| participant.session != session);
+    }
+
+    /// Sends the update to every other participant of the sender's workspace.
+    pub fn relay(&self, params: PresenceParams) {
+        let mut participants = self.participants.lock().unwrap();
+        let workspace = match participants
+            .iter()
+            .find(|participant| participant.session == params.session)
+        {
+            Some(sender) => sender.workspace.clone(),
+            None => return,
+        };
+
+        participants.retain(|participant| {
+            participant.session == params.session
+                || participant.workspace != workspace
+                || participant.sender.unbounded_send(params.clone()).is_ok()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Range, RangeExt, Url};
+
+    fn participant(
+        hub: &PresenceHub,
+        session: &str,
+        workspace: &str,
+    ) -> mpsc::UnboundedReceiver<PresenceParams> {
+        let (sender, receiver) = mpsc::unbounded();
+        let workspace = Url::parse(workspace).unwrap().into();
+        hub.join(session.into(), Some(workspace), sender);
+        receiver
+    }
+
+    fn update(session: &str) -> PresenceParams {
+        PresenceParams {
+            session: session.into(),
+            uri: Some(Url::parse("file:///foo/bar.tex").unwrap()),
+            selections: vec![Range::new_simple(1, 2, 1, 5)],
+        }
+    }
+
+    #[test]
+    fn relay_to_same_workspace() {
+        let hub = PresenceHub::new();
+        let mut foo = participant(&hub, "foo", "file:///foo");
+        let mut bar = participant(&hub, "bar", "file:///foo");
+        let mut baz = participant(&hub, "baz", "file:///baz");
+
+        hub.relay(update("foo"));
+        assert!(foo.try_next().is_err());
+        assert_eq!(bar.try_next().unwrap(), Some(update("foo")));
+        assert!(baz.try_next().is_err());
+    }
+
+    #[test]
+    fn leave() {
+        let hub = PresenceHub::new();
+        let _foo = participant(&hub, "foo", "file:///foo");
+        let mut bar = participant(&hub, "bar", "file:///foo");
+
+        hub.leave("foo");
+        let params = bar.try_next().unwrap().unwrap();
+        assert_eq!(params.session, "foo");
+        assert_eq!(params.uri, None);
+
+        hub.relay(update("foo"));
+        assert!(bar.try_next().is_err());
+    }
+
+    #[test]
+    fn drop_closed_participants() {
+        let hub = PresenceHub::new();
+        let _foo = participant(&hub, "foo", "file:///foo");
+        drop(participant(&hub, "bar", "file:///foo"));
+
+        hub.relay(update("foo"));
+        assert_eq!(hub.participants.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn ignore_participants_without_workspace() {
+        let hub = PresenceHub::new();
+        let (foo_sender, _foo) = mpsc::unbounded();
+        hub.join("foo".into(), None, foo_sender);
+        let (bar_sender, mut bar) = mpsc::unbounded();
+        hub.join("bar".into(), None, bar_sender);
+
+        hub.relay(update("foo"));
+        assert!(bar.try_next().is_err());
+        assert!(hub.participants.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use texlab_protocol::LatexPreviewOptions;
+
+/// Limits the rate of previews while allowing short bursts.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: u32,
+    refill_interval: Duration,
+    tokens: u32,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: u32, refill_interval: Duration, now: Instant) -> Self {
+        Self {
+            capacity,
+            refill_interval,
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let refill_millis = self.refill_interval.as_millis();
+        if self.tokens >= self.capacity || refill_millis == 0 {
+            self.tokens = self.capacity;
+            self.last_refill = now;
+            return;
+        }
+
+        let elapsed = now.duration_since(self.last_refill);
+        let new_tokens =
+            (elapsed.as_millis() / refill_millis).min(u128::from(self.capacity)) as u32;
+        if new_tokens > 0 {
+            self.tokens = (self.tokens + new_tokens).min(self.capacity);
+            self.last_refill += self.refill_interval * new_tokens;
+        }
+    }
+
+    /// Takes a token or returns the time until the next token is available.
+    pub fn take(&mut self, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+        if self.tokens > 0 {
+            self.tokens -= 1;
+            Ok(())
+        } else {
+            Err(self.refill_interval - now.duration_since(self.last_refill))
+        }
+    }
+}
+
+/// Schedules the previews that the client compiles while the user is typing.
+///
+/// Every change starts a new generation, which supersedes the pending previews
+/// and tells the client to cancel the builds of older generations.
+#[derive(Debug, Default)]
+pub struct PreviewManager {
+    generation: AtomicU64,
+    bucket: Mutex<Option<TokenBucket>>,
+}
+
+impl PreviewManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn next_generation(&self) -> u64 {
+        self.generation.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub fn is_current(&self, generation: u64) -> bool {
+        self.generation.load(Ordering::SeqCst) == generation
+    }
+
+    pub fn take(&self, options: &LatexPreviewOptions, now: Instant) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap();
+        let refill_interval = Duration::from_millis(options.refill_interval());
+        let is_outdated = match bucket.as_ref() {
+            Some(bucket) => {
+                bucket.capacity != options.burst() || bucket.refill_interval != refill_interval
+            }
+            None => true,
+        };
+
+        if is_outdated {
+            *bucket = Some(TokenBucket::new(options.burst(), refill_interval, now));
+        }
+        bucket.as_mut().unwrap().take(now)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burst() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2, Duration::from_secs(5), now);
+        assert_eq!(bucket.take(now), Ok(()));
+        assert_eq!(bucket.take(now), Ok(()));
+        assert_eq!(
+            bucket.take(now + Duration::from_secs(1)),
+            Err(Duration::from_secs(4))
+        );
+    }
+
+    #[test]
+    fn refill() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2, Duration::from_secs(5), now);
+        assert_eq!(bucket.take(now), Ok(()));
+        assert_eq!(bucket.take(now), Ok(()));
+        let later = now + Duration::from_secs(11);
+        assert_eq!(bucket.take(later), Ok(()));
+        assert_eq!(bucket.take(later), Ok(()));
+        assert!(bucket.take(later).is_err());
+    }
+
+    #[test]
+    fn superseded() {
+        let manager = PreviewManager::new();
+        let first = manager.next_generation();
+        let second = manager.next_generation();
+        assert!(!manager.is_current(first));
+        assert!(manager.is_current(second));
+    }
+}
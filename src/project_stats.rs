@@ -0,0 +1,150 @@
+use crate::diagnostics::extract_words;
+use texlab_protocol::{Diagnostic, DiagnosticSeverity, LatexSpellCheckOptions, Statistics};
+use texlab_syntax::*;
+
+const FIGURE_ENVIRONMENTS: &[&str] = &["figure", "wrapfigure", "subfigure"];
+
+const TABLE_ENVIRONMENTS: &[&str] = &["table", "wraptable", "subtable"];
+
+const EQUATION_ENVIRONMENTS: &[&str] = &[
+    "equation",
+    "align",
+    "alignat",
+    "flalign",
+    "gather",
+    "multline",
+    "eqnarray",
+    "displaymath",
+];
+
+/// Counts the words, floats, displayed equations and cited keys of a document.
+///
+/// Words are counted the same way as they are spell checked,
+/// so math, code and references are left out.
+pub fn analyze(tree: &LatexSyntaxTree, options: &LatexSpellCheckOptions) -> Statistics {
+    let mut statistics = Statistics {
+        words: extract_words(tree, options).len() as u64,
+        ..Statistics::default()
+    };
+    for environment in &tree.env.environments {
+        let name = match environment.left.name() {
+            Some(name) => name.text().trim_end_matches('*'),
+            None => continue,
+        };
+
+        if FIGURE_ENVIRONMENTS.contains(&name) {
+            statistics.figures += 1;
+        } else if TABLE_ENVIRONMENTS.contains(&name) {
+            statistics.tables += 1;
+        } else if EQUATION_ENVIRONMENTS.contains(&name) {
+            statistics.equations += 1;
+        }
+    }
+
+    statistics.equations += tree
+        .math
+        .equations
+        .iter()
+        .filter(|equation| equation.left.name.text() == "\\[")
+        .count() as u64;
+
+    statistics.citations = tree
+        .citations
+        .iter()
+        .map(|citation| citation.keys().len() as u64)
+        .sum();
+    statistics
+}
+
+pub fn count_diagnostics(statistics: &mut Statistics, diagnostics: &[Diagnostic]) {
+    for diagnostic in diagnostics {
+        match diagnostic.severity.unwrap_or(DiagnosticSeverity::Error) {
+            DiagnosticSeverity::Error => statistics.errors += 1,
+            DiagnosticSeverity::Warning => statistics.warnings += 1,
+            DiagnosticSeverity::Information => statistics.information += 1,
+            DiagnosticSeverity::Hint => statistics.hints += 1,
+        }
+    }
+}
+
+pub fn merge(project: &mut Statistics, statistics: &Statistics) {
+    project.words += statistics.words;
+    project.figures += statistics.figures;
+    project.tables += statistics.tables;
+    project.equations += statistics.equations;
+    project.citations += statistics.citations;
+    project.errors += statistics.errors;
+    project.warnings += statistics.warnings;
+    project.information += statistics.information;
+    project.hints += statistics.hints;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Range, RangeExt};
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    fn statistics(text: &str) -> Statistics {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", text);
+        let document = builder.workspace.find(&uri).unwrap();
+        match &document.tree {
+            SyntaxTree::Latex(tree) => analyze(tree, &LatexSpellCheckOptions::default()),
+            SyntaxTree::Bibtex(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn content() {
+        let statistics = statistics(
+            "\\section{Intro}\nSome words $x = 1$ here \\cite{foo, bar}.\n\
+             \\begin{figure*}\\end{figure*}\n\\begin{table}\\end{table}\n\
+             \\begin{align}\\begin{aligned}\\end{aligned}\\end{align}\n\\[ y \\]\n\\( z \\)",
+        );
+        assert_eq!(statistics.words, 4);
+        assert_eq!(statistics.figures, 1);
+        assert_eq!(statistics.tables, 1);
+        assert_eq!(statistics.equations, 2);
+        assert_eq!(statistics.citations, 2);
+    }
+
+    #[test]
+    fn diagnostics() {
+        let diagnostic = |severity| Diagnostic {
+            range: Range::new_simple(0, 0, 0, 1),
+            severity,
+            code: None,
+            source: None,
+            message: "foo".into(),
+            related_information: None,
+        };
+
+        let mut statistics = Statistics::default();
+        count_diagnostics(
+            &mut statistics,
+            &[
+                diagnostic(Some(DiagnosticSeverity::Error)),
+                diagnostic(None),
+                diagnostic(Some(DiagnosticSeverity::Warning)),
+                diagnostic(Some(DiagnosticSeverity::Hint)),
+            ],
+        );
+        assert_eq!(statistics.errors, 2);
+        assert_eq!(statistics.warnings, 1);
+        assert_eq!(statistics.information, 0);
+        assert_eq!(statistics.hints, 1);
+    }
+
+    #[test]
+    fn merge_documents() {
+        let mut project = statistics("foo bar \\cite{baz}");
+        merge(
+            &mut project,
+            &statistics("qux \\begin{figure}\\end{figure}"),
+        );
+        assert_eq!(project.words, 3);
+        assert_eq!(project.citations, 1);
+        assert_eq!(project.figures, 1);
+    }
+}
@@ -17,15 +17,27 @@ impl FeatureProvider for BibtexEntryReferenceProvider {
         if let Some(key) = Self::find_key(request) {
             for document in request.related_documents() {
                 match &document.tree {
-                    SyntaxTree::Latex(tree) => tree
-                        .citations
-                        .iter()
-                        .flat_map(LatexCitation::keys)
-                        .filter(|citation| citation.text() == key)
-                        .map(|citation| {
-                            Location::new(document.uri.clone().into(), citation.range())
-                        })
-                        .for_each(|location| references.push(location)),
+                    SyntaxTree::Latex(tree) => {
+                        tree.citations
+                            .iter()
+                            .flat_map(LatexCitation::keys)
+                            .filter(|citation| citation.text() == key)
+                            .map(|citation| {
+                                Location::new(document.uri.clone().into(), citation.range())
+                            })
+                            .for_each(|location| references.push(location));
+
+                        if request.params.context.include_declaration {
+                            tree.bib_items
+                                .iter()
+                                .map(LatexBibItem::key)
+                                .filter(|key_token| key_token.text() == key)
+                                .map(|key_token| {
+                                    Location::new(document.uri.clone().into(), key_token.range())
+                                })
+                                .for_each(|location| references.push(location));
+                        }
+                    }
                     SyntaxTree::Bibtex(tree) => {
                         if request.params.context.include_declaration {
                             for entry in tree.entries() {
@@ -57,6 +69,7 @@ impl BibtexEntryReferenceProvider {
                     key.range()
                         .contains(request.params.text_document_position.position)
                 })
+                .or_else(|| tree.bib_item_key_at(request.params.text_document_position.position))
                 .map(LatexToken::text),
             SyntaxTree::Bibtex(tree) => {
                 for entry in tree.entries() {
@@ -181,6 +194,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn bib_item_include_declaration() {
+        let references = test_feature(
+            BibtexEntryReferenceProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\cite{foo}\n\\begin{thebibliography}{9}\n\\bibitem{foo} Foo\n\\end{thebibliography}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 10),
+                include_declaration: true,
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(
+            references,
+            vec![
+                Location::new(FeatureSpec::uri("foo.tex"), Range::new_simple(0, 6, 0, 9)),
+                Location::new(FeatureSpec::uri("foo.tex"), Range::new_simple(2, 9, 2, 12)),
+            ]
+        );
+    }
+
     #[test]
     fn empty() {
         let references = test_feature(
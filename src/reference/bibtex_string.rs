@@ -1,6 +1,6 @@
 use futures_boxed::boxed;
 use texlab_protocol::RangeExt;
-use texlab_protocol::{Location, Position, ReferenceParams, Url};
+use texlab_protocol::{Location, ReferenceParams, Url};
 use texlab_syntax::*;
 use texlab_workspace::*;
 
@@ -15,8 +15,7 @@ impl FeatureProvider for BibtexStringReferenceProvider {
     async fn execute<'a>(&'a self, request: &'a FeatureRequest<ReferenceParams>) -> Vec<Location> {
         let mut references = Vec::new();
         if let SyntaxTree::Bibtex(tree) = &request.document().tree {
-            if let Some(name) =
-                Self::find_name(tree, request.params.text_document_position.position)
+            if let Some(name) = tree.string_name_at(request.params.text_document_position.position)
             {
                 let uri: Url = request.document().uri.clone().into();
                 if request.params.context.include_declaration {
@@ -29,10 +28,7 @@ impl FeatureProvider for BibtexStringReferenceProvider {
                     }
                 }
 
-                let mut visitor = BibtexStringReferenceVisitor::default();
-                visitor.visit_root(&tree.root);
-                visitor
-                    .references
+                tree.string_references()
                     .into_iter()
                     .filter(|reference| reference.text() == name.text())
                     .map(|reference| Location::new(uri.clone(), reference.range()))
@@ -43,79 +39,6 @@ impl FeatureProvider for BibtexStringReferenceProvider {
     }
 }
 
-impl BibtexStringReferenceProvider {
-    fn find_name(tree: &BibtexSyntaxTree, position: Position) -> Option<&BibtexToken> {
-        let mut nodes = tree.find(position);
-        nodes.reverse();
-        match (&nodes[0], nodes.get(1)) {
-            (BibtexNode::Word(word), Some(BibtexNode::Field(_)))
-            | (BibtexNode::Word(word), Some(BibtexNode::Concat(_))) => Some(&word.token),
-            (BibtexNode::String(string), _) => string
-                .name
-                .as_ref()
-                .filter(|name| name.range().contains(position)),
-            _ => None,
-        }
-    }
-}
-
-#[derive(Debug, Default)]
-struct BibtexStringReferenceVisitor<'a> {
-    references: Vec<&'a BibtexToken>,
-}
-
-impl<'a> BibtexVisitor<'a> for BibtexStringReferenceVisitor<'a> {
-    fn visit_root(&mut self, root: &'a BibtexRoot) {
-        BibtexWalker::walk_root(self, root);
-    }
-
-    fn visit_comment(&mut self, _comment: &'a BibtexComment) {}
-
-    fn visit_preamble(&mut self, preamble: &'a BibtexPreamble) {
-        BibtexWalker::walk_preamble(self, preamble);
-    }
-
-    fn visit_string(&mut self, string: &'a BibtexString) {
-        BibtexWalker::walk_string(self, string);
-    }
-
-    fn visit_entry(&mut self, entry: &'a BibtexEntry) {
-        BibtexWalker::walk_entry(self, entry);
-    }
-
-    fn visit_field(&mut self, field: &'a BibtexField) {
-        if let Some(BibtexContent::Word(word)) = &field.content {
-            self.references.push(&word.token);
-        }
-
-        BibtexWalker::walk_field(self, field);
-    }
-
-    fn visit_word(&mut self, _word: &'a BibtexWord) {}
-
-    fn visit_command(&mut self, _command: &'a BibtexCommand) {}
-
-    fn visit_quoted_content(&mut self, content: &'a BibtexQuotedContent) {
-        BibtexWalker::walk_quoted_content(self, content);
-    }
-
-    fn visit_braced_content(&mut self, content: &'a BibtexBracedContent) {
-        BibtexWalker::walk_braced_content(self, content);
-    }
-
-    fn visit_concat(&mut self, concat: &'a BibtexConcat) {
-        if let BibtexContent::Word(word) = &concat.left {
-            self.references.push(&word.token);
-        }
-
-        if let Some(BibtexContent::Word(word)) = &concat.right {
-            self.references.push(&word.token);
-        }
-
-        BibtexWalker::walk_concat(self, concat);
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
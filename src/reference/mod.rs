@@ -6,7 +6,7 @@ use self::bibtex_entry::BibtexEntryReferenceProvider;
 use self::bibtex_string::BibtexStringReferenceProvider;
 use self::latex_label::LatexLabelReferenceProvider;
 use futures_boxed::boxed;
-use texlab_protocol::{Location, ReferenceParams};
+use texlab_protocol::{Location, ReferenceParams, ReferencePreview};
 use texlab_workspace::*;
 
 pub struct ReferenceProvider {
@@ -40,3 +40,53 @@ impl FeatureProvider for ReferenceProvider {
         self.provider.execute(request).await
     }
 }
+
+/// Attaches the lines around the reference so that clients without access
+/// to the file can show it.
+pub fn preview(workspace: &Workspace, location: Location, context_lines: u64) -> ReferencePreview {
+    let first_line = location.range.start.line.saturating_sub(context_lines);
+    let last_line = location.range.end.line.saturating_add(context_lines);
+    let lines = match workspace.find(&location.uri.clone().into()) {
+        Some(document) => document
+            .text
+            .lines()
+            .skip(first_line as usize)
+            .take((last_line - first_line + 1) as usize)
+            .map(ToOwned::to_owned)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    ReferencePreview {
+        location,
+        first_line,
+        lines,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_protocol::{Range, RangeExt};
+
+    #[test]
+    fn surrounding_lines() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", "a\nb\n\\ref{foo}\nc\nd\ne");
+        let location = Location::new(uri.into(), Range::new_simple(2, 5, 2, 8));
+        let preview = preview(&builder.workspace, location.clone(), 1);
+        assert_eq!(preview.location, location);
+        assert_eq!(preview.first_line, 1);
+        assert_eq!(preview.lines, vec!["b", "\\ref{foo}", "c"]);
+    }
+
+    #[test]
+    fn start_of_document() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let uri = builder.add_document("foo.tex", "\\ref{foo}\nb");
+        let location = Location::new(uri.into(), Range::new_simple(0, 5, 0, 8));
+        let preview = preview(&builder.workspace, location, 3);
+        assert_eq!(preview.first_line, 0);
+        assert_eq!(preview.lines, vec!["\\ref{foo}", "b"]);
+    }
+}
@@ -0,0 +1,143 @@
+use texlab_protocol::*;
+
+const WATCHED_FILES_GLOB: &str = "**/*.{tex,sty,cls,bib}";
+
+/// Collects the capabilities that are registered dynamically after the initialization.
+///
+/// A capability is only included if the client announced support for its dynamic registration.
+pub fn registrations(capabilities: &ClientCapabilities) -> Vec<Registration> {
+    let mut registrations = Vec::new();
+    registrations.extend(configuration(
+        capabilities.has_pull_configuration_support(),
+        capabilities.has_push_configuration_support(),
+    ));
+    if capabilities.has_watched_files_registration_support() {
+        registrations.push(watched_files());
+    }
+    registrations
+}
+
+/// Downgrades the rename capability for clients that cannot send `textDocument/prepareRename`.
+pub fn rename_provider(capabilities: &ClientCapabilities) -> RenameProviderCapability {
+    if capabilities.has_prepare_rename_support() {
+        RenameProviderCapability::Options(RenameOptions {
+            prepare_provider: Some(true),
+        })
+    } else {
+        RenameProviderCapability::Simple(true)
+    }
+}
+
+fn configuration(pull_support: bool, push_support: bool) -> Option<Registration> {
+    if !pull_support && push_support {
+        Some(Registration {
+            id: "pull-config".into(),
+            method: "workspace/didChangeConfiguration".into(),
+            register_options: None,
+        })
+    } else {
+        None
+    }
+}
+
+fn watched_files() -> Registration {
+    let options = DidChangeWatchedFilesRegistrationOptions {
+        watchers: vec![FileSystemWatcher {
+            glob_pattern: WATCHED_FILES_GLOB.into(),
+            kind: None,
+        }],
+    };
+    Registration {
+        id: "watched-files".into(),
+        method: "workspace/didChangeWatchedFiles".into(),
+        register_options: Some(serde_json::to_value(options).unwrap()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_dynamic_registration() {
+        let capabilities = ClientCapabilities::default();
+        assert!(registrations(&capabilities).is_empty());
+    }
+
+    #[test]
+    fn dynamic_registration_disabled() {
+        let capabilities = ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                did_change_watched_files: Some(GenericCapability {
+                    dynamic_registration: Some(false),
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert!(registrations(&capabilities).is_empty());
+    }
+
+    #[test]
+    fn watched_files() {
+        let capabilities = ClientCapabilities {
+            workspace: Some(WorkspaceClientCapabilities {
+                did_change_watched_files: Some(GenericCapability {
+                    dynamic_registration: Some(true),
+                }),
+                ..WorkspaceClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        let registrations = registrations(&capabilities);
+        assert_eq!(registrations.len(), 1);
+        assert_eq!(registrations[0].method, "workspace/didChangeWatchedFiles");
+
+        let options: DidChangeWatchedFilesRegistrationOptions =
+            serde_json::from_value(registrations[0].register_options.clone().unwrap()).unwrap();
+        assert_eq!(options.watchers[0].glob_pattern, WATCHED_FILES_GLOB);
+    }
+
+    #[test]
+    fn configuration_push() {
+        let registration = configuration(false, true).unwrap();
+        assert_eq!(registration.id, "pull-config");
+        assert_eq!(registration.method, "workspace/didChangeConfiguration");
+    }
+
+    #[test]
+    fn configuration_pull() {
+        assert!(configuration(true, true).is_none());
+        assert!(configuration(true, false).is_none());
+        assert!(configuration(false, false).is_none());
+    }
+
+    #[test]
+    fn rename_with_prepare_support() {
+        let capabilities = ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                rename: Some(RenameCapability {
+                    prepare_support: Some(true),
+                    ..RenameCapability::default()
+                }),
+                ..TextDocumentClientCapabilities::default()
+            }),
+            ..ClientCapabilities::default()
+        };
+        assert_eq!(
+            rename_provider(&capabilities),
+            RenameProviderCapability::Options(RenameOptions {
+                prepare_provider: Some(true),
+            })
+        );
+    }
+
+    #[test]
+    fn rename_without_prepare_support() {
+        let capabilities = ClientCapabilities::default();
+        assert_eq!(
+            rename_provider(&capabilities),
+            RenameProviderCapability::Simple(true)
+        );
+    }
+}
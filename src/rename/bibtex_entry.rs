@@ -50,6 +50,13 @@ impl FeatureProvider for BibtexEntryRenameProvider {
                             TextEdit::new(citation.range(), request.params.new_name.clone())
                         })
                         .for_each(|edit| edits.push(edit));
+
+                    tree.bib_items
+                        .iter()
+                        .map(LatexBibItem::key)
+                        .filter(|key| key.text() == key_name.text)
+                        .map(|key| TextEdit::new(key.range(), request.params.new_name.clone()))
+                        .for_each(|edit| edits.push(edit));
                 }
                 SyntaxTree::Bibtex(tree) => {
                     for entry in tree.entries() {
@@ -81,7 +88,7 @@ fn find_key(tree: &SyntaxTree, position: Position) -> Option<&Span> {
                     }
                 }
             }
-            None
+            tree.bib_item_key_at(position).map(|key| &key.span)
         }
         SyntaxTree::Bibtex(tree) => {
             for entry in tree.entries() {
@@ -155,6 +162,32 @@ mod tests {
         assert_eq!(edit, Some(WorkspaceEdit::new(changes)));
     }
 
+    #[test]
+    fn bib_item() {
+        let edit = test_feature(
+            BibtexEntryRenameProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file(
+                    "foo.tex",
+                    "\\cite{foo}\n\\begin{thebibliography}{9}\n\\bibitem{foo} Foo\n\\end{thebibliography}",
+                )],
+                main_file: "foo.tex",
+                position: Position::new(2, 10),
+                new_name: "qux",
+                ..FeatureSpec::default()
+            },
+        );
+        let mut changes = HashMap::new();
+        changes.insert(
+            FeatureSpec::uri("foo.tex"),
+            vec![
+                TextEdit::new(Range::new_simple(0, 6, 0, 9), "qux".into()),
+                TextEdit::new(Range::new_simple(2, 9, 2, 12), "qux".into()),
+            ],
+        );
+        assert_eq!(edit, Some(WorkspaceEdit::new(changes)));
+    }
+
     #[test]
     fn field_name() {
         let edit = test_feature(
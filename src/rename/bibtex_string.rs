@@ -0,0 +1,135 @@
+use futures_boxed::boxed;
+use std::collections::HashMap;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexStringPrepareRenameProvider;
+
+impl FeatureProvider for BibtexStringPrepareRenameProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Option<Range>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<TextDocumentPositionParams>,
+    ) -> Option<Range> {
+        if let SyntaxTree::Bibtex(tree) = &request.document().tree {
+            tree.string_name_at(request.params.position)
+                .map(BibtexToken::range)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct BibtexStringRenameProvider;
+
+impl FeatureProvider for BibtexStringRenameProvider {
+    type Params = RenameParams;
+    type Output = Option<WorkspaceEdit>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<RenameParams>,
+    ) -> Option<WorkspaceEdit> {
+        let name = match &request.document().tree {
+            SyntaxTree::Bibtex(tree) => {
+                tree.string_name_at(request.params.text_document_position.position)?
+            }
+            SyntaxTree::Latex(_) => return None,
+        };
+
+        let mut changes = HashMap::new();
+        for document in &request.workspace().documents {
+            if let SyntaxTree::Bibtex(tree) = &document.tree {
+                let definitions = tree
+                    .strings()
+                    .into_iter()
+                    .filter_map(|string| string.name.as_ref());
+                let edits: Vec<_> = definitions
+                    .chain(tree.string_references())
+                    .filter(|token| token.text() == name.text())
+                    .map(|token| TextEdit::new(token.range(), request.params.new_name.clone()))
+                    .collect();
+
+                if !edits.is_empty() {
+                    changes.insert(document.uri.clone().into(), edits);
+                }
+            }
+        }
+        Some(WorkspaceEdit::new(changes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn string() {
+        let edit = test_feature(
+            BibtexStringRenameProvider,
+            FeatureSpec {
+                files: vec![
+                    FeatureSpec::file(
+                        "foo.bib",
+                        "@string{foo = {Foo}}\n@article{baz, author = foo # \" and Bar\"}",
+                    ),
+                    FeatureSpec::file("bar.bib", "@article{qux, journal = foo}"),
+                    FeatureSpec::file("baz.bib", "@article{quux, journal = bar}"),
+                ],
+                main_file: "foo.bib",
+                position: Position::new(1, 24),
+                new_name: "abc",
+                ..FeatureSpec::default()
+            },
+        );
+        let mut changes = HashMap::new();
+        changes.insert(
+            FeatureSpec::uri("foo.bib"),
+            vec![
+                TextEdit::new(Range::new_simple(0, 8, 0, 11), "abc".into()),
+                TextEdit::new(Range::new_simple(1, 23, 1, 26), "abc".into()),
+            ],
+        );
+        changes.insert(
+            FeatureSpec::uri("bar.bib"),
+            vec![TextEdit::new(Range::new_simple(0, 24, 0, 27), "abc".into())],
+        );
+        assert_eq!(edit, Some(WorkspaceEdit::new(changes)));
+    }
+
+    #[test]
+    fn prepare() {
+        let range = test_feature(
+            BibtexStringPrepareRenameProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@string{foo = {Foo}}")],
+                main_file: "foo.bib",
+                position: Position::new(0, 9),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(range, Some(Range::new_simple(0, 8, 0, 11)));
+    }
+
+    #[test]
+    fn entry_key() {
+        let edit = test_feature(
+            BibtexStringRenameProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "@article{foo, author = foo}")],
+                main_file: "foo.bib",
+                position: Position::new(0, 10),
+                new_name: "abc",
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(edit, None);
+    }
+}
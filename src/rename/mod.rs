@@ -1,9 +1,11 @@
 mod bibtex_entry;
+mod bibtex_string;
 mod latex_command;
 mod latex_environment;
 mod latex_label;
 
 use self::bibtex_entry::*;
+use self::bibtex_string::*;
 use self::latex_command::*;
 use self::latex_environment::*;
 use self::latex_label::*;
@@ -20,6 +22,7 @@ impl PrepareRenameProvider {
         Self {
             provider: ChoiceProvider::new(vec![
                 Box::new(BibtexEntryPrepareRenameProvider),
+                Box::new(BibtexStringPrepareRenameProvider),
                 Box::new(LatexCommandPrepareRenameProvider),
                 Box::new(LatexEnvironmentPrepareRenameProvider),
                 Box::new(LatexLabelPrepareRenameProvider),
@@ -56,6 +59,7 @@ impl RenameProvider {
         Self {
             provider: ChoiceProvider::new(vec![
                 Box::new(BibtexEntryRenameProvider),
+                Box::new(BibtexStringRenameProvider),
                 Box::new(LatexCommandRenameProvider),
                 Box::new(LatexEnvironmentRenameProvider),
                 Box::new(LatexLabelRenameProvider),
@@ -0,0 +1,109 @@
+use crate::magic_comment::MagicComments;
+use std::fmt;
+use std::sync::Arc;
+use texlab_protocol::{Options, Uri};
+use texlab_workspace::{Document, Workspace};
+
+pub const SELECT_ROOT_FILE_COMMAND: &str = "texlab.selectRootFile";
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RootError {
+    UnknownDocument(Uri),
+    MissingRoot(Uri),
+}
+
+impl fmt::Display for RootError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RootError::UnknownDocument(uri) => write!(f, "Unknown document: {}", uri),
+            RootError::MissingRoot(uri) => write!(
+                f,
+                "Unable to find the root document of {}. \
+                 Add a \"% !TEX root = <file>\" comment to the document, \
+                 open the root document or select it with the \"{}\" command.",
+                uri, SELECT_ROOT_FILE_COMMAND
+            ),
+        }
+    }
+}
+
+/// Finds the document that is compiled when building the given document.
+///
+/// The root file that was selected by the user takes precedence over the `root` magic comment,
+/// which takes precedence over the documents that include the given document.
+pub fn find_root(
+    workspace: &Workspace,
+    uri: &Uri,
+    options: &Options,
+    selected_root: Option<&Uri>,
+) -> Result<Arc<Document>, RootError> {
+    if let Some(root) = selected_root.and_then(|root| workspace.find(root)) {
+        return Ok(root);
+    }
+
+    let document = workspace
+        .find(uri)
+        .ok_or_else(|| RootError::UnknownDocument(uri.clone()))?;
+
+    let commented_root = MagicComments::parse(&document.text)
+        .root
+        .and_then(|root| uri.join(&root).ok())
+        .and_then(|root| workspace.find(&root.into()));
+    if let Some(root) = commented_root {
+        return Ok(root);
+    }
+
+    workspace
+        .find_parent(uri, options)
+        .ok_or_else(|| RootError::MissingRoot(uri.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_workspace::TestWorkspaceBuilder;
+
+    #[test]
+    fn parent() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let foo = builder.add_document("foo.tex", "\\documentclass{article}\n\\input{bar}");
+        let bar = builder.add_document("bar.tex", "");
+        let root = find_root(&builder.workspace, &bar, &Options::default(), None);
+        assert_eq!(root.unwrap().uri, foo);
+    }
+
+    #[test]
+    fn magic_comment() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let foo = builder.add_document("foo.tex", "\\documentclass{article}");
+        let bar = builder.add_document("bar/baz.tex", "% !TEX root = ../foo.tex");
+        let root = find_root(&builder.workspace, &bar, &Options::default(), None);
+        assert_eq!(root.unwrap().uri, foo);
+    }
+
+    #[test]
+    fn selected_root() {
+        let mut builder = TestWorkspaceBuilder::new();
+        builder.add_document("foo.tex", "\\documentclass{article}\n\\input{bar}");
+        let bar = builder.add_document("bar.tex", "");
+        let baz = builder.add_document("baz.tex", "");
+        let root = find_root(&builder.workspace, &bar, &Options::default(), Some(&baz));
+        assert_eq!(root.unwrap().uri, baz);
+    }
+
+    #[test]
+    fn missing_root() {
+        let mut builder = TestWorkspaceBuilder::new();
+        let foo = builder.add_document("foo.tex", "\\section{Foo}");
+        let root = find_root(&builder.workspace, &foo, &Options::default(), None);
+        assert_eq!(root, Err(RootError::MissingRoot(foo)));
+    }
+
+    #[test]
+    fn unknown_document() {
+        let builder = TestWorkspaceBuilder::new();
+        let uri = Uri::from_file_path(std::env::temp_dir().join("foo.tex")).unwrap();
+        let root = find_root(&builder.workspace, &uri, &Options::default(), None);
+        assert_eq!(root, Err(RootError::UnknownDocument(uri)));
+    }
+}
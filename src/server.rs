@@ -1,33 +1,69 @@
 use crate::action::{Action, ActionManager, LintReason};
+use crate::artifact::{self, ArtifactManager};
+use crate::build_log::{self, BuildLog};
+use crate::capabilities_report;
+use crate::citation_sections;
+use crate::code_action::CodeActionProvider;
+use crate::command_usage;
 // use crate::build::*;
 use crate::config::ConfigStrategy;
+use crate::context_at;
 use crate::definition::DefinitionProvider;
-use crate::diagnostics::DiagnosticsManager;
+use crate::diagnostics::{
+    lines_in_range, run_external_checker, run_linter, DiagnosticsManager, GrammarCheck, SpellCheck,
+    Suppressions, ADD_TO_DICTIONARY_COMMAND, RAISE_DIAGNOSTICS_LIMIT_COMMAND,
+};
 use crate::folding::FoldingProvider;
 // use crate::forward_search;
 use crate::highlight::HighlightProvider;
-use crate::link::LinkProvider;
-use crate::reference::ReferenceProvider;
+use crate::index_export::{ExportIndexParams, WorkspaceIndex, EXPORT_INDEX_COMMAND};
+use crate::limits::ConnectionLimits;
+use crate::link::{LinkData, LinkProvider};
+use crate::log_tail::{LogSubscriptions, LogTail, POLL_INTERVAL};
+use crate::low_bandwidth;
+use crate::matching_environment::{self, GOTO_MATCHING_ENVIRONMENT_COMMAND};
+use crate::moniker::MonikerProvider;
+use crate::paste;
+use crate::policy::ToolPolicy;
+use crate::presence::PresenceHub;
+use crate::preview::PreviewManager;
+use crate::project_stats;
+use crate::reference::{self, ReferenceProvider};
+use crate::registration;
 use crate::rename::{PrepareRenameProvider, RenameProvider};
+use crate::root::{self, RootError, SELECT_ROOT_FILE_COMMAND};
+use crate::shared_workspace::{SharedWorkspace, WorkspaceRegistry};
+use crate::signature_help::SignatureHelpProvider;
+use crate::user_state::{self, UserState};
 use crate::workspace_manager::{WorkspaceLoadError, WorkspaceManager};
+use futures::channel::mpsc;
 use futures::lock::Mutex;
+use futures::{FutureExt, StreamExt};
 use futures_boxed::boxed;
 use jsonrpc::server::{Middleware, Result};
 use jsonrpc_derive::{jsonrpc_method, jsonrpc_server};
 use log::*;
 use once_cell::sync::{Lazy, OnceCell};
+use std::collections::{HashMap, HashSet};
 use std::ffi::OsStr;
 use std::fs;
 use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use texlab_citeproc::render_citation;
-use texlab_completion::{CompletionItemData, CompletionProvider};
-use texlab_distro::{Distribution, DistributionKind, Language};
+use texlab_completion::{image_markup, CompletionItemData, CompletionProvider};
+use texlab_distro::{Distribution, DistributionKind, InstallError, Language};
 use texlab_hover::HoverProvider;
 use texlab_protocol::*;
 use texlab_symbol::SymbolProvider;
 use texlab_syntax::*;
+use texlab_workspace::sandbox::{self, SANDBOX_DIRECTORY};
 use texlab_workspace::*;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tokio::task::JoinHandle;
+use tokio::time;
+use uuid::Uuid;
 use walkdir::WalkDir;
 
 pub struct LatexLspServer<C> {
@@ -36,19 +72,41 @@ pub struct LatexLspServer<C> {
     distribution: Arc<Box<dyn Distribution>>,
     config_strategy: OnceCell<Box<dyn ConfigStrategy>>,
     // build_manager: BuildManager<C>,
-    workspace_manager: WorkspaceManager,
+    local_workspace: Arc<SharedWorkspace>,
+    shared_workspace: OnceCell<Arc<SharedWorkspace>>,
+    workspace_registry: OnceCell<Arc<WorkspaceRegistry>>,
     action_manager: ActionManager,
-    diagnostics_manager: Mutex<DiagnosticsManager>,
+    artifact_manager: ArtifactManager,
+    prompted_packages: Mutex<HashSet<String>>,
+    selected_roots: Mutex<HashMap<Uri, Uri>>,
+    code_action_provider: CodeActionProvider,
     completion_provider: CompletionProvider,
     definition_provider: DefinitionProvider,
     folding_provider: FoldingProvider,
     highlight_provider: HighlightProvider,
+    moniker_provider: MonikerProvider,
+    signature_help_provider: SignatureHelpProvider,
     symbol_provider: SymbolProvider,
     hover_provider: HoverProvider,
     link_provider: LinkProvider,
     reference_provider: ReferenceProvider,
     prepare_rename_provider: PrepareRenameProvider,
     rename_provider: RenameProvider,
+    session: String,
+    presence_hub: OnceCell<Arc<PresenceHub>>,
+    user_state: Mutex<UserState>,
+    user_state_file: OnceCell<PathBuf>,
+    low_bandwidth: OnceCell<bool>,
+    reference_preview_lines: OnceCell<u64>,
+    root_directory: OnceCell<PathBuf>,
+    sandbox_directory: OnceCell<PathBuf>,
+    tool_policy: OnceCell<ToolPolicy>,
+    limits: OnceCell<ConnectionLimits>,
+    processes: OnceCell<Arc<Semaphore>>,
+    linters: Mutex<Vec<JoinHandle<()>>>,
+    preview_manager: Arc<PreviewManager>,
+    log_subscriptions: Arc<LogSubscriptions>,
+    exit_sender: OnceCell<mpsc::UnboundedSender<()>>,
 }
 
 #[jsonrpc_server]
@@ -60,22 +118,145 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             distribution: Arc::clone(&distribution),
             config_strategy: OnceCell::new(),
             // build_manager: BuildManager::new(client),
-            workspace_manager: WorkspaceManager::new(distribution),
+            local_workspace: Arc::new(SharedWorkspace::new(distribution)),
+            shared_workspace: OnceCell::new(),
+            workspace_registry: OnceCell::new(),
             action_manager: ActionManager::default(),
-            diagnostics_manager: Mutex::new(DiagnosticsManager::default()),
+            artifact_manager: ArtifactManager::default(),
+            prompted_packages: Mutex::new(HashSet::new()),
+            selected_roots: Mutex::new(HashMap::new()),
+            code_action_provider: CodeActionProvider::new(),
             completion_provider: CompletionProvider::new(),
             definition_provider: DefinitionProvider::new(),
             folding_provider: FoldingProvider::new(),
             highlight_provider: HighlightProvider::new(),
+            moniker_provider: MonikerProvider,
+            signature_help_provider: SignatureHelpProvider,
             symbol_provider: SymbolProvider::new(),
             hover_provider: HoverProvider::new(),
             link_provider: LinkProvider::new(),
             reference_provider: ReferenceProvider::new(),
             prepare_rename_provider: PrepareRenameProvider::new(),
             rename_provider: RenameProvider::new(),
+            session: Uuid::new_v4().to_string(),
+            presence_hub: OnceCell::new(),
+            user_state: Mutex::new(UserState::default()),
+            user_state_file: OnceCell::new(),
+            low_bandwidth: OnceCell::new(),
+            reference_preview_lines: OnceCell::new(),
+            root_directory: OnceCell::new(),
+            sandbox_directory: OnceCell::new(),
+            tool_policy: OnceCell::new(),
+            limits: OnceCell::new(),
+            processes: OnceCell::new(),
+            linters: Mutex::new(Vec::new()),
+            preview_manager: Arc::new(PreviewManager::new()),
+            log_subscriptions: Arc::new(LogSubscriptions::default()),
+            exit_sender: OnceCell::new(),
         }
     }
 
+    /// Relays the cursors of this client to the other clients that share its workspace.
+    pub fn share_workspace(&self, hub: Arc<PresenceHub>) {
+        let _ = self.presence_hub.set(hub);
+    }
+
+    /// Stops following the build logs of the client.
+    pub fn unsubscribe_logs(&self) {
+        self.log_subscriptions.clear();
+    }
+
+    pub fn stop_sharing(&self) {
+        if let Some(hub) = self.presence_hub.get() {
+            hub.leave(&self.session);
+        }
+
+        if let Some(workspace) = self.shared_workspace.get() {
+            workspace.leave(&self.session);
+        }
+    }
+
+    /// Shares the documents and diagnostics with the other clients of the same workspace.
+    pub fn use_workspace_registry(&self, registry: Arc<WorkspaceRegistry>) {
+        let _ = self.workspace_registry.set(registry);
+    }
+
+    fn workspace(&self) -> &Arc<SharedWorkspace> {
+        self.shared_workspace.get().unwrap_or(&self.local_workspace)
+    }
+
+    fn workspace_manager(&self) -> &WorkspaceManager {
+        &self.workspace().workspace_manager
+    }
+
+    fn diagnostics_manager(&self) -> &Mutex<DiagnosticsManager> {
+        &self.workspace().diagnostics_manager
+    }
+
+    /// Sends the diagnostics to every client of the workspace.
+    async fn publish_diagnostics(&self, params: PublishDiagnosticsParams) {
+        let workspace = self.shared_workspace.get();
+        publish_diagnostics(workspace, &self.session, self.client.as_ref(), params).await;
+    }
+
+    /// Restricts the resources that this client may use.
+    pub fn set_limits(&self, limits: ConnectionLimits) {
+        if let Some(max_processes) = limits.max_processes {
+            let _ = self
+                .processes
+                .set(Arc::new(Semaphore::new(max_processes.max(1))));
+        }
+        let _ = self.limits.set(limits);
+    }
+
+    /// Allows the client to use the external tools of the policy.
+    pub fn set_tool_policy(&self, policy: ToolPolicy) {
+        let _ = self.tool_policy.set(policy);
+    }
+
+    fn tool_policy(&self) -> ToolPolicy {
+        self.tool_policy.get().cloned().unwrap_or_default()
+    }
+
+    /// Restricts the files that the client may access to the given directory
+    /// instead of `/minio`.
+    pub fn set_sandbox_directory(&self, directory: PathBuf) {
+        let _ = self.sandbox_directory.set(directory);
+    }
+
+    fn sandbox_directory(&self) -> &Path {
+        self.sandbox_directory
+            .get()
+            .map(AsRef::as_ref)
+            .unwrap_or_else(|| Path::new(SANDBOX_DIRECTORY))
+    }
+
+    fn confine(&self, path: &Path) -> Option<PathBuf> {
+        sandbox::confine(path, self.sandbox_directory())
+    }
+
+    /// Confines the directory of a project to the sandbox.
+    ///
+    /// The sandbox itself is rejected because it contains the projects of all clients.
+    fn confine_project(&self, directory: &Path) -> Option<PathBuf> {
+        self.confine(directory)
+            .filter(|directory| directory != self.sandbox_directory())
+    }
+
+    /// Confines the path to the root directory of the client,
+    /// which must be a project inside of the sandbox.
+    fn confine_to_root(&self, path: &Path) -> Option<PathBuf> {
+        let root_directory = self.confine_project(self.root_directory.get()?)?;
+        sandbox::confine(path, &root_directory)
+    }
+
+    /// Returns a stream that yields once the client has sent the `exit` notification.
+    pub fn exit_signal(&self) -> mpsc::UnboundedReceiver<()> {
+        let (sender, receiver) = mpsc::unbounded();
+        let _ = self.exit_sender.set(sender);
+        receiver
+    }
+
     pub async fn execute<'a, T, F, A>(&'a self, action: A) -> T
     where
         F: Future<Output = T>,
@@ -84,15 +265,113 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         self.before_message().await;
         let result = action(&self).await;
         self.after_message().await;
+        self.wait_for_linters().await;
         result
     }
 
+    /// Waits until the linters that run in the background have published their results.
+    pub async fn wait_for_linters(&self) {
+        let linters = std::mem::replace(&mut *self.linters.lock().await, Vec::new());
+        for linter in linters {
+            let _ = linter.await;
+        }
+    }
+
     #[jsonrpc_method("initialize", kind = "request")]
     pub async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
         let client = Arc::clone(&self.client);
         let config_strategy = ConfigStrategy::select(&params.capabilities, client);
         let _ = self.config_strategy.set(config_strategy);
 
+        let (initialization_options, problems) =
+            InitializationOptions::parse(params.initialization_options.clone());
+        if !problems.is_empty() {
+            let params = ShowMessageParams {
+                message: format!(
+                    "Some initialization options are ignored: {}",
+                    problems.join("; ")
+                ),
+                typ: MessageType::Warning,
+            };
+            self.client.show_message(params).await;
+        }
+
+        /* Security Patch */
+        let project_directory = initialization_options
+            .project_directory
+            .clone()
+            .filter(|_| params.root_uri.is_none())
+            .and_then(|directory| self.confine_project(&directory));
+
+        let root_uri: Option<Uri> = params.root_uri.clone().map(Into::into).or_else(|| {
+            project_directory
+                .as_ref()
+                .and_then(|directory| Url::from_directory_path(directory).ok())
+                .map(Into::into)
+        });
+
+        /* Security Patch */
+        let shared_root = root_uri.clone().filter(|uri| {
+            uri.to_file_path()
+                .ok()
+                .and_then(|path| self.confine(&path))
+                .is_some()
+        });
+        if let (Some(registry), Some(root_uri)) = (self.workspace_registry.get(), shared_root) {
+            let workspace = registry.join(root_uri, Arc::clone(&self.distribution));
+            let (sender, mut receiver) = mpsc::unbounded();
+            let client = Arc::clone(&self.client);
+            tokio::spawn(async move {
+                while let Some(params) = receiver.next().await {
+                    client.publish_diagnostics(params).await;
+                }
+            });
+            workspace.join(self.session.clone(), sender);
+            let _ = self.shared_workspace.set(workspace);
+        }
+        let is_low_bandwidth = initialization_options.low_bandwidth.unwrap_or(false);
+        let _ = self.low_bandwidth.set(is_low_bandwidth);
+
+        if let Some(lines) = initialization_options.reference_preview_lines {
+            let _ = self.reference_preview_lines.set(lines);
+        }
+
+        if let Some(hub) = self.presence_hub.get() {
+            let (sender, mut receiver) = mpsc::unbounded();
+            let client = Arc::clone(&self.client);
+            tokio::spawn(async move {
+                while let Some(params) = receiver.next().await {
+                    client.presence(params).await;
+                }
+            });
+            hub.join(self.session.clone(), root_uri.clone(), sender);
+        }
+
+        if let Some(root_directory) = root_uri.as_ref().and_then(|uri| uri.to_file_path().ok()) {
+            let _ = self.root_directory.set(root_directory);
+        }
+
+        // Thin clients never open the files of the project, so the server loads them on its own.
+        if let Some(project_directory) = &project_directory {
+            self.action_manager
+                .push(Action::Preload(project_files(project_directory)));
+        }
+
+        if let Some(file) = self
+            .root_directory
+            .get()
+            .and_then(|root_directory| user_state::state_file(root_directory))
+        {
+            let state = UserState::load(&file);
+            let mut diagnostics_manager = self.diagnostics_manager().lock().await;
+            diagnostics_manager
+                .spell_check
+                .set_dictionary(&state.dictionary);
+            *self.user_state.lock().await = state;
+            let _ = self.user_state_file.set(file);
+        }
+
+        let rename_provider = registration::rename_provider(&params.capabilities);
         self.client_capabilities
             .set(Arc::new(params.capabilities))
             .unwrap();
@@ -118,9 +397,13 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                     "@".to_owned(),
                     "/".to_owned(),
                     " ".to_owned(),
+                    "#".to_owned(),
+                    ",".to_owned(),
                 ]),
             }),
-            signature_help_provider: None,
+            signature_help_provider: Some(SignatureHelpOptions {
+                trigger_characters: Some(vec!["[".to_owned(), "{".to_owned()]),
+            }),
             definition_provider: Some(true),
             type_definition_provider: None,
             implementation_provider: None,
@@ -128,20 +411,28 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             document_highlight_provider: Some(true),
             document_symbol_provider: Some(true),
             workspace_symbol_provider: Some(true),
-            code_action_provider: None,
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
             code_lens_provider: None,
             document_formatting_provider: Some(true),
             document_range_formatting_provider: None,
             document_on_type_formatting_provider: None,
-            rename_provider: Some(RenameProviderCapability::Options(RenameOptions {
-                prepare_provider: Some(true),
-            })),
+            rename_provider: Some(rename_provider),
             document_link_provider: Some(DocumentLinkOptions {
-                resolve_provider: Some(false),
+                resolve_provider: Some(true),
             }),
             color_provider: None,
             folding_range_provider: Some(FoldingRangeProviderCapability::Simple(true)),
-            execute_command_provider: None,
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![
+                    "texlab.checkProject".into(),
+                    "texlab.refreshDistro".into(),
+                    ADD_TO_DICTIONARY_COMMAND.into(),
+                    RAISE_DIAGNOSTICS_LIMIT_COMMAND.into(),
+                    SELECT_ROOT_FILE_COMMAND.into(),
+                    EXPORT_INDEX_COMMAND.into(),
+                    GOTO_MATCHING_ENVIRONMENT_COMMAND.into(),
+                ],
+            }),
             workspace: None,
             selection_range_provider: None,
         };
@@ -160,24 +451,459 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
 
     #[jsonrpc_method("shutdown", kind = "request")]
     pub async fn shutdown(&self, _params: ()) -> Result<()> {
+        self.stop_sharing();
+        self.unsubscribe_logs();
         Ok(())
     }
 
     #[jsonrpc_method("exit", kind = "notification")]
-    pub async fn exit(&self, _params: ()) {}
+    pub async fn exit(&self, _params: ()) {
+        self.stop_sharing();
+
+        // Publish the pending diagnostics before the connection is closed.
+        self.after_message().await;
+        if let Some(sender) = self.exit_sender.get() {
+            let _ = sender.unbounded_send(());
+        }
+    }
 
     #[jsonrpc_method("$/cancelRequest", kind = "notification")]
     pub async fn cancel_request(&self, _params: CancelParams) {}
 
+    #[jsonrpc_method("texlab/presence", kind = "notification")]
+    pub async fn presence(&self, params: PresenceUpdateParams) {
+        if let Some(hub) = self.presence_hub.get() {
+            hub.relay(PresenceParams {
+                session: self.session.clone(),
+                uri: Some(params.uri),
+                selections: params.selections,
+            });
+        }
+    }
+
+    #[jsonrpc_method("texlab/configurationSchema", kind = "request")]
+    pub async fn configuration_schema(&self, _params: ()) -> Result<serde_json::Value> {
+        Ok(Options::schema())
+    }
+
+    /// Lists the optional features that are active and the reasons why the others are not.
+    #[jsonrpc_method("texlab/capabilitiesReport", kind = "request")]
+    pub async fn capabilities_report(&self, _params: ()) -> Result<CapabilitiesReport> {
+        let options = self.configuration(false).await;
+        let client_capabilities = self.client_capabilities.get().cloned().unwrap_or_default();
+        Ok(capabilities_report::report(
+            &client_capabilities,
+            &options,
+            self.distribution.kind(),
+            &self.tool_policy(),
+        ))
+    }
+
+    #[jsonrpc_method("texlab/textDocumentContent", kind = "request")]
+    pub async fn text_document_content(
+        &self,
+        params: TextDocumentContentParams,
+    ) -> Result<TextDocumentContentResult> {
+        let uri: Uri = params.uri.into();
+        let workspace = self.workspace_manager().get();
+        let tex_path = build_log::tex_uri(&uri)
+            .filter(|tex_uri| workspace.find(tex_uri).is_some())
+            .and_then(|tex_uri| tex_uri.to_file_path().ok())
+            .ok_or_else(|| format!("Unknown document: {}", uri))?;
+
+        let options = self.configuration(true).await;
+        /* Security Patch */
+        let log_path = options
+            .resolve_output_file(&tex_path, ArtifactKind::Log.extension())
+            .and_then(|log_path| self.confine(&log_path))
+            .ok_or_else(|| format!("Unable to find the build log of {}", tex_path.display()))?;
+        let bytes = fs::read(&log_path).map_err(|why| why.to_string())?;
+
+        let build_dir = options
+            .latex
+            .and_then(|opts| opts.root_directory)
+            .or_else(|| tex_path.parent().map(ToOwned::to_owned))
+            .unwrap_or_default();
+
+        let log = String::from_utf8_lossy(&bytes);
+        let (text, links) = BuildLog::parse(&log, &build_dir).render();
+        Ok(TextDocumentContentResult { text, links })
+    }
+
+    /// Sends the lines that are appended to the build log of the root document
+    /// until the client unsubscribes.
+    #[jsonrpc_method("texlab/subscribeLog", kind = "request")]
+    pub async fn subscribe_log(&self, params: SubscribeLogParams) -> Result<SubscribeLogResult> {
+        let uri: Uri = params.text_document.uri.into();
+        let options = self.configuration(false).await;
+        let workspace = self.workspace_manager().get();
+        let selected_root = self.selected_roots.lock().await.get(&uri).cloned();
+        let root = root::find_root(&workspace, &uri, &options, selected_root.as_ref())
+            .map_err(|why| why.to_string())?;
+
+        /* Security Patch */
+        let log_path = root
+            .uri
+            .to_file_path()
+            .ok()
+            .and_then(|tex_path| {
+                options.resolve_output_file(&tex_path, ArtifactKind::Log.extension())
+            })
+            .and_then(|log_path| self.confine(&log_path))
+            .ok_or_else(|| format!("Unable to find the build log of {}", root.uri))?;
+        let log_uri = Url::from_file_path(&log_path)
+            .map_err(|()| format!("Invalid path: {}", log_path.display()))?;
+
+        let id = self
+            .log_subscriptions
+            .subscribe()
+            .ok_or_else(|| "Too many log subscriptions".to_owned())?;
+        let subscription_id = id.clone();
+        let subscriptions = Arc::clone(&self.log_subscriptions);
+        let client = Arc::clone(&self.client);
+        let mut tail = LogTail::new(log_path);
+        tokio::spawn(async move {
+            while subscriptions.is_active(&subscription_id) {
+                let lines = tail.poll();
+                if !lines.is_empty() {
+                    let params = LogAppendedParams {
+                        id: subscription_id.clone(),
+                        uri: log_uri.clone(),
+                        lines,
+                    };
+                    client.log_appended(params).await;
+                }
+                time::delay_for(POLL_INTERVAL).await;
+            }
+        });
+        Ok(SubscribeLogResult { id })
+    }
+
+    #[jsonrpc_method("texlab/unsubscribeLog", kind = "request")]
+    pub async fn unsubscribe_log(&self, params: UnsubscribeLogParams) -> Result<()> {
+        if self.log_subscriptions.unsubscribe(&params.id) {
+            Ok(())
+        } else {
+            Err(format!("Unknown log subscription: {}", params.id))
+        }
+    }
+
+    /// Lints the given lines right away, unlike the delayed linting of the whole document.
+    #[jsonrpc_method("texlab/lintRange", kind = "request")]
+    pub async fn lint_range(&self, params: LintRangeParams) -> Result<Vec<Diagnostic>> {
+        let uri: Uri = params.text_document.uri.into();
+        let range = params.range;
+        let latex_options = self.configuration(false).await.latex.unwrap_or_default();
+        let workspace = self.workspace_manager().get();
+        let document = workspace
+            .find(&uri)
+            .ok_or_else(|| format!("Unknown document: {}", uri))?;
+
+        match &document.tree {
+            SyntaxTree::Latex(_) => (),
+            _ => return Ok(Vec::new()),
+        }
+
+        let mut diagnostics = {
+            let _permit = self.acquire_process().await;
+            let lint_options = latex_options.lint.unwrap_or_default();
+            let text = lines_in_range(&document.text, range);
+            run_linter(&uri, &text, &lint_options)
+                .await
+                .unwrap_or_default()
+        };
+
+        let spell_check_options = latex_options.spell_check.unwrap_or_default();
+        let spell_check = self
+            .diagnostics_manager()
+            .lock()
+            .await
+            .spell_check
+            .check_range(&document, range, &spell_check_options);
+        if let Some(spell_check) = spell_check {
+            let result = {
+                let _permit = self.acquire_process().await;
+                spell_check.run().await
+            };
+            diagnostics.append(
+                &mut self
+                    .diagnostics_manager()
+                    .lock()
+                    .await
+                    .spell_check
+                    .diagnostics(&result),
+            );
+        }
+
+        diagnostics.retain(|diagnostic| {
+            let line = diagnostic.range.start.line;
+            line >= range.start.line && line <= range.end.line
+        });
+        Suppressions::parse(&document.text).apply(&mut diagnostics);
+        Ok(diagnostics)
+    }
+
+    #[jsonrpc_method("texlab/commandUsage", kind = "request")]
+    pub async fn command_usage(&self, params: CommandUsageParams) -> Result<CommandUsageResult> {
+        let uri: Uri = params.text_document.uri.into();
+        let options = self.configuration(false).await;
+        let workspace = self.workspace_manager().get();
+        let mut documents = Vec::new();
+        let mut project = CommandUsage::default();
+        for document in workspace.related_documents(&uri, &options) {
+            if let SyntaxTree::Latex(tree) = &document.tree {
+                let usage = command_usage::analyze(tree);
+                command_usage::merge(&mut project, &usage);
+                documents.push(DocumentCommandUsage {
+                    uri: document.uri.clone().into(),
+                    usage,
+                });
+            }
+        }
+        Ok(CommandUsageResult { documents, project })
+    }
+
+    #[jsonrpc_method("texlab/projectStats", kind = "request")]
+    pub async fn project_stats(&self, params: ProjectStatsParams) -> Result<ProjectStatsResult> {
+        let uri: Uri = params.text_document.uri.into();
+        let options = self.configuration(false).await;
+        let spell_check_options = options
+            .latex
+            .as_ref()
+            .and_then(|latex| latex.spell_check.clone())
+            .unwrap_or_default();
+        let workspace = self.workspace_manager().get();
+        let diagnostics_manager = self.diagnostics_manager().lock().await;
+        let mut documents = Vec::new();
+        let mut project = Statistics::default();
+        for document in workspace.related_documents(&uri, &options) {
+            let mut statistics = match &document.tree {
+                SyntaxTree::Latex(tree) => project_stats::analyze(tree, &spell_check_options),
+                SyntaxTree::Bibtex(_) => Statistics::default(),
+            };
+            let diagnostics = diagnostics_manager.get(&workspace, &document, &options);
+            project_stats::count_diagnostics(&mut statistics, &diagnostics);
+            project_stats::merge(&mut project, &statistics);
+            documents.push(DocumentStatistics {
+                uri: document.uri.clone().into(),
+                statistics,
+            });
+        }
+        Ok(ProjectStatsResult { documents, project })
+    }
+
+    #[jsonrpc_method("texlab/citationSections", kind = "request")]
+    pub async fn citation_sections(
+        &self,
+        params: CitationSectionsParams,
+    ) -> Result<CitationSectionsResult> {
+        let uri: Uri = params.text_document.uri.into();
+        let options = self.configuration(false).await;
+        let workspace = self.workspace_manager().get();
+        let document = match workspace.find(&uri) {
+            Some(document) => document,
+            None => return Ok(CitationSectionsResult::default()),
+        };
+
+        let view = DocumentView::new(workspace, document, &options);
+        Ok(citation_sections::analyze(&view, &options))
+    }
+
+    #[jsonrpc_method("texlab/contextAt", kind = "request")]
+    pub async fn context_at(&self, params: ContextAtParams) -> Result<ContextAtResult> {
+        let uri: Uri = params.text_document.uri.into();
+        let workspace = self.workspace_manager().get();
+        match workspace.find(&uri).as_ref().map(|document| &document.tree) {
+            Some(SyntaxTree::Latex(tree)) => Ok(context_at::analyze(tree, params.position)),
+            _ => Ok(ContextAtResult::default()),
+        }
+    }
+
+    /// Converts the pasted text to LaTeX unless it is pasted into math mode.
+    #[jsonrpc_method("texlab/formatPaste", kind = "request")]
+    pub async fn format_paste(&self, params: FormatPasteParams) -> Result<TextEdit> {
+        let uri: Uri = params.text_document.uri.into();
+        let position = params.position;
+        let workspace = self.workspace_manager().get();
+        let is_math_mode = match workspace.find(&uri).as_ref().map(|document| &document.tree) {
+            Some(SyntaxTree::Latex(tree)) => tree.is_math_mode(position),
+            _ => false,
+        };
+
+        let new_text = if is_math_mode && params.format != Some(PasteFormat::Html) {
+            params.text
+        } else {
+            paste::convert(&params.text, params.format)
+        };
+        Ok(TextEdit::new(Range::new(position, position), new_text))
+    }
+
+    #[jsonrpc_method("texlab/preload", kind = "request")]
+    pub async fn preload(&self, params: PreloadParams) -> Result<()> {
+        /* Security Patch */
+        let paths = match params.uris {
+            Some(uris) => uris
+                .into_iter()
+                .filter_map(|uri| uri.to_file_path().ok())
+                .filter_map(|path| self.confine(&path))
+                .collect(),
+            None => match self
+                .root_directory
+                .get()
+                .and_then(|root_directory| self.confine_project(root_directory))
+            {
+                Some(root_directory) => project_files(&root_directory),
+                None => return Err("The workspace does not have a project directory".into()),
+            },
+        };
+
+        self.action_manager.push(Action::Preload(paths));
+        Ok(())
+    }
+
+    #[jsonrpc_method("workspace/executeCommand", kind = "request")]
+    pub async fn execute_command(
+        &self,
+        params: ExecuteCommandParams,
+    ) -> Result<Option<serde_json::Value>> {
+        match params.command.as_str() {
+            "texlab.checkProject" => {
+                let identifier: TextDocumentIdentifier = params
+                    .arguments
+                    .into_iter()
+                    .next()
+                    .and_then(|arg| serde_json::from_value(arg).ok())
+                    .ok_or_else(|| "Expected a text document as argument".to_owned())?;
+
+                self.check_project(identifier.as_uri()).await?;
+                Ok(None)
+            }
+            "texlab.refreshDistro" => {
+                self.distribution.refresh().await;
+                self.action_manager.push(Action::LoadDistribution);
+                Ok(None)
+            }
+            ADD_TO_DICTIONARY_COMMAND => {
+                let word: String = params
+                    .arguments
+                    .into_iter()
+                    .next()
+                    .and_then(|arg| serde_json::from_value(arg).ok())
+                    .ok_or_else(|| "Expected a word as argument".to_owned())?;
+
+                let dictionary = self
+                    .update_user_state(|state| {
+                        state.add_word(&word);
+                        state.dictionary.clone()
+                    })
+                    .await;
+
+                let mut diagnostics_manager = self.diagnostics_manager().lock().await;
+                diagnostics_manager.spell_check.set_dictionary(&dictionary);
+                self.action_manager.push(Action::PublishDiagnostics);
+                Ok(None)
+            }
+            RAISE_DIAGNOSTICS_LIMIT_COMMAND => {
+                let identifier: TextDocumentIdentifier = params
+                    .arguments
+                    .into_iter()
+                    .next()
+                    .and_then(|arg| serde_json::from_value(arg).ok())
+                    .ok_or_else(|| "Expected a text document as argument".to_owned())?;
+
+                let mut diagnostics_manager = self.diagnostics_manager().lock().await;
+                diagnostics_manager.raise_limit(identifier.uri.into());
+                self.action_manager.push(Action::PublishDiagnostics);
+                Ok(None)
+            }
+            SELECT_ROOT_FILE_COMMAND => {
+                let identifiers: Vec<TextDocumentIdentifier> = params
+                    .arguments
+                    .into_iter()
+                    .filter_map(|arg| serde_json::from_value(arg).ok())
+                    .collect();
+
+                match identifiers.as_slice() {
+                    [document, root] => {
+                        let mut selected_roots = self.selected_roots.lock().await;
+                        selected_roots.insert(document.as_uri(), root.as_uri());
+                        Ok(None)
+                    }
+                    _ => Err("Expected a text document and its root document as arguments".into()),
+                }
+            }
+            EXPORT_INDEX_COMMAND => {
+                let params: ExportIndexParams = params
+                    .arguments
+                    .into_iter()
+                    .next()
+                    .and_then(|arg| serde_json::from_value(arg).ok())
+                    .ok_or_else(|| "Expected the path of the index file as argument".to_owned())?;
+
+                /* Security Patch */
+                let path = self
+                    .confine_to_root(&params.path)
+                    .ok_or_else(|| format!("Unable to write to {}", params.path.display()))?;
+
+                let index = self.export_index().await;
+                index
+                    .save(&path)
+                    .map_err(|why| format!("Unable to write to {}: {}", path.display(), why))?;
+                Ok(None)
+            }
+            GOTO_MATCHING_ENVIRONMENT_COMMAND => {
+                let params: TextDocumentPositionParams = params
+                    .arguments
+                    .into_iter()
+                    .next()
+                    .and_then(|arg| serde_json::from_value(arg).ok())
+                    .ok_or_else(|| "Expected a text document position as argument".to_owned())?;
+
+                let workspace = self.workspace_manager().get();
+                let uri = params.text_document.as_uri();
+                let position = params.position;
+                let range = match workspace.find(&uri).as_ref().map(|document| &document.tree) {
+                    Some(SyntaxTree::Latex(tree)) => matching_environment::find(tree, position),
+                    _ => None,
+                };
+                Ok(range.map(|range| {
+                    serde_json::to_value(Location::new(params.text_document.uri, range)).unwrap()
+                }))
+            }
+            command => Err(format!("Unknown command: {}", command)),
+        }
+    }
+
     #[jsonrpc_method("textDocument/didOpen", kind = "notification")]
     pub async fn did_open(&self, params: DidOpenTextDocumentParams) {
         let uri = params.text_document.uri.clone();
+        if let Some(max_documents) = self.limits.get().and_then(|limits| limits.max_documents) {
+            let is_open = self
+                .workspace_manager()
+                .version(&self.session, &uri.clone().into())
+                .is_some();
+            if !is_open && self.workspace_manager().open_documents(&self.session) >= max_documents {
+                let params = ShowMessageParams {
+                    message: format!(
+                        "Unable to open {}: at most {} documents can be open at the same time.",
+                        uri, max_documents
+                    ),
+                    typ: MessageType::Warning,
+                };
+                self.client.show_message(params).await;
+                return;
+            }
+        }
+
         let options = self.configuration(false).await;
-        self.workspace_manager.add(params.text_document, &options);
+        self.workspace_manager()
+            .add(&self.session, params.text_document, &options);
         self.action_manager
             .push(Action::DetectRoot(uri.clone().into()));
         self.action_manager
-            .push(Action::RunLinter(Uri::from(uri), LintReason::Save));
+            .push(Action::RunLinter(uri.clone().into(), LintReason::Save));
+        self.action_manager
+            .push(Action::InstallMissingPackages(uri.into()));
         self.action_manager.push(Action::PublishDiagnostics);
         // println!("did_open request done");
     }
@@ -185,15 +911,49 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     #[jsonrpc_method("textDocument/didChange", kind = "notification")]
     pub async fn did_change(&self, params: DidChangeTextDocumentParams) {
         let options = self.configuration(false).await;
+        let uri: Uri = params.text_document.uri.clone().into();
+        let old_workspace = self.workspace_manager().get();
+        let old_keys = citation_keys(&old_workspace, &uri);
         for change in params.content_changes {
             let uri = params.text_document.uri.clone();
-            self.workspace_manager
+            self.workspace_manager()
                 .update(uri.into(), change.text, &options);
         }
+
+        let workspace = self.workspace_manager().get();
+        if let (Some(old_document), Some(document)) =
+            (old_workspace.find(&uri), workspace.find(&uri))
+        {
+            self.diagnostics_manager()
+                .lock()
+                .await
+                .spell_check
+                .track_change(&uri, &old_document.text, &document.text);
+        }
+
+        let new_keys: Vec<String> = citation_keys(&workspace, &uri)
+            .into_iter()
+            .filter(|key| !old_keys.contains(key))
+            .collect();
+        if !new_keys.is_empty() {
+            self.update_user_state(|state| {
+                for key in &new_keys {
+                    state.use_citation(key);
+                }
+            })
+            .await;
+        }
+        self.workspace_manager().set_version(
+            &self.session,
+            params.text_document.uri.clone().into(),
+            params.text_document.version,
+        );
         self.action_manager.push(Action::RunLinter(
-            params.text_document.uri.into(),
+            params.text_document.uri.clone().into(),
             LintReason::Change,
         ));
+        self.action_manager
+            .push(Action::SchedulePreview(params.text_document.uri.into()));
         self.action_manager.push(Action::PublishDiagnostics);
         // println!("did_change request done");
     }
@@ -204,6 +964,9 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             params.text_document.uri.clone().into(),
             LintReason::Save,
         ));
+        self.action_manager.push(Action::InstallMissingPackages(
+            params.text_document.uri.clone().into(),
+        ));
         self.action_manager.push(Action::PublishDiagnostics);
         self.action_manager
             .push(Action::Build(params.text_document.uri.into()));
@@ -211,7 +974,46 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     }
 
     #[jsonrpc_method("textDocument/didClose", kind = "notification")]
-    pub async fn did_close(&self, _params: DidCloseTextDocumentParams) {}
+    pub async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri: Uri = params.text_document.uri.into();
+        let mut diagnostics_manager = self.diagnostics_manager().lock().await;
+        diagnostics_manager.reset_limit(&uri);
+        self.workspace_manager()
+            .set_version(&self.session, uri, None);
+    }
+
+    #[jsonrpc_method("workspace/didChangeWatchedFiles", kind = "notification")]
+    pub async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        let options = self.configuration(false).await;
+        for change in params.changes {
+            let uri: Uri = change.uri.into();
+            if self.workspace_manager().is_open(&uri) {
+                continue;
+            }
+
+            match change.typ {
+                FileChangeType::Deleted => {
+                    if self.workspace_manager().remove(&uri) {
+                        self.diagnostics_manager().lock().await.remove(&uri);
+                        let params = PublishDiagnosticsParams {
+                            uri: uri.into(),
+                            diagnostics: Vec::new(),
+                        };
+                        self.publish_diagnostics(params).await;
+                    }
+                }
+                FileChangeType::Created | FileChangeType::Changed => {
+                    let workspace = self.workspace_manager().get();
+                    if let Some(document) = workspace.find(&uri) {
+                        let _ = self.update_document(&document, &options);
+                    }
+                }
+            }
+        }
+
+        self.detect_children().await;
+        self.action_manager.push(Action::PublishDiagnostics);
+    }
 
     #[jsonrpc_method("workspace/didChangeConfiguration", kind = "notification")]
     pub async fn did_change_configuration(&self, params: DidChangeConfigurationParams) {
@@ -233,7 +1035,19 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             .make_feature_request(params.text_document_position.as_uri(), params)
             .await?;
         // println!("completion request step 1");
-        let items = self.completion_provider.execute(&request).await;
+        let recent_citations = self.user_state.lock().await.recent_citations.clone();
+        self.completion_provider
+            .set_recent_citations(recent_citations);
+        let mut items = self.completion_provider.execute(&request).await;
+        let width = MAX_COMPLETION_ITEMS.to_string().len();
+        for (rank, item) in items.iter_mut().enumerate() {
+            item.sort_text = Some(format!("{:0width$}", rank, width = width));
+        }
+        if self.low_bandwidth() {
+            items
+                .iter_mut()
+                .for_each(low_bandwidth::strip_completion_item);
+        }
         // println!("completion request done");
         Ok(CompletionList {
             is_incomplete: true,
@@ -244,6 +1058,10 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
 
     #[jsonrpc_method("completionItem/resolve", kind = "request")]
     pub async fn completion_resolve(&self, mut item: CompletionItem) -> Result<CompletionItem> {
+        if self.low_bandwidth() {
+            return Ok(item);
+        }
+
         // println!("completion_resolve request starts");
         let data: CompletionItemData = serde_json::from_value(item.data.clone().unwrap()).unwrap();
         match data {
@@ -253,7 +1071,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                     .map(Documentation::MarkupContent);
             }
             CompletionItemData::Citation { uri, key } => {
-                let workspace = self.workspace_manager.get();
+                let workspace = self.workspace_manager().get();
                 if let Some(document) = workspace.find(&uri) {
                     if let SyntaxTree::Bibtex(tree) = &document.tree {
                         let markup = render_citation(&tree, &key);
@@ -261,6 +1079,22 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                     }
                 }
             }
+            CompletionItemData::Command | CompletionItemData::CommandSnippet => {
+                if item.documentation.is_none() && self.supports_completion_images() {
+                    item.documentation =
+                        COMPONENT_DATABASE.command_image(&item.label).map(|image| {
+                            Documentation::MarkupContent(image_markup(&item.label, image))
+                        });
+                }
+            }
+            CompletionItemData::Argument => {
+                if item.documentation.is_none() && self.supports_completion_images() {
+                    item.documentation =
+                        COMPONENT_DATABASE.argument_image(&item.label).map(|image| {
+                            Documentation::MarkupContent(image_markup(&item.label, image))
+                        });
+                }
+            }
             _ => {}
         };
         // println!("completion_resolve request done");
@@ -273,7 +1107,12 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         let request = self
             .make_feature_request(params.text_document.as_uri(), params)
             .await?;
-        let hover = self.hover_provider.execute(&request).await;
+        let hover = if self.low_bandwidth() {
+            self.hover_provider.execute_without_images(&request).await
+        } else {
+            let _permit = self.acquire_process().await;
+            self.hover_provider.execute(&request).await
+        };
         // println!("hover request done");
         Ok(hover)
     }
@@ -303,14 +1142,23 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     }
 
     #[jsonrpc_method("textDocument/references", kind = "request")]
-    pub async fn references(&self, params: ReferenceParams) -> Result<Vec<Location>> {
+    pub async fn references(&self, params: ReferenceParams) -> Result<ReferenceResponse> {
         // println!("textDocument request start");
         let request = self
             .make_feature_request(params.text_document_position.as_uri(), params)
             .await?;
         let results = self.reference_provider.execute(&request).await;
+        let response = match self.reference_preview_lines.get() {
+            Some(lines) => ReferenceResponse::Previews(
+                results
+                    .into_iter()
+                    .map(|location| reference::preview(request.workspace(), location, *lines))
+                    .collect(),
+            ),
+            None => ReferenceResponse::Locations(results),
+        };
         // println!("textDocument request done");
-        Ok(results)
+        Ok(response)
     }
 
     #[jsonrpc_method("textDocument/documentHighlight", kind = "request")]
@@ -327,26 +1175,77 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         Ok(results)
     }
 
+    #[jsonrpc_method("textDocument/moniker", kind = "request")]
+    pub async fn moniker(&self, params: TextDocumentPositionParams) -> Result<Vec<Moniker>> {
+        let request = self
+            .make_feature_request(params.text_document.as_uri(), params)
+            .await?;
+        Ok(self.moniker_provider.execute(&request).await)
+    }
+
+    #[jsonrpc_method("textDocument/signatureHelp", kind = "request")]
+    pub async fn signature_help(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Result<Option<SignatureHelp>> {
+        let request = self
+            .make_feature_request(params.text_document.as_uri(), params)
+            .await?;
+        Ok(self.signature_help_provider.execute(&request).await)
+    }
+
     #[jsonrpc_method("workspace/symbol", kind = "request")]
     pub async fn workspace_symbol(
         &self,
-        params: WorkspaceSymbolParams,
+        params: WorkspaceSymbolRequestParams,
     ) -> Result<Vec<SymbolInformation>> {
         // println!("workspace_symbol request start");
         let distribution = Arc::clone(&self.distribution);
         let client_capabilities = Arc::clone(&self.client_capabilities.get().unwrap());
-        let workspace = self.workspace_manager.get();
+        let workspace = self.workspace_manager().get();
         let options = self.configuration(true).await;
-        let symbols = texlab_symbol::workspace_symbols(
-            distribution,
-            client_capabilities,
-            workspace,
-            &options,
-            &params,
-        )
-        .await;
+        let token = match params.partial_result_token {
+            Some(token) => token,
+            None => {
+                let params = WorkspaceSymbolParams {
+                    query: params.query,
+                };
+                let symbols = texlab_symbol::workspace_symbols(
+                    distribution,
+                    client_capabilities,
+                    workspace,
+                    &options,
+                    &params,
+                )
+                .await;
+                return Ok(symbols);
+            }
+        };
+
+        for document in texlab_symbol::ordered_documents(&workspace, &options) {
+            let symbols = texlab_symbol::document_workspace_symbols(
+                Arc::clone(&distribution),
+                Arc::clone(&client_capabilities),
+                Arc::clone(&workspace),
+                &options,
+                document,
+                &params.query,
+            )
+            .await;
+
+            if !symbols.is_empty() {
+                let params = PartialResultParams {
+                    token: token.clone(),
+                    value: serde_json::to_value(symbols).unwrap(),
+                };
+                self.client.partial_result(params).await;
+            }
+
+            // Give a pending cancellation the chance to abort the scan.
+            tokio::task::yield_now().await;
+        }
         // println!("workspace_symbol request done");
-        Ok(symbols)
+        Ok(Vec::new())
     }
 
     #[jsonrpc_method("textDocument/documentSymbol", kind = "request")]
@@ -371,7 +1270,10 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     }
 
     #[jsonrpc_method("textDocument/documentLink", kind = "request")]
-    pub async fn document_link(&self, params: DocumentLinkParams) -> Result<Vec<DocumentLink>> {
+    pub async fn document_link(
+        &self,
+        params: DocumentLinkParams,
+    ) -> Result<Vec<ResolvableDocumentLink>> {
         // println!("document_link request start");
         let request = self
             .make_feature_request(params.text_document.as_uri(), params)
@@ -382,6 +1284,25 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         Ok(links)
     }
 
+    #[jsonrpc_method("documentLink/resolve", kind = "request")]
+    pub async fn document_link_resolve(
+        &self,
+        mut link: ResolvableDocumentLink,
+    ) -> Result<ResolvableDocumentLink> {
+        if link.target.is_none() {
+            let data = link
+                .data
+                .clone()
+                .and_then(|data| serde_json::from_value::<LinkData>(data).ok());
+
+            if let Some(data) = data {
+                let resolver = self.distribution.resolver().await;
+                link.target = data.resolve(&resolver);
+            }
+        }
+        Ok(link)
+    }
+
     #[jsonrpc_method("textDocument/formatting", kind = "request")]
     pub async fn formatting(&self, params: DocumentFormattingParams) -> Result<Vec<TextEdit>> {
         let request = self
@@ -432,8 +1353,36 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         let request = self
             .make_feature_request(params.text_document_position.as_uri(), params)
             .await?;
-        let edit = self.rename_provider.execute(&request).await;
-        Ok(edit)
+        match self.rename_provider.execute(&request).await {
+            Some(edit) => self.attach_versions(edit).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    #[jsonrpc_method("textDocument/codeAction", kind = "request")]
+    pub async fn code_action(&self, params: CodeActionParams) -> Result<CodeActionResponse> {
+        let request = self
+            .make_feature_request(params.text_document.as_uri(), params)
+            .await?;
+        let mut actions = self.code_action_provider.execute(&request).await;
+        let diagnostics_manager = self.diagnostics_manager().lock().await;
+        if let Some(action) = diagnostics_manager.spell_check.fix_all(request.document()) {
+            actions.push(action);
+        }
+        actions.append(
+            &mut diagnostics_manager
+                .spell_check
+                .suggestions(request.document(), request.params.range),
+        );
+        actions.append(
+            &mut diagnostics_manager
+                .spell_check
+                .add_to_dictionary(request.document(), request.params.range),
+        );
+        Ok(actions
+            .into_iter()
+            .map(CodeActionOrCommand::CodeAction)
+            .collect())
     }
 
     #[jsonrpc_method("textDocument/foldingRange", kind = "request")]
@@ -449,7 +1398,11 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
     }
 
     #[jsonrpc_method("textDocument/build", kind = "request")]
-    pub async fn build(&self, _params: BuildParams) -> Result<BuildResult> {
+    pub async fn build(&self, params: BuildParams) -> Result<BuildResult> {
+        if let Some(variant) = params.variant {
+            self.update_user_state(|state| state.build_variant = Some(variant))
+                .await;
+        }
         // let request = self
         //     .make_feature_request(params.text_document.as_uri(), params)
         //     .await?;
@@ -465,6 +1418,11 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         })
     }
 
+    #[jsonrpc_method("texlab/buildVariant", kind = "request")]
+    pub async fn build_variant(&self, _params: ()) -> Result<Option<String>> {
+        Ok(self.user_state.lock().await.build_variant.clone())
+    }
+
     #[jsonrpc_method("textDocument/forwardSearch", kind = "request")]
     pub async fn forward_search(
         &self,
@@ -495,6 +1453,29 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         })
     }
 
+    /// Finds the root document of the given document
+    /// and tells the user how to select one if it cannot be determined.
+    async fn find_root(
+        &self,
+        workspace: &Workspace,
+        uri: &Uri,
+        options: &Options,
+    ) -> Result<Arc<Document>> {
+        let selected_root = self.selected_roots.lock().await.get(uri).cloned();
+        match root::find_root(workspace, uri, options, selected_root.as_ref()) {
+            Ok(root) => Ok(root),
+            Err(error @ RootError::MissingRoot(_)) => {
+                let params = ShowMessageParams {
+                    message: error.to_string(),
+                    typ: MessageType::Warning,
+                };
+                self.client.show_message(params).await;
+                Err(error.to_string())
+            }
+            Err(error) => Err(error.to_string()),
+        }
+    }
+
     async fn configuration(&self, fetch: bool) -> Options {
         if let Some(strategy) = self.config_strategy.get() {
             strategy.get(fetch).await
@@ -503,9 +1484,602 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         }
     }
 
+    fn attach_versions(&self, edit: WorkspaceEdit) -> Result<WorkspaceEdit> {
+        let workspace = self.workspace_manager().get();
+        let mut document_edits = Vec::new();
+        for (uri, edits) in edit.changes.unwrap_or_default() {
+            if edits.is_empty() {
+                continue;
+            }
+
+            let uri: Uri = uri.into();
+            if let Some(document) = workspace.find(&uri) {
+                if !self.workspace_manager().is_in_sync(&document) {
+                    return Err(format!("Document is out of sync: {}", uri));
+                }
+            }
+
+            document_edits.push(TextDocumentEdit {
+                text_document: VersionedTextDocumentIdentifier {
+                    version: self.workspace_manager().version(&self.session, &uri),
+                    uri: uri.into(),
+                },
+                edits,
+            });
+        }
+
+        let client_capabilities = self.client_capabilities.get().unwrap();
+        if client_capabilities.has_document_changes_support() {
+            Ok(WorkspaceEdit {
+                changes: None,
+                document_changes: Some(DocumentChanges::Edits(document_edits)),
+            })
+        } else {
+            let changes = document_edits
+                .into_iter()
+                .map(|edit| (edit.text_document.uri, edit.edits))
+                .collect();
+            Ok(WorkspaceEdit::new(changes))
+        }
+    }
+
+    async fn export_index(&self) -> WorkspaceIndex {
+        let distribution = Arc::clone(&self.distribution);
+        let client_capabilities = Arc::clone(&self.client_capabilities.get().unwrap());
+        let workspace = self.workspace_manager().get();
+        let options = self.configuration(true).await;
+        let mut index = WorkspaceIndex::new(&workspace, &options);
+        for document in texlab_symbol::ordered_documents(&workspace, &options) {
+            index.symbols.append(
+                &mut texlab_symbol::document_workspace_symbols(
+                    Arc::clone(&distribution),
+                    Arc::clone(&client_capabilities),
+                    Arc::clone(&workspace),
+                    &options,
+                    document,
+                    "",
+                )
+                .await,
+            );
+        }
+        index
+    }
+
+    async fn check_project(&self, uri: Uri) -> Result<()> {
+        let options = self.configuration(true).await;
+        let workspace = self.workspace_manager().get();
+        let root = self.find_root(&workspace, &uri, &options).await?;
+
+        let documents = workspace.related_documents(&root.uri, &options);
+
+        let token = ProgressToken::String(format!("texlab-check-{}", Uuid::new_v4()));
+        let client_capabilities = self.client_capabilities.get().unwrap();
+        let progress = client_capabilities.has_work_done_progress_support()
+            && self
+                .client
+                .work_done_progress_create(WorkDoneProgressCreateParams {
+                    token: token.clone(),
+                })
+                .await
+                .is_ok();
+
+        if progress {
+            let params = ProgressParams {
+                token: token.clone(),
+                value: ProgressParamsValue::WorkDone(WorkDoneProgress::Begin(
+                    WorkDoneProgressBegin {
+                        title: "Checking project".into(),
+                        cancellable: Some(false),
+                        message: None,
+                        percentage: None,
+                    },
+                )),
+            };
+            self.client.progress(params).await;
+        }
+
+        let latex_options = options.latex.unwrap_or_default();
+        let lint_options = latex_options.lint.unwrap_or_default();
+        let spell_check_options = latex_options.spell_check.unwrap_or_default();
+        let grammar_check_options = latex_options.grammar_check.unwrap_or_default();
+        let external_checker_options = latex_options.external_checker.unwrap_or_default();
+        let tools = self.tool_policy();
+        let language_tool = tools.language_tool.clone();
+
+        for (i, document) in documents.iter().enumerate() {
+            if progress {
+                let name = document
+                    .uri
+                    .path_segments()
+                    .and_then(Iterator::last)
+                    .unwrap_or_default();
+
+                let params = ProgressParams {
+                    token: token.clone(),
+                    value: ProgressParamsValue::WorkDone(WorkDoneProgress::Report(
+                        WorkDoneProgressReport {
+                            cancellable: Some(false),
+                            message: Some(format!("{}/{}: {}", i + 1, documents.len(), name)),
+                            percentage: None,
+                        },
+                    )),
+                };
+                self.client.progress(params).await;
+            }
+
+            if let SyntaxTree::Bibtex(_) = &document.tree {
+                continue;
+            }
+
+            // The linters run without holding the diagnostics manager.
+            let _permit = self.acquire_process().await;
+            let diagnostics = run_linter(&document.uri, &document.text, &lint_options)
+                .await
+                .unwrap_or_default();
+            self.diagnostics_manager()
+                .lock()
+                .await
+                .latex
+                .set(document.uri.clone(), diagnostics);
+
+            let spell_check = self
+                .diagnostics_manager()
+                .lock()
+                .await
+                .spell_check
+                .check(&document, &spell_check_options);
+            if let Some(spell_check) = spell_check {
+                let result = spell_check.run().await;
+                self.diagnostics_manager()
+                    .lock()
+                    .await
+                    .spell_check
+                    .set(result);
+            }
+
+            if document.is_file() {
+                let diagnostics =
+                    run_external_checker(&document.uri, &external_checker_options, &tools).await;
+                if let Some(diagnostics) = diagnostics {
+                    self.diagnostics_manager()
+                        .lock()
+                        .await
+                        .external_checker
+                        .set(document.uri.clone(), diagnostics);
+                }
+            }
+
+            let grammar_check = language_tool
+                .as_ref()
+                .and_then(|_| GrammarCheck::new(&document, &grammar_check_options));
+            if let (Some(url), Some(grammar_check)) = (&language_tool, grammar_check) {
+                let diagnostics = grammar_check.run(url).await;
+                self.diagnostics_manager()
+                    .lock()
+                    .await
+                    .grammar_check
+                    .set(document.uri.clone(), diagnostics);
+            }
+        }
+
+        if progress {
+            let params = ProgressParams {
+                token,
+                value: ProgressParamsValue::WorkDone(WorkDoneProgress::Done(
+                    WorkDoneProgressDone { message: None },
+                )),
+            };
+            self.client.progress(params).await;
+        }
+
+        self.action_manager.push(Action::PublishDiagnostics);
+        Ok(())
+    }
+
+    async fn install_missing_packages(&self, uri: Uri) {
+        if self.distribution.kind() != DistributionKind::Miktex
+            || !self.tool_policy().install_packages
+        {
+            return;
+        }
+
+        let options = self.configuration(true).await;
+        let install_missing_packages = options
+            .latex
+            .as_ref()
+            .and_then(|opts| opts.miktex.as_ref())
+            .map_or(false, LatexMiktexOptions::install_missing_packages);
+
+        let workspace = self.workspace_manager().get();
+        let document = match workspace.find(&uri) {
+            Some(document) if install_missing_packages => document,
+            _ => return,
+        };
+
+        let tree = match &document.tree {
+            SyntaxTree::Latex(tree) => tree,
+            SyntaxTree::Bibtex(_) => return,
+        };
+
+        // Forget the dismissed prompts of packages that are no longer used,
+        // so that the user is asked again once they are added back.
+        {
+            let components: HashSet<&String> = workspace
+                .documents
+                .iter()
+                .filter_map(|document| match &document.tree {
+                    SyntaxTree::Latex(tree) => Some(&tree.components),
+                    SyntaxTree::Bibtex(_) => None,
+                })
+                .flatten()
+                .collect();
+            let mut prompted_packages = self.prompted_packages.lock().await;
+            prompted_packages.retain(|name| components.contains(name));
+        }
+
+        let resolver = self.distribution.resolver().await;
+        let mut installed = false;
+        for name in &tree.components {
+            if resolver.files_by_name.contains_key(name) {
+                continue;
+            }
+
+            {
+                let mut prompted_packages = self.prompted_packages.lock().await;
+                if !prompted_packages.insert(name.clone()) {
+                    continue;
+                }
+            }
+
+            let params = ShowMessageRequestParams {
+                typ: MessageType::Info,
+                message: format!(
+                    "The package \"{}\" is not installed. Do you want to install it now?",
+                    name
+                ),
+                actions: Some(vec![
+                    MessageActionItem {
+                        title: "Install".into(),
+                    },
+                    MessageActionItem {
+                        title: "Ignore".into(),
+                    },
+                ]),
+            };
+
+            match self.client.show_message_request(params).await {
+                Ok(Some(action)) if action.title == "Install" => {
+                    installed |= self.install_package(name).await;
+                }
+                _ => (),
+            }
+        }
+
+        if installed {
+            self.workspace_manager()
+                .update(document.uri.clone(), document.text.clone(), &options);
+            self.action_manager.push(Action::PublishDiagnostics);
+        }
+    }
+
+    async fn install_package(&self, name: &str) -> bool {
+        let token = ProgressToken::String(format!("texlab-install-{}", Uuid::new_v4()));
+        let client_capabilities = self.client_capabilities.get().unwrap();
+        let progress = client_capabilities.has_work_done_progress_support()
+            && self
+                .client
+                .work_done_progress_create(WorkDoneProgressCreateParams {
+                    token: token.clone(),
+                })
+                .await
+                .is_ok();
+
+        if progress {
+            let params = ProgressParams {
+                token: token.clone(),
+                value: ProgressParamsValue::WorkDone(WorkDoneProgress::Begin(
+                    WorkDoneProgressBegin {
+                        title: format!("Installing {}", name),
+                        cancellable: Some(false),
+                        message: None,
+                        percentage: None,
+                    },
+                )),
+            };
+            self.client.progress(params).await;
+        }
+
+        let result = self.distribution.install_package(name).await;
+
+        if progress {
+            let params = ProgressParams {
+                token,
+                value: ProgressParamsValue::WorkDone(WorkDoneProgress::Done(
+                    WorkDoneProgressDone { message: None },
+                )),
+            };
+            self.client.progress(params).await;
+        }
+
+        if let Err(why) = result {
+            let message = match why {
+                InstallError::NotSupported => {
+                    "Installing packages is not supported by your TeX distribution."
+                }
+                InstallError::InstallerNotFound => {
+                    "An error occurred while executing `mpm`. \
+                     Please make sure that the MiKTeX package manager is in your PATH."
+                }
+                InstallError::PackageNotFound => "No MiKTeX package provides this file.",
+                InstallError::Failed => "The package could not be installed.",
+            };
+            let params = ShowMessageParams {
+                message: format!("{} ({})", message, name),
+                typ: MessageType::Error,
+            };
+            self.client.show_message(params).await;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Waits until this client may spawn another external process.
+    async fn acquire_process(&self) -> Option<SemaphorePermit<'_>> {
+        match self.processes.get() {
+            Some(processes) => Some(processes.acquire().await),
+            None => None,
+        }
+    }
+
+    /// Asks the client to compile a preview once the user stops typing.
+    ///
+    /// The previews are throttled and a pending preview is dropped if another change arrives.
+    async fn schedule_preview(&self, uri: Uri) {
+        let options = self.configuration(false).await;
+        let preview_options = options
+            .latex
+            .as_ref()
+            .and_then(|latex| latex.preview.clone())
+            .unwrap_or_default();
+
+        if !preview_options.enabled() {
+            return;
+        }
+
+        let workspace = self.workspace_manager().get();
+        let selected_root = self.selected_roots.lock().await.get(&uri).cloned();
+        let root_uri = match root::find_root(&workspace, &uri, &options, selected_root.as_ref()) {
+            Ok(root) => root.uri.clone(),
+            Err(_) => return,
+        };
+
+        let generation = self.preview_manager.next_generation();
+        let preview_manager = Arc::clone(&self.preview_manager);
+        let client = Arc::clone(&self.client);
+        tokio::spawn(async move {
+            let mut delay = Duration::from_millis(preview_options.idle_delay());
+            loop {
+                time::delay_for(delay).await;
+                if !preview_manager.is_current(generation) {
+                    return;
+                }
+
+                match preview_manager.take(&preview_options, Instant::now()) {
+                    Ok(()) => break,
+                    Err(wait) => delay = wait,
+                }
+            }
+
+            let params = PreviewParams {
+                text_document: TextDocumentIdentifier::new(root_uri.into()),
+                generation,
+            };
+            client.preview(params).await;
+        });
+    }
+
+    /// Runs the LaTeX linter in the background and publishes its results once it has finished
+    /// so that the document changes are not held up by the linter.
+    async fn spawn_linter(&self, document: Arc<Document>) {
+        let options = self.configuration(false).await;
+        let lint_options = options
+            .latex
+            .as_ref()
+            .and_then(|latex| latex.lint.clone())
+            .unwrap_or_default();
+        let workspace = Arc::clone(self.workspace());
+        let shared_workspace = self.shared_workspace.get().cloned();
+        let session = self.session.clone();
+        let processes = self.processes.get().cloned();
+        let client = Arc::clone(&self.client);
+        let linter = tokio::spawn(async move {
+            let _permit = match &processes {
+                Some(processes) => Some(processes.acquire().await),
+                None => None,
+            };
+            let diagnostics = run_linter(&document.uri, &document.text, &lint_options)
+                .await
+                .unwrap_or_default();
+
+            let snapshot = workspace.workspace_manager.get();
+            let mut diagnostics_manager = workspace.diagnostics_manager.lock().await;
+            diagnostics_manager
+                .latex
+                .set(document.uri.clone(), diagnostics);
+
+            if let Some(document) = snapshot.find(&document.uri) {
+                let params = PublishDiagnosticsParams {
+                    uri: document.uri.clone().into(),
+                    diagnostics: diagnostics_manager.get(&snapshot, &document, &options),
+                };
+                drop(diagnostics_manager);
+                publish_diagnostics(shared_workspace.as_ref(), &session, client.as_ref(), params)
+                    .await;
+            }
+        });
+        self.track_linter(linter).await;
+    }
+
+    /// Runs the external checker in the background on the saved document
+    /// and publishes its results once it has finished.
+    async fn spawn_external_checker(&self, document: Arc<Document>) {
+        let options = self.configuration(false).await;
+        let checker_options = match options
+            .latex
+            .as_ref()
+            .and_then(|latex| latex.external_checker.clone())
+        {
+            Some(checker_options) => checker_options,
+            None => return,
+        };
+        let tools = self.tool_policy();
+        let workspace = Arc::clone(self.workspace());
+        let shared_workspace = self.shared_workspace.get().cloned();
+        let session = self.session.clone();
+        let processes = self.processes.get().cloned();
+        let client = Arc::clone(&self.client);
+        let linter = tokio::spawn(async move {
+            let _permit = match &processes {
+                Some(processes) => Some(processes.acquire().await),
+                None => None,
+            };
+            let diagnostics =
+                match run_external_checker(&document.uri, &checker_options, &tools).await {
+                    Some(diagnostics) => diagnostics,
+                    None => return,
+                };
+
+            let snapshot = workspace.workspace_manager.get();
+            let mut diagnostics_manager = workspace.diagnostics_manager.lock().await;
+            diagnostics_manager
+                .external_checker
+                .set(document.uri.clone(), diagnostics);
+
+            if let Some(document) = snapshot.find(&document.uri) {
+                let params = PublishDiagnosticsParams {
+                    uri: document.uri.clone().into(),
+                    diagnostics: diagnostics_manager.get(&snapshot, &document, &options),
+                };
+                drop(diagnostics_manager);
+                publish_diagnostics(shared_workspace.as_ref(), &session, client.as_ref(), params)
+                    .await;
+            }
+        });
+        self.track_linter(linter).await;
+    }
+
+    /// Runs the spell checker in the background
+    /// and publishes its results once it has finished.
+    async fn spawn_spell_check(&self, spell_check: SpellCheck) {
+        let options = self.configuration(false).await;
+        let workspace = Arc::clone(self.workspace());
+        let shared_workspace = self.shared_workspace.get().cloned();
+        let session = self.session.clone();
+        let processes = self.processes.get().cloned();
+        let client = Arc::clone(&self.client);
+        let linter = tokio::spawn(async move {
+            let _permit = match &processes {
+                Some(processes) => Some(processes.acquire().await),
+                None => None,
+            };
+            let uri = spell_check.uri.clone();
+            let result = spell_check.run().await;
+
+            let snapshot = workspace.workspace_manager.get();
+            let mut diagnostics_manager = workspace.diagnostics_manager.lock().await;
+            diagnostics_manager.spell_check.set(result);
+
+            if let Some(document) = snapshot.find(&uri) {
+                let params = PublishDiagnosticsParams {
+                    uri: document.uri.clone().into(),
+                    diagnostics: diagnostics_manager.get(&snapshot, &document, &options),
+                };
+                drop(diagnostics_manager);
+                publish_diagnostics(shared_workspace.as_ref(), &session, client.as_ref(), params)
+                    .await;
+            }
+        });
+        self.track_linter(linter).await;
+    }
+
+    /// Sends the document to the LanguageTool server in the background
+    /// and publishes the results once the server has answered.
+    async fn spawn_grammar_check(&self, grammar_check: GrammarCheck, url: Url) {
+        let options = self.configuration(false).await;
+        let workspace = Arc::clone(self.workspace());
+        let shared_workspace = self.shared_workspace.get().cloned();
+        let session = self.session.clone();
+        let client = Arc::clone(&self.client);
+        let linter = tokio::spawn(async move {
+            let uri = grammar_check.uri.clone();
+            let diagnostics = grammar_check.run(&url).await;
+
+            let snapshot = workspace.workspace_manager.get();
+            let mut diagnostics_manager = workspace.diagnostics_manager.lock().await;
+            diagnostics_manager
+                .grammar_check
+                .set(uri.clone(), diagnostics);
+
+            if let Some(document) = snapshot.find(&uri) {
+                let params = PublishDiagnosticsParams {
+                    uri: document.uri.clone().into(),
+                    diagnostics: diagnostics_manager.get(&snapshot, &document, &options),
+                };
+                drop(diagnostics_manager);
+                publish_diagnostics(shared_workspace.as_ref(), &session, client.as_ref(), params)
+                    .await;
+            }
+        });
+        self.track_linter(linter).await;
+    }
+
+    /// Keeps the handle of a background linter so that its results can be awaited.
+    async fn track_linter(&self, linter: JoinHandle<()>) {
+        let mut linters = self.linters.lock().await;
+        let mut running: Vec<_> = linters
+            .drain(..)
+            .filter_map(|mut linter| match (&mut linter).now_or_never() {
+                Some(_) => None,
+                None => Some(linter),
+            })
+            .collect();
+        running.push(linter);
+        *linters = running;
+    }
+
+    fn low_bandwidth(&self) -> bool {
+        self.low_bandwidth.get().copied().unwrap_or(false)
+    }
+
+    fn supports_completion_images(&self) -> bool {
+        self.client_capabilities
+            .get()
+            .map_or(false, |cap| cap.has_completion_markdown_support())
+    }
+
+    /// Applies the update to the user state and writes it back to disk.
+    async fn update_user_state<T, F>(&self, update: F) -> T
+    where
+        F: FnOnce(&mut UserState) -> T,
+    {
+        let mut state = self.user_state.lock().await;
+        let result = update(&mut state);
+        if let Some(file) = self.user_state_file.get() {
+            if let Err(why) = state.save(file) {
+                warn!(
+                    "Unable to save the user state to {}: {}",
+                    file.display(),
+                    why
+                );
+            }
+        }
+        result
+    }
+
     async fn make_feature_request<P>(&self, uri: Uri, params: P) -> Result<FeatureRequest<P>> {
         // println!("f1");
-        let workspace = self.workspace_manager.get();
+        let workspace = self.workspace_manager().get();
         let client_capabilities = self
             .client_capabilities
             .get()
@@ -533,10 +2107,10 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         loop {
             let mut changed = false;
 
-            let workspace = self.workspace_manager.get();
+            let workspace = self.workspace_manager().get();
             for path in workspace.unresolved_includes(&options) {
                 if path.exists() {
-                    changed |= self.workspace_manager.load(&path, &options).is_ok();
+                    changed |= self.workspace_manager().load(&path, &options).is_ok();
                 }
             }
 
@@ -558,15 +2132,46 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
         let path = document.uri.to_file_path().unwrap();
         let data = fs::metadata(&path).map_err(WorkspaceLoadError::IO)?;
         if data.modified().map_err(WorkspaceLoadError::IO)? > document.modified {
-            self.workspace_manager.load(&path, &options)
+            self.workspace_manager().load(&path, &options)
         } else {
             Ok(())
         }
     }
 
+    fn detect_artifacts(&self, document: &Document, options: &Options) {
+        if !document.is_file() {
+            return;
+        }
+
+        if let SyntaxTree::Latex(tree) = &document.tree {
+            if !tree.env.is_standalone {
+                return;
+            }
+
+            if let Ok(path) = document.uri.to_file_path() {
+                let sandbox = self.sandbox_directory();
+                let artifacts = self.artifact_manager.update(&path, options, sandbox);
+                if !artifacts.is_empty() {
+                    let log_warnings = if artifacts.contains(&ArtifactKind::Log) {
+                        artifact::count_log_warnings(&path, options, sandbox)
+                    } else {
+                        None
+                    };
+
+                    let params = ArtifactsChangedParams {
+                        text_document: TextDocumentIdentifier::new(document.uri.clone().into()),
+                        artifacts,
+                        log_warnings,
+                    };
+                    self.action_manager.push(Action::NotifyArtifacts(params));
+                }
+            }
+        }
+    }
+
     // async fn update_build_diagnostics(&self) {
-    //     let workspace = self.workspace_manager.get();
-    //     let mut diagnostics_manager = self.diagnostics_manager.lock().await;
+    //     let workspace = self.workspace_manager().get();
+    //     let mut diagnostics_manager = self.diagnostics_manager().lock().await;
     //     let options = self.configuration(false).await;
 
     //     for document in &workspace.documents {
@@ -595,7 +2200,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
             let mut path = uri.to_file_path().unwrap();
             let options = self.configuration(false).await;
             while path.pop() {
-                let workspace = self.workspace_manager.get();
+                let workspace = self.workspace_manager().get();
                 if workspace.find_parent(&uri, &options).is_some() {
                     break;
                 }
@@ -617,7 +2222,7 @@ impl<C: LspClient + Send + Sync + 'static> LatexLspServer<C> {
                 {
                     if let Ok(parent_uri) = Uri::from_file_path(entry.path()) {
                         if workspace.find(&parent_uri).is_none() {
-                            let _ = self.workspace_manager.load(entry.path(), &options);
+                            let _ = self.workspace_manager().load(entry.path(), &options);
                         }
                     }
                 }
@@ -631,10 +2236,16 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
     async fn before_message(&self) {
         self.detect_children().await;
 
+        // The build artifacts are only scanned from time to time
+        // because clients can send a lot of messages while typing.
         let options = self.configuration(false).await;
-        let workspace = self.workspace_manager.get();
+        let workspace = self.workspace_manager().get();
+        let scan_artifacts = self.artifact_manager.is_due();
         for document in &workspace.documents {
             let _ = self.update_document(document, &options);
+            if scan_artifacts {
+                self.detect_artifacts(document, &options);
+            }
         }
     }
 
@@ -645,21 +2256,12 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
             match action {
                 Action::RegisterCapabilities => {
                     let capabilities = self.client_capabilities.get().unwrap();
-                    if !capabilities.has_pull_configuration_support()
-                        && capabilities.has_push_configuration_support()
-                    {
-                        let registration = Registration {
-                            id: "pull-config".into(),
-                            method: "workspace/didChangeConfiguration".into(),
-                            register_options: None,
-                        };
-                        let params = RegistrationParams {
-                            registrations: vec![registration],
-                        };
-                        self.client
-                            .register_capability(params)
-                            .await
-                            .expect("failed to register \"workspace/didChangeConfiguration\"");
+                    let registrations = registration::registrations(&capabilities);
+                    if !registrations.is_empty() {
+                        let params = RegistrationParams { registrations };
+                        if let Err(why) = self.client.register_capability(params).await {
+                            warn!("Failed to register capabilities: {}", why.message);
+                        }
                     }
                 }
                 Action::LoadDistribution => {
@@ -674,31 +2276,35 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
                         self.client.show_message(params).await;
                     }
 
-                    if let Err(why) = self.distribution.load().await {
-                        let message = match why {
-                            texlab_distro::LoadError::KpsewhichNotFound => {
-                                "An error occurred while executing `kpsewhich`.\
-                                 Please make sure that your distribution is in your PATH \
-                                 environment variable and provides the `kpsewhich` tool."
-                            }
-                            texlab_distro::LoadError::CorruptFileDatabase => {
-                                "The file database of your TeX distribution seems \
-                                 to be corrupt. Please rebuild it and try again."
-                            }
-                        };
-                        let params = ShowMessageParams {
-                            message: message.into(),
-                            typ: MessageType::Error,
-                        };
-                        self.client.show_message(params).await;
-                    };
+                    let distribution = Arc::clone(&self.distribution);
+                    let client = Arc::clone(&self.client);
+                    tokio::spawn(async move {
+                        if let Err(why) = distribution.load().await {
+                            let message = match why {
+                                texlab_distro::LoadError::KpsewhichNotFound => {
+                                    "An error occurred while executing `kpsewhich`.\
+                                     Please make sure that your distribution is in your PATH \
+                                     environment variable and provides the `kpsewhich` tool."
+                                }
+                                texlab_distro::LoadError::CorruptFileDatabase => {
+                                    "The file database of your TeX distribution seems \
+                                     to be corrupt. Please rebuild it and try again."
+                                }
+                            };
+                            let params = ShowMessageParams {
+                                message: message.into(),
+                                typ: MessageType::Error,
+                            };
+                            client.show_message(params).await;
+                        }
+                    });
                 }
                 Action::LoadConfiguration => {
                     let options = self.configuration(true).await;
-                    let workspace = self.workspace_manager.get();
+                    let workspace = self.workspace_manager().get();
                     for document in &workspace.documents {
                         if let Ok(path) = document.uri.to_file_path() {
-                            let _ = self.workspace_manager.load(&path, &options);
+                            let _ = self.workspace_manager().load(&path, &options);
                         }
                     }
                 }
@@ -708,45 +2314,115 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
                 Action::DetectRoot(uri) => {
                     self.detect_root(uri).await;
                 }
+                Action::Preload(paths) => {
+                    let options = self.configuration(false).await;
+                    let max_documents = self.limits.get().and_then(|limits| limits.max_documents);
+                    let count = self
+                        .workspace_manager()
+                        .preload(&paths, &options, max_documents);
+                    self.detect_children().await;
+                    info!("Preloaded {} documents", count);
+                }
                 Action::PublishDiagnostics => {
-                    let workspace = self.workspace_manager.get();
+                    let options = self.configuration(false).await;
+                    let workspace = self.workspace_manager().get();
                     for document in &workspace.documents {
                         let diagnostics = {
-                            let manager = self.diagnostics_manager.lock().await;
-                            manager.get(&document)
+                            let manager = self.diagnostics_manager().lock().await;
+                            manager.get(&workspace, &document, &options)
                         };
 
                         let params = PublishDiagnosticsParams {
                             uri: document.uri.clone().into(),
                             diagnostics,
                         };
-                        self.client.publish_diagnostics(params).await;
+                        self.publish_diagnostics(params).await;
                     }
                 }
                 Action::RunLinter(uri, reason) => {
-                    let options = self
-                        .configuration(true)
-                        .await
-                        .latex
-                        .and_then(|opts| opts.lint)
-                        .unwrap_or_default();
+                    let all_options = self.configuration(true).await;
+                    if reason == LintReason::Save {
+                        let workspace = self.workspace_manager().get();
+                        if let Some(document) = workspace.find(&uri) {
+                            let mut diagnostics_manager = self.diagnostics_manager().lock().await;
+                            diagnostics_manager.graphics.update(&document, &all_options);
+                        }
+                    }
+
+                    let mut latex_options = all_options.latex.unwrap_or_default();
+                    if self.low_bandwidth() {
+                        low_bandwidth::slow_down_linters(&mut latex_options);
+                    }
+                    let options = latex_options.lint.clone().unwrap_or_default();
 
                     let should_lint = match reason {
                         LintReason::Change => options.on_change(),
                         LintReason::Save => options.on_save(),
                     };
+
+                    if reason == LintReason::Save && latex_options.external_checker.is_some() {
+                        let workspace = self.workspace_manager().get();
+                        if let Some(document) = workspace.find(&uri) {
+                            if document.is_file() {
+                                if let SyntaxTree::Latex(_) = &document.tree {
+                                    self.spawn_external_checker(document).await;
+                                }
+                            }
+                        }
+                    }
                     
                     if should_lint {
-                        let workspace = self.workspace_manager.get();
+                        let workspace = self.workspace_manager().get();
                         if let Some(document) = workspace.find(&uri) {
                             if let SyntaxTree::Latex(_) = &document.tree {
-                                let mut diagnostics_manager = self.diagnostics_manager.lock().await;
-                                diagnostics_manager.latex.update(&uri, &document.text);
-                                diagnostics_manager.english.update(&uri, &document.text);
+                                let language_tool = self.tool_policy().language_tool;
+                                let (is_due, spell_check, grammar_check) = {
+                                    let mut diagnostics_manager =
+                                        self.diagnostics_manager().lock().await;
+                                    let spell_check_options =
+                                        latex_options.spell_check.unwrap_or_default();
+                                    let spell_check = diagnostics_manager.spell_check.update(
+                                        &document,
+                                        reason,
+                                        &spell_check_options,
+                                    );
+                                    let grammar_check_options =
+                                        latex_options.grammar_check.unwrap_or_default();
+                                    let grammar_check = language_tool.as_ref().and_then(|_| {
+                                        diagnostics_manager.grammar_check.update(
+                                            &document,
+                                            reason,
+                                            &grammar_check_options,
+                                        )
+                                    });
+                                    let is_due = diagnostics_manager.latex.is_due(reason, &options);
+                                    (is_due, spell_check, grammar_check)
+                                };
+
+                                if let Some(spell_check) = spell_check {
+                                    self.spawn_spell_check(spell_check).await;
+                                }
+
+                                if is_due {
+                                    self.spawn_linter(Arc::clone(&document)).await;
+                                }
+
+                                if let (Some(url), Some(grammar_check)) =
+                                    (language_tool, grammar_check)
+                                {
+                                    self.spawn_grammar_check(grammar_check, url).await;
+                                }
                             }
                         }
                     }
                 }
+                Action::SchedulePreview(uri) => {
+                    self.schedule_preview(uri).await;
+                }
+                Action::InstallMissingPackages(uri) => {
+                    let _permit = self.acquire_process().await;
+                    self.install_missing_packages(uri).await;
+                }
                 Action::Build(_uri) => {
                     // let options = self
                     //     .configuration(true)
@@ -757,13 +2433,66 @@ impl<C: LspClient + Send + Sync + 'static> Middleware for LatexLspServer<C> {
 
                     // if options.on_save() {
                     //     let text_document = TextDocumentIdentifier::new(uri.into());
-                    //     self.build(BuildParams { text_document }).await.unwrap();
+                    //     self.build(BuildParams { text_document, variant: None }).await.unwrap();
                     // }
                 }
                 Action::CancelBuild(_token) => {
                     // self.build_manager.cancel(token).await;
                 }
+                Action::NotifyArtifacts(params) => {
+                    self.client.artifacts_changed(params).await;
+                }
             }
         }
     }
 }
+
+/// Sends the diagnostics to the client and to the other clients of the shared workspace.
+async fn publish_diagnostics<C: LspClient>(
+    shared_workspace: Option<&Arc<SharedWorkspace>>,
+    session: &str,
+    client: &C,
+    params: PublishDiagnosticsParams,
+) {
+    if let Some(workspace) = shared_workspace {
+        workspace.publish_diagnostics(session, params.clone());
+    }
+    client.publish_diagnostics(params).await;
+}
+
+fn citation_keys(workspace: &Workspace, uri: &Uri) -> Vec<String> {
+    match workspace.find(uri).as_ref().map(|document| &document.tree) {
+        Some(SyntaxTree::Latex(tree)) => tree
+            .citations
+            .iter()
+            .flat_map(LatexCitation::keys)
+            .map(|key| key.text().to_owned())
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the TeX and BibTeX files below the directory while skipping hidden directories.
+pub(crate) fn project_files(root_directory: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root_directory)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry
+                    .file_name()
+                    .to_str()
+                    .map_or(false, |name| name.starts_with('.'))
+        })
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(OsStr::to_str)
+                .and_then(Language::by_extension)
+                .is_some()
+        })
+        .map(|entry| entry.into_path())
+        .collect()
+}
@@ -0,0 +1,145 @@
+use crate::diagnostics::DiagnosticsManager;
+use crate::workspace_manager::WorkspaceManager;
+use futures::channel::mpsc;
+use futures::lock::Mutex;
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use texlab_distro::Distribution;
+use texlab_protocol::{PublishDiagnosticsParams, Uri};
+
+#[derive(Debug)]
+struct Member {
+    client: String,
+    sender: mpsc::UnboundedSender<PublishDiagnosticsParams>,
+}
+
+/// The documents and diagnostics of a workspace.
+pub struct SharedWorkspace {
+    pub workspace_manager: WorkspaceManager,
+    pub diagnostics_manager: Mutex<DiagnosticsManager>,
+    members: std::sync::Mutex<Vec<Member>>,
+}
+
+impl SharedWorkspace {
+    pub fn new(distribution: Arc<Box<dyn Distribution>>) -> Self {
+        Self {
+            workspace_manager: WorkspaceManager::new(distribution),
+            diagnostics_manager: Mutex::new(DiagnosticsManager::default()),
+            members: std::sync::Mutex::default(),
+        }
+    }
+
+    /// Registers a client that receives the diagnostics of the workspace.
+    pub fn join(&self, client: String, sender: mpsc::UnboundedSender<PublishDiagnosticsParams>) {
+        let mut members = self.members.lock().unwrap();
+        members.retain(|member| member.client != client);
+        members.push(Member { client, sender });
+    }
+
+    /// Unregisters a client and closes the documents that it has opened.
+    pub fn leave(&self, client: &str) {
+        self.members
+            .lock()
+            .unwrap()
+            .retain(|member| member.client != client);
+        self.workspace_manager.close_all(client);
+    }
+
+    /// Sends the diagnostics that were computed for one client to the other clients.
+    pub fn publish_diagnostics(&self, client: &str, params: PublishDiagnosticsParams) {
+        let mut members = self.members.lock().unwrap();
+        members.retain(|member| {
+            member.client == client || member.sender.unbounded_send(params.clone()).is_ok()
+        });
+    }
+}
+
+/// Hands out the same workspace to the clients that were initialized with the same root.
+///
+/// A workspace is dropped once the last of its clients has disconnected.
+#[derive(Default)]
+pub struct WorkspaceRegistry {
+    workspaces: std::sync::Mutex<HashMap<Uri, Weak<SharedWorkspace>>>,
+}
+
+impl WorkspaceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn join(
+        &self,
+        root: Uri,
+        distribution: Arc<Box<dyn Distribution>>,
+    ) -> Arc<SharedWorkspace> {
+        let mut workspaces = self.workspaces.lock().unwrap();
+        workspaces.retain(|_, workspace| workspace.strong_count() > 0);
+        if let Some(workspace) = workspaces.get(&root).and_then(Weak::upgrade) {
+            return workspace;
+        }
+
+        let workspace = Arc::new(SharedWorkspace::new(distribution));
+        workspaces.insert(root, Arc::downgrade(&workspace));
+        workspace
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use texlab_distro::UnknownDistribution;
+    use texlab_protocol::Url;
+
+    fn diagnostics() -> PublishDiagnosticsParams {
+        PublishDiagnosticsParams {
+            uri: Url::parse("file:///foo/bar.tex").unwrap(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    fn join(registry: &WorkspaceRegistry, root: &str) -> Arc<SharedWorkspace> {
+        let distribution: Box<dyn Distribution> = Box::new(UnknownDistribution::new());
+        let root = Url::parse(root).unwrap().into();
+        registry.join(root, Arc::new(distribution))
+    }
+
+    #[test]
+    fn same_root() {
+        let registry = WorkspaceRegistry::new();
+        let foo = join(&registry, "file:///foo");
+        let bar = join(&registry, "file:///foo");
+        let baz = join(&registry, "file:///baz");
+        assert!(Arc::ptr_eq(&foo, &bar));
+        assert!(!Arc::ptr_eq(&foo, &baz));
+    }
+
+    #[test]
+    fn publish_to_every_member() {
+        let registry = WorkspaceRegistry::new();
+        let workspace = join(&registry, "file:///foo");
+        let (foo_sender, mut foo) = mpsc::unbounded();
+        workspace.join("foo".into(), foo_sender);
+        let (bar_sender, mut bar) = mpsc::unbounded();
+        workspace.join("bar".into(), bar_sender);
+        let (baz_sender, mut baz) = mpsc::unbounded();
+        workspace.join("baz".into(), baz_sender);
+
+        workspace.publish_diagnostics("foo", diagnostics());
+        assert!(foo.try_next().is_err());
+        assert_eq!(bar.try_next().unwrap(), Some(diagnostics()));
+        assert_eq!(baz.try_next().unwrap(), Some(diagnostics()));
+
+        workspace.leave("bar");
+        workspace.publish_diagnostics("foo", diagnostics());
+        assert!(bar.try_next().unwrap().is_none());
+        assert_eq!(baz.try_next().unwrap(), Some(diagnostics()));
+    }
+
+    #[test]
+    fn drop_unused() {
+        let registry = WorkspaceRegistry::new();
+        drop(join(&registry, "file:///foo"));
+        join(&registry, "file:///bar");
+        assert_eq!(registry.workspaces.lock().unwrap().len(), 1);
+    }
+}
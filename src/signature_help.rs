@@ -0,0 +1,236 @@
+use futures_boxed::boxed;
+use texlab_protocol::*;
+use texlab_syntax::*;
+use texlab_workspace::*;
+
+/// The number of characters before the cursor that are searched for the command.
+const MAX_LOOKBEHIND: usize = 1000;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct SignatureHelpProvider;
+
+impl FeatureProvider for SignatureHelpProvider {
+    type Params = TextDocumentPositionParams;
+    type Output = Option<SignatureHelp>;
+
+    #[boxed]
+    async fn execute<'a>(
+        &'a self,
+        request: &'a FeatureRequest<TextDocumentPositionParams>,
+    ) -> Option<SignatureHelp> {
+        let document = request.document();
+        if let SyntaxTree::Bibtex(_) = document.tree {
+            return None;
+        }
+
+        let range = Range::new(Position::new(0, 0), request.params.position);
+        let text = CharStream::extract(&document.text, range);
+        let mut chars: Vec<char> = text.chars().rev().take(MAX_LOOKBEHIND).collect();
+        chars.reverse();
+
+        let (name, groups) = find_command(&chars)?;
+        let signature = LANGUAGE_DATA.find_command_signature(&name)?;
+        let active_parameter = active_parameter(signature, &groups)?;
+        Some(SignatureHelp {
+            signatures: vec![signature_information(signature)],
+            active_signature: Some(0),
+            active_parameter: Some(active_parameter as i64),
+        })
+    }
+}
+
+/// Finds the command whose argument contains the end of the text.
+///
+/// Returns the name of the command and the kinds of its arguments up to the cursor.
+fn find_command(chars: &[char]) -> Option<(String, Vec<LatexParameterKind>)> {
+    let mut end = open_group(chars, chars.len())?;
+    let mut groups = vec![group_kind(chars[end])];
+    loop {
+        while end > 0 && chars[end - 1].is_whitespace() {
+            end -= 1;
+        }
+
+        if end == 0 {
+            return None;
+        }
+
+        match chars[end - 1] {
+            '}' | ']' if !is_escaped(chars, end - 1) => {
+                end = open_group(chars, end - 1)?;
+                groups.push(group_kind(chars[end]));
+            }
+            _ => break,
+        }
+    }
+
+    if chars[end - 1] == '*' {
+        end -= 1;
+    }
+
+    let mut start = end;
+    while start > 0 && chars[start - 1].is_ascii_alphabetic() {
+        start -= 1;
+    }
+
+    if start == end || start == 0 || chars[start - 1] != '\\' {
+        return None;
+    }
+
+    groups.reverse();
+    let name = chars[start - 1..end].iter().collect();
+    Some((name, groups))
+}
+
+/// Returns the index of the unclosed bracket or brace before `end`.
+fn open_group(chars: &[char], end: usize) -> Option<usize> {
+    let mut depth = 0;
+    for i in (0..end).rev() {
+        if is_escaped(chars, i) {
+            continue;
+        }
+
+        match chars[i] {
+            '}' | ']' => depth += 1,
+            '{' | '[' if depth == 0 => return Some(i),
+            '{' | '[' => depth -= 1,
+            _ => (),
+        }
+    }
+    None
+}
+
+fn is_escaped(chars: &[char], index: usize) -> bool {
+    index > 0 && chars[index - 1] == '\\'
+}
+
+fn group_kind(bracket: char) -> LatexParameterKind {
+    if bracket == '[' {
+        LatexParameterKind::Optional
+    } else {
+        LatexParameterKind::Required
+    }
+}
+
+/// Maps the arguments onto the parameters of the signature.
+///
+/// Optional parameters may be omitted, so a required argument skips them.
+fn active_parameter(
+    signature: &LatexCommandSignature,
+    groups: &[LatexParameterKind],
+) -> Option<usize> {
+    let mut next = 0;
+    let mut active = None;
+    for group in groups {
+        while signature.parameters.get(next)?.kind != *group {
+            if *group == LatexParameterKind::Optional {
+                return None;
+            }
+            next += 1;
+        }
+        active = Some(next);
+        next += 1;
+    }
+    active
+}
+
+fn signature_information(signature: &LatexCommandSignature) -> SignatureInformation {
+    let mut label = signature.name.clone();
+    let mut parameters = Vec::new();
+    for parameter in &signature.parameters {
+        let start = label.chars().count() as u64;
+        label.push_str(&parameter.bracketed_label());
+        let end = label.chars().count() as u64;
+        parameters.push(ParameterInformation {
+            label: ParameterLabel::LabelOffsets([start, end]),
+            documentation: Some(Documentation::String(parameter.documentation.clone())),
+        });
+    }
+
+    SignatureInformation {
+        label,
+        documentation: None,
+        parameters: Some(parameters),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signature_help(text: &'static str, position: Position) -> Option<SignatureHelp> {
+        test_feature(
+            SignatureHelpProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.tex", text)],
+                main_file: "foo.tex",
+                position,
+                ..FeatureSpec::default()
+            },
+        )
+    }
+
+    #[test]
+    fn optional_argument() {
+        let help = signature_help("\\includegraphics[", Position::new(0, 17)).unwrap();
+        assert_eq!(help.active_parameter, Some(0));
+        assert_eq!(help.signatures[0].label, "\\includegraphics[options]{file}");
+
+        let parameters = help.signatures[0].parameters.as_ref().unwrap();
+        assert_eq!(parameters[0].label, ParameterLabel::LabelOffsets([16, 25]));
+        assert_eq!(parameters[1].label, ParameterLabel::LabelOffsets([25, 31]));
+    }
+
+    #[test]
+    fn skipped_optional_arguments() {
+        let help = signature_help("\\parbox{", Position::new(0, 8)).unwrap();
+        assert_eq!(help.active_parameter, Some(3));
+    }
+
+    #[test]
+    fn nested_groups() {
+        let help = signature_help("\\frac{\\textbf{a}[b]}{c", Position::new(0, 22)).unwrap();
+        assert_eq!(help.signatures[0].label, "\\frac{numerator}{denominator}");
+        assert_eq!(help.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn multiple_lines() {
+        let help = signature_help("\\parbox[t]\n  {3cm}{foo", Position::new(1, 10)).unwrap();
+        assert_eq!(help.active_parameter, Some(4));
+    }
+
+    #[test]
+    fn starred_command() {
+        let help = signature_help("\\section*{", Position::new(0, 10)).unwrap();
+        assert_eq!(help.active_parameter, Some(1));
+    }
+
+    #[test]
+    fn closed_group() {
+        assert_eq!(signature_help("\\frac{a}{b}", Position::new(0, 11)), None);
+    }
+
+    #[test]
+    fn unknown_command() {
+        assert_eq!(signature_help("\\foo{", Position::new(0, 5)), None);
+    }
+
+    #[test]
+    fn too_many_arguments() {
+        assert_eq!(signature_help("\\frac{a}{b}{", Position::new(0, 12)), None);
+    }
+
+    #[test]
+    fn bibtex() {
+        let help = test_feature(
+            SignatureHelpProvider,
+            FeatureSpec {
+                files: vec![FeatureSpec::file("foo.bib", "\\frac{")],
+                main_file: "foo.bib",
+                position: Position::new(0, 6),
+                ..FeatureSpec::default()
+            },
+        );
+        assert_eq!(help, None);
+    }
+}
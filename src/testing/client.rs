@@ -8,9 +8,15 @@ use texlab_protocol::*;
 #[derive(Debug, Default)]
 pub struct MockLspClient {
     pub messages: Mutex<Vec<ShowMessageParams>>,
+    pub message_requests: Mutex<Vec<ShowMessageRequestParams>>,
     pub options: Mutex<Options>,
     pub diagnostics_by_uri: Mutex<HashMap<Uri, Vec<Diagnostic>>>,
     pub log_messages: Mutex<Vec<LogMessageParams>>,
+    pub artifacts: Mutex<Vec<ArtifactsChangedParams>>,
+    pub partial_results: Mutex<Vec<PartialResultParams>>,
+    pub presences: Mutex<Vec<PresenceParams>>,
+    pub previews: Mutex<Vec<PreviewParams>>,
+    pub log_lines: Mutex<Vec<LogAppendedParams>>,
 }
 
 impl MockLspClient {
@@ -57,6 +63,16 @@ impl LspClient for MockLspClient {
         messages.push(params);
     }
 
+    #[boxed]
+    async fn show_message_request(
+        &self,
+        params: ShowMessageRequestParams,
+    ) -> Result<Option<MessageActionItem>> {
+        let mut message_requests = self.message_requests.lock().await;
+        message_requests.push(params);
+        Ok(None)
+    }
+
     #[boxed]
     async fn register_capability(&self, _params: RegistrationParams) -> Result<()> {
         Ok(())
@@ -76,9 +92,44 @@ impl LspClient for MockLspClient {
     #[boxed]
     async fn progress(&self, _params: ProgressParams) {}
 
+    #[boxed]
+    async fn partial_result(&self, params: PartialResultParams) {
+        let mut partial_results = self.partial_results.lock().await;
+        partial_results.push(params);
+    }
+
     #[boxed]
     async fn log_message(&self, params: LogMessageParams) {
         let mut messages = self.log_messages.lock().await;
         messages.push(params);
     }
+
+    #[boxed]
+    async fn artifacts_changed(&self, params: ArtifactsChangedParams) {
+        let mut artifacts = self.artifacts.lock().await;
+        artifacts.push(params);
+    }
+
+    #[boxed]
+    async fn presence(&self, params: PresenceParams) {
+        let mut presences = self.presences.lock().await;
+        presences.push(params);
+    }
+
+    #[boxed]
+    async fn preview(&self, params: PreviewParams) {
+        let mut previews = self.previews.lock().await;
+        previews.push(params);
+    }
+
+    #[boxed]
+    async fn log_appended(&self, params: LogAppendedParams) {
+        let mut log_lines = self.log_lines.lock().await;
+        log_lines.push(params);
+    }
+
+    #[boxed]
+    async fn ping(&self, _params: ()) -> Result<()> {
+        Ok(())
+    }
 }
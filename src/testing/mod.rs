@@ -0,0 +1,24 @@
+//! Utilities to write integration tests against the language server.
+//!
+//! This module is only available with the `testing` feature.
+//! A `Scenario` starts a `LatexLspServer` with a `MockLspClient`
+//! that records the notifications of the server:
+//!
+//! ```no_run
+//! # async fn example() {
+//! use std::path::Path;
+//! use texlab::testing::{Scenario, CLIENT_FULL_CAPABILITIES};
+//!
+//! let scenario = Scenario::from_directory(Path::new("tests/foo"), false).await;
+//! scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+//! scenario.open("foo.tex").await;
+//! # }
+//! ```
+
+mod capabilities;
+mod client;
+mod scenario;
+
+pub use self::capabilities::*;
+pub use self::client::*;
+pub use self::scenario::*;
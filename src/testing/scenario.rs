@@ -1,27 +1,45 @@
 use super::client::MockLspClient;
+use crate::server::LatexLspServer;
 use copy_dir::copy_dir;
 use futures::lock::Mutex;
 use once_cell::sync::Lazy;
-use std::fs::remove_dir;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tempfile::{tempdir, TempDir};
-use texlab::server::LatexLspServer;
 use texlab_distro::{Distribution, UnknownDistribution};
 use texlab_protocol::*;
 
 static DISTRIBUTION: Lazy<Mutex<Option<Arc<Box<dyn Distribution>>>>> =
     Lazy::new(|| Mutex::new(None));
 
+/// A server that runs against a temporary copy of a directory of documents.
+///
+/// The copy is a project inside of the sandbox of the server.
 pub struct Scenario {
     pub distribution: Arc<Box<dyn Distribution>>,
-    pub directory: TempDir,
+    pub sandbox: TempDir,
+    pub directory: PathBuf,
     pub server: LatexLspServer<MockLspClient>,
     pub client: Arc<MockLspClient>,
 }
 
 impl Scenario {
+    /// Creates a scenario from one of the scenarios of the `texlab-test` crate.
     pub async fn new(name: &str, use_distribution: bool) -> Self {
+        let source = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("crates")
+            .join("texlab_test")
+            .join("scenarios")
+            .join(name);
+        Self::from_directory(&source, use_distribution).await
+    }
+
+    /// Creates a scenario from the documents of the given directory.
+    ///
+    /// The directory is copied so that the server can modify its files.
+    /// If `use_distribution` is `true`, the installed TeX distribution is detected once
+    /// and shared with all other scenarios.
+    pub async fn from_directory(source: &Path, use_distribution: bool) -> Self {
         let distribution: Arc<Box<dyn Distribution>> = if use_distribution {
             let mut guard = DISTRIBUTION.lock().await;
             if guard.is_none() {
@@ -32,17 +50,16 @@ impl Scenario {
             Arc::new(Box::new(UnknownDistribution::new()))
         };
 
-        let directory = tempdir().unwrap();
-        remove_dir(directory.path()).unwrap();
-        let source = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
-            .join("scenarios")
-            .join(name);
-        copy_dir(source, directory.path()).unwrap();
+        let sandbox = tempdir().unwrap();
+        let directory = sandbox.path().join("project");
+        copy_dir(source, &directory).unwrap();
 
         let client = Arc::new(MockLspClient::new());
         let server = LatexLspServer::new(Arc::clone(&client), Arc::clone(&distribution));
+        server.set_sandbox_directory(sandbox.path().to_owned());
         Self {
             distribution,
+            sandbox,
             directory,
             server,
             client,
@@ -50,10 +67,10 @@ impl Scenario {
     }
 
     pub async fn initialize(&self, capabilities: &ClientCapabilities) {
-        let root_uri = Uri::from_file_path(self.directory.path()).unwrap();
+        let root_uri = Uri::from_file_path(&self.directory).unwrap();
         let params = InitializeParams {
             process_id: None,
-            root_path: Some(self.directory.path().to_string_lossy().into_owned()),
+            root_path: Some(self.directory.to_string_lossy().into_owned()),
             root_uri: Some(root_uri.into()),
             initialization_options: None,
             capabilities: capabilities.clone(),
@@ -72,13 +89,13 @@ impl Scenario {
     }
 
     pub fn uri(&self, name: &str) -> Uri {
-        let mut path = self.directory.path().to_owned();
+        let mut path = self.directory.clone();
         path.push(name);
         Uri::from_file_path(path).unwrap()
     }
 
     pub async fn read(&self, name: &'static str) -> String {
-        let mut path = self.directory.path().to_owned();
+        let mut path = self.directory.clone();
         path.push(name);
         let data = tokio::fs::read(path)
             .await
@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const MAX_RECENT_CITATIONS: usize = 20;
+
+const PROJECT_STATE_DIRECTORY: &str = ".texlab";
+
+const STATE_FILE: &str = "state.json";
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The choices of the user that are remembered across server restarts.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UserState {
+    pub recent_citations: Vec<String>,
+    pub dictionary: Vec<String>,
+    pub build_variant: Option<String>,
+}
+
+impl UserState {
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Moves the key to the front of the recently used citations.
+    pub fn use_citation(&mut self, key: &str) {
+        self.recent_citations.retain(|recent| recent != key);
+        self.recent_citations.insert(0, key.to_owned());
+        self.recent_citations.truncate(MAX_RECENT_CITATIONS);
+    }
+
+    /// Returns `false` if the word is already part of the dictionary.
+    pub fn add_word(&mut self, word: &str) -> bool {
+        if self.dictionary.iter().any(|known| known == word) {
+            false
+        } else {
+            self.dictionary.push(word.to_owned());
+            true
+        }
+    }
+}
+
+/// Returns the file that stores the state of the project.
+///
+/// Projects with a `.texlab` directory keep their state next to the sources.
+/// Otherwise, the state is stored in the XDG data directory.
+pub fn state_file(root_directory: &Path) -> Option<PathBuf> {
+    let project_directory = root_directory.join(PROJECT_STATE_DIRECTORY);
+    if project_directory.is_dir() {
+        return Some(project_directory.join(STATE_FILE));
+    }
+
+    let data_directory = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))?;
+
+    let hash = fnv1a(root_directory.to_string_lossy().as_bytes());
+    Some(
+        data_directory
+            .join("texlab")
+            .join("projects")
+            .join(format!("{:016x}.json", hash)),
+    )
+}
+
+/// Computes the 64-bit FNV-1a hash of the bytes.
+///
+/// Unlike `DefaultHasher`, the result does not change between Rust releases,
+/// so that the state file of a project can still be found after an update.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_citations() {
+        let mut state = UserState::default();
+        state.use_citation("foo");
+        state.use_citation("bar");
+        state.use_citation("foo");
+        assert_eq!(state.recent_citations, vec!["foo", "bar"]);
+
+        for i in 0..MAX_RECENT_CITATIONS {
+            state.use_citation(&i.to_string());
+        }
+        assert_eq!(state.recent_citations.len(), MAX_RECENT_CITATIONS);
+        assert_eq!(state.recent_citations[0], "19");
+    }
+
+    #[test]
+    fn add_word() {
+        let mut state = UserState::default();
+        assert!(state.add_word("texlab"));
+        assert!(!state.add_word("texlab"));
+        assert_eq!(state.dictionary, vec!["texlab"]);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path = env::temp_dir()
+            .join("texlab-user-state-test")
+            .join(STATE_FILE);
+        let state = UserState {
+            recent_citations: vec!["foo".into()],
+            dictionary: vec!["bar".into()],
+            build_variant: Some("draft".into()),
+        };
+        state.save(&path).unwrap();
+        assert_eq!(UserState::load(&path), state);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(UserState::load(&path), UserState::default());
+    }
+
+    #[test]
+    fn project_state_file() {
+        let directory = env::temp_dir().join("texlab-user-state-project");
+        fs::create_dir_all(directory.join(PROJECT_STATE_DIRECTORY)).unwrap();
+        assert_eq!(
+            state_file(&directory),
+            Some(directory.join(".texlab").join("state.json"))
+        );
+    }
+
+    #[test]
+    fn stable_hash() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
@@ -1,8 +1,10 @@
+use crate::encoding;
 use futures::executor::block_on;
 use log::*;
+use std::collections::HashMap;
 use std::ffi::OsStr;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::Mutex;
 use texlab_distro::{Distribution, Language};
@@ -20,6 +22,8 @@ pub enum WorkspaceLoadError {
 pub struct WorkspaceManager {
     distribution: Arc<Box<dyn Distribution>>,
     workspace: Mutex<Arc<Workspace>>,
+    versions: Mutex<HashMap<Uri, HashMap<String, u64>>>,
+    languages: Mutex<HashMap<Uri, Language>>,
 }
 
 impl WorkspaceManager {
@@ -27,6 +31,8 @@ impl WorkspaceManager {
         Self {
             distribution,
             workspace: Mutex::default(),
+            versions: Mutex::default(),
+            languages: Mutex::default(),
         }
     }
 
@@ -35,38 +41,32 @@ impl WorkspaceManager {
         Arc::clone(&workspace)
     }
 
-    pub fn add(&self, document: TextDocumentItem, options: &Options) {
+    /// Adds a document that has been opened in the given client.
+    ///
+    /// The language id of the client takes precedence over the extension of the file
+    /// and is remembered when the document is reloaded from disk later on.
+    pub fn add(&self, client: &str, document: TextDocumentItem, options: &Options) {
+        let uri: Uri = document.uri.into();
         let language = match Language::by_language_id(&document.language_id) {
-            Some(language) => language,
-            None => {
-                error!("Invalid language id: {}", &document.language_id);
-                return;
+            Some(language) => {
+                self.languages.lock().unwrap().insert(uri.clone(), language);
+                language
             }
+            None => match language_by_path(Path::new(uri.path())) {
+                Some(language) => language,
+                None => {
+                    error!("Invalid language id: {}", &document.language_id);
+                    return;
+                }
+            },
         };
 
+        self.set_version(client, uri.clone(), Some(document.version));
         let mut workspace = self.workspace.lock().unwrap();
-        *workspace = self.add_or_update(
-            &workspace,
-            document.uri.into(),
-            document.text,
-            language,
-            options,
-        );
+        *workspace = self.add_or_update(&workspace, uri, document.text, language, options);
     }
 
     pub fn load(&self, path: &Path, options: &Options) -> Result<(), WorkspaceLoadError> {
-        let language = match path
-            .extension()
-            .and_then(OsStr::to_str)
-            .and_then(Language::by_extension)
-        {
-            Some(language) => language,
-            None => {
-                warn!("Could not determine language: {}", path.to_string_lossy());
-                return Err(WorkspaceLoadError::UnknownLanguage);
-            }
-        };
-
         let uri = match Uri::from_file_path(path) {
             Ok(uri) => uri,
             Err(_) => {
@@ -75,8 +75,17 @@ impl WorkspaceManager {
             }
         };
 
-        let text = match fs::read_to_string(path) {
-            Ok(text) => text,
+        let language = self.languages.lock().unwrap().get(&uri).copied();
+        let language = match language.or_else(|| language_by_path(path)) {
+            Some(language) => language,
+            None => {
+                warn!("Could not determine language: {}", path.to_string_lossy());
+                return Err(WorkspaceLoadError::UnknownLanguage);
+            }
+        };
+
+        let text = match fs::read(path) {
+            Ok(bytes) => encoding::decode(&bytes),
             Err(why) => {
                 warn!("Could not open file: {}", path.to_string_lossy());
                 return Err(WorkspaceLoadError::IO(why));
@@ -88,6 +97,35 @@ impl WorkspaceManager {
         Ok(())
     }
 
+    /// Loads the files that are not part of the workspace yet and returns how many were loaded.
+    ///
+    /// No more files are loaded once the workspace contains `max_documents` documents.
+    pub fn preload(
+        &self,
+        paths: &[PathBuf],
+        options: &Options,
+        max_documents: Option<usize>,
+    ) -> usize {
+        let mut count = 0;
+        for path in paths {
+            let workspace = self.get();
+            let is_full = max_documents.map_or(false, |max| workspace.documents.len() >= max);
+            if is_full {
+                warn!("Stopped preloading after {} documents", count);
+                break;
+            }
+
+            let is_loaded = Uri::from_file_path(path)
+                .map(|uri| workspace.find(&uri).is_some())
+                .unwrap_or(true);
+
+            if !is_loaded && self.load(path, options).is_ok() {
+                count += 1;
+            }
+        }
+        count
+    }
+
     pub fn update(&self, uri: Uri, text: String, options: &Options) {
         let mut workspace = self.workspace.lock().unwrap();
 
@@ -107,6 +145,95 @@ impl WorkspaceManager {
         *workspace = self.add_or_update(&workspace, uri, text, language, options);
     }
 
+    /// Removes a document from the workspace and returns whether it was present.
+    pub fn remove(&self, uri: &Uri) -> bool {
+        self.versions.lock().unwrap().remove(uri);
+        self.languages.lock().unwrap().remove(uri);
+        let mut workspace = self.workspace.lock().unwrap();
+        if workspace.find(uri).is_none() {
+            return false;
+        }
+
+        let documents = workspace
+            .documents
+            .iter()
+            .filter(|document| document.uri != *uri)
+            .cloned()
+            .collect();
+        *workspace = Arc::new(Workspace { documents });
+        true
+    }
+
+    /// Returns the number of documents that are opened in the given client.
+    pub fn open_documents(&self, client: &str) -> usize {
+        let versions = self.versions.lock().unwrap();
+        versions
+            .values()
+            .filter(|versions| versions.contains_key(client))
+            .count()
+    }
+
+    /// Returns the version of the document in the given client.
+    pub fn version(&self, client: &str, uri: &Uri) -> Option<u64> {
+        let versions = self.versions.lock().unwrap();
+        versions
+            .get(uri)
+            .and_then(|versions| versions.get(client))
+            .copied()
+    }
+
+    /// Checks whether the document is opened in any client.
+    pub fn is_open(&self, uri: &Uri) -> bool {
+        self.versions.lock().unwrap().contains_key(uri)
+    }
+
+    pub fn set_version(&self, client: &str, uri: Uri, version: Option<u64>) {
+        let mut versions = self.versions.lock().unwrap();
+        match version {
+            Some(version) => {
+                versions
+                    .entry(uri)
+                    .or_default()
+                    .insert(client.to_owned(), version);
+            }
+            None => {
+                if let Some(client_versions) = versions.get_mut(&uri) {
+                    client_versions.remove(client);
+                    if client_versions.is_empty() {
+                        versions.remove(&uri);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Closes the documents of a client that has disconnected.
+    pub fn close_all(&self, client: &str) {
+        let mut versions = self.versions.lock().unwrap();
+        for client_versions in versions.values_mut() {
+            client_versions.remove(client);
+        }
+        versions.retain(|_, client_versions| !client_versions.is_empty());
+    }
+
+    /// Checks whether the document still reflects the file on disk.
+    ///
+    /// Documents that are opened in a client are owned by the client
+    /// and are always considered to be in sync.
+    pub fn is_in_sync(&self, document: &Document) -> bool {
+        if !document.is_file() || self.is_open(&document.uri) {
+            return true;
+        }
+
+        document
+            .uri
+            .to_file_path()
+            .ok()
+            .and_then(|path| fs::metadata(path).ok())
+            .and_then(|data| data.modified().ok())
+            .map_or(false, |modified| modified <= document.modified)
+    }
+
     fn add_or_update(
         &self,
         workspace: &Workspace,
@@ -128,3 +255,91 @@ impl WorkspaceManager {
         Arc::new(Workspace { documents })
     }
 }
+
+fn language_by_path(path: &Path) -> Option<Language> {
+    path.extension()
+        .and_then(OsStr::to_str)
+        .and_then(Language::by_extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use texlab_distro::UnknownDistribution;
+
+    const CLIENT: &str = "foo";
+
+    fn manager() -> WorkspaceManager {
+        let distribution: Box<dyn Distribution> = Box::new(UnknownDistribution::new());
+        WorkspaceManager::new(Arc::new(distribution))
+    }
+
+    fn open(manager: &WorkspaceManager, path: &Path, language_id: &str, text: &str) -> Uri {
+        let uri = Uri::from_file_path(path).unwrap();
+        let document = TextDocumentItem {
+            uri: uri.clone().into(),
+            language_id: language_id.into(),
+            version: 0,
+            text: text.into(),
+        };
+        manager.add(CLIENT, document, &Options::default());
+        uri
+    }
+
+    fn is_bibtex(manager: &WorkspaceManager, uri: &Uri) -> bool {
+        match manager.get().find(uri).unwrap().tree {
+            SyntaxTree::Bibtex(_) => true,
+            SyntaxTree::Latex(_) => false,
+        }
+    }
+
+    #[test]
+    fn language_id_overrides_extension() {
+        let directory = env::temp_dir().join("texlab-language-id-test");
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("refs.txt");
+        fs::write(&path, "@article{foo,}").unwrap();
+
+        let manager = manager();
+        let uri = open(&manager, &path, "bibtex", "@article{foo,}");
+        assert!(is_bibtex(&manager, &uri));
+
+        manager.set_version(CLIENT, uri.clone(), None);
+        manager.load(&path, &Options::default()).unwrap();
+        assert!(is_bibtex(&manager, &uri));
+    }
+
+    #[test]
+    fn unknown_language_id() {
+        let manager = manager();
+        let uri = open(
+            &manager,
+            &env::temp_dir().join("foo.bib"),
+            "plaintext",
+            "@article{foo,}",
+        );
+        assert!(is_bibtex(&manager, &uri));
+
+        let uri = open(&manager, &env::temp_dir().join("foo.txt"), "plaintext", "");
+        assert!(manager.get().find(&uri).is_none());
+    }
+
+    #[test]
+    fn versions_per_client() {
+        let manager = manager();
+        let uri = open(&manager, &env::temp_dir().join("foo.tex"), "latex", "");
+        manager.set_version("bar", uri.clone(), Some(3));
+        assert_eq!(manager.version(CLIENT, &uri), Some(0));
+        assert_eq!(manager.version("bar", &uri), Some(3));
+        assert_eq!(manager.open_documents("bar"), 1);
+
+        manager.set_version(CLIENT, uri.clone(), None);
+        assert_eq!(manager.version(CLIENT, &uri), None);
+        assert!(manager.is_open(&uri));
+
+        manager.close_all("bar");
+        assert!(!manager.is_open(&uri));
+        assert_eq!(manager.open_documents("bar"), 0);
+    }
+}
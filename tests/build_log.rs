@@ -0,0 +1,69 @@
+use texlab::build_log;
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+#[tokio::test]
+async fn text_document_content() {
+    let scenario = Scenario::new("diagnostics/build", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+    let tex_uri = scenario.uri("foo.tex");
+    let params = TextDocumentContentParams {
+        uri: build_log::log_uri(&tex_uri).unwrap().into(),
+    };
+    let target_uri: Url = tex_uri.into();
+
+    let result = scenario
+        .server
+        .execute(|svr| svr.text_document_content(params))
+        .await
+        .unwrap();
+
+    assert!(result.text.contains("! Undefined control sequence."));
+    assert!(result.links.iter().any(|link| {
+        link.target_uri == target_uri && link.target_range == Range::new_simple(3, 0, 3, 0)
+    }));
+}
+
+#[tokio::test]
+async fn text_document_content_unknown_document() {
+    let scenario = Scenario::new("diagnostics/build", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    let params = TextDocumentContentParams {
+        uri: build_log::log_uri(&scenario.uri("foo.tex")).unwrap().into(),
+    };
+
+    let result = scenario
+        .server
+        .execute(|svr| svr.text_document_content(params))
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn text_document_content_outside_of_sandbox() {
+    let scenario = Scenario::new("diagnostics/build", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    *scenario.client.options.lock().await = Options {
+        latex: Some(LatexOptions {
+            build: Some(LatexBuildOptions {
+                output_directory: Some("..".into()),
+                ..LatexBuildOptions::default()
+            }),
+            ..LatexOptions::default()
+        }),
+        bibtex: None,
+    };
+    scenario.open("foo.tex").await;
+    let params = TextDocumentContentParams {
+        uri: build_log::log_uri(&scenario.uri("foo.tex")).unwrap().into(),
+    };
+
+    let result = scenario
+        .server
+        .execute(|svr| svr.text_document_content(params))
+        .await;
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,31 @@
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+#[tokio::test]
+async fn project() {
+    let scenario = Scenario::new("citation/sections", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+    scenario.open("bar.bib").await;
+    let params = CitationSectionsParams {
+        text_document: TextDocumentIdentifier::new(scenario.uri("foo.tex").into()),
+    };
+    let result = scenario
+        .server
+        .execute(|svr| svr.citation_sections(params))
+        .await
+        .unwrap();
+
+    assert_eq!(result.entries.len(), 2);
+    assert_eq!(result.entries[0].key, "foo");
+    assert_eq!(result.entries[0].uri, scenario.uri("bar.bib").into());
+    assert_eq!(result.entries[0].count, 1);
+    assert_eq!(result.entries[0].sections[0].title, "Foo");
+    assert_eq!(
+        result.entries[0].sections[0].range,
+        Range::new_simple(2, 0, 2, 13)
+    );
+    assert_eq!(result.entries[1].count, 0);
+    assert_eq!(result.uncited_sections.len(), 1);
+    assert_eq!(result.uncited_sections[0].title, "Bar");
+}
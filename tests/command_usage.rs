@@ -0,0 +1,47 @@
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+#[tokio::test]
+async fn project() {
+    let scenario = Scenario::new("diagnostics/project", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+    let params = CommandUsageParams {
+        text_document: TextDocumentIdentifier::new(scenario.uri("foo.tex").into()),
+    };
+    let result = scenario
+        .server
+        .execute(|svr| svr.command_usage(params))
+        .await
+        .unwrap();
+
+    assert_eq!(result.documents.len(), 1);
+    assert_eq!(result.documents[0].usage, result.project);
+    assert_eq!(result.project.commands["\\documentclass"], 1);
+    assert_eq!(result.project.commands["\\bibliography"], 1);
+    assert_eq!(result.project.environments["document"], 1);
+}
+
+#[tokio::test]
+async fn discouraged_commands() {
+    let scenario = Scenario::new("diagnostics/project", false).await;
+    *scenario.client.options.lock().await = Options {
+        latex: Some(LatexOptions {
+            style: Some(LatexStyleOptions {
+                discouraged_commands: Some(vec!["bibliography".into()]),
+            }),
+            ..LatexOptions::default()
+        }),
+        bibtex: None,
+    };
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+
+    let diagnostics_by_uri = scenario.client.diagnostics_by_uri.lock().await;
+    let diagnostics = &diagnostics_by_uri[&scenario.uri("foo.tex")];
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        "Use of discouraged command \\bibliography"
+    );
+}
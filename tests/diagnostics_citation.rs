@@ -0,0 +1,52 @@
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+async fn undefined_citations(scenario: &Scenario) -> Vec<Diagnostic> {
+    let diagnostics_by_uri = scenario.client.diagnostics_by_uri.lock().await;
+    diagnostics_by_uri[&scenario.uri("foo.tex")]
+        .iter()
+        .filter(|diagnostic| diagnostic.message.starts_with("Undefined citation"))
+        .cloned()
+        .collect()
+}
+
+async fn change(scenario: &Scenario, name: &str, text: &str) {
+    let params = DidChangeTextDocumentParams {
+        text_document: VersionedTextDocumentIdentifier::new(scenario.uri(name).into(), 1),
+        content_changes: vec![TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: text.into(),
+        }],
+    };
+    scenario.server.execute(|svr| svr.did_change(params)).await;
+}
+
+#[tokio::test]
+async fn bibliography_changed() {
+    let scenario = Scenario::new("diagnostics/citation", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+    scenario.open("bar.bib").await;
+    {
+        let diagnostics = undefined_citations(&scenario).await;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].message, "Undefined citation: foo");
+        assert_eq!(diagnostics[0].range.start.line, 2);
+    }
+
+    change(&scenario, "bar.bib", "@article{foo,}\n").await;
+    assert!(undefined_citations(&scenario).await.is_empty());
+}
+
+#[tokio::test]
+async fn document_changed() {
+    let scenario = Scenario::new("diagnostics/citation", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+    scenario.open("bar.bib").await;
+
+    let text = "\\documentclass{article}\n\\begin{document}\n\\cite{bar}\n\\bibliography{bar}\n\\end{document}\n";
+    change(&scenario, "foo.tex", text).await;
+    assert!(undefined_citations(&scenario).await.is_empty());
+}
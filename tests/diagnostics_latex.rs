@@ -10,8 +10,14 @@ async fn disabled() {
             *scenario.client.options.lock().await = Options {
                 latex: Some(LatexOptions {
                     lint: Some(LatexLintOptions {
+                        tool: None,
                         on_change: Some(false),
                         on_save: Some(false),
+                        max_diagnostics: None,
+                        delay: None,
+                        chktex: None,
+                        max_input_size: None,
+                        severities: None,
                     }),
                     ..LatexOptions::default()
                 }),
@@ -37,8 +43,14 @@ async fn on_open() {
             *scenario.client.options.lock().await = Options {
                 latex: Some(LatexOptions {
                     lint: Some(LatexLintOptions {
+                        tool: None,
                         on_change: Some(false),
                         on_save: Some(true),
+                        max_diagnostics: None,
+                        delay: None,
+                        chktex: None,
+                        max_input_size: None,
+                        severities: None,
                     }),
                     ..LatexOptions::default()
                 }),
@@ -66,8 +78,14 @@ async fn on_save() {
             *scenario.client.options.lock().await = Options {
                 latex: Some(LatexOptions {
                     lint: Some(LatexLintOptions {
+                        tool: None,
                         on_change: Some(false),
                         on_save: Some(true),
+                        max_diagnostics: None,
+                        delay: None,
+                        chktex: None,
+                        max_input_size: None,
+                        severities: None,
                     }),
                     ..LatexOptions::default()
                 }),
@@ -120,8 +138,14 @@ async fn on_change() {
             *scenario.client.options.lock().await = Options {
                 latex: Some(LatexOptions {
                     lint: Some(LatexLintOptions {
+                        tool: None,
                         on_change: Some(true),
                         on_save: Some(true),
+                        max_diagnostics: None,
+                        delay: None,
+                        chktex: None,
+                        max_input_size: None,
+                        severities: None,
                     }),
                     ..LatexOptions::default()
                 }),
@@ -156,3 +180,38 @@ async fn on_change() {
         Tectonic | Unknown => (),
     }
 }
+
+#[tokio::test]
+async fn custom_args() {
+    let scenario = Scenario::new("diagnostics/latex", true).await;
+    match scenario.distribution.kind() {
+        Texlive | Miktex => {
+            *scenario.client.options.lock().await = Options {
+                latex: Some(LatexOptions {
+                    lint: Some(LatexLintOptions {
+                        tool: None,
+                        on_change: Some(false),
+                        on_save: Some(true),
+                        max_diagnostics: None,
+                        delay: None,
+                        chktex: Some(LatexChktexOptions {
+                            args: Some(vec!["-n1".into()]),
+                        }),
+                        max_input_size: None,
+                        severities: None,
+                    }),
+                    ..LatexOptions::default()
+                }),
+                bibtex: None,
+            };
+
+            scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+            scenario.open("on_open.tex").await;
+            scenario
+                .client
+                .verify_no_diagnostics(&scenario.uri("on_open.tex"))
+                .await;
+        }
+        Tectonic | Unknown => (),
+    }
+}
@@ -0,0 +1,44 @@
+use texlab::diagnostics::BibtexErrorCode;
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+#[tokio::test]
+async fn check_project() {
+    let scenario = Scenario::new("diagnostics/project", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+    let identifier = TextDocumentIdentifier::new(scenario.uri("foo.tex").into());
+    let params = ExecuteCommandParams {
+        command: "texlab.checkProject".into(),
+        arguments: vec![serde_json::to_value(identifier).unwrap()],
+    };
+    let result = scenario
+        .server
+        .execute(|svr| svr.execute_command(params))
+        .await
+        .unwrap();
+    assert_eq!(result, None);
+
+    let diagnostics_by_uri = scenario.client.diagnostics_by_uri.lock().await;
+    let diagnostics = &diagnostics_by_uri[&scenario.uri("bar.bib")];
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0].message,
+        BibtexErrorCode::MissingBeginBrace.message()
+    );
+}
+
+#[tokio::test]
+async fn unknown_command() {
+    let scenario = Scenario::new("diagnostics/project", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    let params = ExecuteCommandParams {
+        command: "texlab.foo".into(),
+        arguments: Vec::new(),
+    };
+    let result = scenario
+        .server
+        .execute(|svr| svr.execute_command(params))
+        .await;
+    assert!(result.is_err());
+}
@@ -38,6 +38,13 @@ async fn latex() {
     assert_eq!(
         foldings,
         vec![
+            FoldingRange {
+                start_line: 0,
+                start_character: Some(23),
+                end_line: 4,
+                end_character: Some(0),
+                kind: Some(FoldingRangeKind::Imports)
+            },
             FoldingRange {
                 start_line: 4,
                 start_character: Some(16),
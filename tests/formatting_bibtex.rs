@@ -18,6 +18,7 @@ async fn infinite_line_length() {
         "infinite_line_length/unformatted.bib",
         Some(BibtexFormattingOptions {
             line_length: Some(0),
+            preserve_field_case: None,
         }),
     )
     .await;
@@ -20,3 +20,15 @@ async fn invalid() {
     let contents = run(SCENARIO, "foo.bib", 3, 7).await;
     assert_eq!(contents, None);
 }
+
+#[tokio::test]
+async fn bib_item() {
+    let contents = run(SCENARIO, "bibitem.tex", 0, 7).await.unwrap();
+    assert_eq!(
+        contents,
+        HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::PlainText,
+            value: "Qux, F. \\emph{Foo Bar}, 2019.".into()
+        })
+    );
+}
@@ -0,0 +1,64 @@
+use jsonrpc::server::Result;
+use std::path::Path;
+use texlab::index_export::{ExportIndexParams, EXPORT_INDEX_COMMAND};
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+async fn export_index(scenario: &Scenario, path: &Path) -> Result<Option<serde_json::Value>> {
+    let params = ExecuteCommandParams {
+        command: EXPORT_INDEX_COMMAND.into(),
+        arguments: vec![serde_json::to_value(ExportIndexParams { path: path.into() }).unwrap()],
+    };
+    scenario
+        .server
+        .execute(|svr| svr.execute_command(params))
+        .await
+}
+
+#[tokio::test]
+async fn inside_of_sandbox() {
+    let scenario = Scenario::new("diagnostics/project", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+
+    let directory = &scenario.directory;
+    let path = directory.join("foo").join("..").join("index.json");
+    assert_eq!(export_index(&scenario, &path).await, Ok(None));
+    assert!(directory.join("index.json").is_file());
+}
+
+#[tokio::test]
+async fn outside_of_root_directory() {
+    let scenario = Scenario::new("diagnostics/project", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+
+    let directory = &scenario.directory;
+    let name = format!("{}.json", directory.file_name().unwrap().to_string_lossy());
+    let path = directory.join("..").join(&name);
+    assert!(export_index(&scenario, &path).await.is_err());
+    assert!(!directory.parent().unwrap().join(&name).exists());
+}
+
+#[tokio::test]
+async fn overwrite_index() {
+    let scenario = Scenario::new("diagnostics/project", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+
+    let path = scenario.directory.join("index.json");
+    assert_eq!(export_index(&scenario, &path).await, Ok(None));
+    assert_eq!(export_index(&scenario, &path).await, Ok(None));
+}
+
+#[tokio::test]
+async fn overwrite_document() {
+    let scenario = Scenario::new("diagnostics/project", false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+
+    let text = scenario.read("foo.tex").await;
+    let path = scenario.directory.join("foo.tex");
+    assert!(export_index(&scenario, &path).await.is_err());
+    assert_eq!(scenario.read("foo.tex").await, text);
+}
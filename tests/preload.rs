@@ -0,0 +1,116 @@
+use texlab::limits::ConnectionLimits;
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+const SCENARIO: &str = "symbol/workspace";
+
+async fn bibtex_symbols(scenario: &Scenario) -> usize {
+    let params = WorkspaceSymbolRequestParams {
+        query: "bibtex".into(),
+        partial_result_token: None,
+    };
+    scenario
+        .server
+        .execute(|svr| svr.workspace_symbol(params))
+        .await
+        .unwrap()
+        .len()
+}
+
+#[tokio::test]
+async fn all_project_files() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    assert_eq!(bibtex_symbols(&scenario).await, 0);
+
+    let params = PreloadParams { uris: None };
+    scenario
+        .server
+        .execute(|svr| svr.preload(params))
+        .await
+        .unwrap();
+    assert_eq!(bibtex_symbols(&scenario).await, 2);
+}
+
+#[tokio::test]
+async fn selected_files() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+
+    let params = PreloadParams {
+        uris: Some(vec![scenario.uri("bar.bib").into()]),
+    };
+    scenario
+        .server
+        .execute(|svr| svr.preload(params))
+        .await
+        .unwrap();
+    assert_eq!(bibtex_symbols(&scenario).await, 2);
+}
+
+#[tokio::test]
+async fn files_outside_of_sandbox() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+
+    let directory = scenario.sandbox.path();
+    let name = format!("{}.bib", directory.file_name().unwrap().to_string_lossy());
+    let path = directory.parent().unwrap().join(name);
+    std::fs::write(&path, "@article{foo,}").unwrap();
+
+    let params = PreloadParams {
+        uris: Some(vec![Url::from_file_path(&path).unwrap()]),
+    };
+    let result = scenario.server.execute(|svr| svr.preload(params)).await;
+    std::fs::remove_file(&path).unwrap();
+    result.unwrap();
+    assert_eq!(bibtex_symbols(&scenario).await, 0);
+}
+
+#[tokio::test]
+async fn max_documents() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    scenario.server.set_limits(ConnectionLimits {
+        max_documents: Some(1),
+        max_processes: None,
+    });
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+
+    let params = PreloadParams {
+        uris: Some(vec![
+            scenario.uri("foo.tex").into(),
+            scenario.uri("bar.bib").into(),
+        ]),
+    };
+    scenario
+        .server
+        .execute(|svr| svr.preload(params))
+        .await
+        .unwrap();
+    assert_eq!(bibtex_symbols(&scenario).await, 0);
+}
+
+#[tokio::test]
+async fn sandbox_as_root_directory() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    let root_uri = Uri::from_file_path(scenario.sandbox.path()).unwrap();
+    let params = InitializeParams {
+        process_id: None,
+        root_path: None,
+        root_uri: Some(root_uri.into()),
+        initialization_options: None,
+        capabilities: CLIENT_FULL_CAPABILITIES.clone(),
+        trace: None,
+        workspace_folders: None,
+    };
+    scenario
+        .server
+        .execute(|svr| svr.initialize(params))
+        .await
+        .unwrap();
+
+    let params = PreloadParams { uris: None };
+    let result = scenario.server.execute(|svr| svr.preload(params)).await;
+    assert!(result.is_err());
+    assert_eq!(bibtex_symbols(&scenario).await, 0);
+}
@@ -0,0 +1,60 @@
+use std::path::Path;
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+const SCENARIO: &str = "symbol/workspace";
+
+async fn initialize(scenario: &Scenario, project_directory: &Path) {
+    let params = InitializeParams {
+        process_id: None,
+        root_path: None,
+        root_uri: None,
+        initialization_options: Some(serde_json::json!({
+            "projectDirectory": project_directory,
+        })),
+        capabilities: CLIENT_FULL_CAPABILITIES.clone(),
+        trace: None,
+        workspace_folders: None,
+    };
+    scenario
+        .server
+        .execute(|svr| svr.initialize(params))
+        .await
+        .unwrap();
+}
+
+async fn bibtex_symbols(scenario: &Scenario) -> usize {
+    let params = WorkspaceSymbolRequestParams {
+        query: "bibtex".into(),
+        partial_result_token: None,
+    };
+    scenario
+        .server
+        .execute(|svr| svr.workspace_symbol(params))
+        .await
+        .unwrap()
+        .len()
+}
+
+#[tokio::test]
+async fn inside_of_sandbox() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    let directory = scenario.directory.join("foo").join("..");
+    initialize(&scenario, &directory).await;
+    assert_eq!(bibtex_symbols(&scenario).await, 2);
+}
+
+#[tokio::test]
+async fn outside_of_sandbox() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    let directory = scenario.sandbox.path().join("..");
+    initialize(&scenario, &directory).await;
+    assert_eq!(bibtex_symbols(&scenario).await, 0);
+}
+
+#[tokio::test]
+async fn sandbox_itself() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    initialize(&scenario, scenario.sandbox.path()).await;
+    assert_eq!(bibtex_symbols(&scenario).await, 0);
+}
@@ -0,0 +1,61 @@
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+const SCENARIO: &str = "rename/bibtex";
+
+async fn run(capabilities: &ClientCapabilities) -> (Scenario, WorkspaceEdit) {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    scenario.initialize(capabilities).await;
+    scenario.open("foo.tex").await;
+    scenario.open("foo.bib").await;
+    let identifier = TextDocumentIdentifier::new(scenario.uri("foo.bib").into());
+    let params = RenameParams {
+        text_document_position: TextDocumentPositionParams::new(identifier, Position::new(0, 10)),
+        new_name: "bar".into(),
+    };
+    let edit = scenario
+        .server
+        .execute(|svr| svr.rename(params))
+        .await
+        .unwrap()
+        .unwrap();
+    (scenario, edit)
+}
+
+#[tokio::test]
+async fn changes() {
+    let (scenario, edit) = run(&CLIENT_FULL_CAPABILITIES).await;
+    let changes = edit.changes.unwrap();
+    let tex_uri: Url = scenario.uri("foo.tex").into();
+    let bib_uri: Url = scenario.uri("foo.bib").into();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(
+        changes[&tex_uri],
+        vec![TextEdit::new(Range::new_simple(1, 6, 1, 9), "bar".into())]
+    );
+    assert_eq!(
+        changes[&bib_uri],
+        vec![TextEdit::new(Range::new_simple(0, 9, 0, 12), "bar".into())]
+    );
+}
+
+#[tokio::test]
+async fn document_changes() {
+    let mut capabilities = CLIENT_FULL_CAPABILITIES.clone();
+    capabilities.workspace.as_mut().unwrap().workspace_edit = Some(WorkspaceEditCapability {
+        document_changes: Some(true),
+        ..WorkspaceEditCapability::default()
+    });
+
+    let (_, edit) = run(&capabilities).await;
+    assert_eq!(edit.changes, None);
+    match edit.document_changes.unwrap() {
+        DocumentChanges::Edits(edits) => {
+            assert_eq!(edits.len(), 2);
+            assert!(edits
+                .iter()
+                .all(|edit| edit.text_document.version == Some(0) && edit.edits.len() == 1));
+        }
+        DocumentChanges::Operations(_) => panic!("Unexpected resource operations"),
+    }
+}
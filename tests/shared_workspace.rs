@@ -0,0 +1,120 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use texlab::server::LatexLspServer;
+use texlab::shared_workspace::WorkspaceRegistry;
+use texlab_protocol::*;
+use texlab_test::{MockLspClient, Scenario, CLIENT_FULL_CAPABILITIES};
+
+const SCENARIO: &str = "diagnostics/bibtex";
+
+struct Peer {
+    server: LatexLspServer<MockLspClient>,
+    client: Arc<MockLspClient>,
+}
+
+impl Peer {
+    async fn new(scenario: &Scenario, registry: &Arc<WorkspaceRegistry>, sandbox: &Path) -> Self {
+        let client = Arc::new(MockLspClient::new());
+        let server = LatexLspServer::new(Arc::clone(&client), Arc::clone(&scenario.distribution));
+        server.set_sandbox_directory(sandbox.to_owned());
+        server.use_workspace_registry(Arc::clone(registry));
+
+        let root_uri = Uri::from_file_path(&scenario.directory).unwrap();
+        let params = InitializeParams {
+            process_id: None,
+            root_path: None,
+            root_uri: Some(root_uri.into()),
+            initialization_options: None,
+            capabilities: CLIENT_FULL_CAPABILITIES.clone(),
+            trace: None,
+            workspace_folders: None,
+        };
+        server.execute(|svr| svr.initialize(params)).await.unwrap();
+        Self { server, client }
+    }
+
+    async fn open(&self, scenario: &Scenario) {
+        let params = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem {
+                uri: scenario.uri("foo.bib").into(),
+                version: 0,
+                language_id: "bibtex".into(),
+                text: scenario.read("foo.bib").await,
+            },
+        };
+        self.server.execute(|svr| svr.did_open(params)).await;
+    }
+}
+
+async fn shared_scenario() -> (Scenario, Arc<WorkspaceRegistry>) {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    let registry = Arc::new(WorkspaceRegistry::new());
+    scenario
+        .server
+        .use_workspace_registry(Arc::clone(&registry));
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    (scenario, registry)
+}
+
+/// Waits until the diagnostics of the other clients have been forwarded.
+async fn diagnostics(client: &MockLspClient, uri: &Uri) -> Option<usize> {
+    for _ in 0..50 {
+        if let Some(diagnostics) = client.diagnostics_by_uri.lock().await.get(uri) {
+            return Some(diagnostics.len());
+        }
+        tokio::time::delay_for(Duration::from_millis(10)).await;
+    }
+    None
+}
+
+#[tokio::test]
+async fn publish_to_other_clients() {
+    let (scenario, registry) = shared_scenario().await;
+    let peer = Peer::new(&scenario, &registry, scenario.sandbox.path()).await;
+
+    scenario.open("foo.bib").await;
+    let uri = scenario.uri("foo.bib");
+    assert_eq!(diagnostics(&scenario.client, &uri).await, Some(1));
+    assert_eq!(diagnostics(&peer.client, &uri).await, Some(1));
+}
+
+#[tokio::test]
+async fn outside_of_sandbox() {
+    let (scenario, registry) = shared_scenario().await;
+    let sandbox = scenario.directory.join("sandbox");
+    let peer = Peer::new(&scenario, &registry, &sandbox).await;
+
+    scenario.open("foo.bib").await;
+    let uri = scenario.uri("foo.bib");
+    assert_eq!(diagnostics(&scenario.client, &uri).await, Some(1));
+    assert_eq!(diagnostics(&peer.client, &uri).await, None);
+}
+
+#[tokio::test]
+async fn close_in_one_client() {
+    let (scenario, registry) = shared_scenario().await;
+    let peer = Peer::new(&scenario, &registry, scenario.sandbox.path()).await;
+    scenario.open("foo.bib").await;
+    peer.open(&scenario).await;
+
+    let params = DidCloseTextDocumentParams {
+        text_document: TextDocumentIdentifier::new(scenario.uri("foo.bib").into()),
+    };
+    scenario.server.execute(|svr| svr.did_close(params)).await;
+
+    // The document is still owned by the peer, so it must not be reloaded or removed.
+    let params = DidChangeWatchedFilesParams {
+        changes: vec![FileEvent {
+            uri: scenario.uri("foo.bib").into(),
+            typ: FileChangeType::Deleted,
+        }],
+    };
+    scenario
+        .server
+        .execute(|svr| svr.did_change_watched_files(params))
+        .await;
+
+    let uri = scenario.uri("foo.bib");
+    assert_eq!(diagnostics(&scenario.client, &uri).await, Some(1));
+}
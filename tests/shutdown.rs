@@ -0,0 +1,36 @@
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+const SCENARIO: &str = "diagnostics/project";
+
+#[tokio::test]
+async fn exit_signal() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    let mut exit = scenario.server.exit_signal();
+    assert!(exit.try_next().is_err());
+
+    scenario.server.shutdown(()).await.unwrap();
+    scenario.server.exit(()).await;
+    assert_eq!(exit.try_next().unwrap(), Some(()));
+}
+
+#[tokio::test]
+async fn publish_pending_diagnostics() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    let params = DidOpenTextDocumentParams {
+        text_document: TextDocumentItem {
+            uri: scenario.uri("bar.bib").into(),
+            version: 0,
+            language_id: "bibtex".into(),
+            text: scenario.read("bar.bib").await,
+        },
+    };
+
+    // Without going through the middleware, the diagnostics are only queued.
+    scenario.server.did_open(params).await;
+    scenario.server.exit(()).await;
+    let diagnostics_by_uri = scenario.client.diagnostics_by_uri.lock().await;
+    assert_eq!(diagnostics_by_uri[&scenario.uri("bar.bib")].len(), 1);
+}
@@ -1,3 +1,4 @@
+use texlab_protocol::{ProgressToken, SymbolInformation};
 use texlab_test::symbol::*;
 
 #[tokio::test]
@@ -44,3 +45,20 @@ async fn filter_bibtex() {
     verify::symbol_info(&symbols[0], &scenario, "bar.bib", "foo", 0, 0, 0, 14);
     verify::symbol_info(&symbols[1], &scenario, "bar.bib", "bar", 2, 0, 2, 20);
 }
+
+#[tokio::test]
+async fn partial_results() {
+    let (scenario, symbols, partial_results) = run_workspace_partial("bibtex").await;
+    assert!(symbols.is_empty());
+    assert_eq!(partial_results.len(), 1);
+    assert_eq!(
+        partial_results[0].token,
+        ProgressToken::String("symbols".into())
+    );
+
+    let symbols: Vec<SymbolInformation> =
+        serde_json::from_value(partial_results[0].value.clone()).unwrap();
+    assert_eq!(symbols.len(), 2);
+    verify::symbol_info(&symbols[0], &scenario, "bar.bib", "foo", 0, 0, 0, 14);
+    verify::symbol_info(&symbols[1], &scenario, "bar.bib", "bar", 2, 0, 2, 20);
+}
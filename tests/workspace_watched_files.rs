@@ -0,0 +1,51 @@
+use std::fs;
+use texlab_protocol::*;
+use texlab_test::{Scenario, CLIENT_FULL_CAPABILITIES};
+
+const SCENARIO: &str = "diagnostics/project";
+
+async fn notify(scenario: &Scenario, name: &str, typ: FileChangeType) {
+    let params = DidChangeWatchedFilesParams {
+        changes: vec![FileEvent {
+            uri: scenario.uri(name).into(),
+            typ,
+        }],
+    };
+    scenario
+        .server
+        .execute(|svr| svr.did_change_watched_files(params))
+        .await;
+}
+
+async fn bar_diagnostics(scenario: &Scenario) -> usize {
+    let diagnostics_by_uri = scenario.client.diagnostics_by_uri.lock().await;
+    diagnostics_by_uri
+        .get(&scenario.uri("bar.bib"))
+        .map(Vec::len)
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn created_and_deleted_file() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+    notify(&scenario, "bar.bib", FileChangeType::Created).await;
+    assert_eq!(bar_diagnostics(&scenario).await, 1);
+
+    fs::remove_file(scenario.directory.join("bar.bib")).unwrap();
+    notify(&scenario, "bar.bib", FileChangeType::Deleted).await;
+    assert_eq!(bar_diagnostics(&scenario).await, 0);
+}
+
+#[tokio::test]
+async fn deleted_open_file() {
+    let scenario = Scenario::new(SCENARIO, false).await;
+    scenario.initialize(&CLIENT_FULL_CAPABILITIES).await;
+    scenario.open("foo.tex").await;
+    scenario.open("bar.bib").await;
+
+    fs::remove_file(scenario.directory.join("bar.bib")).unwrap();
+    notify(&scenario, "bar.bib", FileChangeType::Deleted).await;
+    assert_eq!(bar_diagnostics(&scenario).await, 1);
+}